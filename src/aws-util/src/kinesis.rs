@@ -101,3 +101,31 @@ pub async fn get_shard_iterator(
         .await?;
     Ok(res.shard_iterator)
 }
+
+/// Constructs an iterator over a Kinesis shard that starts right after the
+/// record with the given sequence number.
+///
+/// This function is like [`get_shard_iterator`], but returns an
+/// `AFTER_SEQUENCE_NUMBER` shard iterator, which allows resuming reading a
+/// shard from a previously recorded position.
+///
+/// # Errors
+///
+/// Any errors from the underlying `GetShardIterator` API call are surfaced
+/// directly.
+pub async fn get_shard_iterator_after(
+    client: &Client,
+    stream_name: &str,
+    shard_id: &str,
+    sequence_number: &str,
+) -> Result<Option<String>, SdkError<GetShardIteratorError>> {
+    let res = client
+        .get_shard_iterator()
+        .stream_name(stream_name)
+        .shard_id(shard_id)
+        .shard_iterator_type(ShardIteratorType::AfterSequenceNumber)
+        .starting_sequence_number(sequence_number)
+        .send()
+        .await?;
+    Ok(res.shard_iterator)
+}
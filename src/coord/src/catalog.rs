@@ -20,8 +20,9 @@ use fail::fail_point;
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use mz_dataflow_types::{
-    sinks::SinkEnvelope, sources::persistence::EnvelopePersistDesc,
-    sources::persistence::SourcePersistDesc, sources::ExternalSourceConnector, sources::MzOffset,
+    sinks::SinkEnvelope, sources::decode_error_redirect_position,
+    sources::persistence::EnvelopePersistDesc, sources::persistence::SourcePersistDesc,
+    sources::ExternalSourceConnector, sources::MzOffset,
 };
 use mz_expr::PartitionId;
 use mz_ore::collections::CollectionExt;
@@ -39,7 +40,7 @@ use mz_dataflow_types::{
 };
 use mz_expr::{ExprHumanizer, GlobalId, MirScalarExpr, OptimizedMirRelationExpr};
 use mz_pgrepr::oid::FIRST_USER_OID;
-use mz_repr::{RelationDesc, ScalarType};
+use mz_repr::{ColumnName, RelationDesc, Row, ScalarType};
 use mz_sql::ast::display::AstDisplay;
 use mz_sql::ast::{Expr, Raw};
 use mz_sql::catalog::{
@@ -48,8 +49,8 @@ use mz_sql::catalog::{
 };
 use mz_sql::names::{DatabaseSpecifier, FullName, PartialName, SchemaName};
 use mz_sql::plan::{
-    CreateIndexPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
+    CreateIndexPlan, CreateSinkPlan, CreateSourcePlan, CreateSubscriptionPlan, CreateTablePlan,
+    CreateTypePlan, CreateViewPlan, Params, Plan, PlanContext, StatementDesc,
 };
 use mz_transform::Optimizer;
 use uuid::Uuid;
@@ -245,6 +246,7 @@ impl CatalogState {
             | CatalogItem::Func(_)
             | CatalogItem::Index(_)
             | CatalogItem::Sink(_)
+            | CatalogItem::Subscription(_)
             | CatalogItem::Type(_) => false,
         }
     }
@@ -309,7 +311,10 @@ impl CatalogState {
                     }
                 }
             }
-            CatalogItem::Func(_) | CatalogItem::Sink(_) | CatalogItem::Type(_) => (),
+            CatalogItem::Func(_)
+            | CatalogItem::Sink(_)
+            | CatalogItem::Subscription(_)
+            | CatalogItem::Type(_) => (),
         }
     }
 
@@ -365,11 +370,15 @@ impl CatalogState {
                 SourceConnector::External { connector, .. } => match &connector {
                     ExternalSourceConnector::PubNub(_) => Volatile,
                     ExternalSourceConnector::Kinesis(_) => Volatile,
+                    ExternalSourceConnector::Webhook(_) => Volatile,
                     _ => Unknown,
                 },
                 SourceConnector::Local { .. } => Volatile,
             },
-            CatalogItem::Index(_) | CatalogItem::View(_) | CatalogItem::Sink(_) => {
+            CatalogItem::Index(_)
+            | CatalogItem::View(_)
+            | CatalogItem::Sink(_)
+            | CatalogItem::Subscription(_) => {
                 // Volatility follows trinary logic like SQL. If even one
                 // volatile dependency exists, then this item is volatile.
                 // Otherwise, if a single dependency with unknown volatility
@@ -462,6 +471,7 @@ pub enum CatalogItem {
     Index(Index),
     Type(Type),
     Func(Func),
+    Subscription(Subscription),
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -488,10 +498,28 @@ pub struct Source {
     pub create_sql: String,
     pub connector: SourceConnector,
     pub persist_details: Option<SerializedSourcePersistDetails>,
+    /// The secret with which requests to a webhook source are signed. It is
+    /// kept out of `create_sql` so that it does not leak into `SHOW CREATE`
+    /// or the system catalog.
+    #[serde(skip)]
+    pub webhook_secret: Option<String>,
     pub desc: RelationDesc,
+    pub depends_on: Vec<GlobalId>,
 }
 
 impl Source {
+    /// Returns the name of the persistent stream that records the requests of
+    /// a webhook source, if any.
+    pub fn webhook_persist_name(&self) -> Option<&str> {
+        match &self.connector {
+            SourceConnector::External {
+                connector: ExternalSourceConnector::Webhook(webhook),
+                ..
+            } => webhook.persisted_name.as_deref(),
+            _ => None,
+        }
+    }
+
     pub fn requires_single_materialization(&self) -> bool {
         // Persisted sources must only be persisted once because we use the source ID to derive the
         // names of the persistent collections that back it. If we allowed multiple instances,
@@ -543,6 +571,14 @@ pub struct Type {
     pub depends_on: Vec<GlobalId>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct Subscription {
+    pub create_sql: String,
+    pub from: GlobalId,
+    pub max_lag_ms: u64,
+    pub depends_on: Vec<GlobalId>,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Func {
     #[serde(skip)]
@@ -577,6 +613,7 @@ impl CatalogItem {
             CatalogItem::Index(_) => mz_sql::catalog::CatalogItemType::Index,
             CatalogItem::Type(_) => mz_sql::catalog::CatalogItemType::Type,
             CatalogItem::Func(_) => mz_sql::catalog::CatalogItemType::Func,
+            CatalogItem::Subscription(_) => mz_sql::catalog::CatalogItemType::Subscription,
         }
     }
 
@@ -588,6 +625,7 @@ impl CatalogItem {
             CatalogItem::Func(_)
             | CatalogItem::Index(_)
             | CatalogItem::Sink(_)
+            | CatalogItem::Subscription(_)
             | CatalogItem::Type(_) => Err(SqlCatalogError::InvalidDependency {
                 name: name.to_string(),
                 typ: self.typ(),
@@ -616,7 +654,8 @@ impl CatalogItem {
             CatalogItem::Func(_) => &[],
             CatalogItem::Index(idx) => &idx.depends_on,
             CatalogItem::Sink(sink) => &sink.depends_on,
-            CatalogItem::Source(_) => &[],
+            CatalogItem::Source(source) => &source.depends_on,
+            CatalogItem::Subscription(subscription) => &subscription.depends_on,
             CatalogItem::Table(table) => &table.depends_on,
             CatalogItem::Type(typ) => &typ.depends_on,
             CatalogItem::View(view) => &view.depends_on,
@@ -630,6 +669,7 @@ impl CatalogItem {
             CatalogItem::Func(_)
             | CatalogItem::Index(_)
             | CatalogItem::Source(_)
+            | CatalogItem::Subscription(_)
             | CatalogItem::Table(_)
             | CatalogItem::Type(_)
             | CatalogItem::View(_) => false,
@@ -691,6 +731,11 @@ impl CatalogItem {
                 i.create_sql = do_rewrite(i.create_sql)?;
                 Ok(CatalogItem::Sink(i))
             }
+            CatalogItem::Subscription(i) => {
+                let mut i = i.clone();
+                i.create_sql = do_rewrite(i.create_sql)?;
+                Ok(CatalogItem::Subscription(i))
+            }
             CatalogItem::View(i) => {
                 let mut i = i.clone();
                 i.create_sql = do_rewrite(i.create_sql)?;
@@ -708,11 +753,7 @@ impl CatalogItem {
     }
 
     pub fn requires_single_materialization(&self) -> bool {
-        if let CatalogItem::Source(Source {
-            connector: SourceConnector::External { ref connector, .. },
-            ..
-        }) = self
-        {
+        if let CatalogItem::Source(Source { connector, .. }) = self {
             connector.requires_single_materialization()
         } else {
             false
@@ -903,7 +944,9 @@ impl Catalog {
                                 persisted_name: None,
                             },
                             persist_details: None,
+                            webhook_secret: None,
                             desc: log.variant.desc(),
+                            depends_on: vec![],
                         }),
                     );
                     let oid = catalog.allocate_oid()?;
@@ -992,6 +1035,7 @@ impl Catalog {
                 Builtin::View(view) if config.enable_logging || !view.needs_logs => {
                     let table_persist_name = None;
                     let source_persist_details = None;
+                    let webhook_secret = None;
                     let item = catalog
                         .parse_item(
                             view.id,
@@ -999,6 +1043,7 @@ impl Catalog {
                             None,
                             table_persist_name,
                             source_persist_details,
+                            webhook_secret,
                         )
                         .unwrap_or_else(|e| {
                             panic!(
@@ -1558,16 +1603,17 @@ impl Catalog {
 
     /// Insert timestamp bindings into SQLite, and ignores duplicate timestamp bindings.
     ///
-    /// Each individual binding is listed as (source_id, partition_id, timestamp, offset)
-    /// and it indicates that all data from (source, partition) for offsets < `offset`, can
-    /// be assigned `timestamp` iff `offset` is the minimal such offset (this is a way to encode
-    /// a [start, end) offset interval without having to duplicate adjacent starts and ends in
-    /// storage).
+    /// Each individual binding is listed as (source_id, partition_id, timestamp, offset,
+    /// upstream_position) and it indicates that all data from (source, partition) for offsets <
+    /// `offset`, can be assigned `timestamp` iff `offset` is the minimal such offset (this is a
+    /// way to encode a [start, end) offset interval without having to duplicate adjacent starts
+    /// and ends in storage). The optional `upstream_position` is the source-native position of
+    /// the message at `offset - 1`.
     /// TODO: we intentionally ignore duplicates because BYO sources can send multiple
     /// copies of the same timestamp.
     pub fn insert_timestamp_bindings(
         &mut self,
-        timestamps: impl IntoIterator<Item = (GlobalId, String, Timestamp, i64)>,
+        timestamps: impl IntoIterator<Item = (GlobalId, PartitionId, Timestamp, i64, Option<String>)>,
     ) -> Result<(), Error> {
         fail_point!("insert_timestamp_bindings_before", |_| {
             Err(Error::new(ErrorKind::FailpointReached(
@@ -1578,8 +1624,8 @@ impl Catalog {
         let mut storage = self.storage();
         let tx = storage.transaction()?;
 
-        for (sid, pid, ts, offset) in timestamps.into_iter() {
-            tx.insert_timestamp_binding(&sid, &pid, ts, offset)?;
+        for (sid, pid, ts, offset, upstream_position) in timestamps.into_iter() {
+            tx.insert_timestamp_binding(&sid, &pid, ts, offset, upstream_position.as_deref())?;
         }
         tx.commit()?;
 
@@ -1598,11 +1644,20 @@ impl Catalog {
     pub fn load_timestamp_bindings(
         &mut self,
         source_id: GlobalId,
-    ) -> Result<Vec<(PartitionId, Timestamp, MzOffset)>, Error> {
+    ) -> Result<Vec<(PartitionId, Timestamp, MzOffset, Option<String>)>, Error> {
+        // Bindings recorded by earlier versions are interpreted according to
+        // the kind of the source.
+        let connector = match self.try_get_by_id(source_id).map(|entry| entry.item()) {
+            Some(CatalogItem::Source(Source {
+                connector: SourceConnector::External { connector, .. },
+                ..
+            })) => connector.name(),
+            _ => "local",
+        };
         let mut storage = self.storage();
         let tx = storage.transaction()?;
 
-        let ret = tx.load_timestamp_bindings(source_id)?;
+        let ret = tx.load_timestamp_bindings(source_id, connector)?;
         tx.commit()?;
 
         Ok(ret)
@@ -1625,6 +1680,126 @@ impl Catalog {
         Ok(())
     }
 
+    /// Returns the last timestamp acknowledged by the consumer of a
+    /// subscription, if any.
+    pub fn load_subscription_acknowledgement(
+        &mut self,
+        subscription_id: GlobalId,
+    ) -> Result<Option<Timestamp>, Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        let ret = tx.load_subscription_acknowledgement(subscription_id)?;
+        tx.commit()?;
+        Ok(ret)
+    }
+
+    /// Durably records that the consumer of a subscription has processed all
+    /// updates up to and including `acknowledged`.
+    pub fn set_subscription_acknowledgement(
+        &mut self,
+        subscription_id: GlobalId,
+        acknowledged: Timestamp,
+    ) -> Result<(), Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        tx.set_subscription_acknowledgement(subscription_id, acknowledged)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the position of the last request accepted by a webhook
+    /// source, if any.
+    pub fn load_webhook_position(&mut self, source_id: GlobalId) -> Result<Option<i64>, Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        let ret = tx.load_webhook_position(source_id)?;
+        tx.commit()?;
+        Ok(ret)
+    }
+
+    /// Durably records the position of the last request accepted by a
+    /// webhook source.
+    pub fn set_webhook_position(
+        &mut self,
+        source_id: GlobalId,
+        position: i64,
+    ) -> Result<(), Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        tx.set_webhook_position(source_id, position)?;
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Returns the rows a source redirected to its decode error table that
+    /// describe records the source had not already redirected, e.g. before a
+    /// restart.
+    pub fn new_decode_error_redirects(
+        &mut self,
+        source_id: GlobalId,
+        rows: Vec<Row>,
+    ) -> Result<Vec<Row>, Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        let mut new_rows = vec![];
+        for row in rows {
+            let (partition, offset) = decode_error_redirect_position(&row);
+            if !tx.has_decode_error_redirect(source_id, partition.unwrap_or(""), offset)? {
+                new_rows.push(row);
+            }
+        }
+        tx.commit()?;
+        Ok(new_rows)
+    }
+
+    /// Durably records the rows a source redirected to its decode error table,
+    /// once they have been written to the table.
+    pub fn record_decode_error_redirects(
+        &mut self,
+        source_id: GlobalId,
+        rows: &[Row],
+    ) -> Result<(), Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        for row in rows {
+            let (partition, offset) = decode_error_redirect_position(row);
+            tx.insert_decode_error_redirect(source_id, partition.unwrap_or(""), offset)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Forgets the records a source redirected to its decode error table that
+    /// precede the offsets from which it resumed reading.
+    pub fn compact_decode_error_redirects(
+        &mut self,
+        source_id: GlobalId,
+        offsets: &[(PartitionId, MzOffset)],
+    ) -> Result<(), Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        for (pid, offset) in offsets {
+            // Matches the partitions of the rows that describe the records.
+            let pid = match pid {
+                PartitionId::None => String::new(),
+                pid => pid.to_string(),
+            };
+            tx.compact_decode_error_redirects(source_id, &pid, offset.offset)?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Forgets which records a source has redirected to its decode error
+    /// table, for when the table's contents did not survive a restart.
+    pub fn forget_decode_error_redirects(&mut self, source_id: GlobalId) -> Result<(), Error> {
+        let mut storage = self.storage();
+        let tx = storage.transaction()?;
+        tx.delete_decode_error_redirects(source_id)?;
+        tx.commit()?;
+        Ok(())
+    }
+
     pub fn transact<F, T>(
         &mut self,
         ops: Vec<Op>,
@@ -1844,6 +2019,11 @@ impl Catalog {
                         }
                         CatalogItem::Source(_) => {
                             tx.delete_timestamp_bindings(id)?;
+                            tx.delete_webhook_position(id)?;
+                            tx.delete_decode_error_redirects(id)?;
+                        }
+                        CatalogItem::Subscription(_) => {
+                            tx.delete_subscription_acknowledgement(id)?;
                         }
                         _ => {}
                     }
@@ -2104,6 +2284,41 @@ impl Catalog {
         Ok((builtin_table_updates, result))
     }
 
+    /// Returns the view `id` with its references to `source` replaced by
+    /// subqueries that select only `columns`, or errors if the rewritten
+    /// definition no longer plans to the view's existing relation.
+    ///
+    /// This keeps the persisted definition of a view that selects `*` from
+    /// `source` stable when columns are appended to `source`.
+    pub fn project_view_refs(
+        &self,
+        id: GlobalId,
+        source: &FullName,
+        columns: &[ColumnName],
+    ) -> Result<CatalogItem, anyhow::Error> {
+        let entry = self.get_by_id(&id);
+        let view = match entry.item() {
+            CatalogItem::View(view) => view,
+            _ => unreachable!("{} is not a view", id),
+        };
+        let mut create_stmt = mz_sql::parse::parse(&view.create_sql)?.into_element();
+        mz_sql::ast::transform::create_stmt_project_refs(&mut create_stmt, source.clone(), columns);
+        let create_sql = create_stmt.to_ast_string_stable();
+        match self.parse_item(id, create_sql, Some(&PlanContext::zero()), None, None, None) {
+            Ok(CatalogItem::View(new_view)) if new_view.desc.iter().eq(view.desc.iter()) => {
+                Ok(CatalogItem::View(View {
+                    create_sql: new_view.create_sql,
+                    ..view.clone()
+                }))
+            }
+            _ => bail!(
+                "cannot rewrite view {} to select only the existing columns of {}",
+                entry.name(),
+                source
+            ),
+        }
+    }
+
     fn serialize_item(&self, item: &CatalogItem) -> Vec<u8> {
         let item = match item {
             CatalogItem::Table(table) => SerializedCatalogItem::V1 {
@@ -2111,36 +2326,51 @@ impl Catalog {
                 eval_env: None,
                 table_persist_name: table.persist_name.clone(),
                 source_persist_details: None,
+                webhook_secret: None,
             },
             CatalogItem::Source(source) => SerializedCatalogItem::V1 {
                 create_sql: source.create_sql.clone(),
                 eval_env: None,
-                table_persist_name: None,
+                // Webhook sources record their requests like the rows of a
+                // table.
+                table_persist_name: source.webhook_persist_name().map(str::to_owned),
                 source_persist_details: source.persist_details.clone(),
+                webhook_secret: source.webhook_secret.clone(),
             },
             CatalogItem::View(view) => SerializedCatalogItem::V1 {
                 create_sql: view.create_sql.clone(),
                 eval_env: None,
                 table_persist_name: None,
                 source_persist_details: None,
+                webhook_secret: None,
             },
             CatalogItem::Index(index) => SerializedCatalogItem::V1 {
                 create_sql: index.create_sql.clone(),
                 eval_env: None,
                 table_persist_name: None,
                 source_persist_details: None,
+                webhook_secret: None,
             },
             CatalogItem::Sink(sink) => SerializedCatalogItem::V1 {
                 create_sql: sink.create_sql.clone(),
                 eval_env: None,
                 table_persist_name: None,
                 source_persist_details: None,
+                webhook_secret: None,
+            },
+            CatalogItem::Subscription(subscription) => SerializedCatalogItem::V1 {
+                create_sql: subscription.create_sql.clone(),
+                eval_env: None,
+                table_persist_name: None,
+                source_persist_details: None,
+                webhook_secret: None,
             },
             CatalogItem::Type(typ) => SerializedCatalogItem::V1 {
                 create_sql: typ.create_sql.clone(),
                 eval_env: None,
                 table_persist_name: None,
                 source_persist_details: None,
+                webhook_secret: None,
             },
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         };
@@ -2153,6 +2383,7 @@ impl Catalog {
             eval_env: _,
             table_persist_name,
             source_persist_details,
+            webhook_secret,
         } = serde_json::from_slice(&bytes)?;
         self.parse_item(
             id,
@@ -2160,13 +2391,15 @@ impl Catalog {
             Some(&PlanContext::zero()),
             table_persist_name,
             source_persist_details,
+            webhook_secret,
         )
     }
 
     // Parses the given SQL string into a `CatalogItem`.
     //
     // The given `persist_details` are an optional description of the persisted streams that this
-    // source uses, if it is a persisted source.
+    // source uses, if it is a persisted source. The given `webhook_secret` is the secret of a
+    // webhook source, which is not part of its `create_sql`.
     fn parse_item(
         &self,
         id: GlobalId,
@@ -2174,6 +2407,7 @@ impl Catalog {
         pcx: Option<&PlanContext>,
         table_persist_name: Option<String>,
         source_persist_details: Option<SerializedSourcePersistDetails>,
+        webhook_secret: Option<String>,
     ) -> Result<CatalogItem, anyhow::Error> {
         let stmt = mz_sql::parse::parse(&create_sql)?.into_element();
         let plan = mz_sql::plan::plan(pcx, &self.for_system_session(), stmt, &Params::empty())?;
@@ -2193,15 +2427,24 @@ impl Catalog {
                 })
             }
             Plan::CreateSource(CreateSourcePlan { source, .. }) => {
-                assert!(
-                    table_persist_name.is_none(),
-                    "got some table_persist_name while we didn't expect them for a source"
-                );
+                let mut connector = source.connector;
+                match &mut connector {
+                    SourceConnector::External {
+                        connector: ExternalSourceConnector::Webhook(webhook),
+                        ..
+                    } => webhook.persisted_name = table_persist_name,
+                    _ => assert!(
+                        table_persist_name.is_none(),
+                        "got some table_persist_name while we didn't expect them for a source"
+                    ),
+                }
                 CatalogItem::Source(Source {
                     create_sql: source.create_sql,
-                    connector: source.connector,
+                    connector,
                     persist_details: source_persist_details,
+                    webhook_secret,
                     desc: source.desc,
+                    depends_on: source.depends_on,
                 })
             }
             Plan::CreateView(CreateViewPlan { view, .. }) => {
@@ -2236,6 +2479,14 @@ impl Catalog {
                 with_snapshot,
                 depends_on: sink.depends_on,
             }),
+            Plan::CreateSubscription(CreateSubscriptionPlan { subscription, .. }) => {
+                CatalogItem::Subscription(Subscription {
+                    create_sql: subscription.create_sql,
+                    from: subscription.from,
+                    max_lag_ms: subscription.max_lag_ms,
+                    depends_on: subscription.depends_on,
+                })
+            }
             Plan::CreateType(CreateTypePlan { typ, .. }) => CatalogItem::Type(Type {
                 create_sql: typ.create_sql,
                 details: CatalogTypeDetails {
@@ -2467,6 +2718,8 @@ enum SerializedCatalogItem {
         #[serde(alias = "persist_name")]
         table_persist_name: Option<String>,
         source_persist_details: Option<SerializedSourcePersistDetails>,
+        #[serde(default)]
+        webhook_secret: Option<String>,
     },
 }
 
@@ -2793,6 +3046,7 @@ impl mz_sql::catalog::CatalogItem for CatalogEntry {
             CatalogItem::Table(Table { create_sql, .. }) => create_sql,
             CatalogItem::Source(Source { create_sql, .. }) => create_sql,
             CatalogItem::Sink(Sink { create_sql, .. }) => create_sql,
+            CatalogItem::Subscription(Subscription { create_sql, .. }) => create_sql,
             CatalogItem::View(View { create_sql, .. }) => create_sql,
             CatalogItem::Index(Index { create_sql, .. }) => create_sql,
             CatalogItem::Type(Type { create_sql, .. }) => create_sql,
@@ -1023,6 +1023,18 @@ lazy_static! {
         index_id: GlobalId::System(4008),
         persistent: false,
     };
+    pub static ref MZ_FILE_SINKS: BuiltinTable = BuiltinTable {
+        name: "mz_file_sinks",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("sink_id", ScalarType::String.nullable(false))
+            .with_column("bucket", ScalarType::String.nullable(true))
+            .with_column("path", ScalarType::String.nullable(false))
+            .with_key(vec![0]),
+        id: GlobalId::System(4049),
+        index_id: GlobalId::System(4050),
+        persistent: false,
+    };
     pub static ref MZ_DATABASES: BuiltinTable = BuiltinTable {
         name: "mz_databases",
         schema: MZ_CATALOG_SCHEMA,
@@ -2037,6 +2049,13 @@ pub const MZ_SYSTEM: BuiltinRole = BuiltinRole {
     id: -1,
 };
 
+/// The role as which requests to webhook sources are executed. Those requests
+/// are authorized by their signatures alone, rather than by logging in.
+pub const MZ_WEBHOOK: BuiltinRole = BuiltinRole {
+    name: "mz_webhook",
+    id: -2,
+};
+
 lazy_static! {
     pub static ref BUILTINS: BTreeMap<GlobalId, Builtin> = {
         let mut builtins = vec![
@@ -2119,6 +2138,7 @@ lazy_static! {
             Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
             Builtin::Table(&MZ_KAFKA_SINKS),
             Builtin::Table(&MZ_AVRO_OCF_SINKS),
+            Builtin::Table(&MZ_FILE_SINKS),
             Builtin::Table(&MZ_DATABASES),
             Builtin::Table(&MZ_SCHEMAS),
             Builtin::Table(&MZ_COLUMNS),
@@ -2243,7 +2263,7 @@ lazy_static! {
         builtins.into_iter().map(|b| (b.id(), b)).collect()
     };
 
-    pub static ref BUILTIN_ROLES: Vec<BuiltinRole> = vec![MZ_SYSTEM];
+    pub static ref BUILTIN_ROLES: Vec<BuiltinRole> = vec![MZ_SYSTEM, MZ_WEBHOOK];
 }
 
 impl BUILTINS {
@@ -9,7 +9,9 @@
 
 use std::os::unix::ffi::OsStringExt;
 
-use mz_dataflow_types::sinks::{AvroOcfSinkConnector, KafkaSinkConnector};
+use mz_dataflow_types::sinks::{
+    AvroOcfSinkConnector, FileSinkConnector, FileSinkTarget, KafkaSinkConnector,
+};
 use mz_expr::{GlobalId, MirScalarExpr};
 use mz_ore::collections::CollectionExt;
 use mz_repr::adt::array::ArrayDimension;
@@ -20,9 +22,9 @@ use mz_sql::names::DatabaseSpecifier;
 use mz_sql_parser::ast::display::AstDisplay;
 
 use crate::catalog::builtin::{
-    MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES, MZ_FUNCTIONS,
-    MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES, MZ_PSEUDO_TYPES,
-    MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
+    MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES, MZ_FILE_SINKS,
+    MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES,
+    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
 };
 use crate::catalog::{
     CatalogItem, CatalogState, Func, Index, Sink, SinkConnector, SinkConnectorState, Source, Table,
@@ -114,6 +116,8 @@ impl CatalogState {
             CatalogItem::Sink(sink) => self.pack_sink_update(id, oid, schema_id, name, sink, diff),
             CatalogItem::Type(ty) => self.pack_type_update(id, oid, schema_id, name, ty, diff),
             CatalogItem::Func(func) => self.pack_func_update(id, schema_id, name, func, diff),
+            // Subscriptions are not yet exposed in the system catalog.
+            CatalogItem::Subscription(_) => vec![],
         };
 
         if let Ok(desc) = entry.desc() {
@@ -262,6 +266,25 @@ impl CatalogState {
                         diff,
                     });
                 }
+                SinkConnector::File(FileSinkConnector { target, .. }) => {
+                    let (bucket, path) = match target {
+                        FileSinkTarget::Local { path } => {
+                            (Datum::Null, path.to_string_lossy().into_owned())
+                        }
+                        FileSinkTarget::S3 { bucket, prefix, .. } => {
+                            (Datum::String(bucket.as_str()), prefix.clone())
+                        }
+                    };
+                    updates.push(BuiltinTableUpdate {
+                        id: MZ_FILE_SINKS.id,
+                        row: Row::pack_slice(&[
+                            Datum::String(&id.to_string()),
+                            bucket,
+                            Datum::String(&path),
+                        ]),
+                        diff,
+                    });
+                }
                 _ => (),
             }
             updates.push(BuiltinTableUpdate {
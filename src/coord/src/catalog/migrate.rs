@@ -51,6 +51,7 @@ where
             eval_env,
             table_persist_name,
             source_persist_details,
+            webhook_secret,
         } = serde_json::from_slice(&def)?;
         let mut stmt = mz_sql::parse::parse(&create_sql)?.into_element();
 
@@ -61,6 +62,7 @@ where
             eval_env,
             table_persist_name,
             source_persist_details,
+            webhook_secret,
         };
 
         let serialized_item =
@@ -410,8 +412,10 @@ fn ast_rewrite_pg_catalog_char_to_text_0_9_1(
         }
 
         // At the time the migration was written, sinks and sources
-        // could not contain references to types.
-        Statement::CreateSource(_) | Statement::CreateSink(_) => {}
+        // could not contain references to types. Subscriptions never do.
+        Statement::CreateSource(_)
+        | Statement::CreateSink(_)
+        | Statement::CreateSubscription(_) => {}
 
         _ => bail!("catalog item contained inappropriate statement: {}", stmt),
     };
@@ -581,8 +585,12 @@ fn ast_use_pg_catalog_0_7_1(stmt: &mut mz_sql::ast::Statement<Raw>) -> Result<()
         }
 
         // At the time the migration was written, tables, sources, and
-        // types could not contain references to functions.
-        Statement::CreateTable(_) | Statement::CreateSource(_) | Statement::CreateType(_) => {}
+        // types could not contain references to functions. Subscriptions
+        // never do.
+        Statement::CreateTable(_)
+        | Statement::CreateSource(_)
+        | Statement::CreateSubscription(_)
+        | Statement::CreateType(_) => {}
 
         _ => bail!("catalog item contained inappropriate statement: {}", stmt),
     };
@@ -679,8 +687,10 @@ fn ast_rewrite_type_references_0_6_1(
         },
 
         // At the time the migration was written, sinks and sources
-        // could not contain references to types.
-        Statement::CreateSource(_) | Statement::CreateSink(_) => {}
+        // could not contain references to types. Subscriptions never do.
+        Statement::CreateSource(_)
+        | Statement::CreateSink(_)
+        | Statement::CreateSubscription(_) => {}
 
         _ => bail!("catalog item contained inappropriate statement: {}", stmt),
     };
@@ -732,7 +742,7 @@ fn ast_rewrite_csv_column_aliases_0_9_2(
             })?;
 
             block_on(async {
-                mz_sql::pure::purify_csv(file, &connector, *delimiter, columns).await
+                mz_sql::pure::purify_csv(file, None, &connector, *delimiter, columns).await
             })?;
         }
         Ok(())
@@ -132,6 +132,47 @@ const MIGRATIONS: &[&str] = &[
     //
     // Introduced in v0.12.0.
     "CREATE INDEX timestamps_sid_timestamp ON timestamps (sid, timestamp)",
+    // Records the upstream position of the last message covered by a timestamp
+    // binding, for sources whose upstream positions are not offsets (Kinesis).
+    //
+    // Introduced in v0.22.1.
+    "ALTER TABLE timestamps ADD COLUMN upstream_position text",
+    // Records the last timestamp acknowledged by the consumer of each
+    // subscription, so that subscriptions can resume after a restart.
+    //
+    // Introduced in v0.22.1.
+    "CREATE TABLE subscriptions (
+        gid blob PRIMARY KEY,
+        acknowledged integer NOT NULL
+    );",
+    // Records the partition of each timestamp binding as a serialized
+    // `PartitionId`, as the `pid` column holds its display form, which does
+    // not identify the kind of partition. Bindings recorded by earlier
+    // versions have no `partition` and are interpreted according to the kind
+    // of their source.
+    //
+    // Introduced in v0.22.1.
+    "ALTER TABLE timestamps ADD COLUMN partition blob",
+    // Records the position of the last request accepted by each webhook
+    // source, so that requests keep being numbered in order after a restart.
+    //
+    // Introduced in v0.22.1.
+    "CREATE TABLE webhook_positions (
+        gid blob PRIMARY KEY,
+        position integer NOT NULL
+    );",
+    // Records the records that each source has redirected to its decode error
+    // table, so that records the source reads again after a restart are not
+    // inserted into the table twice. `pid` is the display form of the
+    // record's partition, or the empty string for unpartitioned sources.
+    //
+    // Introduced in v0.22.1.
+    "CREATE TABLE decode_error_redirects (
+        gid blob NOT NULL,
+        pid text NOT NULL,
+        \"offset\" integer NOT NULL,
+        PRIMARY KEY (gid, pid, \"offset\")
+    );",
     // Add new migrations here.
     //
     // Migrations should be preceded with a comment of the following form:
@@ -453,15 +494,17 @@ impl Transaction<'_> {
 
     fn validate_timestamp_bindings(&self, source_id: &GlobalId) -> Result<(), String> {
         let bindings_vec = self
-            .load_timestamp_bindings(*source_id)
+            .load_raw_timestamp_bindings(*source_id)
             .map_err(|e| format!("{}", e))?;
 
-        let bindings_by_pid = bindings_vec.iter().group_by(|(pid, _ts, _offset)| pid);
+        let bindings_by_pid = bindings_vec
+            .iter()
+            .group_by(|(pid, _partition, _ts, _offset, _upstream_position)| pid);
 
         for (pid, bindings) in &bindings_by_pid {
             let mut latest_offset = 0;
             let mut latest_ts = 0;
-            for (_pid, ts, offset) in bindings {
+            for (_pid, _partition, ts, offset, _upstream_position) in bindings {
                 if offset.offset < latest_offset {
                     return Err(format!(
                         "Unexpected offset {} for pid {}. All bindings: {:?}",
@@ -482,21 +525,51 @@ impl Transaction<'_> {
         Ok(())
     }
 
+    /// Loads the timestamp bindings of a source, whose connector is named
+    /// `connector` (see `ExternalSourceConnector::name`).
     pub fn load_timestamp_bindings(
         &self,
         source_id: GlobalId,
-    ) -> Result<Vec<(PartitionId, Timestamp, MzOffset)>, Error> {
+        connector: &str,
+    ) -> Result<Vec<(PartitionId, Timestamp, MzOffset, Option<String>)>, Error> {
+        self.load_raw_timestamp_bindings(source_id)?
+            .into_iter()
+            .map(|(pid, partition, timestamp, offset, upstream_position)| {
+                let partition = match partition {
+                    Some(partition) => partition,
+                    None => parse_legacy_partition_id(&pid, connector)?,
+                };
+                Ok((partition, timestamp, offset, upstream_position))
+            })
+            .collect()
+    }
+
+    fn load_raw_timestamp_bindings(
+        &self,
+        source_id: GlobalId,
+    ) -> Result<
+        Vec<(
+            String,
+            Option<PartitionId>,
+            Timestamp,
+            MzOffset,
+            Option<String>,
+        )>,
+        Error,
+    > {
         self.inner
             .prepare_cached(
-                "SELECT pid, timestamp, offset from timestamps where sid = ? order by pid, timestamp")?
+                "SELECT pid, partition, timestamp, offset, upstream_position from timestamps where sid = ? order by pid, timestamp")?
             .query_and_then(params![SqlVal(&source_id)], |row| -> Result<_, Error> {
-                let partition: PartitionId = row.get::<_, String>(0)?.parse().expect("parsing partition id from string cannot fail");
-                let timestamp: Timestamp = row.get(1)?;
+                let pid: String = row.get(0)?;
+                let partition: Option<SqlVal<PartitionId>> = row.get(1)?;
+                let timestamp: Timestamp = row.get(2)?;
                 let offset = MzOffset {
-                    offset: row.get(2)?,
+                    offset: row.get(3)?,
                 };
+                let upstream_position: Option<String> = row.get(4)?;
 
-                Ok((partition, timestamp, offset))
+                Ok((pid, partition.map(|p| p.0), timestamp, offset, upstream_position))
             })?
             .collect()
     }
@@ -568,16 +641,17 @@ impl Transaction<'_> {
     pub fn insert_timestamp_binding(
         &self,
         source_id: &GlobalId,
-        partition_id: &str,
+        partition_id: &PartitionId,
         timestamp: Timestamp,
         offset: i64,
+        upstream_position: Option<&str>,
     ) -> Result<(), Error> {
         let result = self
             .inner
             .prepare_cached(
-                "INSERT OR IGNORE INTO timestamps (sid, pid, timestamp, offset) VALUES (?, ?, ?, ?)",
+                "INSERT OR IGNORE INTO timestamps (sid, pid, partition, timestamp, offset, upstream_position) VALUES (?, ?, ?, ?, ?, ?)",
             )?
-              .execute(params![SqlVal(source_id), partition_id, timestamp, offset]);
+              .execute(params![SqlVal(source_id), partition_id.to_string(), SqlVal(partition_id), timestamp, offset, upstream_position]);
 
         soft_assert_eq!(self.validate_timestamp_bindings(source_id), Ok(()));
 
@@ -599,6 +673,124 @@ impl Transaction<'_> {
         }
     }
 
+    pub fn load_subscription_acknowledgement(
+        &self,
+        subscription_id: GlobalId,
+    ) -> Result<Option<Timestamp>, Error> {
+        Ok(self
+            .inner
+            .prepare_cached("SELECT acknowledged FROM subscriptions WHERE gid = ?")?
+            .query_row(params![SqlVal(&subscription_id)], |row| row.get(0))
+            .optional()?)
+    }
+
+    pub fn set_subscription_acknowledgement(
+        &self,
+        subscription_id: GlobalId,
+        acknowledged: Timestamp,
+    ) -> Result<(), Error> {
+        self.inner
+            .prepare_cached(
+                "INSERT OR REPLACE INTO subscriptions (gid, acknowledged) VALUES (?, ?)",
+            )?
+            .execute(params![SqlVal(&subscription_id), acknowledged])?;
+        Ok(())
+    }
+
+    pub fn delete_subscription_acknowledgement(
+        &self,
+        subscription_id: GlobalId,
+    ) -> Result<(), Error> {
+        self.inner
+            .prepare_cached("DELETE FROM subscriptions WHERE gid = ?")?
+            .execute(params![SqlVal(&subscription_id)])?;
+        Ok(())
+    }
+
+    pub fn load_webhook_position(&self, source_id: GlobalId) -> Result<Option<i64>, Error> {
+        Ok(self
+            .inner
+            .prepare_cached("SELECT position FROM webhook_positions WHERE gid = ?")?
+            .query_row(params![SqlVal(&source_id)], |row| row.get(0))
+            .optional()?)
+    }
+
+    pub fn set_webhook_position(&self, source_id: GlobalId, position: i64) -> Result<(), Error> {
+        self.inner
+            .prepare_cached(
+                "INSERT OR REPLACE INTO webhook_positions (gid, position) VALUES (?, ?)",
+            )?
+            .execute(params![SqlVal(&source_id), position])?;
+        Ok(())
+    }
+
+    pub fn delete_webhook_position(&self, source_id: GlobalId) -> Result<(), Error> {
+        self.inner
+            .prepare_cached("DELETE FROM webhook_positions WHERE gid = ?")?
+            .execute(params![SqlVal(&source_id)])?;
+        Ok(())
+    }
+
+    /// Reports whether a source has redirected the record at the given
+    /// partition and offset to its decode error table.
+    pub fn has_decode_error_redirect(
+        &self,
+        source_id: GlobalId,
+        pid: &str,
+        offset: i64,
+    ) -> Result<bool, Error> {
+        Ok(self
+            .inner
+            .prepare_cached(
+                "SELECT 1 FROM decode_error_redirects WHERE gid = ? AND pid = ? AND \"offset\" = ?",
+            )?
+            .query_row(params![SqlVal(&source_id), pid, offset], |_| Ok(()))
+            .optional()?
+            .is_some())
+    }
+
+    /// Records that a source redirected the record at the given partition and
+    /// offset to its decode error table. Returns whether the record had not
+    /// been redirected before.
+    pub fn insert_decode_error_redirect(
+        &self,
+        source_id: GlobalId,
+        pid: &str,
+        offset: i64,
+    ) -> Result<bool, Error> {
+        let inserted = self
+            .inner
+            .prepare_cached(
+                "INSERT OR IGNORE INTO decode_error_redirects (gid, pid, \"offset\") VALUES (?, ?, ?)",
+            )?
+            .execute(params![SqlVal(&source_id), pid, offset])?;
+        Ok(inserted > 0)
+    }
+
+    pub fn delete_decode_error_redirects(&self, source_id: GlobalId) -> Result<(), Error> {
+        self.inner
+            .prepare_cached("DELETE FROM decode_error_redirects WHERE gid = ?")?
+            .execute(params![SqlVal(&source_id)])?;
+        Ok(())
+    }
+
+    /// Forgets the records before `offset` in the given partition that a
+    /// source redirected to its decode error table, as the source never reads
+    /// them again.
+    pub fn compact_decode_error_redirects(
+        &self,
+        source_id: GlobalId,
+        pid: &str,
+        offset: i64,
+    ) -> Result<(), Error> {
+        self.inner
+            .prepare_cached(
+                "DELETE FROM decode_error_redirects WHERE gid = ? AND pid = ? AND \"offset\" < ?",
+            )?
+            .execute(params![SqlVal(&source_id), pid, offset])?;
+        Ok(())
+    }
+
     pub fn compact_timestamp_bindings(
         &self,
         source_id: GlobalId,
@@ -701,6 +893,32 @@ impl Transaction<'_> {
     }
 }
 
+/// Parses the display form of a partition ID that was recorded before
+/// partition IDs were stored in their serialized form, according to the kind
+/// of the source it belongs to.
+fn parse_legacy_partition_id(pid: &str, connector: &str) -> Result<PartitionId, Error> {
+    let invalid = || {
+        Error::new(ErrorKind::Corruption {
+            detail: format!("invalid partition ID {} for {} source", pid, connector),
+        })
+    };
+    match (connector, pid) {
+        (_, "none") => Ok(PartitionId::None),
+        // Kafka topic names cannot contain colons, so the last colon separates
+        // the topic from the partition.
+        ("kafka", pid) => match pid.rsplit_once(':') {
+            Some((topic, partition)) => Ok(PartitionId::KafkaTopic(
+                topic.to_owned(),
+                partition.parse().map_err(|_| invalid())?,
+            )),
+            None => Ok(PartitionId::Kafka(pid.parse().map_err(|_| invalid())?)),
+        },
+        ("kinesis", pid) => Ok(PartitionId::Kinesis(pid.to_owned())),
+        ("file" | "avro-ocf", pid) => Ok(PartitionId::File(pid.to_owned())),
+        _ => Err(invalid()),
+    }
+}
+
 fn is_constraint_violation(err: &rusqlite::Error) -> bool {
     match err {
         rusqlite::Error::SqliteFailure(err, _) => {
@@ -737,3 +955,82 @@ where
         ))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tempfile::NamedTempFile;
+
+    use mz_dataflow_types::sources::MzOffset;
+    use mz_expr::{GlobalId, PartitionId};
+
+    use super::{parse_legacy_partition_id, Connection};
+
+    #[test]
+    fn test_timestamp_binding_partitions() -> Result<(), anyhow::Error> {
+        let catalog_file = NamedTempFile::new()?;
+        let mut conn = Connection::open(catalog_file.path(), Some(false))?;
+        let source_id = GlobalId::User(1);
+
+        // The display form of this partition resembles a Kinesis shard ID,
+        // but its serialized form identifies it as a Kafka partition.
+        let partition = PartitionId::KafkaTopic("shardId-foo".into(), 3);
+        let tx = conn.transaction()?;
+        tx.insert_timestamp_binding(&source_id, &partition, 1, 10, None)?;
+        assert_eq!(
+            tx.load_timestamp_bindings(source_id, "kafka")?,
+            vec![(partition.clone(), 1, MzOffset { offset: 10 }, None)]
+        );
+        tx.commit()?;
+
+        // Bindings recorded before partitions were serialized are interpreted
+        // according to the kind of their source.
+        assert_eq!(
+            parse_legacy_partition_id("shardId-foo:3", "kafka")?,
+            partition
+        );
+        assert_eq!(
+            parse_legacy_partition_id("3", "kafka")?,
+            PartitionId::Kafka(3)
+        );
+        assert_eq!(
+            parse_legacy_partition_id("shardId-000000000000", "kinesis")?,
+            PartitionId::Kinesis("shardId-000000000000".into())
+        );
+        assert_eq!(
+            parse_legacy_partition_id("/data/1.log", "file")?,
+            PartitionId::File("/data/1.log".into())
+        );
+        assert_eq!(parse_legacy_partition_id("none", "s3")?, PartitionId::None);
+        assert!(parse_legacy_partition_id("shardId-foo", "kafka").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_error_redirects() -> Result<(), anyhow::Error> {
+        let catalog_file = NamedTempFile::new()?;
+        let mut conn = Connection::open(catalog_file.path(), Some(false))?;
+        let source_id = GlobalId::User(1);
+
+        let tx = conn.transaction()?;
+        assert!(tx.insert_decode_error_redirect(source_id, "0", 5)?);
+        assert!(tx.insert_decode_error_redirect(source_id, "1", 5)?);
+        assert!(tx.insert_decode_error_redirect(GlobalId::User(2), "0", 5)?);
+        // Records read again after a restart are not redirected twice.
+        assert!(tx.has_decode_error_redirect(source_id, "0", 5)?);
+        assert!(!tx.has_decode_error_redirect(source_id, "0", 6)?);
+        assert!(!tx.insert_decode_error_redirect(source_id, "0", 5)?);
+        // Records before the offsets the source resumed from are forgotten.
+        tx.compact_decode_error_redirects(source_id, "0", 6)?;
+        assert!(!tx.has_decode_error_redirect(source_id, "0", 5)?);
+        assert!(tx.has_decode_error_redirect(source_id, "1", 5)?);
+        tx.compact_decode_error_redirects(source_id, "1", 5)?;
+        assert!(tx.has_decode_error_redirect(source_id, "1", 5)?);
+        tx.delete_decode_error_redirects(source_id)?;
+        assert!(tx.insert_decode_error_redirect(source_id, "1", 5)?);
+        assert!(tx.has_decode_error_redirect(GlobalId::User(2), "0", 5)?);
+        tx.commit()?;
+
+        Ok(())
+    }
+}
@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
 use std::future::Future;
 use std::sync::Arc;
 use std::time::Instant;
@@ -20,6 +21,7 @@ use mz_ore::collections::CollectionExt;
 use mz_ore::thread::JoinOnDropHandle;
 use mz_repr::{Datum, Row, ScalarType};
 use mz_sql::ast::{Raw, Statement};
+use mz_sql::names::PartialName;
 
 use crate::command::{
     Canceled, Command, ExecuteResponse, Response, SimpleExecuteResponse, SimpleResult,
@@ -380,6 +382,27 @@ impl SessionClient {
         .await
     }
 
+    /// Appends a request to the named webhook source within the current
+    /// transaction.
+    ///
+    /// `headers` must be keyed by lowercase header name, and must include the
+    /// source's signature header.
+    pub async fn append_webhook(
+        &mut self,
+        name: PartialName,
+        body: Vec<u8>,
+        headers: BTreeMap<String, String>,
+    ) -> Result<(), CoordError> {
+        self.send(|tx, session| Command::AppendWebhook {
+            name,
+            body,
+            headers,
+            session,
+            tx,
+        })
+        .await
+    }
+
     /// Executes SQL statements using a simple protocol that does not involve
     /// portals.
     ///
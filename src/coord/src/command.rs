@@ -7,6 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::collections::BTreeMap;
 use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
@@ -21,6 +22,7 @@ use mz_expr::GlobalId;
 use mz_ore::str::StrExt;
 use mz_repr::{Row, ScalarType};
 use mz_sql::ast::{FetchDirection, NoticeSeverity, ObjectType, Raw, Statement};
+use mz_sql::names::PartialName;
 use mz_sql::plan::ExecuteTimeout;
 use tokio::sync::watch;
 
@@ -94,6 +96,14 @@ pub enum Command {
         tx: oneshot::Sender<Response<ExecuteResponse>>,
     },
 
+    AppendWebhook {
+        name: PartialName,
+        body: Vec<u8>,
+        headers: BTreeMap<String, String>,
+        session: Session,
+        tx: oneshot::Sender<Response<()>>,
+    },
+
     Terminate {
         session: Session,
     },
@@ -202,6 +212,10 @@ pub enum ExecuteResponse {
     },
     /// The requested sources were created.
     CreatedSources,
+    /// The requested subscription was created.
+    CreatedSubscription {
+        existed: bool,
+    },
     /// The requested table was created.
     CreatedTable {
         existed: bool,
@@ -240,6 +254,8 @@ pub enum ExecuteResponse {
     DroppedIndex,
     /// The requested sink was dropped.
     DroppedSink,
+    /// The requested subscription was dropped.
+    DroppedSubscription,
     /// The requested type was dropped.
     DroppedType,
     /// The provided query was empty.
@@ -84,8 +84,10 @@
 //!
 
 use std::cell::RefCell;
+use std::cmp;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::future::Future;
+use std::mem;
 use std::rc::Rc;
 use std::sync::Arc;
 use std::thread;
@@ -115,9 +117,12 @@ use mz_dataflow_types::client::{
     CreateSourceCommand, Response as DataflowResponse, StorageResponse,
 };
 use mz_dataflow_types::logging::LoggingConfig as DataflowLoggingConfig;
-use mz_dataflow_types::sinks::{SinkAsOf, SinkConnector, SinkDesc, TailSinkConnector};
+use mz_dataflow_types::sinks::{
+    KafkaSinkConnector, SinkAsOf, SinkConnector, SinkDesc, TailSinkConnector,
+};
 use mz_dataflow_types::sources::{
-    AwsExternalId, ExternalSourceConnector, PostgresSourceConnector, SourceConnector, Timeline,
+    AwsExternalId, DecodeErrorPolicy, ExternalSourceConnector, PostgresSourceConnector,
+    SourceConnector, Timeline, WebhookSourceConnector,
 };
 use mz_dataflow_types::{
     BuildDesc, DataflowDesc, DataflowDescription, IndexDesc, PeekResponse, PeekResponseUnary,
@@ -142,20 +147,21 @@ use mz_sql::ast::{
     FetchStatement, Ident, InsertSource, ObjectType, Query, Raw, SetExpr, Statement,
 };
 use mz_sql::catalog::{CatalogError, CatalogTypeDetails, SessionCatalog as _};
-use mz_sql::names::{DatabaseSpecifier, FullName};
+use mz_sql::names::{DatabaseSpecifier, FullName, PartialName};
 use mz_sql::plan::{
     AlterIndexEnablePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan, CreateSchemaPlan,
-    CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
-    CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan,
-    ExplainPlan, FetchPlan, HirRelationExpr, IndexOption, IndexOptionName, InsertPlan,
-    MutationKind, Params, PeekPlan, PeekWhen, Plan, RaisePlan, ReadThenWritePlan, SendDiffsPlan,
-    SetVariablePlan, ShowVariablePlan, TailFrom, TailPlan,
+    AlterItemRenamePlan, AlterSinkPlan, AlterSourcePlan, AlterSubscriptionPlan, CreateDatabasePlan,
+    CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan,
+    CreateSubscriptionPlan, CreateTablePlan, CreateTypePlan, CreateViewPlan, CreateViewsPlan,
+    DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan, ExecutePlan, ExplainPlan,
+    FetchPlan, HirRelationExpr, IndexOption, IndexOptionName, InsertPlan, MutationKind, Params,
+    PeekPlan, PeekWhen, Plan, RaisePlan, ReadThenWritePlan, SendDiffsPlan, SetVariablePlan,
+    ShowVariablePlan, TailFrom, TailPlan,
 };
 use mz_sql::plan::{OptimizerConfig, StatementDesc, View};
 use mz_transform::Optimizer;
 
-use self::arrangement_state::{ArrangementFrontiers, Frontiers, SinkWrites};
+use self::arrangement_state::{ArrangementFrontiers, Frontiers, SinkWrites, SubscriptionReads};
 use self::prometheus::Scraper;
 use crate::catalog::builtin::{BUILTINS, MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS};
 use crate::catalog::{self, storage, BuiltinTableUpdate, Catalog, CatalogItem, SinkConnectorState};
@@ -174,6 +180,7 @@ use crate::session::{
 use crate::sink_connector;
 use crate::tail::PendingTail;
 use crate::util::ClientTransmitter;
+use crate::webhook;
 
 mod antichain;
 mod arrangement_state;
@@ -186,6 +193,8 @@ pub enum Message {
     Worker(mz_dataflow_types::client::Response),
     StatementReady(StatementReady),
     SinkConnectorReady(SinkConnectorReady),
+    SinkResumed(SinkResumed),
+    DecodeErrorsRedirected(DecodeErrorsRedirected),
     ScrapeMetrics,
     SendDiffs(SendDiffs),
     WriteLockGrant(tokio::sync::OwnedMutexGuard<()>),
@@ -235,6 +244,27 @@ pub struct SinkConnectorReady {
     pub result: Result<SinkConnector, CoordError>,
 }
 
+/// The outcome of writing the progress record of a sink that `ALTER SINK`
+/// resumes from an earlier timestamp.
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct SinkResumed {
+    pub session: Session,
+    #[derivative(Debug = "ignore")]
+    pub tx: ClientTransmitter<ExecuteResponse>,
+    pub id: GlobalId,
+    pub as_of: SinkAsOf,
+    pub result: Result<(), CoordError>,
+}
+
+/// Rows describing records that a source failed to decode, which have been
+/// written to the source's decode error table.
+#[derive(Debug)]
+pub struct DecodeErrorsRedirected {
+    pub source_id: GlobalId,
+    pub rows: Vec<Row>,
+}
+
 #[derive(Debug)]
 pub struct TimestampedUpdate {
     pub updates: Vec<BuiltinTableUpdate>,
@@ -334,6 +364,11 @@ pub struct Coordinator {
     txn_reads: HashMap<u32, TxnReads>,
     /// Tracks write frontiers for active exactly-once sinks.
     sink_writes: HashMap<GlobalId, SinkWrites<Timestamp>>,
+    /// Holds back the inputs of sinks that `ALTER SINK` is resuming at the
+    /// as_of of their restarted dataflows, until those are shipped.
+    pending_sink_resumes: HashMap<GlobalId, Vec<AntichainToken<Timestamp>>>,
+    /// Tracks the history retained for each durable subscription.
+    subscription_reads: HashMap<GlobalId, SubscriptionReads<Timestamp>>,
 
     /// A map from pending peek ids to the queue into which responses are sent, and
     /// the connection id of the client that initiated the peek.
@@ -342,6 +377,8 @@ pub struct Coordinator {
     client_pending_peeks: HashMap<u32, BTreeSet<Uuid>>,
     /// A map from pending tails to the tail description.
     pending_tails: HashMap<GlobalId, PendingTail>,
+    /// The position of the last request appended to each webhook source.
+    webhook_positions: HashMap<GlobalId, i64>,
 
     /// Serializes accesses to write critical sections.
     write_lock: Arc<tokio::sync::Mutex<()>>,
@@ -538,12 +575,19 @@ impl Coordinator {
                 // using a single dataflow, we have to make sure the rebuild process re-runs
                 // the same multiple-build dataflow.
                 CatalogItem::Source(source) => {
-                    let since_ts = self
-                        .persister
-                        .load_source_persist_desc(&source)
-                        .map_err(CoordError::Persistence)?
-                        .map(|p| p.since_ts)
-                        .unwrap_or(0);
+                    self.persister
+                        .add_webhook_source(entry.id(), &source)
+                        .map_err(CoordError::Persistence)?;
+
+                    let since_ts = match self.persister.table_details.get(&entry.id()) {
+                        Some(td) => td.since_ts,
+                        None => self
+                            .persister
+                            .load_source_persist_desc(&source)
+                            .map_err(CoordError::Persistence)?
+                            .map(|p| p.since_ts)
+                            .unwrap_or(0),
+                    };
 
                     let frontiers = self.new_source_frontiers(
                         entry.id(),
@@ -551,6 +595,21 @@ impl Coordinator {
                         self.logical_compaction_window_ms,
                     );
                     self.sources.insert(entry.id(), frontiers);
+
+                    // The contents of a table that is not persisted did not
+                    // survive the restart, so the records the source reads
+                    // again must be redirected to it again. Tables precede the
+                    // sources that depend on them in the catalog.
+                    if let SourceConnector::External {
+                        decode_errors: DecodeErrorPolicy::Redirect(table_id),
+                        ..
+                    } = &source.connector
+                    {
+                        if !self.persister.table_details.contains_key(table_id) {
+                            self.catalog.forget_decode_error_redirects(entry.id())?;
+                        }
+                    }
+
                     // Re-announce the source description.
                     let source_description = self
                         .catalog
@@ -659,6 +718,11 @@ impl Coordinator {
                     self.handle_sink_connector_ready(entry.id(), entry.oid(), connector)
                         .await?;
                 }
+                CatalogItem::Subscription(subscription) => {
+                    let acknowledged =
+                        self.catalog.load_subscription_acknowledgement(entry.id())?;
+                    self.install_subscription_reads(entry.id(), subscription, acknowledged);
+                }
                 _ => (), // Handled in prior loop.
             }
         }
@@ -783,6 +847,10 @@ impl Coordinator {
                 Message::SinkConnectorReady(ready) => {
                     self.message_sink_connector_ready(ready).await
                 }
+                Message::SinkResumed(resumed) => self.message_sink_resumed(resumed).await,
+                Message::DecodeErrorsRedirected(redirected) => {
+                    self.message_decode_errors_redirected(redirected)
+                }
                 Message::WriteLockGrant(write_lock_guard) => {
                     // It's possible to have more incoming write lock grants
                     // than pending writes because of cancellations.
@@ -819,18 +887,16 @@ impl Coordinator {
         // Close the stream up to the newly opened timestamp.
         let advance_to = self.last_open_local_ts;
 
-        // Ensure that the persister is aware of exactly the set of tables for
-        // which persistence is enabled.
+        // Ensure that the persister is aware of exactly the set of tables, and
+        // of webhook sources, for which persistence is enabled.
         soft_assert_eq!(
             self.catalog
                 .entries()
-                .filter(|entry| matches!(
-                    entry.item(),
-                    CatalogItem::Table(catalog::Table {
-                        persist_name: Some(_),
-                        ..
-                    })
-                ))
+                .filter(|entry| match entry.item() {
+                    CatalogItem::Table(table) => table.persist_name.is_some(),
+                    CatalogItem::Source(source) => source.webhook_persist_name().is_some(),
+                    _ => false,
+                })
                 .map(|entry| entry.id())
                 .collect::<Vec<_>>(),
             self.persister
@@ -925,11 +991,11 @@ impl Coordinator {
                 TimestampBindingFeedback { bindings, changes },
             )) => {
                 self.catalog
-                    .insert_timestamp_bindings(
-                        bindings
-                            .into_iter()
-                            .map(|(id, pid, ts, offset)| (id, pid.to_string(), ts, offset.offset)),
-                    )
+                    .insert_timestamp_bindings(bindings.into_iter().map(
+                        |(id, pid, ts, offset, upstream_position)| {
+                            (id, pid, ts, offset.offset, upstream_position)
+                        },
+                    ))
                     .expect("inserting timestamp bindings cannot fail");
 
                 let mut durability_updates = Vec::new();
@@ -990,6 +1056,125 @@ impl Coordinator {
                         .unwrap();
                 }
             }
+            DataflowResponse::Storage(StorageResponse::DecodeErrorRedirects(redirects)) => {
+                for (source_id, table_id, rows) in redirects {
+                    if let Err(e) = self.redirect_decode_errors(source_id, table_id, rows).await {
+                        error!(
+                            "failed to redirect decode errors of source {}: {}",
+                            source_id, e
+                        );
+                    }
+                }
+            }
+            DataflowResponse::Storage(StorageResponse::DecodeErrorRestoredOffsets(offsets)) => {
+                for (source_id, offsets) in offsets {
+                    if self.catalog.try_get_by_id(source_id).is_none() {
+                        continue;
+                    }
+                    if let Err(e) = self
+                        .catalog
+                        .compact_decode_error_redirects(source_id, &offsets)
+                    {
+                        error!(
+                            "failed to compact decode error redirects of source {}: {}",
+                            source_id, e
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    /// Writes the rows describing records that a source failed to decode to
+    /// its decode error table, like the rows of any other write to the table.
+    ///
+    /// A source reads the records after its restored offsets again when it
+    /// restarts, and must not insert the rows describing them into the table
+    /// twice. The rows are only recorded as redirected once they have been
+    /// written, so that they are redirected again if the write fails.
+    async fn redirect_decode_errors(
+        &mut self,
+        source_id: GlobalId,
+        table_id: GlobalId,
+        rows: Vec<Row>,
+    ) -> Result<(), CoordError> {
+        // The source may have been dropped while the rows were in flight,
+        // which drops the table along with it.
+        if self.catalog.try_get_by_id(source_id).is_none() {
+            return Ok(());
+        }
+        let rows = self.catalog.new_decode_error_redirects(source_id, rows)?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let timestamp = self.get_local_write_ts();
+        if let Some(persist) = self.persister.table_details.get(&table_id) {
+            let updates: Vec<((Row, ()), Timestamp, Diff)> = rows
+                .iter()
+                .map(|row| ((row.clone(), ()), timestamp, 1))
+                .collect();
+            let persist_multi = self.persister.table_writer.as_mut().ok_or_else(|| {
+                anyhow!("internal error: persist_multi_details invariant violated")
+            })?;
+            // NB: Keep this method call outside the tokio::spawn. We're
+            // guaranteed by persist that writes and seals happen in order,
+            // but only if we synchronously wait for the (fast) registration
+            // of that work to return.
+            let write_fut = persist_multi.write_atomic(|builder| {
+                builder.add_write(&persist.write_handle, updates)?;
+                Ok(())
+            });
+            let internal_cmd_tx = self.internal_cmd_tx.clone();
+            task::spawn(
+                || format!("redirect_decode_errors:{source_id}"),
+                async move {
+                    match write_fut.await {
+                        Ok(_) => {
+                            // It is not an error for the coordinator to have
+                            // shut down in the meantime.
+                            let _ = internal_cmd_tx.send(Message::DecodeErrorsRedirected(
+                                DecodeErrorsRedirected { source_id, rows },
+                            ));
+                        }
+                        Err(e) => error!(
+                            "failed to redirect decode errors of source {}: {}",
+                            source_id, e
+                        ),
+                    }
+                },
+            );
+        } else {
+            let updates = rows
+                .iter()
+                .map(|row| Update {
+                    row: row.clone(),
+                    diff: 1,
+                    timestamp,
+                })
+                .collect();
+            self.dataflow_client
+                .storage()
+                .table_insert(table_id, updates)
+                .await
+                .map_err(|e| anyhow!("{:?}", e))?;
+            self.catalog
+                .record_decode_error_redirects(source_id, &rows)?;
+        }
+        Ok(())
+    }
+
+    fn message_decode_errors_redirected(
+        &mut self,
+        DecodeErrorsRedirected { source_id, rows }: DecodeErrorsRedirected,
+    ) {
+        if self.catalog.try_get_by_id(source_id).is_none() {
+            return;
+        }
+        if let Err(e) = self.catalog.record_decode_error_redirects(source_id, &rows) {
+            error!(
+                "failed to record decode error redirects of source {}: {}",
+                source_id, e
+            );
         }
     }
 
@@ -1060,6 +1245,33 @@ impl Coordinator {
         }
     }
 
+    async fn message_sink_resumed(
+        &mut self,
+        SinkResumed {
+            session,
+            tx,
+            id,
+            as_of,
+            result,
+        }: SinkResumed,
+    ) {
+        // Keep holding the sink's inputs until its dataflow is shipped.
+        let _handles = self.pending_sink_resumes.remove(&id);
+        let result = match result {
+            Ok(()) if self.catalog.try_get_by_id(id).is_some() => {
+                self.ship_resumed_sink(id, as_of).await
+            }
+            // Another session dropped the sink while its progress record was
+            // being written.
+            Ok(()) => Ok(()),
+            Err(e) => Err(e),
+        };
+        tx.send(
+            result.map(|()| ExecuteResponse::AlteredObject(ObjectType::Sink)),
+            session,
+        );
+    }
+
     fn message_send_diffs(
         &mut self,
         SendDiffs {
@@ -1274,12 +1486,16 @@ impl Coordinator {
                                 // Statements below must by run singly (in Started).
                                 Statement::AlterIndex(_)
                                 | Statement::AlterObjectRename(_)
+                                | Statement::AlterSource(_)
+                                | Statement::AlterSink(_)
+                                | Statement::AlterSubscription(_)
                                 | Statement::CreateDatabase(_)
                                 | Statement::CreateIndex(_)
                                 | Statement::CreateRole(_)
                                 | Statement::CreateSchema(_)
                                 | Statement::CreateSink(_)
                                 | Statement::CreateSource(_)
+                                | Statement::CreateSubscription(_)
                                 | Statement::CreateTable(_)
                                 | Statement::CreateType(_)
                                 | Statement::CreateView(_)
@@ -1385,6 +1601,17 @@ impl Coordinator {
                 let _ = tx.send(Response { result, session });
             }
 
+            Command::AppendWebhook {
+                name,
+                body,
+                headers,
+                mut session,
+                tx,
+            } => {
+                let result = self.sequence_append_webhook(&mut session, name, body, headers);
+                let _ = tx.send(Response { result, session });
+            }
+
             Command::Terminate { mut session } => {
                 self.handle_terminate(&mut session).await;
             }
@@ -1585,6 +1812,15 @@ impl Coordinator {
     /// Primarily, this involves sequencing compaction commands, which should be
     /// issued whenever available.
     async fn maintenance(&mut self) {
+        // Release the history that subscriptions retain beyond their maximum
+        // lag, so that an abandoned subscription cannot block compaction
+        // forever.
+        self.refresh_subscription_reads();
+        let now = (self.catalog.config().now)();
+        for reads in self.subscription_reads.values_mut() {
+            reads.advance(now.saturating_sub(reads.max_lag));
+        }
+
         // Take this opportunity to drain `since_update` commands.
         // Don't try to compact to an empty frontier. There may be a good reason to do this
         // in principle, but not in any current Mz use case.
@@ -1829,6 +2065,13 @@ impl Coordinator {
             })
             .await?;
 
+        self.install_sink_writes(id, &connector);
+        Ok(self.ship_dataflow(df).await)
+    }
+
+    /// Installs the compaction holdback for the sources of the sink `id`, if
+    /// its connector requires one.
+    fn install_sink_writes(&mut self, id: GlobalId, connector: &SinkConnector) {
         // For some sinks, we need to block compaction of each timestamp binding
         // until all sinks that depend on a given source have finished writing out that timestamp.
         // To achieve that, each sink will hold a AntichainToken for all of the sources it depends
@@ -1847,7 +2090,124 @@ impl Coordinator {
             let sink_writes = SinkWrites::new(tokens);
             self.sink_writes.insert(id, sink_writes);
         }
-        Ok(self.ship_dataflow(df).await)
+    }
+
+    /// Installs the compaction holdback for the subscription `id`, which
+    /// retains the history of its input after `acknowledged`, or after the
+    /// current time if its consumer has not yet acknowledged anything.
+    ///
+    /// Previous acknowledgements are only passed when the catalog is loaded
+    /// after a restart, when the history of the input may no longer reach
+    /// back to them. Inputs whose history does not survive restarts are then
+    /// held from the current time, and a resumed `TAIL` fails.
+    fn install_subscription_reads(
+        &mut self,
+        id: GlobalId,
+        subscription: &catalog::Subscription,
+        acknowledged: Option<Timestamp>,
+    ) {
+        let since = match acknowledged {
+            Some(acknowledged) if self.history_survives_restart(subscription.from) => acknowledged,
+            _ => self.determine_frontier(&[subscription.from]).elements()[0],
+        };
+        let inputs = self.catalog.nearest_indexes(&[subscription.from]);
+        let (since, handles) = self.hold_subscription_inputs(&inputs, since);
+        self.subscription_reads.insert(
+            id,
+            SubscriptionReads {
+                since,
+                acknowledged,
+                max_lag: subscription.max_lag_ms,
+                inputs,
+                inputs_since: since,
+                handles,
+                previous_handles: vec![],
+            },
+        );
+    }
+
+    /// Holds back the since frontiers of `inputs`, the indexes and
+    /// unmaterialized sources that a `TAIL` of a subscription reads, at
+    /// `since`, or at the earliest time at which all of them are still valid
+    /// if that is later. Returns that time and the handles that hold it.
+    fn hold_subscription_inputs(
+        &self,
+        inputs: &(Vec<GlobalId>, Vec<GlobalId>),
+        since: Timestamp,
+    ) -> (Timestamp, Vec<AntichainToken<Timestamp>>) {
+        let (index_ids, source_ids) = inputs;
+        let mut valid_since = self.indexes.least_valid_since(index_ids.iter().copied());
+        valid_since.join_assign(&self.sources.least_valid_since(source_ids.iter().copied()));
+        let since = valid_since.elements().iter().copied().fold(since, cmp::max);
+
+        let mut handles = Vec::new();
+        for id in index_ids {
+            let frontiers = self.indexes.get(id).expect("index known to exist");
+            handles.push(frontiers.since_handle([since]));
+        }
+        for id in source_ids {
+            let frontiers = self.sources.get(id).expect("source known to exist");
+            handles.push(frontiers.since_handle([since]));
+        }
+        (since, handles)
+    }
+
+    /// Moves the compaction holdback of each subscription to the indexes and
+    /// sources that a `TAIL` of the subscription currently reads, which change
+    /// when indexes on its input are created or dropped.
+    ///
+    /// A new index only has the history after its creation, so the previous
+    /// inputs keep holding the subscription's history until it is released
+    /// past that point. Until then, a `TAIL` cannot resume from before it,
+    /// unless the new index is dropped again. The history that was only
+    /// retained by a dropped index is lost.
+    fn refresh_subscription_reads(&mut self) {
+        let mut refreshed = vec![];
+        for (id, reads) in &self.subscription_reads {
+            let from = match self.catalog.get_by_id(id).item() {
+                CatalogItem::Subscription(subscription) => subscription.from,
+                _ => unreachable!("{} is a subscription", id),
+            };
+            let inputs = self.catalog.nearest_indexes(&[from]);
+            if inputs != reads.inputs {
+                let (inputs_since, handles) = self.hold_subscription_inputs(&inputs, reads.since);
+                let (index_ids, source_ids) = &reads.inputs;
+                let previous_exist = index_ids.iter().all(|id| self.indexes.get(id).is_some())
+                    && source_ids.iter().all(|id| self.sources.get(id).is_some());
+                refreshed.push((*id, inputs, inputs_since, handles, previous_exist));
+            }
+        }
+        for (id, inputs, inputs_since, handles, previous_exist) in refreshed {
+            let reads = self.subscription_reads.get_mut(&id).unwrap();
+            let previous_handles = mem::replace(&mut reads.handles, handles);
+            if inputs_since == reads.since {
+                reads.previous_handles.clear();
+            } else if previous_exist {
+                reads.previous_handles.extend(previous_handles);
+            } else {
+                reads.since = inputs_since;
+                reads.previous_handles.clear();
+            }
+            reads.inputs = inputs;
+            reads.inputs_since = inputs_since;
+        }
+    }
+
+    /// Reports whether the history of `id` survives a restart, which requires
+    /// that it only depends on persisted tables, on persisted webhook sources,
+    /// and on sources that assign the same timestamps to the same data again.
+    fn history_survives_restart(&self, id: GlobalId) -> bool {
+        match self.catalog.get_by_id(&id).item() {
+            CatalogItem::Table(_) => self.persister.table_details.contains_key(&id),
+            CatalogItem::Source(source) => {
+                source.connector.yields_stable_input()
+                    || self.persister.table_details.contains_key(&id)
+            }
+            item => item
+                .uses()
+                .iter()
+                .all(|id| self.history_survives_restart(*id)),
+        }
     }
 
     async fn sequence_plan(
@@ -1878,6 +2238,9 @@ impl Coordinator {
             Plan::CreateSink(plan) => {
                 self.sequence_create_sink(session, plan, tx).await;
             }
+            Plan::CreateSubscription(plan) => {
+                tx.send(self.sequence_create_subscription(plan).await, session);
+            }
             Plan::CreateView(plan) => {
                 tx.send(self.sequence_create_view(&session, plan).await, session);
             }
@@ -1976,6 +2339,15 @@ impl Coordinator {
             Plan::AlterItemRename(plan) => {
                 tx.send(self.sequence_alter_item_rename(plan).await, session);
             }
+            Plan::AlterSource(plan) => {
+                tx.send(self.sequence_alter_source(plan).await, session);
+            }
+            Plan::AlterSink(plan) => {
+                self.sequence_alter_sink(tx, session, plan).await;
+            }
+            Plan::AlterSubscription(plan) => {
+                tx.send(self.sequence_alter_subscription(plan), session);
+            }
             Plan::AlterIndexSetOptions(plan) => {
                 tx.send(self.sequence_alter_index_set_options(plan), session);
             }
@@ -2333,12 +2705,18 @@ impl Coordinator {
                         let source = catalog_state.get_by_id(&id).source().ok_or_else(|| {
                             CoordError::Internal(format!("ID {} unexpectedly not a source", id))
                         })?;
-                        let since_ts = self
-                            .persister
-                            .load_source_persist_desc(&source)
-                            .map_err(CoordError::Persistence)?
-                            .map(|p| p.since_ts)
-                            .unwrap_or(0);
+                        self.persister
+                            .add_webhook_source(*id, &source)
+                            .map_err(CoordError::Persistence)?;
+                        let since_ts = match self.persister.table_details.get(id) {
+                            Some(td) => td.since_ts,
+                            None => self
+                                .persister
+                                .load_source_persist_desc(&source)
+                                .map_err(CoordError::Persistence)?
+                                .map(|p| p.since_ts)
+                                .unwrap_or(0),
+                        };
                         Ok::<_, CoordError>(since_ts)
                     })
                     .collect::<Result<Vec<_>, _>>()?;
@@ -2413,11 +2791,29 @@ impl Coordinator {
                 &name.to_string(),
             );
 
+            let mut connector = source.connector;
+            if let SourceConnector::External {
+                connector: ExternalSourceConnector::Webhook(webhook),
+                ..
+            } = &mut connector
+            {
+                // Signatures are the only means by which requests to webhook
+                // sources are authorized.
+                if source.webhook_secret.is_none() {
+                    coord_bail!("webhook sources require hmac_secret");
+                }
+                webhook.persisted_name = self
+                    .persister
+                    .new_table_persist_name(source_id, &name.to_string());
+            }
+
             let source = catalog::Source {
                 create_sql: source.create_sql,
-                connector: source.connector,
+                connector,
                 persist_details,
+                webhook_secret: source.webhook_secret,
                 desc: source.desc,
+                depends_on: source.depends_on,
             };
             ops.push(catalog::Op::CreateItem {
                 id: source_id,
@@ -2523,7 +2919,7 @@ impl Coordinator {
                                 .desc()
                                 .unwrap()
                                 .clone(),
-                            connector: SinkConnector::Tail(TailSinkConnector {}),
+                            connector: SinkConnector::Tail(TailSinkConnector::default()),
                             envelope: Some(sink.envelope),
                             as_of: SinkAsOf {
                                 frontier: Antichain::new(),
@@ -2799,6 +3195,42 @@ impl Coordinator {
         }
     }
 
+    async fn sequence_create_subscription(
+        &mut self,
+        plan: CreateSubscriptionPlan,
+    ) -> Result<ExecuteResponse, CoordError> {
+        let CreateSubscriptionPlan {
+            name,
+            subscription,
+            if_not_exists,
+        } = plan;
+        let subscription = catalog::Subscription {
+            create_sql: subscription.create_sql,
+            from: subscription.from,
+            max_lag_ms: subscription.max_lag_ms,
+            depends_on: subscription.depends_on,
+        };
+        let id = self.catalog.allocate_id()?;
+        let oid = self.catalog.allocate_oid()?;
+        let op = catalog::Op::CreateItem {
+            id,
+            oid,
+            name,
+            item: CatalogItem::Subscription(subscription.clone()),
+        };
+        match self.catalog_transact(vec![op], |_builder| Ok(())).await {
+            Ok(()) => {
+                self.install_subscription_reads(id, &subscription, None);
+                Ok(ExecuteResponse::CreatedSubscription { existed: false })
+            }
+            Err(CoordError::Catalog(catalog::Error {
+                kind: catalog::ErrorKind::ItemAlreadyExists(_),
+                ..
+            })) if if_not_exists => Ok(ExecuteResponse::CreatedSubscription { existed: true }),
+            Err(err) => Err(err),
+        }
+    }
+
     async fn sequence_drop_database(
         &mut self,
         plan: DropDatabasePlan,
@@ -2842,6 +3274,7 @@ impl Coordinator {
             ObjectType::View => ExecuteResponse::DroppedView,
             ObjectType::Table => ExecuteResponse::DroppedTable,
             ObjectType::Sink => ExecuteResponse::DroppedSink,
+            ObjectType::Subscription => ExecuteResponse::DroppedSubscription,
             ObjectType::Index => ExecuteResponse::DroppedIndex,
             ObjectType::Type => ExecuteResponse::DroppedType,
             ObjectType::Role => unreachable!("DROP ROLE not supported"),
@@ -2980,7 +3413,7 @@ impl Coordinator {
                         let mut volatile_updates = Vec::new();
                         for WriteOp { id, rows } in inserts {
                             // Re-verify this id exists.
-                            let _ = self.catalog.try_get_by_id(id).ok_or_else(|| {
+                            let entry = self.catalog.try_get_by_id(id).ok_or_else(|| {
                                 CoordError::SqlCatalog(CatalogError::UnknownItem(id.to_string()))
                             })?;
                             // This can be empty if, say, a DELETE's WHERE clause had 0 results.
@@ -3300,6 +3733,7 @@ impl Coordinator {
             ts,
             copy_to,
             emit_progress,
+            envelope,
         } = plan;
         // TAIL AS OF, similar to peeks, doesn't need to worry about transaction
         // timestamp semantics.
@@ -3323,7 +3757,9 @@ impl Coordinator {
             Ok::<_, CoordError>(SinkDesc {
                 from,
                 from_desc,
-                connector: SinkConnector::Tail(TailSinkConnector::default()),
+                connector: SinkConnector::Tail(TailSinkConnector {
+                    envelope: envelope.clone(),
+                }),
                 envelope: None,
                 as_of: SinkAsOf {
                     frontier,
@@ -3342,6 +3778,74 @@ impl Coordinator {
                 self.dataflow_builder()
                     .build_sink_dataflow(sink_name, sink_id, sink_desc)?
             }
+            TailFrom::Subscription(subscription_id) => {
+                let entry = self.catalog.get_by_id(&subscription_id);
+                let name = entry.name().clone();
+                let from_id = match entry.item() {
+                    CatalogItem::Subscription(subscription) => subscription.from,
+                    _ => unreachable!("planner verified that {} is a subscription", name),
+                };
+                self.refresh_subscription_reads();
+                let reads = &self.subscription_reads[&subscription_id];
+
+                // Resume with the updates after the last acknowledged
+                // timestamp, or, if the consumer has not acknowledged anything
+                // yet, start with a snapshot at the subscription's since.
+                let (resume_from, strict) = match reads.acknowledged {
+                    Some(acknowledged) => (acknowledged, true),
+                    None => (reads.since, false),
+                };
+                if resume_from < reads.since {
+                    coord_bail!(
+                        "cannot resume subscription {} from {}: \
+                         its history has been released up to {}",
+                        name,
+                        resume_from,
+                        reads.since
+                    );
+                }
+                if resume_from < reads.inputs_since {
+                    coord_bail!(
+                        "cannot resume subscription {} from {}: \
+                         the indexes it reads changed, and the new ones only retain \
+                         its history after {}; drop them to resume from the previous ones",
+                        name,
+                        resume_from,
+                        reads.inputs_since
+                    );
+                }
+                let (index_ids, unmaterialized_source_ids) =
+                    self.catalog.nearest_indexes(&[from_id]);
+                let mut since = self.indexes.least_valid_since(index_ids);
+                since.join_assign(&self.sources.least_valid_since(unmaterialized_source_ids));
+                if !since.less_equal(&resume_from) {
+                    coord_bail!(
+                        "cannot resume subscription {} from {}: \
+                         its input has been compacted up to {:?}",
+                        name,
+                        resume_from,
+                        since.elements()
+                    );
+                }
+
+                let from_desc = self.catalog.get_by_id(&from_id).desc().unwrap().clone();
+                let sink_id = self.catalog.allocate_id()?;
+                let sink_desc = SinkDesc {
+                    from: from_id,
+                    from_desc,
+                    connector: SinkConnector::Tail(TailSinkConnector {
+                        envelope: envelope.clone(),
+                    }),
+                    envelope: None,
+                    as_of: SinkAsOf {
+                        frontier: Antichain::from_elem(resume_from),
+                        strict,
+                    },
+                };
+                let sink_name = format!("tail-{}", sink_id);
+                self.dataflow_builder()
+                    .build_sink_dataflow(sink_name, sink_id, sink_desc)?
+            }
             TailFrom::Query {
                 expr,
                 desc,
@@ -3363,8 +3867,10 @@ impl Coordinator {
         session.add_drop_sink(*sink_id);
         let arity = sink_desc.from_desc.arity();
         let (tx, rx) = mpsc::unbounded_channel();
-        self.pending_tails
-            .insert(*sink_id, PendingTail::new(tx, emit_progress, arity));
+        self.pending_tails.insert(
+            *sink_id,
+            PendingTail::new(tx, emit_progress, arity, envelope.as_ref()),
+        );
         self.ship_dataflow(dataflow).await;
 
         let resp = ExecuteResponse::Tailing { rx };
@@ -3949,6 +4455,75 @@ impl Coordinator {
         self.sequence_insert_constant(session, id, values.into_inner())
     }
 
+    /// Adds a request pushed to a webhook source to the session's transaction.
+    ///
+    /// Requests must carry a valid signature, regardless of whether the user
+    /// that sent them is authenticated.
+    fn sequence_append_webhook(
+        &mut self,
+        session: &mut Session,
+        name: PartialName,
+        body: Vec<u8>,
+        headers: BTreeMap<String, String>,
+    ) -> Result<(), CoordError> {
+        let entry = self.catalog.resolve_item(
+            session.vars().database(),
+            session.vars().search_path(),
+            &name,
+            session.conn_id(),
+        )?;
+        let id = entry.id();
+        let (connector, secret) = match entry.item() {
+            CatalogItem::Source(catalog::Source {
+                connector:
+                    SourceConnector::External {
+                        connector: ExternalSourceConnector::Webhook(connector),
+                        ..
+                    },
+                webhook_secret: Some(secret),
+                ..
+            }) => (connector, secret),
+            _ => coord_bail!("{} is not a webhook source", entry.name()),
+        };
+        let authorized = headers
+            .get(&connector.signature_header)
+            .map_or(false, |signature| {
+                webhook::verify_signature(secret, &body, signature)
+            });
+        if !authorized {
+            return Err(CoordError::UnauthorizedWebhookRequest(
+                entry.name().to_string(),
+            ));
+        }
+        // Unless they are persisted, requests are only retained by the
+        // source's indexes, so refuse them rather than silently dropping them.
+        if !self.persister.table_details.contains_key(&id)
+            && !self
+                .catalog
+                .get_indexes_on(id)
+                .iter()
+                .any(|idx| self.catalog.is_index_enabled(idx))
+        {
+            coord_bail!("webhook source {} is not materialized", entry.name());
+        }
+        // Number requests after the last one accepted, even across restarts.
+        let position = match self.webhook_positions.get(&id) {
+            Some(position) => *position,
+            None => self.catalog.load_webhook_position(id)?.unwrap_or(0),
+        } + 1;
+        self.catalog.set_webhook_position(id, position)?;
+        self.webhook_positions.insert(id, position);
+        let row = WebhookSourceConnector::pack_request(
+            position,
+            &body,
+            headers.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+        );
+        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+            id,
+            rows: vec![(row, 1)],
+        }]))
+    }
+
     // ReadThenWrite is a plan whose writes depend on the results of a
     // read. This works by doing a Peek then queuing a SendDiffs. No writes
     // or read-then-writes can occur between the Peek and SendDiff otherwise a
@@ -4103,6 +4678,276 @@ impl Coordinator {
         }
     }
 
+    async fn sequence_alter_source(
+        &mut self,
+        plan: AlterSourcePlan,
+    ) -> Result<ExecuteResponse, CoordError> {
+        let AlterSourcePlan { id, source } = plan;
+        let entry = self.catalog.get_by_id(&id);
+        let old_source = match entry.item() {
+            CatalogItem::Source(source) => source.clone(),
+            _ => unreachable!("planner verified that {} is a source", id),
+        };
+        if old_source.persist_details.is_some() {
+            return Err(CoordError::Unsupported(
+                "ALTER SOURCE ... REFRESH SCHEMA on persisted sources",
+            ));
+        }
+        // Sinks have already written the old schema to their downstream
+        // systems, so they cannot transparently adopt the new one.
+        let mut stack = entry.used_by().to_vec();
+        while let Some(dependent_id) = stack.pop() {
+            let dependent = self.catalog.get_by_id(&dependent_id);
+            if let CatalogItem::Sink(_) = dependent.item() {
+                coord_bail!(
+                    "cannot refresh schema of source {}: sink {} depends on it",
+                    entry.name(),
+                    dependent.name()
+                );
+            }
+            stack.extend(dependent.used_by());
+        }
+
+        // Views that select `*` from the source would otherwise plan to the new
+        // columns when they are next loaded from the catalog, which would
+        // invalidate the views and indexes that depend on them. Pin their
+        // definitions to the columns that they were planned with.
+        let columns: Vec<_> = old_source.desc.iter_names().cloned().collect();
+        let mut ops = vec![catalog::Op::UpdateItem {
+            id,
+            to_item: CatalogItem::Source(catalog::Source {
+                create_sql: source.create_sql,
+                connector: source.connector,
+                desc: source.desc,
+                ..old_source
+            }),
+        }];
+        for dependent_id in entry.used_by() {
+            match self.catalog.get_by_id(dependent_id).item() {
+                item @ CatalogItem::View(_) if !item.is_temporary() => {
+                    ops.push(catalog::Op::UpdateItem {
+                        id: *dependent_id,
+                        to_item: self.catalog.project_view_refs(
+                            *dependent_id,
+                            entry.name(),
+                            &columns,
+                        )?,
+                    });
+                }
+                _ => (),
+            }
+        }
+        self.catalog_transact(ops, |_builder| Ok(())).await?;
+
+        // Dataflows that are already running decode records with the old
+        // schema, so rebuild every index that transitively depends on the
+        // source. Indexes are rebuilt in order of creation so that each index
+        // can use the indexes it was originally built on.
+        let mut indexes = self.catalog.dependent_indexes(id);
+        indexes.sort();
+        indexes.dedup();
+        self.drop_indexes(indexes.clone()).await;
+        let source_description = self
+            .catalog
+            .state()
+            .source_description_for(id)
+            .expect("source exists");
+        self.dataflow_client
+            .storage()
+            .alter_sources(vec![(id, source_description)])
+            .await
+            .unwrap();
+        for index_id in indexes {
+            if let Some((name, description)) =
+                Self::prepare_index_build(self.catalog.state(), &index_id)
+            {
+                let df =
+                    self.dataflow_builder()
+                        .build_index_dataflow(name, index_id, description)?;
+                self.ship_dataflow(df).await;
+            }
+        }
+
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
+    async fn sequence_alter_sink(
+        &mut self,
+        tx: ClientTransmitter<ExecuteResponse>,
+        session: Session,
+        plan: AlterSinkPlan,
+    ) {
+        let AlterSinkPlan { id, resume_from } = plan;
+        let (kafka, as_of) = match self.validate_alter_sink(id, resume_from) {
+            Ok(validated) => validated,
+            Err(e) => {
+                tx.send(Err(e), session);
+                return;
+            }
+        };
+        let name = self.catalog.get_by_id(&id).name().clone();
+
+        // Stop the sink before writing its progress record, which fences out
+        // the producer of any instance of the sink that is still writing, and
+        // then start it again once the record is written so that it picks up
+        // the record. Until then, the sink's compaction holdback stays in
+        // place, and its input is held at the restarted sink's as_of. If
+        // writing the record fails, it is unknown where the sink would resume
+        // from, so it stays stopped until a later `ALTER SINK` succeeds or
+        // Materialize restarts.
+        self.drop_sinks(vec![id]).await;
+        let from = match self.catalog.get_by_id(&id).item() {
+            CatalogItem::Sink(sink) => sink.from,
+            _ => unreachable!("validated that {} is a sink", id),
+        };
+        let inputs = self.catalog.nearest_indexes(&[from]);
+        let (_since, handles) =
+            self.hold_subscription_inputs(&inputs, as_of.frontier.elements()[0]);
+        self.pending_sink_resumes.insert(id, handles);
+
+        // Writing the record may block for several seconds, so arrange to
+        // finish the `ALTER SINK` on the main coordinator thread when it is
+        // written.
+        let internal_cmd_tx = self.internal_cmd_tx.clone();
+        task::spawn(|| format!("sink_resumed:{}", id), async move {
+            let result = sink_connector::resume_kafka_sink(&kafka, id, resume_from)
+                .await
+                .map_err(|e| {
+                    CoordError::Unstructured(anyhow!(
+                        "sink {} stopped: failed to resume it from {}: {}",
+                        name,
+                        resume_from,
+                        e
+                    ))
+                });
+            internal_cmd_tx
+                .send(Message::SinkResumed(SinkResumed {
+                    session,
+                    tx,
+                    id,
+                    as_of,
+                    result,
+                }))
+                .expect("sending to internal_cmd_tx cannot fail");
+        });
+    }
+
+    /// Checks that the sink `id` can be resumed from `resume_from`, and
+    /// returns its connector and the as_of of its restarted dataflow.
+    fn validate_alter_sink(
+        &mut self,
+        id: GlobalId,
+        resume_from: Timestamp,
+    ) -> Result<(KafkaSinkConnector, SinkAsOf), CoordError> {
+        let entry = self.catalog.get_by_id(&id);
+        let name = entry.name().clone();
+        let sink = match entry.item() {
+            CatalogItem::Sink(sink) => sink.clone(),
+            _ => unreachable!("planner verified that {} is a sink", id),
+        };
+        let kafka = match &sink.connector {
+            SinkConnectorState::Ready(SinkConnector::Kafka(kafka)) if kafka.progress.is_some() => {
+                kafka.clone()
+            }
+            SinkConnectorState::Pending(_) => {
+                coord_bail!("sink {} is still being created", name)
+            }
+            SinkConnectorState::Ready(_) => coord_bail!(
+                "ALTER SINK ... RESUME FROM requires an exactly-once Kafka sink, but {} is not one",
+                name
+            ),
+        };
+        if self.pending_sink_resumes.contains_key(&id) {
+            coord_bail!("sink {} is already being resumed", name);
+        }
+
+        // Updates at or before the restarted sink's as_of are consolidated
+        // into a snapshot, so the sink cannot replay the individual updates
+        // after `resume_from` unless they lie beyond it.
+        let as_of = SinkAsOf {
+            frontier: self.determine_frontier(&[sink.from]),
+            strict: !sink.with_snapshot,
+        };
+        if !as_of.frontier.less_equal(&resume_from) {
+            coord_bail!(
+                "cannot resume sink {} from {}: its input has been compacted up to {}",
+                name,
+                resume_from,
+                as_of.frontier.elements()[0]
+            );
+        }
+        Ok((kafka, as_of))
+    }
+
+    /// Restarts the dataflow of the sink `id`, which `ALTER SINK` stopped, once
+    /// its progress record is written.
+    async fn ship_resumed_sink(&mut self, id: GlobalId, as_of: SinkAsOf) -> Result<(), CoordError> {
+        let entry = self.catalog.get_by_id(&id);
+        let name = entry.name().clone();
+        let sink = match entry.item() {
+            CatalogItem::Sink(sink) => sink.clone(),
+            _ => unreachable!("{} is a sink", id),
+        };
+        let connector = match &sink.connector {
+            SinkConnectorState::Ready(connector) => connector.clone(),
+            SinkConnectorState::Pending(_) => unreachable!("validated that {} is ready", id),
+        };
+        let sink_description = SinkDesc {
+            from: sink.from,
+            from_desc: self.catalog.get_by_id(&sink.from).desc().unwrap().clone(),
+            connector: connector.clone(),
+            envelope: Some(sink.envelope),
+            as_of,
+        };
+        let df =
+            self.dataflow_builder()
+                .build_sink_dataflow(name.to_string(), id, sink_description)?;
+        // Replacing the sink's compaction holdback only releases the old one
+        // once the new one is in place.
+        self.install_sink_writes(id, &connector);
+        self.ship_dataflow(df).await;
+        Ok(())
+    }
+
+    fn sequence_alter_subscription(
+        &mut self,
+        plan: AlterSubscriptionPlan,
+    ) -> Result<ExecuteResponse, CoordError> {
+        let AlterSubscriptionPlan { id, acknowledged } = plan;
+        let name = self.catalog.get_by_id(&id).name().clone();
+        self.refresh_subscription_reads();
+        let reads = self
+            .subscription_reads
+            .get(&id)
+            .expect("subscription known to exist");
+        if let Some(previous) = reads.acknowledged {
+            if acknowledged < previous {
+                coord_bail!(
+                    "cannot acknowledge {} for subscription {}: {} was already acknowledged",
+                    acknowledged,
+                    name,
+                    previous
+                );
+            }
+        }
+        if acknowledged < reads.since {
+            coord_bail!(
+                "cannot acknowledge {} for subscription {}: \
+                 its history has been released up to {}",
+                acknowledged,
+                name,
+                reads.since
+            );
+        }
+
+        self.catalog
+            .set_subscription_acknowledgement(id, acknowledged)?;
+        let reads = self.subscription_reads.get_mut(&id).unwrap();
+        reads.acknowledged = Some(acknowledged);
+        reads.advance(acknowledged);
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Subscription))
+    }
+
     fn sequence_alter_index_set_options(
         &mut self,
         plan: AlterIndexSetOptionsPlan,
@@ -4167,6 +5012,7 @@ impl Coordinator {
         let mut tables_to_drop = vec![];
         let mut sinks_to_drop = vec![];
         let mut indexes_to_drop = vec![];
+        let mut subscriptions_to_drop = vec![];
         let mut replication_slots_to_drop: HashMap<String, Vec<String>> = HashMap::new();
 
         for op in &ops {
@@ -4202,6 +5048,9 @@ impl Coordinator {
                     CatalogItem::Index(_) => {
                         indexes_to_drop.push(*id);
                     }
+                    CatalogItem::Subscription(_) => {
+                        subscriptions_to_drop.push(*id);
+                    }
                     _ => (),
                 }
             }
@@ -4229,6 +5078,11 @@ impl Coordinator {
             if !sources_to_drop.is_empty() {
                 for id in &sources_to_drop {
                     self.sources.remove(id);
+                    // Webhook sources may be persisted like tables.
+                    if self.persister.table_details.contains_key(id) {
+                        self.persister.remove_table(*id);
+                    }
+                    self.webhook_positions.remove(id);
                     self.since_handles.remove(id);
                 }
                 self.dataflow_client
@@ -4266,6 +5120,10 @@ impl Coordinator {
             if !indexes_to_drop.is_empty() {
                 self.drop_indexes(indexes_to_drop).await;
             }
+            for id in &subscriptions_to_drop {
+                // Dropping the handles releases the subscription's holdback.
+                self.subscription_reads.remove(id);
+            }
 
             // We don't want to block the coordinator on an external postgres server, so
             // move the drop slots to a separate task. This does mean that a failed drop
@@ -4697,9 +5555,12 @@ pub async fn serve(
                 index_since_updates: Rc::new(RefCell::new(HashMap::new())),
                 source_since_updates: Rc::new(RefCell::new(HashMap::new())),
                 sink_writes: HashMap::new(),
+                pending_sink_resumes: HashMap::new(),
+                subscription_reads: HashMap::new(),
                 pending_peeks: HashMap::new(),
                 client_pending_peeks: HashMap::new(),
                 pending_tails: HashMap::new(),
+                webhook_positions: HashMap::new(),
                 write_lock: Arc::new(tokio::sync::Mutex::new(())),
                 write_lock_wait_group: VecDeque::new(),
             };
@@ -14,6 +14,7 @@ use std::collections::HashMap;
 use std::rc::Rc;
 
 use differential_dataflow::lattice::Lattice;
+use timely::order::PartialOrder;
 use timely::progress::frontier::{Antichain, AntichainRef, MutableAntichain};
 use timely::progress::Timestamp;
 
@@ -207,6 +208,45 @@ impl<T: Timestamp> SinkWrites<T> {
     }
 }
 
+/// Tracks the history of its input that a durable subscription retains.
+pub struct SubscriptionReads<T: Timestamp> {
+    /// The time after which the subscription's input remains available to a
+    /// resumed `TAIL`.
+    pub since: T,
+    /// The last time acknowledged by the subscription's consumer, if any.
+    pub acknowledged: Option<T>,
+    /// How far `since` may trail the current time.
+    pub max_lag: T,
+    /// The indexes and unmaterialized sources that a `TAIL` of the
+    /// subscription reads.
+    pub inputs: (Vec<GlobalId>, Vec<GlobalId>),
+    /// The time after which `inputs` retain the subscription's history, which
+    /// is later than `since` if they changed after `since`.
+    pub inputs_since: T,
+    /// Handles that hold back the since frontiers of `inputs`.
+    pub handles: Vec<AntichainToken<T>>,
+    /// Handles that hold back the since frontiers of the inputs that a `TAIL`
+    /// read before `inputs` changed, until `inputs` retain the history after
+    /// `since` themselves.
+    pub previous_handles: Vec<AntichainToken<T>>,
+}
+
+impl<T: Timestamp> SubscriptionReads<T> {
+    /// Allows the subscription's inputs to compact up to `since`, if the
+    /// subscription does not already allow that.
+    pub fn advance(&mut self, since: T) {
+        if self.since.less_than(&since) {
+            for handle in self.handles.iter_mut().chain(&mut self.previous_handles) {
+                handle.maybe_advance(Some(since.clone()));
+            }
+            self.since = since;
+            if self.inputs_since.less_equal(&self.since) {
+                self.previous_handles.clear();
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
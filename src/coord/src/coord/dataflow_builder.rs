@@ -14,16 +14,19 @@
 //! and indicate which identifiers have arrangements available. This module
 //! isolates that logic from the rest of the somewhat complicated coordinator.
 
-use mz_dataflow_types::sinks::SinkDesc;
+use std::collections::HashSet;
+
+use mz_dataflow_types::sinks::{SinkDesc, SinkEnvelope};
 use mz_dataflow_types::{BuildDesc, DataflowDesc, IndexDesc};
 use mz_expr::{
-    GlobalId, MapFilterProject, MirRelationExpr, MirScalarExpr, NullaryFunc,
+    GlobalId, Id, MapFilterProject, MirRelationExpr, MirScalarExpr, NullaryFunc,
     OptimizedMirRelationExpr,
 };
 use mz_ore::stack::maybe_grow;
 use mz_repr::adt::array::ArrayDimension;
 use mz_repr::adt::numeric::Numeric;
 use mz_repr::{Datum, Row, Timestamp};
+use mz_transform::monotonic::MonotonicFlag;
 
 use crate::catalog::{CatalogItem, CatalogState};
 use crate::coord::ArrangementFrontiers;
@@ -202,11 +205,38 @@ impl<'a> DataflowBuilder<'a> {
                 }
             }
         }
-        dataflow.insert_view(*view_id, view.clone());
+        dataflow.insert_view(*view_id, self.narrow_altered_gets(view));
 
         Ok(())
     }
 
+    /// Projects away any columns that were appended to the items referenced by
+    /// `view` after it was planned, e.g. by `ALTER SOURCE ... REFRESH SCHEMA`,
+    /// so that the view continues to produce the columns it was planned with.
+    /// The catalog definitions of such views are rewritten to match, see
+    /// `Catalog::project_view_refs`.
+    fn narrow_altered_gets(&self, view: &OptimizedMirRelationExpr) -> OptimizedMirRelationExpr {
+        let mut view = view.clone();
+        view.as_inner_mut().visit_mut_post(&mut |expr| {
+            if let MirRelationExpr::Get {
+                id: Id::Global(id),
+                typ,
+            } = expr
+            {
+                let desc = match self.catalog.get_by_id(id).desc() {
+                    Ok(desc) => desc,
+                    Err(_) => return,
+                };
+                let arity = typ.arity();
+                if desc.arity() > arity {
+                    *typ = desc.typ().clone();
+                    *expr = expr.take_dangerous().project((0..arity).collect());
+                }
+            }
+        });
+        view
+    }
+
     /// Builds a dataflow description for the index with the specified ID.
     pub fn build_index_dataflow(
         &mut self,
@@ -256,6 +286,14 @@ impl<'a> DataflowBuilder<'a> {
         id: GlobalId,
         sink_description: SinkDesc,
     ) -> Result<(), CoordError> {
+        if sink_description.envelope == Some(SinkEnvelope::Append)
+            && !self.is_append_only(sink_description.from)?
+        {
+            coord_bail!(
+                "ENVELOPE NONE sinks require an append-only input, but {} may retract rows",
+                self.catalog.get_by_id(&sink_description.from).name()
+            );
+        }
         dataflow.set_as_of(sink_description.as_of.frontier.clone());
         self.import_into_dataflow(&sink_description.from, dataflow)?;
         for BuildDesc { view, .. } in &mut dataflow.objects_to_build {
@@ -269,6 +307,33 @@ impl<'a> DataflowBuilder<'a> {
         Ok(())
     }
 
+    /// Reports whether the collection with the specified ID provably never
+    /// retracts rows.
+    ///
+    /// Sources are append-only if their connector never retracts rows, and
+    /// views are append-only if they preserve the monotonicity of their
+    /// inputs, as determined by [`MonotonicFlag`].
+    fn is_append_only(&self, id: GlobalId) -> Result<bool, CoordError> {
+        match self.catalog.get_by_id(&id).item() {
+            CatalogItem::Source(source) => Ok(source.connector.is_append_only()),
+            CatalogItem::View(view) => {
+                let mut monotonic = HashSet::new();
+                for input in view.optimized_expr.global_uses() {
+                    if self.is_append_only(input)? {
+                        monotonic.insert(input);
+                    }
+                }
+                let mut expr = view.optimized_expr.clone();
+                Ok(MonotonicFlag::default().apply(
+                    expr.as_inner_mut(),
+                    &monotonic,
+                    &mut HashSet::new(),
+                )?)
+            }
+            _ => Ok(false),
+        }
+    }
+
     /// Prepares a relation expression for dataflow execution by preparing all
     /// contained scalar expressions (see `prep_scalar_expr`) in the specified
     /// style.
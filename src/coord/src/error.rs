@@ -13,7 +13,7 @@ use std::num::TryFromIntError;
 
 use dec::TryFromDecimalError;
 
-use mz_dataflow_types::sources::{ExternalSourceConnector, SourceConnector};
+use mz_dataflow_types::sources::{DecodeErrorPolicy, ExternalSourceConnector, SourceConnector};
 use mz_expr::{EvalError, NullaryFunc};
 use mz_ore::stack::RecursionLimitError;
 use mz_ore::str::StrExt;
@@ -103,6 +103,8 @@ pub enum CoordError {
     TailOnlyTransaction,
     /// An error occurred in the MIR stage of the optimizer.
     Transform(TransformError),
+    /// A request to the named webhook source was not validly signed.
+    UnauthorizedWebhookRequest(String),
     /// The named cursor does not exist.
     UnknownCursor(String),
     /// The named role does not exist.
@@ -193,7 +195,9 @@ impl CoordError {
                 let source_name = match source_type {
                     RematerializedSourceType::Postgres => "Postgres",
                     RematerializedSourceType::S3 => "S3 with SQS notification ",
+                    RematerializedSourceType::Webhook => "Webhook",
                     RematerializedSourceType::PersistedSource => "Persisted",
+                    RematerializedSourceType::DecodeErrorRedirect => "ON DECODE ERROR REDIRECT",
                 };
                 Some(format!(
                     "{} sources can be materialized by only one set of indexes at a time. \
@@ -260,7 +264,9 @@ impl CoordError {
                 let doc_page = match source_type {
                     RematerializedSourceType::Postgres => "postgres",
                     RematerializedSourceType::S3 => "text-s3",
-                    RematerializedSourceType::PersistedSource => {
+                    RematerializedSourceType::Webhook => "webhook",
+                    RematerializedSourceType::PersistedSource
+                    | RematerializedSourceType::DecodeErrorRedirect => {
                         // TODO: Make this more helpful once we have documentation for persisted
                         // sources.
                         return Some(
@@ -370,6 +376,13 @@ impl fmt::Display for CoordError {
                 f.write_str("TAIL in transactions must be the only read statement")
             }
             CoordError::Transform(e) => e.fmt(f),
+            CoordError::UnauthorizedWebhookRequest(name) => {
+                write!(
+                    f,
+                    "request to webhook source {} is not authorized",
+                    name.quoted()
+                )
+            }
             CoordError::UnknownCursor(name) => {
                 write!(f, "cursor {} does not exist", name.quoted())
             }
@@ -459,7 +472,9 @@ impl Error for CoordError {}
 pub enum RematerializedSourceType {
     Postgres,
     S3,
+    Webhook,
     PersistedSource,
+    DecodeErrorRedirect,
 }
 
 impl RematerializedSourceType {
@@ -474,9 +489,17 @@ impl RematerializedSourceType {
         }
 
         match &source.connector {
-            SourceConnector::External { connector, .. } => match connector {
+            SourceConnector::External {
+                connector,
+                decode_errors,
+                ..
+            } => match connector {
                 ExternalSourceConnector::S3(_) => RematerializedSourceType::S3,
                 ExternalSourceConnector::Postgres(_) => RematerializedSourceType::Postgres,
+                ExternalSourceConnector::Webhook(_) => RematerializedSourceType::Webhook,
+                _ if matches!(decode_errors, DecodeErrorPolicy::Redirect(_)) => {
+                    RematerializedSourceType::DecodeErrorRedirect
+                }
                 _ => unreachable!(),
             },
             _ => unreachable!(),
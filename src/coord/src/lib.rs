@@ -41,6 +41,7 @@ mod persistcfg;
 mod sink_connector;
 mod tail;
 mod util;
+mod webhook;
 
 pub mod catalog;
 pub mod session;
@@ -260,10 +260,27 @@ impl PersisterWithConfig {
 
     /// Adds the given table to the set of tables managed by the persister.
     pub fn add_table(&mut self, id: GlobalId, table: &catalog::Table) -> Result<(), Error> {
-        let stream_name = match &table.persist_name {
-            Some(x) => x.clone(),
-            None => return Ok(()),
-        };
+        match &table.persist_name {
+            Some(stream_name) => self.add_stream(id, stream_name.clone()),
+            None => Ok(()),
+        }
+    }
+
+    /// Adds the requests of the given webhook source to the set of tables
+    /// managed by the persister. Webhook sources are written like tables, so
+    /// their requests are sealed and compacted along with the table data.
+    pub fn add_webhook_source(
+        &mut self,
+        id: GlobalId,
+        source: &catalog::Source,
+    ) -> Result<(), Error> {
+        match source.webhook_persist_name() {
+            Some(stream_name) => self.add_stream(id, stream_name.to_owned()),
+            None => Ok(()),
+        }
+    }
+
+    fn add_stream(&mut self, id: GlobalId, stream_name: String) -> Result<(), Error> {
         let persister = match self.runtime.as_ref() {
             Some(x) => x,
             None => return Ok(()),
@@ -7,21 +7,27 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::fs::OpenOptions;
+use std::fs::{self, OpenOptions};
 use std::time::Duration;
 
 use anyhow::{anyhow, Context};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, ResourceSpecifier, TopicReplication};
 use rdkafka::config::ClientConfig;
+use rdkafka::error::KafkaResult;
+use rdkafka::producer::{BaseRecord, Producer, ThreadedProducer};
 
 use mz_dataflow_types::sinks::{
-    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, KafkaSinkConnector,
-    KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention, KafkaSinkConsistencyConnector,
-    PublishedSchemaInfo, SinkConnector, SinkConnectorBuilder,
+    AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, FileSinkConnector, FileSinkConnectorBuilder,
+    FileSinkTarget, KafkaSinkConnector, KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention,
+    KafkaSinkConsistencyConnector, KafkaSinkProgressConnector, KafkaSinkProgressRecord,
+    PostgresSinkConnector, PostgresSinkConnectorBuilder, PublishedSchemaInfo, SinkConnector,
+    SinkConnectorBuilder,
 };
 use mz_expr::GlobalId;
 use mz_kafka_util::client::MzClientContext;
 use mz_ore::collections::CollectionExt;
+use mz_ore::task;
+use mz_repr::Timestamp;
 
 use crate::error::CoordError;
 
@@ -32,6 +38,8 @@ pub async fn build(
     match builder {
         SinkConnectorBuilder::Kafka(k) => build_kafka(k, id).await,
         SinkConnectorBuilder::AvroOcf(a) => build_avro_ocf(a, id),
+        SinkConnectorBuilder::File(f) => build_file(f, id),
+        SinkConnectorBuilder::Postgres(p) => build_postgres(p).await,
     }
 }
 
@@ -41,6 +49,7 @@ async fn register_kafka_topic(
     mut partition_count: i32,
     mut replication_factor: i32,
     succeed_if_exists: bool,
+    compacted: bool,
     retention: KafkaSinkConnectorRetention,
 ) -> Result<(), CoordError> {
     // if either partition count or replication factor should be defaulted to the broker's config
@@ -143,6 +152,9 @@ async fn register_kafka_topic(
     if let Some(ref retention_bytes) = retention_bytes_str {
         kafka_topic = kafka_topic.set("retention.bytes", retention_bytes);
     }
+    if compacted {
+        kafka_topic = kafka_topic.set("cleanup.policy", "compact");
+    }
 
     if succeed_if_exists {
         mz_kafka_util::admin::ensure_topic(
@@ -242,10 +254,12 @@ async fn build_kafka(
         builder.partition_count,
         builder.replication_factor,
         builder.reuse_topic,
+        false,
         builder.retention,
     )
     .await
     .context("error registering kafka topic for sink")?;
+    let encoding = builder.format.encoding();
     let published_schema_info = match builder.format {
         mz_dataflow_types::sinks::KafkaSinkFormat::Avro {
             key_schema,
@@ -269,7 +283,31 @@ async fn build_kafka(
                 value_schema_id,
             })
         }
-        mz_dataflow_types::sinks::KafkaSinkFormat::Json => None,
+        mz_dataflow_types::sinks::KafkaSinkFormat::Protobuf {
+            key_schema,
+            value_schema,
+            ccsr_config: Some(ccsr_config),
+        } => {
+            let ccsr = ccsr_config.build()?;
+            let (key_schema_id, value_schema_id) = publish_kafka_schemas(
+                &ccsr,
+                &topic,
+                key_schema.as_deref(),
+                Some(mz_ccsr::SchemaType::Protobuf),
+                &value_schema,
+                mz_ccsr::SchemaType::Protobuf,
+            )
+            .await
+            .context("error publishing kafka schemas for sink")?;
+            Some(PublishedSchemaInfo {
+                key_schema_id,
+                value_schema_id,
+            })
+        }
+        mz_dataflow_types::sinks::KafkaSinkFormat::Protobuf {
+            ccsr_config: None, ..
+        }
+        | mz_dataflow_types::sinks::KafkaSinkFormat::Json => None,
     };
 
     let consistency = match builder.consistency_format {
@@ -291,6 +329,7 @@ async fn build_kafka(
                 1,
                 builder.replication_factor,
                 builder.reuse_topic,
+                false,
                 KafkaSinkConnectorRetention::default(),
             )
             .await
@@ -310,11 +349,63 @@ async fn build_kafka(
 
             Some(KafkaSinkConsistencyConnector {
                 topic: consistency_topic,
-                schema_id: consistency_schema_id,
+                schema_id: Some(consistency_schema_id),
             })
         }
-        Some(other) => unreachable!("non-Avro consistency format for Kafka sink {:#?}", &other),
-        _ => None,
+        Some(mz_dataflow_types::sinks::KafkaSinkFormat::Protobuf { .. }) => {
+            unreachable!("Protobuf consistency format for Kafka sink")
+        }
+        Some(mz_dataflow_types::sinks::KafkaSinkFormat::Json) => {
+            let consistency_topic = maybe_append_nonce(
+                builder
+                    .consistency_topic_prefix
+                    .as_ref()
+                    .expect("known to exist"),
+            );
+            // JSON consistency records are self-describing, so there is no
+            // schema to publish.
+            register_kafka_topic(
+                &client,
+                &consistency_topic,
+                1,
+                builder.replication_factor,
+                builder.reuse_topic,
+                false,
+                KafkaSinkConnectorRetention::default(),
+            )
+            .await
+            .context("error registering kafka consistency topic for sink")?;
+
+            Some(KafkaSinkConsistencyConnector {
+                topic: consistency_topic,
+                schema_id: None,
+            })
+        }
+        None => None,
+    };
+
+    let progress = match builder.progress_topic {
+        Some(progress_topic) => {
+            // The progress topic is shared by all exactly-once sinks that name
+            // it, and only the latest record for each sink matters.
+            register_kafka_topic(
+                &client,
+                &progress_topic,
+                1,
+                builder.replication_factor,
+                true,
+                true,
+                KafkaSinkConnectorRetention::default(),
+            )
+            .await
+            .context("error registering kafka progress topic for sink")?;
+
+            Some(KafkaSinkProgressConnector {
+                topic: progress_topic,
+                key: format!("{}/{}", builder.cluster_id, id),
+            })
+        }
+        None => None,
     };
 
     Ok(SinkConnector::Kafka(KafkaSinkConnector {
@@ -324,15 +415,79 @@ async fn build_kafka(
         relation_key_indices: builder.relation_key_indices,
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
+        partition_by: builder.partition_by,
+        headers: builder.headers,
+        encoding,
         published_schema_info,
         consistency,
         exactly_once: builder.reuse_topic,
+        progress,
         transitive_source_dependencies: builder.transitive_source_dependencies,
         fuel: builder.fuel,
         config_options: builder.config_options,
     }))
 }
 
+/// Records in the progress topic of the exactly-once Kafka sink `id` that the
+/// sink has written all of its updates at or before `timestamp`, so that the
+/// sink resumes with the updates after `timestamp` when it next starts.
+///
+/// The record is written in a transaction with the sink's transactional ID,
+/// which fences out any instance of the sink that is still writing.
+pub async fn resume_kafka_sink(
+    connector: &KafkaSinkConnector,
+    id: GlobalId,
+    timestamp: Timestamp,
+) -> Result<(), CoordError> {
+    let progress = match &connector.progress {
+        Some(progress) => progress.clone(),
+        None => coord_bail!("sink {} does not record its progress", id),
+    };
+
+    let mut config = ClientConfig::new();
+    config.set("bootstrap.servers", &connector.addrs.to_string());
+    for (k, v) in connector.config_options.iter() {
+        // Explicitly reject the statistics interval option here because its not
+        // properly supported for this client.
+        // Explicitly reject isolation.level as it's a consumer-specific
+        // parameter and will generate a benign WARN for producers
+        if k != "statistics.interval.ms" && k != "isolation.level" {
+            config.set(k, v);
+        }
+    }
+    config.set(
+        "transactional.id",
+        format!("mz-producer-{}", connector.topic),
+    );
+    let producer: ThreadedProducer<_> = config
+        .create_with_context(MzClientContext)
+        .context("creating kafka producer for sink failed")?;
+
+    let payload = serde_json::to_vec(&KafkaSinkProgressRecord { timestamp })
+        .expect("progress records serialize to JSON");
+    task::spawn_blocking(
+        || format!("resume_kafka_sink:{}", id),
+        move || -> KafkaResult<()> {
+            let timeout = Duration::from_secs(5);
+            producer.init_transactions(timeout)?;
+            producer.begin_transaction()?;
+            producer
+                .send(
+                    BaseRecord::to(&progress.topic)
+                        .key(&progress.key)
+                        .payload(&payload),
+                )
+                .map_err(|(e, _record)| e)?;
+            producer.commit_transaction(timeout)
+        },
+    )
+    .await
+    .map_err(|e| anyhow!(e))?
+    .context("error writing progress record for kafka sink")?;
+
+    Ok(())
+}
+
 fn build_avro_ocf(
     builder: AvroOcfSinkConnectorBuilder,
     id: GlobalId,
@@ -372,3 +527,137 @@ fn build_avro_ocf(
         value_desc: builder.value_desc,
     }))
 }
+
+fn build_file(
+    builder: FileSinkConnectorBuilder,
+    id: GlobalId,
+) -> Result<SinkConnector, CoordError> {
+    // Each sink writes into its own directory, so that its manifest and files
+    // never collide with those of another sink.
+    let directory = format!("{}-{}", id, builder.directory_suffix);
+    let target = match builder.target {
+        FileSinkTarget::Local { path } => {
+            let path = path.join(directory);
+            fs::create_dir_all(&path).map_err(|e| {
+                anyhow!(
+                    "unable to create file sink directory {}: {}",
+                    path.display(),
+                    e
+                )
+            })?;
+            FileSinkTarget::Local { path }
+        }
+        FileSinkTarget::S3 {
+            bucket,
+            prefix,
+            aws,
+        } => {
+            let prefix = match prefix.trim_end_matches('/') {
+                "" => format!("{}/", directory),
+                prefix => format!("{}/{}/", prefix, directory),
+            };
+            FileSinkTarget::S3 {
+                bucket,
+                prefix,
+                aws,
+            }
+        }
+    };
+    Ok(SinkConnector::File(FileSinkConnector {
+        value_desc: builder.value_desc,
+        format: builder.format,
+        target,
+        max_file_bytes: builder.max_file_bytes,
+        max_file_interval: builder.max_file_interval,
+    }))
+}
+
+async fn build_postgres(
+    builder: PostgresSinkConnectorBuilder,
+) -> Result<SinkConnector, CoordError> {
+    let client = mz_postgres_util::connect(&builder.conn)
+        .await
+        .context("error connecting to Postgres for sink")?;
+
+    let upstream_columns: Vec<String> = client
+        .query(
+            "SELECT column_name::text FROM information_schema.columns
+             WHERE table_schema = $1 AND table_name = $2",
+            &[&builder.schema, &builder.table],
+        )
+        .await
+        .context("error fetching upstream table columns for Postgres sink")?
+        .into_iter()
+        .map(|row| row.get(0))
+        .collect();
+    if upstream_columns.is_empty() {
+        coord_bail!(
+            "upstream table {}.{} does not exist",
+            builder.schema,
+            builder.table
+        );
+    }
+    for name in builder.value_desc.iter_names() {
+        if !upstream_columns.iter().any(|c| c == name.as_str()) {
+            coord_bail!(
+                "upstream table {}.{} has no column named {}",
+                builder.schema,
+                builder.table,
+                name
+            );
+        }
+    }
+
+    // Upserts are expressed with `ON CONFLICT`, which requires a unique index
+    // on exactly the key columns.
+    let (key_desc, _indices) = &builder.key_desc_and_indices;
+    let mut key_columns: Vec<String> = key_desc.iter_names().map(|n| n.to_string()).collect();
+    key_columns.sort();
+    let has_unique_index: bool = client
+        .query_one(
+            "SELECT EXISTS (
+                SELECT 1
+                FROM pg_index AS i
+                    JOIN pg_class AS c ON c.oid = i.indrelid
+                    JOIN pg_namespace AS n ON n.oid = c.relnamespace
+                WHERE n.nspname = $1 AND c.relname = $2
+                    AND i.indisunique AND i.indpred IS NULL
+                    AND (
+                        SELECT array_agg(a.attname::text ORDER BY a.attname::text)
+                        FROM pg_attribute AS a
+                        WHERE a.attrelid = c.oid AND a.attnum = ANY (i.indkey)
+                    ) = $3
+            )",
+            &[&builder.schema, &builder.table, &key_columns],
+        )
+        .await
+        .context("error fetching upstream table indexes for Postgres sink")?
+        .get(0);
+    if !has_unique_index {
+        coord_bail!(
+            "upstream table {}.{} has no unique index on the sink key ({})",
+            builder.schema,
+            builder.table,
+            key_columns.join(", ")
+        );
+    }
+
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE IF NOT EXISTS {}.{} (sink_table text PRIMARY KEY, upper bigint NOT NULL)",
+            mz_postgres_util::quote_identifier(&builder.schema),
+            mz_postgres_util::quote_identifier(&builder.progress_table),
+        ))
+        .await
+        .context("error creating progress table for Postgres sink")?;
+
+    Ok(SinkConnector::Postgres(PostgresSinkConnector {
+        conn: builder.conn,
+        schema: builder.schema,
+        table: builder.table,
+        key_desc_and_indices: builder.key_desc_and_indices,
+        value_desc: builder.value_desc,
+        progress_table: builder.progress_table,
+        transitive_source_dependencies: builder.transitive_source_dependencies,
+    }))
+}
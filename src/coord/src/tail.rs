@@ -9,6 +9,7 @@
 
 //! Implementations around supporting the TAIL protocol with the dataflow layer
 
+use mz_dataflow_types::sinks::TailEnvelope;
 use mz_dataflow_types::TailResponse;
 use mz_repr::adt::numeric;
 use mz_repr::{Datum, Row};
@@ -22,7 +23,10 @@ pub(crate) struct PendingTail {
     channel: mpsc::UnboundedSender<Vec<Row>>,
     /// Whether progress information should be emitted
     emit_progress: bool,
-    /// Number of columns in the output
+    /// Whether the output includes an `mz_diff` column, which is omitted when
+    /// the tail has an envelope
+    emit_diff: bool,
+    /// Number of columns in the rows produced by the dataflow
     arity: usize,
 }
 
@@ -31,15 +35,22 @@ impl PendingTail {
     /// * The `channel` receives batches of finalized rows.
     /// * If `emit_progress` is true, the finalized rows are either data or progress updates
     /// * `arity` is the arity of the sink relation.
+    /// * If `envelope` is set, the dataflow produces rows already formatted
+    ///   according to it, and no diff column is emitted.
     pub(crate) fn new(
         channel: mpsc::UnboundedSender<Vec<Row>>,
         emit_progress: bool,
         arity: usize,
+        envelope: Option<&TailEnvelope>,
     ) -> Self {
         Self {
             channel,
             emit_progress,
-            arity,
+            emit_diff: envelope.is_none(),
+            arity: match envelope {
+                None => arity,
+                Some(envelope) => envelope.output_arity(arity),
+            },
         }
     }
 
@@ -59,8 +70,9 @@ impl PendingTail {
                     let mut packer = row_buf.packer();
                     packer.push(Datum::from(numeric::Numeric::from(*&upper[0])));
                     packer.push(Datum::True);
-                    // Fill in the diff column and all table columns with NULL.
-                    for _ in 0..(self.arity + 1) {
+                    // Fill in the diff column (if any) and all table columns
+                    // with NULL.
+                    for _ in 0..(self.arity + usize::from(self.emit_diff)) {
                         packer.push(Datum::Null);
                     }
 
@@ -92,7 +104,9 @@ impl PendingTail {
                             packer.push(Datum::False);
                         }
 
-                        packer.push(Datum::Int64(diff));
+                        if self.emit_diff {
+                            packer.push(Datum::Int64(diff));
+                        }
 
                         packer.extend_by_row(&row);
 
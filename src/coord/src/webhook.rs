@@ -0,0 +1,57 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Validation of the requests pushed to webhook sources.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Reports whether `signature`, the value of a request's signature header, is
+/// the HMAC-SHA256 of `body` under `secret`.
+///
+/// The signature must be hex-encoded and may be prefixed by `sha256=`, as is
+/// the convention of GitHub and many other services. The comparison is
+/// performed in constant time.
+pub fn verify_signature(secret: &str, body: &[u8], signature: &str) -> bool {
+    let signature = signature.trim();
+    let signature = signature.strip_prefix("sha256=").unwrap_or(signature);
+    let signature = match hex::decode(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify_signature;
+
+    #[test]
+    fn test_verify_signature() {
+        // Test case 2 from RFC 4231.
+        let secret = "Jefe";
+        let body = b"what do ya want for nothing?";
+        let signature = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+        assert!(verify_signature(secret, body, signature));
+        assert!(verify_signature(
+            secret,
+            body,
+            &format!("sha256={}", signature)
+        ));
+        assert!(verify_signature(secret, body, &signature.to_uppercase()));
+        assert!(!verify_signature(secret, b"what do ya want?", signature));
+        assert!(!verify_signature(secret, body, &signature[2..]));
+        assert!(!verify_signature(secret, body, "not hex"));
+        assert!(!verify_signature(secret, body, ""));
+    }
+}
@@ -139,8 +139,9 @@ pub struct CreateSourceCommand<T> {
     pub desc: SourceDesc,
     /// The initial `since` frontier
     pub since: Antichain<T>,
-    /// Any previously stored timestamp bindings
-    pub ts_bindings: Vec<(PartitionId, T, crate::sources::MzOffset)>,
+    /// Any previously stored timestamp bindings, along with the upstream position of the
+    /// last message bound by each, if the source records one.
+    pub ts_bindings: Vec<(PartitionId, T, crate::sources::MzOffset, Option<String>)>,
 }
 
 /// Commands related to the ingress and egress of collections.
@@ -153,6 +154,11 @@ pub struct CreateSourceCommand<T> {
 pub enum StorageCommand<T = mz_repr::Timestamp> {
     /// Create the enumerated sources, each associated with its identifier.
     CreateSources(Vec<CreateSourceCommand<T>>),
+    /// Replace the descriptions of the enumerated sources.
+    ///
+    /// Dataflows that are rendered afterwards use the new descriptions, while
+    /// dataflows that are already running are unaffected.
+    AlterSources(Vec<(GlobalId, SourceDesc)>),
     /// Render the enumerated sources.
     ///
     /// Each source has a name for debugging purposes, an optional "as of" frontier and collection
@@ -199,6 +205,7 @@ impl StorageCommandKind {
         match self {
             StorageCommandKind::AdvanceAllLocalInputs => "advance_all_local_inputs",
             StorageCommandKind::AllowCompaction => "allow_storage_compaction",
+            StorageCommandKind::AlterSources => "alter_sources",
             StorageCommandKind::CreateSources => "create_sources",
             StorageCommandKind::DurabilityFrontierUpdates => "durability_frontier_updates",
             StorageCommandKind::Insert => "insert",
@@ -329,8 +336,9 @@ impl<T: timely::progress::Timestamp> Command<T> {
 pub struct TimestampBindingFeedback<T = mz_repr::Timestamp> {
     /// Durability frontier changes
     pub changes: Vec<(GlobalId, ChangeBatch<T>)>,
-    /// Timestamp bindings for all of those frontier changes
-    pub bindings: Vec<(GlobalId, PartitionId, T, MzOffset)>,
+    /// Timestamp bindings for all of those frontier changes, along with the upstream position
+    /// of the last message bound by each, if the source records one.
+    pub bindings: Vec<(GlobalId, PartitionId, T, MzOffset, Option<String>)>,
 }
 
 /// Responses that the worker/dataflow can provide back to the coordinator.
@@ -359,6 +367,14 @@ pub enum StorageResponse<T = mz_repr::Timestamp> {
     /// Timestamp bindings and prior and new frontiers for those bindings for all
     /// sources
     TimestampBindings(TimestampBindingFeedback<T>),
+    /// Records that sources with `ON DECODE ERROR REDIRECT` failed to decode,
+    /// as rows to insert into the table with the second ID, keyed by the ID of
+    /// the source that read them
+    DecodeErrorRedirects(Vec<(GlobalId, GlobalId, Vec<Row>)>),
+    /// The offsets from which sources with `ON DECODE ERROR REDIRECT` resumed
+    /// reading their partitions, keyed by the ID of the source. The sources
+    /// never read the records before those offsets again.
+    DecodeErrorRestoredOffsets(Vec<(GlobalId, Vec<(PartitionId, MzOffset)>)>),
 }
 
 /// A client to a running dataflow server.
@@ -784,6 +800,22 @@ pub mod partitioned {
                         .into_iter(),
                     )
                 }
+                // Each worker reports the records it failed to decode itself.
+                Response::Storage(StorageResponse::DecodeErrorRedirects(redirects)) => Box::new(
+                    Some(Response::Storage(StorageResponse::DecodeErrorRedirects(
+                        redirects,
+                    )))
+                    .into_iter(),
+                ),
+                // Each worker reports the offsets of the partitions it reads.
+                Response::Storage(StorageResponse::DecodeErrorRestoredOffsets(offsets)) => {
+                    Box::new(
+                        Some(Response::Storage(
+                            StorageResponse::DecodeErrorRestoredOffsets(offsets),
+                        ))
+                        .into_iter(),
+                    )
+                }
                 Response::Compute(ComputeResponse::PeekResponse(uuid, response), instance) => {
                     // Incorporate new peek responses; awaiting all responses.
                     let entry = self
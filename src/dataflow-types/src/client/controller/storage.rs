@@ -121,6 +121,27 @@ impl<'a, C: Client<T>, T: Timestamp + Lattice> StorageController<'a, C, T> {
 
         Ok(())
     }
+    /// Replaces the descriptions of existing sources.
+    ///
+    /// Dataflows that read from the sources and are created afterwards must use the new
+    /// descriptions.
+    pub async fn alter_sources(
+        &mut self,
+        sources: Vec<(GlobalId, SourceDesc)>,
+    ) -> Result<(), StorageError> {
+        self.validate_ids(sources.iter().map(|(id, _)| *id))?;
+        for (id, desc) in sources.iter() {
+            self.collection_mut(*id)?.description.0 = desc.clone();
+        }
+
+        self.client
+            .send(Command::Storage(StorageCommand::AlterSources(sources)))
+            .await
+            .expect("Storage command failed; unrecoverable");
+
+        Ok(())
+    }
+
     /// Drops the read capability for the sources and allows their resources to be reclaimed.
     pub async fn drop_sources(&mut self, identifiers: Vec<GlobalId>) -> Result<(), StorageError> {
         self.validate_ids(identifiers.iter().cloned())?;
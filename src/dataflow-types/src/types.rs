@@ -358,15 +358,17 @@ pub mod sources {
     use uuid::Uuid;
 
     use crate::gen::postgres_source::PostgresSourceDetails;
+    use mz_expr::GlobalId;
     use mz_kafka_util::KafkaAddrs;
-    use mz_repr::{ColumnType, RelationDesc, RelationType, ScalarType};
+    use mz_repr::adt::numeric::NumericMaxScale;
+    use mz_repr::{ColumnType, Datum, RelationDesc, RelationType, Row, ScalarType};
 
     // Types and traits related to the *decoding* of data for sources.
     pub mod encoding {
         use anyhow::Context;
         use serde::{Deserialize, Serialize};
 
-        use mz_interchange::{avro, protobuf};
+        use mz_interchange::{avro, parquet, protobuf};
         use mz_repr::{ColumnType, RelationDesc, ScalarType};
 
         /// A description of how to interpret data from various sources
@@ -391,9 +393,14 @@ pub mod sources {
             Protobuf(ProtobufEncoding),
             Csv(CsvEncoding),
             Regex(RegexEncoding),
+            Parquet(ParquetEncoding),
             Postgres,
             Bytes,
             Text,
+            Json,
+            /// Rows that are produced directly by the source, rather than
+            /// decoded, and that are described by the given `RelationDesc`.
+            RowCodec(RelationDesc),
         }
 
         impl SourceDataEncoding {
@@ -505,6 +512,13 @@ pub mod sources {
                     },
                     DataEncoding::Text => RelationDesc::empty()
                         .with_column("text", ScalarType::String.nullable(false)),
+                    DataEncoding::Json => {
+                        RelationDesc::empty().with_column("data", ScalarType::Jsonb.nullable(false))
+                    }
+                    DataEncoding::RowCodec(desc) => desc.clone(),
+                    DataEncoding::Parquet(ParquetEncoding { columns, .. }) => {
+                        parquet::columns_to_relationdesc(columns)
+                    }
                     DataEncoding::Postgres => RelationDesc::empty()
                         .with_column("oid", ScalarType::Int32.nullable(false))
                         .with_column(
@@ -527,6 +541,9 @@ pub mod sources {
                     DataEncoding::Regex { .. } => "Regex",
                     DataEncoding::Csv(_) => "Csv",
                     DataEncoding::Text => "Text",
+                    DataEncoding::Json => "Json",
+                    DataEncoding::RowCodec(_) => "RowCodec",
+                    DataEncoding::Parquet(_) => "Parquet",
                     DataEncoding::Postgres => "Postgres",
                 }
             }
@@ -553,6 +570,16 @@ pub mod sources {
             pub confluent_wire_format: bool,
         }
 
+        /// Encoding in Parquet format.
+        #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+        pub struct ParquetEncoding {
+            pub columns: Vec<parquet::ParquetColumn>,
+            /// The indexes of the columns that are demanded by the dataflow reading
+            /// the source, or `None` if all columns are demanded. Filled in when the
+            /// source is rendered.
+            pub projection: Option<Vec<usize>>,
+        }
+
         /// Arguments necessary to define how to decode from CSV format
         #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
         pub struct CsvEncoding {
@@ -721,7 +748,7 @@ pub mod sources {
     }
 
     /// Which piece of metadata a column corresponds to
-    #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub enum IncludedColumnSource {
         /// The materialize-specific notion of "position"
         ///
@@ -731,6 +758,12 @@ pub mod sources {
         Offset,
         Timestamp,
         Topic,
+        Headers,
+        /// The value of the last header with the given key.
+        Header {
+            key: String,
+            use_bytes: bool,
+        },
     }
 
     /// Whether and how to include the decoded key of a stream in dataflows
@@ -757,6 +790,16 @@ pub mod sources {
         pub pos: usize,
     }
 
+    /// A column that was created via an `INCLUDE HEADER` expression
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct IncludedHeader {
+        /// The key of the header whose value is included.
+        pub key: String,
+        pub column: IncludedColumnPos,
+        /// Whether the value is included as `bytea` rather than `text`.
+        pub use_bytes: bool,
+    }
+
     /// The meaning of the timestamp number produced by data sources. This type
     /// is not concerned with the source of the timestamp (like if the data came
     /// from a Debezium consistency topic or a CDCv2 stream), instead only what the
@@ -787,6 +830,72 @@ pub mod sources {
         Debezium(DebeziumEnvelope),
         Upsert(UpsertEnvelope),
         CdcV2,
+        /// The source produces rows along with their diffs, which may retract
+        /// rows it produced earlier.
+        DifferentialRow,
+    }
+
+    /// What a source does with records that it fails to decode.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub enum DecodeErrorPolicy {
+        /// Put the source into an error state.
+        Fail,
+        /// Drop the record.
+        Skip,
+        /// Drop the record and insert its raw contents into the table with the
+        /// given ID. The table must have the shape described by
+        /// [`decode_error_redirect_desc`].
+        Redirect(GlobalId),
+    }
+
+    /// Binds the records of a source to the time in one of their columns,
+    /// rather than to the time at which they were ingested.
+    ///
+    /// The source's frontier trails the largest event time observed so far by
+    /// `max_out_of_orderness`. Records whose event time is already behind the
+    /// frontier when they arrive are bound to the frontier instead.
+    #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct EventTimeConfig {
+        /// The index of the `timestamp` or `timestamptz` column that holds each
+        /// record's event time.
+        pub column: usize,
+        /// How far the event times of records may lag behind the largest event
+        /// time observed so far.
+        pub max_out_of_orderness: Duration,
+        /// How long the source may go without records before its watermark
+        /// advances with processing time, if at all.
+        pub idle_timeout: Option<Duration>,
+        /// How far the event times of records may be ahead of the wall clock
+        /// before they are capped to it, if at all.
+        pub max_skew: Option<Duration>,
+    }
+
+    /// The columns of a table that receives the records a source failed to
+    /// decode via `ON DECODE ERROR REDIRECT TO`.
+    ///
+    /// The key and value are the raw bytes of the record, if the source has
+    /// them, and `partition` and `offset` describe where in the upstream
+    /// system the record was read from.
+    pub fn decode_error_redirect_desc() -> RelationDesc {
+        RelationDesc::empty()
+            .with_column("key", ScalarType::Bytes.nullable(true))
+            .with_column("value", ScalarType::Bytes.nullable(true))
+            .with_column("partition", ScalarType::String.nullable(true))
+            .with_column("offset", ScalarType::Int64.nullable(false))
+            .with_column("error", ScalarType::String.nullable(false))
+    }
+
+    /// Returns the partition and offset of a row described by
+    /// [`decode_error_redirect_desc`], which identify the record it describes
+    /// within its source.
+    pub fn decode_error_redirect_position(row: &Row) -> (Option<&str>, i64) {
+        let mut datums = row.iter().skip(2);
+        let partition = match datums.next().unwrap() {
+            Datum::Null => None,
+            d => Some(d.unwrap_str()),
+        };
+        let offset = datums.next().unwrap().unwrap_int64();
+        (partition, offset)
     }
 
     /// `UnplannedSourceEnvelope` is a `SourceEnvelope` missing some information. This information
@@ -799,6 +908,7 @@ pub mod sources {
         Debezium(DebeziumEnvelope),
         Upsert(UpsertStyle),
         CdcV2,
+        DifferentialRow,
     }
 
     #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -953,6 +1063,7 @@ pub mod sources {
                 }
                 UnplannedSourceEnvelope::None(inner) => SourceEnvelope::None(inner),
                 UnplannedSourceEnvelope::CdcV2 => SourceEnvelope::CdcV2,
+                UnplannedSourceEnvelope::DifferentialRow => SourceEnvelope::DifferentialRow,
             }
         }
 
@@ -1050,8 +1161,10 @@ pub mod sources {
                 UnplannedSourceEnvelope::CdcV2 => {
                     // the correct types
 
-                    // CdcV2 row data are in a record in a record in a list
+                    // CdcV2 row data are in a record in a record in a list, except
+                    // for schemaless encodings, whose rows are JSON documents
                     match &value_desc.typ().column_types[0].scalar_type {
+                        ScalarType::Jsonb => (self.into_source_envelope(None), value_desc.clone()),
                         ScalarType::List { element_type, .. } => match &**element_type {
                             ScalarType::Record { fields, .. } => {
                                 // TODO maybe check this by name
@@ -1070,6 +1183,10 @@ pub mod sources {
                         ty => bail!("Unepxected type for MATERIALIZE envelope: {:?}", ty),
                     }
                 }
+                UnplannedSourceEnvelope::DifferentialRow => (
+                    self.into_source_envelope(None),
+                    value_desc.concat(metadata_desc),
+                ),
             })
         }
     }
@@ -1077,7 +1194,12 @@ pub mod sources {
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct KafkaSourceConnector {
         pub addrs: KafkaAddrs,
+        /// The topic to read from or, if `topic_pattern` is present, the
+        /// pattern the topics to read from must match.
         pub topic: String,
+        /// If present, the source reads every topic whose name matches the
+        /// pattern, including topics that are created after the source.
+        pub topic_pattern: Option<mz_repr::adt::regex::Regex>,
         // Represents options specified by user when creating the source, e.g.
         // security settings.
         pub config_options: BTreeMap<String, String>,
@@ -1093,6 +1215,30 @@ pub mod sources {
         pub include_topic: Option<IncludedColumnPos>,
         /// If present, include the offset as an output column of the source with the given name.
         pub include_offset: Option<IncludedColumnPos>,
+        /// If present, include all of the message's headers as an output column of the source
+        /// with the given name.
+        pub include_headers: Option<IncludedColumnPos>,
+        /// The individual message headers to include as output columns of the source.
+        pub include_header: Vec<IncludedHeader>,
+    }
+
+    /// The type of the column produced by `INCLUDE HEADERS` for Kafka sources.
+    ///
+    /// Kafka permits a message to carry several headers with the same key, so
+    /// the headers are a list of `(key, value)` records in the order in which
+    /// they appear in the message, rather than a map.
+    pub fn kafka_headers_type() -> ScalarType {
+        ScalarType::List {
+            element_type: Box::new(ScalarType::Record {
+                fields: vec![
+                    ("key".into(), ScalarType::String.nullable(false)),
+                    ("value".into(), ScalarType::Bytes.nullable(true)),
+                ],
+                custom_oid: None,
+                custom_name: None,
+            }),
+            custom_oid: None,
+        }
     }
 
     /// Legacy logic included something like an offset into almost data streams
@@ -1114,6 +1260,14 @@ pub mod sources {
     #[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub enum Compression {
         Gzip,
+        Zstd,
+        Bzip2,
+        Xz,
+        /// The Snappy framing format, not raw Snappy blocks.
+        Snappy,
+        /// Detect the codec separately for each file or object, from its
+        /// extension or, failing that, its leading magic bytes.
+        Auto,
         None,
     }
 
@@ -1137,6 +1291,8 @@ pub mod sources {
             metadata_columns: Vec<IncludedColumnSource>,
             ts_frequency: Duration,
             timeline: Timeline,
+            decode_errors: DecodeErrorPolicy,
+            event_time: Option<EventTimeConfig>,
         },
 
         /// A local "source" is either fed by a local input handle, or by reading from a
@@ -1169,15 +1325,18 @@ pub mod sources {
         /// even when failures/restarts happen.
         pub fn yields_stable_input(&self) -> bool {
             if let SourceConnector::External { connector, .. } = self {
-                // Conservatively, set all Kafka, File, or AvroOcf sources as having stable inputs because
-                // we know they will be read in a known, repeatable offset order (modulo compaction for some Kafka sources).
+                // Conservatively, set all Kafka, Kinesis, File, or AvroOcf sources as having stable inputs because
+                // we know they will be read in a known, repeatable offset order (modulo compaction for some Kafka sources
+                // and retention for Kinesis streams). Kinesis offsets are numbered per shard and resume from the
+                // recorded sequence numbers of the last bound records. Files matched by a glob are not stable, as
+                // they may be rotated away or truncated.
                 match connector {
+                    ExternalSourceConnector::File(FileSourceConnector { pattern, .. }) => {
+                        pattern.is_none()
+                    }
                     ExternalSourceConnector::Kafka(_)
-                    | ExternalSourceConnector::File(_)
+                    | ExternalSourceConnector::Kinesis(_)
                     | ExternalSourceConnector::AvroOcf(_) => true,
-                    // Currently, the Kinesis connector assigns "offsets" by counting the message in the order it was received
-                    // and this order is not replayable across different reads of the same Kinesis stream.
-                    ExternalSourceConnector::Kinesis(_) => false,
                     _ => false,
                 }
             } else {
@@ -1185,6 +1344,26 @@ pub mod sources {
             }
         }
 
+        /// Returns `true` if this connector never retracts the rows it
+        /// produces.
+        ///
+        /// Only sources without an envelope qualify, and even then not those
+        /// that delete rows, like Postgres sources and load generators.
+        pub fn is_append_only(&self) -> bool {
+            match self {
+                SourceConnector::External {
+                    connector:
+                        ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::LoadGenerator(_),
+                    ..
+                } => false,
+                SourceConnector::External {
+                    envelope: SourceEnvelope::None(_),
+                    ..
+                } => true,
+                _ => false,
+            }
+        }
+
         pub fn name(&self) -> &'static str {
             match self {
                 SourceConnector::External { connector, .. } => connector.name(),
@@ -1199,8 +1378,16 @@ pub mod sources {
             }
         }
         pub fn requires_single_materialization(&self) -> bool {
-            if let SourceConnector::External { connector, .. } = self {
+            if let SourceConnector::External {
+                connector,
+                decode_errors,
+                ..
+            } = self
+            {
+                // Each instance of a source that redirects its decode errors
+                // would insert every undecodable record into the table again.
                 connector.requires_single_materialization()
+                    || matches!(decode_errors, DecodeErrorPolicy::Redirect(_))
             } else {
                 false
             }
@@ -1216,6 +1403,8 @@ pub mod sources {
         S3(S3SourceConnector),
         Postgres(PostgresSourceConnector),
         PubNub(PubNubSourceConnector),
+        Webhook(WebhookSourceConnector),
+        LoadGenerator(LoadGeneratorSourceConnector),
     }
 
     impl ExternalSourceConnector {
@@ -1237,12 +1426,14 @@ pub mod sources {
                     include_timestamp: time,
                     include_topic: topic,
                     include_offset: offset,
+                    include_headers: headers,
+                    include_header: header,
                     ..
                 }) => {
                     let mut items = BTreeMap::new();
                     // put the offset at the end if necessary
                     if include_defaults && offset.is_none() {
-                        items.insert(4, default_col("mz_offset"));
+                        items.insert(usize::MAX, default_col("mz_offset"));
                     }
 
                     for (include, ty) in [
@@ -1255,6 +1446,23 @@ pub mod sources {
                             items.insert(include.pos + 1, (&include.name, ty.nullable(false)));
                         }
                     }
+                    if let Some(include) = headers {
+                        items.insert(
+                            include.pos + 1,
+                            (&include.name, kafka_headers_type().nullable(false)),
+                        );
+                    }
+                    for IncludedHeader {
+                        column, use_bytes, ..
+                    } in header
+                    {
+                        let ty = if *use_bytes {
+                            ScalarType::Bytes
+                        } else {
+                            ScalarType::String
+                        };
+                        items.insert(column.pos + 1, (&column.name, ty.nullable(true)));
+                    }
 
                     items.into_values().collect()
                 }
@@ -1285,6 +1493,25 @@ pub mod sources {
                 }
                 Self::Postgres(_) => vec![],
                 Self::PubNub(_) => vec![],
+                Self::LoadGenerator(_) => vec![],
+                Self::Webhook(WebhookSourceConnector {
+                    include_headers, ..
+                }) => {
+                    if let Some(include) = include_headers {
+                        columns.push((
+                            include.name.as_str(),
+                            ScalarType::Map {
+                                value_type: Box::new(ScalarType::String),
+                                custom_oid: None,
+                            }
+                            .nullable(false),
+                        ));
+                    }
+                    if include_defaults {
+                        columns.push(default_col("mz_offset"))
+                    };
+                    columns
+                }
             }
         }
 
@@ -1298,6 +1525,8 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => Some("mz_record"),
                 ExternalSourceConnector::Postgres(_) => None,
                 ExternalSourceConnector::PubNub(_) => None,
+                ExternalSourceConnector::Webhook(_) => Some("mz_offset"),
+                ExternalSourceConnector::LoadGenerator(_) => None,
             }
         }
 
@@ -1308,25 +1537,43 @@ pub mod sources {
                     include_timestamp: time,
                     include_topic: topic,
                     include_offset: offset,
+                    include_headers: headers,
+                    include_header: header,
                     ..
                 }) => {
                     // create a sorted list of column types based on the order they were declared in sql
                     // TODO: should key be included in the sorted list? Breaking change, and it's
                     // already special (it commonly multiple columns embedded in it).
                     let mut items = BTreeMap::new();
-                    if include_defaults {
-                        items.insert(4, IncludedColumnSource::DefaultPosition);
+                    // keep in sync with `metadata_columns`
+                    if include_defaults && offset.is_none() {
+                        items.insert(usize::MAX, IncludedColumnSource::DefaultPosition);
                     }
                     for (include, ty) in [
                         (offset, IncludedColumnSource::Offset),
                         (part, IncludedColumnSource::Partition),
                         (time, IncludedColumnSource::Timestamp),
                         (topic, IncludedColumnSource::Topic),
+                        (headers, IncludedColumnSource::Headers),
                     ] {
                         if let Some(include) = include {
                             items.insert(include.pos, ty);
                         }
                     }
+                    for IncludedHeader {
+                        key,
+                        column,
+                        use_bytes,
+                    } in header
+                    {
+                        items.insert(
+                            column.pos,
+                            IncludedColumnSource::Header {
+                                key: key.clone(),
+                                use_bytes: *use_bytes,
+                            },
+                        );
+                    }
 
                     items.into_values().collect()
                 }
@@ -1341,9 +1588,22 @@ pub mod sources {
                         Vec::new()
                     }
                 }
-                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::PubNub(_) => {
-                    Vec::new()
+                ExternalSourceConnector::Webhook(WebhookSourceConnector {
+                    include_headers,
+                    ..
+                }) => {
+                    let mut items = Vec::new();
+                    if include_headers.is_some() {
+                        items.push(IncludedColumnSource::Headers);
+                    }
+                    if include_defaults {
+                        items.push(IncludedColumnSource::DefaultPosition);
+                    }
+                    items
                 }
+                ExternalSourceConnector::Postgres(_)
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::LoadGenerator(_) => Vec::new(),
             }
         }
 
@@ -1357,6 +1617,8 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => "s3",
                 ExternalSourceConnector::Postgres(_) => "postgres",
                 ExternalSourceConnector::PubNub(_) => "pubnub",
+                ExternalSourceConnector::Webhook(_) => "webhook",
+                ExternalSourceConnector::LoadGenerator(_) => "load-generator",
             }
         }
 
@@ -1376,6 +1638,8 @@ pub mod sources {
                 ExternalSourceConnector::S3(_) => None,
                 ExternalSourceConnector::Postgres(_) => None,
                 ExternalSourceConnector::PubNub(_) => None,
+                ExternalSourceConnector::Webhook(_) => None,
+                ExternalSourceConnector::LoadGenerator(_) => None,
             }
         }
 
@@ -1383,12 +1647,16 @@ pub mod sources {
             match self {
                 ExternalSourceConnector::S3(c) => c.requires_single_materialization(),
                 ExternalSourceConnector::Postgres(_) => true,
+                // Requests are inserted into the single local input that
+                // materializes the source.
+                ExternalSourceConnector::Webhook(_) => true,
 
                 ExternalSourceConnector::Kafka(_)
                 | ExternalSourceConnector::Kinesis(_)
                 | ExternalSourceConnector::File(_)
                 | ExternalSourceConnector::AvroOcf(_)
-                | ExternalSourceConnector::PubNub(_) => false,
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::LoadGenerator(_) => false,
             }
         }
     }
@@ -1401,7 +1669,12 @@ pub mod sources {
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct FileSourceConnector {
+        /// The file to read or, if `pattern` is set, the directory in which to
+        /// look for files that match it.
         pub path: PathBuf,
+        /// A glob over absolute paths. If set, every matching file under `path`
+        /// is read, and files that appear later are picked up when tailing.
+        pub pattern: Option<Glob>,
         pub tail: bool,
         pub compression: Compression,
     }
@@ -1420,6 +1693,119 @@ pub mod sources {
         pub channel: String,
     }
 
+    /// A source that generates deterministic data on its own.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct LoadGeneratorSourceConnector {
+        pub generator: LoadGenerator,
+        /// The interval at which the generator emits a batch of changes.
+        pub tick_interval: Duration,
+    }
+
+    /// The data produced by a [`LoadGeneratorSourceConnector`].
+    ///
+    /// The changes emitted at each tick depend only on the generator and the
+    /// number of preceding ticks, so a restarted generator replays the same
+    /// changes at the same offsets.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum LoadGenerator {
+        /// Inserts the next integer at every tick. If `max_cardinality` is
+        /// set, the oldest integer is deleted once that many are present.
+        Counter { max_cardinality: Option<u64> },
+        /// Opens auctions, bids on them, and closes them.
+        Auction,
+        /// Loads the TPC-H `orders` relation, then continuously inserts,
+        /// updates, and deletes orders.
+        Tpch { count_orders: u64 },
+    }
+
+    impl LoadGenerator {
+        /// Describes the rows produced by the generator.
+        pub fn desc(&self) -> RelationDesc {
+            match self {
+                LoadGenerator::Counter { .. } => {
+                    RelationDesc::empty().with_column("counter", ScalarType::Int64.nullable(false))
+                }
+                LoadGenerator::Auction => RelationDesc::empty()
+                    .with_column("id", ScalarType::Int64.nullable(false))
+                    .with_column("item", ScalarType::String.nullable(false))
+                    .with_column("seller", ScalarType::Int64.nullable(false))
+                    .with_column("highest_bid", ScalarType::Int32.nullable(true))
+                    .with_column("bidder", ScalarType::Int64.nullable(true))
+                    .with_key(vec![0]),
+                LoadGenerator::Tpch { .. } => RelationDesc::empty()
+                    .with_column("o_orderkey", ScalarType::Int64.nullable(false))
+                    .with_column("o_custkey", ScalarType::Int64.nullable(false))
+                    .with_column("o_orderstatus", ScalarType::String.nullable(false))
+                    .with_column(
+                        "o_totalprice",
+                        ScalarType::Numeric {
+                            max_scale: Some(NumericMaxScale::try_from(2i64).unwrap()),
+                        }
+                        .nullable(false),
+                    )
+                    .with_column("o_orderdate", ScalarType::Date.nullable(false))
+                    .with_column("o_orderpriority", ScalarType::String.nullable(false))
+                    .with_column("o_clerk", ScalarType::String.nullable(false))
+                    .with_column("o_shippriority", ScalarType::Int32.nullable(false))
+                    .with_column("o_comment", ScalarType::String.nullable(false))
+                    .with_key(vec![0]),
+            }
+        }
+    }
+
+    /// A source whose records are pushed to Materialize's HTTP server, one
+    /// record per request body.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct WebhookSourceConnector {
+        /// The lowercase name of the header that holds the hex-encoded
+        /// HMAC-SHA256 of each request's body, optionally prefixed by
+        /// `sha256=`.
+        ///
+        /// The secret with which requests are signed is stored alongside the
+        /// source in the catalog, rather than in its definition.
+        pub signature_header: String,
+        /// The column, if any, in which to include the request headers.
+        pub include_headers: Option<IncludedColumnPos>,
+        /// The name of the persistent stream that records the source's
+        /// requests, like the rows of a persisted table, if user tables are
+        /// persisted. Assigned by the coordinator.
+        pub persisted_name: Option<String>,
+    }
+
+    impl WebhookSourceConnector {
+        /// Packs a request to a webhook source into the row that the
+        /// coordinator inserts into the source's local input.
+        ///
+        /// The request's `headers` must be sorted by name.
+        pub fn pack_request<'a, I>(position: i64, body: &[u8], headers: I) -> Row
+        where
+            I: IntoIterator<Item = (&'a str, &'a str)>,
+        {
+            let mut row = Row::default();
+            let mut packer = row.packer();
+            packer.push(Datum::Int64(position));
+            packer.push(Datum::Bytes(body));
+            packer.push_dict(headers.into_iter().map(|(k, v)| (k, Datum::String(v))));
+            row
+        }
+
+        /// Unpacks a row produced by [`WebhookSourceConnector::pack_request`]
+        /// into the request's position, body, and headers.
+        pub fn unpack_request(row: &Row) -> (i64, Vec<u8>, Vec<(String, Option<Vec<u8>>)>) {
+            let mut datums = row.iter();
+            let position = datums.next().unwrap().unwrap_int64();
+            let body = datums.next().unwrap().unwrap_bytes().to_vec();
+            let headers = datums
+                .next()
+                .unwrap()
+                .unwrap_map()
+                .iter()
+                .map(|(k, v)| (k.to_owned(), Some(v.unwrap_str().as_bytes().to_vec())))
+                .collect();
+            (position, body, headers)
+        }
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct S3SourceConnector {
         pub key_sources: Vec<S3KeySource>,
@@ -1606,10 +1992,13 @@ pub mod sinks {
     use serde::{Deserialize, Serialize};
     use timely::progress::frontier::Antichain;
     use url::Url;
+    use uuid::Uuid;
 
     use mz_expr::GlobalId;
     use mz_kafka_util::KafkaAddrs;
-    use mz_repr::RelationDesc;
+    use mz_repr::{RelationDesc, Timestamp};
+
+    use crate::types::sources::AwsConfig;
 
     /// A sink for updates to a relational collection.
     #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -1625,6 +2014,12 @@ pub mod sinks {
     pub enum SinkEnvelope {
         Debezium,
         Upsert,
+        /// Writes each inserted row as a plain message. Only valid for
+        /// collections that never retract rows.
+        Append,
+        /// Writes batches of updates and progress statements in the CDCv2
+        /// format, as read by `ENVELOPE MATERIALIZE` sources.
+        CdcV2,
     }
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1638,12 +2033,29 @@ pub mod sinks {
         Kafka(KafkaSinkConnector),
         Tail(TailSinkConnector),
         AvroOcf(AvroOcfSinkConnector),
+        File(FileSinkConnector),
+        Postgres(PostgresSinkConnector),
     }
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct KafkaSinkConsistencyConnector {
         pub topic: String,
-        pub schema_id: i32,
+        /// The ID of the consistency schema in the schema registry, if the
+        /// consistency records are Avro-encoded. `None` indicates that the
+        /// records are JSON-encoded.
+        pub schema_id: Option<i32>,
+    }
+
+    /// Where an exactly-once Kafka sink records its progress.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct KafkaSinkProgressConnector {
+        /// The compacted topic that records the progress of all exactly-once
+        /// sinks that name it.
+        pub topic: String,
+        /// The key of the sink's records in `topic`. It is namespaced by the
+        /// cluster ID of the Materialize instance, as sink IDs are only unique
+        /// within an instance.
+        pub key: String,
     }
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1654,9 +2066,19 @@ pub mod sinks {
         pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
         pub relation_key_indices: Option<Vec<usize>>,
         pub value_desc: RelationDesc,
+        /// An expression over the sink's input whose value, hashed with
+        /// murmur2, chooses the partition of each message.
+        pub partition_by: Option<MirScalarExpr>,
+        /// The names of and expressions over the sink's input for the headers
+        /// of each message.
+        pub headers: Vec<(String, MirScalarExpr)>,
+        pub encoding: KafkaSinkEncoding,
         pub published_schema_info: Option<PublishedSchemaInfo>,
         pub consistency: Option<KafkaSinkConsistencyConnector>,
         pub exactly_once: bool,
+        /// Where the sink records the timestamp up to which it has written its
+        /// updates. Only present if `exactly_once` is set.
+        pub progress: Option<KafkaSinkProgressConnector>,
         // Source dependencies for exactly-once sinks.
         pub transitive_source_dependencies: Vec<GlobalId>,
         // Maximum number of records the sink will attempt to send each time it is
@@ -1665,6 +2087,14 @@ pub mod sinks {
         pub config_options: BTreeMap<String, String>,
     }
 
+    /// The encoding of the messages written by a [`KafkaSinkConnector`].
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum KafkaSinkEncoding {
+        Avro,
+        Json,
+        Protobuf,
+    }
+
     /// TODO(JLDLaughlin): Documentation.
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct PublishedSchemaInfo {
@@ -1678,12 +2108,79 @@ pub mod sinks {
         pub path: PathBuf,
     }
 
+    /// A sink that writes the updates of each closed timestamp to a directory
+    /// of files, along with a manifest describing the completed timestamps.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct FileSinkConnector {
+        pub value_desc: RelationDesc,
+        pub format: FileSinkFormat,
+        /// The directory to write files to, unique to this sink.
+        pub target: FileSinkTarget,
+        /// Start a new file once the current one holds at least this many bytes.
+        pub max_file_bytes: u64,
+        /// Start a new file once the current one has been open for this long.
+        pub max_file_interval: Duration,
+    }
+
+    /// The encoding of the files written by a [`FileSinkConnector`].
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum FileSinkFormat {
+        Parquet,
+        /// Newline-delimited JSON objects.
+        Json,
+    }
+
+    impl FileSinkFormat {
+        /// Returns the file extension of files in this format.
+        pub fn extension(&self) -> &'static str {
+            match self {
+                FileSinkFormat::Parquet => "parquet",
+                FileSinkFormat::Json => "ndjson",
+            }
+        }
+    }
+
+    /// Where a [`FileSinkConnector`] writes its files.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum FileSinkTarget {
+        /// A directory on the local filesystem.
+        Local { path: PathBuf },
+        /// A key prefix in an S3 bucket.
+        S3 {
+            bucket: String,
+            prefix: String,
+            aws: AwsConfig,
+        },
+    }
+
+    /// A sink that applies the changes of each closed timestamp to a table in
+    /// an upstream Postgres database, as a single transaction of upserts and
+    /// deletes on the sink's key.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct PostgresSinkConnector {
+        /// The connection string for the upstream database.
+        pub conn: String,
+        /// The schema containing both `table` and `progress_table`.
+        pub schema: String,
+        pub table: String,
+        pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+        pub value_desc: RelationDesc,
+        /// The table that records, for each sinked table, the timestamp before
+        /// which all changes have been applied. It is updated in the same
+        /// transaction as the changes, so that a restarted sink can resume
+        /// exactly where it left off.
+        pub progress_table: String,
+        pub transitive_source_dependencies: Vec<GlobalId>,
+    }
+
     impl SinkConnector {
         /// Returns the name of the sink connector.
         pub fn name(&self) -> &'static str {
             match self {
                 SinkConnector::AvroOcf(_) => "avro-ocf",
+                SinkConnector::File(_) => "file",
                 SinkConnector::Kafka(_) => "kafka",
+                SinkConnector::Postgres(_) => "postgres",
                 SinkConnector::Tail(_) => "tail",
             }
         }
@@ -1706,6 +2203,8 @@ pub mod sinks {
             match self {
                 SinkConnector::Kafka(k) => k.exactly_once,
                 SinkConnector::AvroOcf(_) => false,
+                SinkConnector::File(_) => false,
+                SinkConnector::Postgres(_) => true,
                 SinkConnector::Tail(_) => false,
             }
         }
@@ -1716,18 +2215,57 @@ pub mod sinks {
             match self {
                 SinkConnector::Kafka(k) => &k.transitive_source_dependencies,
                 SinkConnector::AvroOcf(_) => &[],
+                SinkConnector::File(_) => &[],
+                SinkConnector::Postgres(p) => &p.transitive_source_dependencies,
                 SinkConnector::Tail(_) => &[],
             }
         }
     }
 
     #[derive(Default, Clone, Debug, Serialize, Deserialize)]
-    pub struct TailSinkConnector {}
+    pub struct TailSinkConnector {
+        /// How to present the changes to each key, if the tail was
+        /// requested with an envelope.
+        pub envelope: Option<TailEnvelope>,
+    }
+
+    /// The envelope of a `TAIL`, which collapses the updates to each key at
+    /// each timestamp into a single output row.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub enum TailEnvelope {
+        /// Emit the latest value of each changed key, or a delete marker.
+        Upsert { key_indices: Vec<usize> },
+        /// Emit the value of each changed key before and after the change.
+        Debezium { key_indices: Vec<usize> },
+    }
+
+    impl TailEnvelope {
+        /// The indices of the key columns in the tailed relation.
+        pub fn key_indices(&self) -> &[usize] {
+            match self {
+                TailEnvelope::Upsert { key_indices } | TailEnvelope::Debezium { key_indices } => {
+                    key_indices
+                }
+            }
+        }
+
+        /// The number of columns in the rows emitted for a relation of
+        /// `arity` columns: a state column, the key columns, and the value
+        /// columns (twice, for Debezium).
+        pub fn output_arity(&self, arity: usize) -> usize {
+            match self {
+                TailEnvelope::Upsert { .. } => 1 + arity,
+                TailEnvelope::Debezium { key_indices } => 1 + 2 * arity - key_indices.len(),
+            }
+        }
+    }
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub enum SinkConnectorBuilder {
         Kafka(KafkaSinkConnectorBuilder),
         AvroOcf(AvroOcfSinkConnectorBuilder),
+        File(FileSinkConnectorBuilder),
+        Postgres(PostgresSinkConnectorBuilder),
     }
 
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -1737,6 +2275,29 @@ pub mod sinks {
         pub value_desc: RelationDesc,
     }
 
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct FileSinkConnectorBuilder {
+        pub value_desc: RelationDesc,
+        pub format: FileSinkFormat,
+        /// The user-specified directory, within which the sink creates its own
+        /// directory.
+        pub target: FileSinkTarget,
+        pub directory_suffix: String,
+        pub max_file_bytes: u64,
+        pub max_file_interval: Duration,
+    }
+
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct PostgresSinkConnectorBuilder {
+        pub conn: String,
+        pub schema: String,
+        pub table: String,
+        pub key_desc_and_indices: (RelationDesc, Vec<usize>),
+        pub value_desc: RelationDesc,
+        pub progress_table: String,
+        pub transitive_source_dependencies: Vec<GlobalId>,
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
     pub struct KafkaSinkConnectorBuilder {
         pub broker_addrs: KafkaAddrs,
@@ -1746,6 +2307,10 @@ pub mod sinks {
         /// The user-specified key for the sink.
         pub key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
         pub value_desc: RelationDesc,
+        /// See [`KafkaSinkConnector::partition_by`].
+        pub partition_by: Option<MirScalarExpr>,
+        /// See [`KafkaSinkConnector::headers`].
+        pub headers: Vec<(String, MirScalarExpr)>,
         pub topic_prefix: String,
         pub consistency_topic_prefix: Option<String>,
         pub consistency_format: Option<KafkaSinkFormat>,
@@ -1757,11 +2322,26 @@ pub mod sinks {
         // Forces the sink to always write to the same topic across restarts instead
         // of picking a new topic each time.
         pub reuse_topic: bool,
+        /// The topic of [`KafkaSinkConnector::progress`].
+        pub progress_topic: Option<String>,
+        /// The cluster ID of the Materialize instance, which namespaces the
+        /// key of [`KafkaSinkConnector::progress`].
+        pub cluster_id: Uuid,
         // Source dependencies for exactly-once sinks.
         pub transitive_source_dependencies: Vec<GlobalId>,
         pub retention: KafkaSinkConnectorRetention,
     }
 
+    /// A record in the progress topic of an exactly-once Kafka sink.
+    ///
+    /// Progress records are keyed by [`KafkaSinkProgressConnector::key`] and
+    /// state that the sink has written all of its updates at or before
+    /// `timestamp`. They are JSON-encoded.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct KafkaSinkProgressRecord {
+        pub timestamp: Timestamp,
+    }
+
     #[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
     pub struct KafkaSinkConnectorRetention {
         pub duration: Option<Option<Duration>>,
@@ -1777,6 +2357,24 @@ pub mod sinks {
             ccsr_config: mz_ccsr::ClientConfig,
         },
         Json,
+        Protobuf {
+            key_schema: Option<String>,
+            value_schema: String,
+            /// The schema registry to publish the schemas to, if any. If
+            /// present, messages are framed in the Confluent wire format.
+            ccsr_config: Option<mz_ccsr::ClientConfig>,
+        },
+    }
+
+    impl KafkaSinkFormat {
+        /// Returns the encoding of the messages written in this format.
+        pub fn encoding(&self) -> KafkaSinkEncoding {
+            match self {
+                KafkaSinkFormat::Avro { .. } => KafkaSinkEncoding::Avro,
+                KafkaSinkFormat::Json => KafkaSinkEncoding::Json,
+                KafkaSinkFormat::Protobuf { .. } => KafkaSinkEncoding::Protobuf,
+            }
+        }
     }
 }
 
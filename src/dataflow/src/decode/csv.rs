@@ -13,6 +13,9 @@ use mz_repr::{Datum, Row};
 #[derive(Debug)]
 pub struct CsvDecoderState {
     next_row_is_header: bool,
+    /// Whether to drop every record that matches the header, rather than
+    /// checking only the first row against it.
+    drop_header_rows: bool,
     header_names: Option<Vec<String>>,
     n_cols: usize,
     output: Vec<u8>,
@@ -31,7 +34,11 @@ impl CsvDecoderState {
         self.events_error + self.events_success
     }
 
-    pub fn new(format: CsvEncoding, operators: &mut Option<LinearOperator>) -> Self {
+    pub fn new(
+        format: CsvEncoding,
+        operators: &mut Option<LinearOperator>,
+        is_connector_delimited: bool,
+    ) -> Self {
         let CsvEncoding { columns, delimiter } = format;
         let n_cols = columns.arity();
 
@@ -46,8 +53,12 @@ impl CsvDecoderState {
             .collect::<Vec<_>>();
 
         let header_names = columns.into_header_names();
+        // Sources that delimit their own records, like Kafka, have no first row
+        // but the first record of each partition, which `start_partition`
+        // marks. Producers may publish the header again whenever they restart.
         Self {
-            next_row_is_header: header_names.is_some(),
+            next_row_is_header: header_names.is_some() && !is_connector_delimited,
+            drop_header_rows: header_names.is_some() && is_connector_delimited,
             header_names,
             n_cols,
             output: vec![0],
@@ -62,7 +73,27 @@ impl CsvDecoderState {
         }
     }
 
+    /// Reports whether the record whose fields are in `output` matches the
+    /// header names.
+    fn is_header(&self, output: &str) -> bool {
+        self.header_names
+            .iter()
+            .flatten()
+            .enumerate()
+            .all(|(i, name)| &output[self.ends[i]..self.ends[i + 1]] == name)
+    }
+
     pub fn reset_for_new_object(&mut self) {
+        // Each object of a source that delimits its own records is a single
+        // record, not a file that starts with a header.
+        if self.header_names.is_some() && !self.drop_header_rows {
+            self.next_row_is_header = true;
+        }
+    }
+
+    /// Requires the next record, the first of a partition of a source that
+    /// delimits its own records, to be the header.
+    pub fn start_partition(&mut self) {
         if self.header_names.is_some() {
             self.next_row_is_header = true;
         }
@@ -106,6 +137,11 @@ impl CsvDecoderState {
                             )))
                         } else {
                             match std::str::from_utf8(&self.output[0..self.output_cursor]) {
+                                Ok(output) if self.drop_header_rows && self.is_header(output) => {
+                                    self.output_cursor = 0;
+                                    self.ends_cursor = 1;
+                                    Ok(None)
+                                }
                                 Ok(output) => {
                                     self.events_success += 1;
                                     let mut row_packer = self.row_buf.packer();
@@ -155,10 +191,10 @@ impl CsvDecoderState {
                                 )));
                             }
                         }
-                        if chunk.is_empty() {
-                            break Ok(None);
-                        } else if result.is_err() {
+                        if result.is_err() {
                             break result;
+                        } else if chunk.is_empty() {
+                            break Ok(None);
                         }
                     } else {
                         break result;
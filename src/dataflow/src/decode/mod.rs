@@ -11,7 +11,7 @@ use std::{any::Any, cell::RefCell, collections::VecDeque, rc::Rc, time::Duration
 
 use ::regex::Regex;
 use chrono::NaiveDateTime;
-use differential_dataflow::capture::YieldingIter;
+use differential_dataflow::capture::{Message, YieldingIter};
 use differential_dataflow::Hashable;
 use differential_dataflow::{AsCollection, Collection};
 use futures::executor::block_on;
@@ -25,12 +25,14 @@ use timely::scheduling::SyncActivator;
 
 use mz_dataflow_types::{
     sources::{
-        encoding::{AvroEncoding, AvroOcfEncoding, DataEncoding, RegexEncoding},
+        encoding::{AvroEncoding, AvroOcfEncoding, DataEncoding, ProtobufEncoding, RegexEncoding},
         IncludedColumnSource, SourceEnvelope,
     },
     DecodeError, LinearOperator,
 };
 use mz_interchange::avro::ConfluentAvroResolver;
+use mz_interchange::protobuf::DecodedDescriptors;
+use mz_ore::str::StrExt;
 use mz_repr::Datum;
 use mz_repr::{Diff, Row, Timestamp};
 use tracing::error;
@@ -45,14 +47,80 @@ mod avro;
 mod csv;
 mod protobuf;
 
+/// Decodes the messages of a CDCv2 source, each of which is either a batch of
+/// updates or a progress statement.
+enum CdcV2Decoder {
+    Avro(ConfluentAvroResolver),
+    Json,
+    Protobuf(mz_interchange::protobuf::cdc_v2::Decoder),
+}
+
+impl CdcV2Decoder {
+    fn new(encoding: DataEncoding) -> Self {
+        match encoding {
+            DataEncoding::Avro(AvroEncoding {
+                schema,
+                schema_registry_config,
+                confluent_wire_format,
+            }) => {
+                // We will have already checked validity of the schema by now, so this can't fail.
+                let resolver = ConfluentAvroResolver::new(
+                    &schema,
+                    schema_registry_config,
+                    confluent_wire_format,
+                )
+                .unwrap();
+                CdcV2Decoder::Avro(resolver)
+            }
+            DataEncoding::Json => CdcV2Decoder::Json,
+            DataEncoding::Protobuf(ProtobufEncoding {
+                descriptors,
+                message_name,
+                confluent_wire_format,
+            }) => {
+                // The planner has already validated the shape of the message.
+                let descriptors = DecodedDescriptors::from_bytes(&descriptors, message_name)
+                    .expect("descriptors provided to protobuf source are pre-validated");
+                let decoder = mz_interchange::protobuf::cdc_v2::Decoder::new(
+                    descriptors,
+                    confluent_wire_format,
+                )
+                .expect("CDCv2 protobuf message is pre-validated");
+                CdcV2Decoder::Protobuf(decoder)
+            }
+            encoding => unreachable!(
+                "Attempted to create {}-encoded CDCv2 source",
+                encoding.op_name()
+            ),
+        }
+    }
+
+    fn decode(&mut self, value: &[u8]) -> Result<Message<Row, Timestamp, Diff>, String> {
+        match self {
+            CdcV2Decoder::Avro(resolver) => {
+                let (mut data, schema, _) = block_on(resolver.resolve(value))
+                    .map_err(|e| format!("Failed to get schema info for CDCv2 record: {}", e))?;
+                let d = GeneralDeserializer {
+                    schema: schema.top_node(),
+                };
+                let dec = mz_interchange::avro::cdc_v2::Decoder;
+                d.deserialize(&mut data, dec)
+                    .map_err(|e| format!("Failed to deserialize avro message: {}", e))
+            }
+            CdcV2Decoder::Json => mz_interchange::json::cdc_v2::decode(value)
+                .map_err(|e| format!("Failed to deserialize JSON message: {:#}", e)),
+            CdcV2Decoder::Protobuf(decoder) => decoder
+                .decode(value)
+                .map_err(|e| format!("Failed to deserialize protobuf message: {:#}", e)),
+        }
+    }
+}
+
 pub fn decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
     stream: &Stream<G, SourceOutput<Option<Vec<u8>>, Option<Vec<u8>>>>,
-    schema: &str,
-    registry: Option<mz_ccsr::ClientConfig>,
-    confluent_wire_format: bool,
+    encoding: DataEncoding,
 ) -> (Collection<G, Row, Diff>, Box<dyn Any>) {
-    // We will have already checked validity of the schema by now, so this can't fail.
-    let mut resolver = ConfluentAvroResolver::new(schema, registry, confluent_wire_format).unwrap();
+    let mut decoder = CdcV2Decoder::new(encoding);
     let channel = Rc::new(RefCell::new(VecDeque::new()));
     let activator: Rc<RefCell<Option<SyncActivator>>> = Rc::new(RefCell::new(None));
     let mut vector = Vec::new();
@@ -70,21 +138,10 @@ pub fn decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
                             Some(value) => value,
                             None => continue,
                         };
-                        let (mut data, schema, _) = match block_on(resolver.resolve(&*value)) {
+                        let message = match decoder.decode(value) {
                             Ok(ok) => ok,
                             Err(e) => {
-                                error!("Failed to get schema info for CDCv2 record: {}", e);
-                                continue;
-                            }
-                        };
-                        let d = GeneralDeserializer {
-                            schema: schema.top_node(),
-                        };
-                        let dec = mz_interchange::avro::cdc_v2::Decoder;
-                        let message = match d.deserialize(&mut data, dec) {
-                            Ok(ok) => ok,
-                            Err(e) => {
-                                error!("Failed to deserialize avro message: {}", e);
+                                error!("{}", e);
                                 continue;
                             }
                         };
@@ -160,6 +217,9 @@ pub(crate) enum DataDecoderInner {
         format: PreDelimitedFormat,
     },
     Csv(CsvDecoderState),
+    /// Parquet sources decode their files while reading them, and produce
+    /// [`MessagePayload::Row`]s instead of bytes.
+    Parquet,
 
     PreDelimited(PreDelimitedFormat),
 }
@@ -185,6 +245,12 @@ impl DataDecoder {
             }
             DataDecoderInner::Avro(avro) => avro.decode(bytes),
             DataDecoderInner::Csv(csv) => csv.decode(bytes),
+            DataDecoderInner::Parquet => {
+                *bytes = &[];
+                Err(DecodeError::Text(
+                    "Parquet sources cannot decode raw bytes".to_string(),
+                ))
+            }
             DataDecoderInner::PreDelimited(format) => {
                 let result = format.decode(*bytes);
                 *bytes = &[];
@@ -193,6 +259,15 @@ impl DataDecoder {
         }
     }
 
+    /// Prepares the decoder for the first record of a partition of a source
+    /// that delimits its own records, which CSV sources with a header expect
+    /// to be the header.
+    pub fn start_partition(&mut self) {
+        if let DataDecoderInner::Csv(csv) = &mut self.inner {
+            csv.start_partition();
+        }
+    }
+
     /// Get the next record if it exists, assuming an EOF has occurred.
     ///
     /// This is distinct from `next` because, for example, a CSV record should be returned even if it
@@ -293,14 +368,24 @@ fn get_decoder(
             }
         }
         DataEncoding::Csv(enc) => {
-            let state = CsvDecoderState::new(enc, operators);
+            let state = CsvDecoderState::new(enc, operators, is_connector_delimited);
             DataDecoder {
                 inner: DataDecoderInner::Csv(state),
                 metrics,
             }
         }
-        DataEncoding::Postgres => {
-            unreachable!("Postgres sources should not go through the general decoding path.")
+        DataEncoding::Parquet(_) => DataDecoder {
+            inner: DataDecoderInner::Parquet,
+            metrics,
+        },
+        DataEncoding::Postgres | DataEncoding::RowCodec(_) => {
+            unreachable!(
+                "{} sources should not go through the general decoding path.",
+                encoding.op_name()
+            )
+        }
+        DataEncoding::Json => {
+            unreachable!("JSON sources are only decoded as ENVELOPE MATERIALIZE messages.")
         }
     }
 }
@@ -376,37 +461,62 @@ where
             input.for_each(|cap, data| {
                 let mut session = output.session(&cap);
                 for SourceOutput {
-                    key,
-                    value,
+                    key: raw_key,
+                    value: raw_value,
                     position,
                     upstream_time_millis,
                     partition,
+                    headers,
                 } in data.iter()
                 {
                     let key = key_decoder
                         .as_mut()
-                        .and_then(|decoder| try_decode(decoder, key.as_ref()));
+                        .and_then(|decoder| try_decode(decoder, raw_key.as_ref()));
 
-                    let value = try_decode(&mut value_decoder, value.as_ref());
+                    // Positions are 1-indexed, so this is the first record of
+                    // its partition.
+                    if *position == 1 && raw_value.is_some() {
+                        value_decoder.start_partition();
+                    }
+                    let value = try_decode(&mut value_decoder, raw_value.as_ref());
+
+                    // A message whose metadata cannot be decoded is as
+                    // erroneous as one whose value cannot be.
+                    let (value, metadata) = match to_metadata_row(
+                        &metadata_items,
+                        partition.clone(),
+                        *position,
+                        *upstream_time_millis,
+                        headers.as_deref(),
+                    ) {
+                        Ok(metadata) => (value, metadata),
+                        Err(e) => (Some(Err(e)), Row::default()),
+                    };
 
-                    if matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_))) {
+                    let failed = matches!(&key, Some(Err(_))) || matches!(&value, Some(Err(_)));
+                    if failed {
                         n_errors += 1;
                     } else if matches!(&value, Some(Ok(_))) {
                         n_successes += 1;
                     }
 
+                    // Keep the raw bytes of records that failed to decode around, so
+                    // that `ON DECODE ERROR REDIRECT` can hand them to the user.
+                    let (raw_key, raw_value) = if failed {
+                        (raw_key.clone(), raw_value.clone())
+                    } else {
+                        (None, None)
+                    };
+
                     session.give(DecodeResult {
                         key,
                         value,
                         position: *position,
                         upstream_time_millis: *upstream_time_millis,
                         partition: partition.clone(),
-                        metadata: to_metadata_row(
-                            &metadata_items,
-                            partition.clone(),
-                            *position,
-                            *upstream_time_millis,
-                        ),
+                        metadata,
+                        raw_key,
+                        raw_value,
                     });
                 }
             });
@@ -476,10 +586,35 @@ where
                     position: _,
                     upstream_time_millis,
                     partition,
+                    headers: _,
                 } in data.iter()
                 {
                     let value = match value {
                         MessagePayload::Data(data) => data,
+                        MessagePayload::Row(row) => {
+                            n_successes += 1;
+                            // `RangeFrom` `Iterator`'s never end
+                            let position = n_seen.next().unwrap();
+                            let metadata = to_metadata_row(
+                                &metadata_items,
+                                partition.clone(),
+                                position,
+                                *upstream_time_millis,
+                                None,
+                            )
+                            .expect("only Kafka headers can fail to decode");
+                            session.give(DecodeResult {
+                                key: None,
+                                value: Some(Ok(row.clone())),
+                                position,
+                                upstream_time_millis: *upstream_time_millis,
+                                partition: partition.clone(),
+                                metadata,
+                                raw_key: None,
+                                raw_value: None,
+                            });
+                            continue;
+                        }
                         MessagePayload::EOF => {
                             let data = &mut &value_buf[..];
                             let mut result = value_decoder.eof(data);
@@ -506,7 +641,9 @@ where
                                         partition.clone(),
                                         position,
                                         *upstream_time_millis,
-                                    );
+                                        None,
+                                    )
+                                    .expect("only Kafka headers can fail to decode");
 
                                     session.give(DecodeResult {
                                         key: None,
@@ -515,6 +652,8 @@ where
                                         upstream_time_millis: *upstream_time_millis,
                                         partition: partition.clone(),
                                         metadata,
+                                        raw_key: None,
+                                        raw_value: None,
                                     });
                                 }
                             }
@@ -566,7 +705,9 @@ where
                             partition.clone(),
                             position,
                             *upstream_time_millis,
-                        );
+                            None,
+                        )
+                        .expect("only Kafka headers can fail to decode");
 
                         if value_bytes_remaining.is_empty() {
                             session.give(DecodeResult {
@@ -576,6 +717,8 @@ where
                                 upstream_time_millis: *upstream_time_millis,
                                 partition: partition.clone(),
                                 metadata,
+                                raw_key: None,
+                                raw_value: None,
                             });
                             value_buf = vec![];
                             break;
@@ -587,6 +730,8 @@ where
                                 upstream_time_millis: *upstream_time_millis,
                                 partition: partition.clone(),
                                 metadata,
+                                raw_key: None,
+                                raw_value: None,
                             });
                         }
                         if is_err {
@@ -614,11 +759,17 @@ fn to_metadata_row(
     partition: PartitionId,
     position: i64,
     upstream_time_millis: Option<i64>,
-) -> Row {
+    headers: Option<&[(String, Option<Vec<u8>>)]>,
+) -> Result<Row, DecodeError> {
     let mut row = Row::default();
     let mut packer = row.packer();
-    match partition {
-        PartitionId::Kafka(partition) => {
+    let kafka_partition = match partition {
+        PartitionId::Kafka(partition) => Some((None, partition)),
+        PartitionId::KafkaTopic(topic, partition) => Some((Some(topic), partition)),
+        PartitionId::Kinesis(_) | PartitionId::File(_) | PartitionId::None => None,
+    };
+    match kafka_partition {
+        Some((topic, partition)) => {
             for item in metadata_items.iter() {
                 match item {
                     IncludedColumnSource::Partition => packer.push(Datum::from(partition)),
@@ -638,18 +789,64 @@ fn to_metadata_row(
                             millis * 1_000_000,
                         )))
                     }
-                    IncludedColumnSource::Topic => unreachable!("Topic is not implemented yet"),
+                    IncludedColumnSource::Topic => packer.push(Datum::String(
+                        topic
+                            .as_deref()
+                            .expect("INCLUDE TOPIC requires a topic pattern"),
+                    )),
+                    IncludedColumnSource::Headers => packer.push_list_with(|packer| {
+                        for (key, value) in headers.expect("kafka sources always have headers") {
+                            packer.push_list_with(|packer| {
+                                packer.push(Datum::String(key));
+                                packer.push(match value {
+                                    Some(value) => Datum::Bytes(value),
+                                    None => Datum::Null,
+                                });
+                            });
+                        }
+                    }),
+                    IncludedColumnSource::Header { key, use_bytes } => {
+                        let value = headers
+                            .expect("kafka sources always have headers")
+                            .iter()
+                            .rev()
+                            .find(|(k, _)| k == key)
+                            .and_then(|(_, v)| v.as_deref());
+                        match value {
+                            None => packer.push(Datum::Null),
+                            Some(value) if *use_bytes => packer.push(Datum::Bytes(value)),
+                            Some(value) => match std::str::from_utf8(value) {
+                                Ok(value) => packer.push(Datum::String(value)),
+                                Err(e) => {
+                                    return Err(DecodeError::Text(format!(
+                                        "invalid UTF-8 in header {}: {}",
+                                        key.quoted(),
+                                        e
+                                    )))
+                                }
+                            },
+                        }
+                    }
                 }
             }
         }
-        PartitionId::None => {
+        None => {
             for item in metadata_items.iter() {
                 match item {
                     IncludedColumnSource::DefaultPosition => packer.push(Datum::from(position)),
+                    // Webhook request headers, which are sorted by name and
+                    // always have a textual value.
+                    IncludedColumnSource::Headers => packer.push_dict_with(|packer| {
+                        for (name, value) in headers.expect("webhook sources have headers") {
+                            packer.push(Datum::String(name));
+                            let value = value.as_deref().unwrap_or_default();
+                            packer.push(Datum::String(&String::from_utf8_lossy(value)));
+                        }
+                    }),
                     _ => unreachable!("Only Kafka supports non-defaultposition metadata items"),
                 }
             }
         }
     }
-    row
+    Ok(row)
 }
@@ -0,0 +1,153 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Binds the records of a source to the event times in their data.
+//!
+//! Records are routed to a single worker, which tracks the largest event time
+//! observed so far. The watermark, which is that event time less the maximum
+//! out-of-orderness, becomes the frontier of the output. Records are emitted
+//! at their event time, unless it is already behind the watermark, in which
+//! case they are emitted at the watermark.
+//!
+//! Two optional bounds keep the watermark moving sensibly. Once the source has
+//! been idle for the idle timeout, the largest event time advances with
+//! processing time, so that an idle source does not hold back its frontier
+//! forever. And event times further ahead of the wall clock than the maximum
+//! skew are capped to it, so that a single far-future event can't make every
+//! later record late.
+
+use std::cmp::{max, min};
+use std::convert::TryFrom;
+use std::time::Duration;
+
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::{OkErr, Operator};
+use timely::dataflow::{Scope, Stream};
+
+use mz_dataflow_types::sources::EventTimeConfig;
+use mz_dataflow_types::{DataflowError, DecodeError};
+use mz_ore::cast::CastFrom;
+use mz_ore::now::NowFn;
+use mz_repr::{Datum, Diff, Row, Timestamp};
+
+pub(crate) fn render<G>(
+    input: &Stream<G, (Row, Timestamp, Diff)>,
+    config: EventTimeConfig,
+    worker: usize,
+    now: NowFn,
+) -> (
+    Stream<G, (Row, Timestamp, Diff)>,
+    Stream<G, (DataflowError, Timestamp, Diff)>,
+)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let EventTimeConfig {
+        column,
+        max_out_of_orderness,
+        idle_timeout,
+        max_skew,
+    } = config;
+    let max_out_of_orderness = duration_millis(max_out_of_orderness);
+    let idle_timeout = idle_timeout.map(duration_millis);
+    let max_skew = max_skew.map(duration_millis);
+    let scope = input.scope();
+    let active = scope.index() == worker;
+
+    input
+        .unary_frontier(
+            Exchange::new(move |_| u64::cast_from(worker)),
+            "EventTime",
+            move |capability, info| {
+                let activator = scope.activator_for(&info.address[..]);
+                // Only the active worker receives records, so the other workers
+                // never produce output and can give up their capability.
+                let mut capability = if active { Some(capability) } else { None };
+                let mut max_event_time = 0;
+                // The processing time at which the last record arrived, and
+                // the largest event time as of then.
+                let mut last_record = (now(), max_event_time);
+                let mut data = vec![];
+                move |input, output| {
+                    while let Some((_cap, refmut_data)) = input.next() {
+                        let capability = capability
+                            .as_ref()
+                            .expect("records routed to inactive event time worker");
+                        let mut session = output.session(capability);
+                        refmut_data.swap(&mut data);
+                        let skew_limit = max_skew.map(|max_skew| now() + max_skew);
+                        for (row, _time, diff) in data.drain(..) {
+                            let watermark = max(
+                                *capability.time(),
+                                max_event_time.saturating_sub(max_out_of_orderness),
+                            );
+                            match event_time(&row, column) {
+                                Ok(time) => {
+                                    let time = skew_limit.map_or(time, |limit| min(time, limit));
+                                    max_event_time = max(max_event_time, time);
+                                    session.give((Ok(row), max(time, watermark), diff));
+                                }
+                                Err(err) => session.give((Err(err), watermark, diff)),
+                            }
+                        }
+                        last_record = (now(), max_event_time);
+                    }
+
+                    if input.frontier().is_empty() {
+                        capability = None;
+                    } else if let Some(capability) = &mut capability {
+                        if let Some(idle_timeout) = idle_timeout {
+                            let (last_record_at, last_max_event_time) = last_record;
+                            let idle = now().saturating_sub(last_record_at);
+                            if idle > idle_timeout {
+                                max_event_time =
+                                    max(max_event_time, last_max_event_time + idle - idle_timeout);
+                            }
+                            // Keep advancing while no records arrive.
+                            activator
+                                .activate_after(Duration::from_millis(min(idle_timeout, 1000)));
+                        }
+                        let watermark = max_event_time.saturating_sub(max_out_of_orderness);
+                        if watermark > *capability.time() {
+                            capability.downgrade(&watermark);
+                        }
+                    }
+                }
+            },
+        )
+        .ok_err(|(res, time, diff)| match res {
+            Ok(row) => Ok((row, time, diff)),
+            Err(err) => Err((err, time, diff)),
+        })
+}
+
+fn duration_millis(duration: Duration) -> Timestamp {
+    Timestamp::try_from(duration.as_millis()).expect("duration fits in a timestamp")
+}
+
+/// Extracts the event time, in milliseconds since the Unix epoch, from the
+/// specified column of `row`.
+fn event_time(row: &Row, column: usize) -> Result<Timestamp, DataflowError> {
+    let millis = match row.iter().nth(column).unwrap() {
+        Datum::Timestamp(ts) => ts.timestamp_millis(),
+        Datum::TimestampTz(ts) => ts.timestamp_millis(),
+        Datum::Null => {
+            return Err(DataflowError::DecodeError(DecodeError::Text(
+                "event time is null".into(),
+            )))
+        }
+        d => panic!("type error: expected timestamp, found {:?}", d),
+    };
+    Timestamp::try_from(millis).map_err(|_| {
+        DataflowError::DecodeError(DecodeError::Text(format!(
+            "event time {} is before the Unix epoch",
+            millis
+        )))
+    })
+}
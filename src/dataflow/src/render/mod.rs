@@ -125,6 +125,7 @@ use crate::server::{ComputeState, StorageState};
 pub mod context;
 mod debezium;
 mod envelope_none;
+mod event_time;
 mod flat_map;
 mod join;
 mod reduce;
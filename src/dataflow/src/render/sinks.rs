@@ -15,11 +15,17 @@ use std::collections::HashSet;
 use std::rc::Rc;
 
 use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
-use differential_dataflow::{Collection, Hashable};
+use differential_dataflow::{AsCollection, Collection, Hashable};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::operator::{self, Operator};
+use timely::dataflow::operators::{Branch, Capability};
 use timely::dataflow::Scope;
+use timely::Data;
+use tracing::error;
 
 use mz_dataflow_types::sinks::*;
-use mz_expr::{permutation_for_arrangement, GlobalId, MapFilterProject};
+use mz_dataflow_types::DataflowError;
+use mz_expr::{permutation_for_arrangement, EvalError, GlobalId, MapFilterProject};
 use mz_interchange::envelopes::{combine_at_timestamp, dbz_format, upsert_format};
 use mz_repr::{Datum, Diff, Row, Timestamp};
 
@@ -72,10 +78,12 @@ where
             collection
         };
 
-        let collection = apply_sink_envelope(sink, &sink_render, collection);
+        // Errors produced by applying the envelope halt the sink.
+        let (collection, errs) = apply_sink_envelope(sink, &sink_render, collection);
+        let collection = halt_on_errors(sink_id, collection, errs);
 
-        // TODO(benesch): errors should stream out through the sink,
-        // if we figure out a protocol for that.
+        // TODO(benesch): errors in the sink's input should stream out through
+        // the sink, if we figure out a protocol for that.
 
         let sink_token =
             sink_render.render_continuous_sink(compute_state, sink, sink_id, collection);
@@ -95,7 +103,10 @@ fn apply_sink_envelope<G>(
     sink: &SinkDesc,
     sink_render: &Box<dyn SinkRender<G>>,
     collection: Collection<G, Row, Diff>,
-) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+) -> (
+    Collection<G, (Option<Row>, Option<Row>), Diff>,
+    Collection<G, DataflowError, Diff>,
+)
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -158,6 +169,10 @@ where
     //   It then renders those as Avro.
     // * Upsert" does the same, except at the last step, it renders the diff pair in upsert format.
     //   (As part of doing so, it asserts that there are not multiple conflicting values at the same timestamp)
+    // * "Append" passes inserted rows through unchanged.
+    // * "CdcV2" passes all updates through, to be batched by the sink. File
+    //   sinks always use it.
+    let mut errs = operator::empty(&keyed.scope()).as_collection();
     let collection = match sink.envelope {
         Some(SinkEnvelope::Debezium) => {
             let combined = combine_at_timestamp(keyed.arrange_by_key().stream);
@@ -194,12 +209,96 @@ where
             });
             collection
         }
+        Some(SinkEnvelope::Append) => {
+            // if there is no user-specified key, remove the synthetic
+            // distribution key again
+            let keyed = if sink_render.get_key_indices().is_some() {
+                keyed
+            } else {
+                keyed.map(|(_key, value)| (None, value))
+            };
+
+            // The planner only permits append-only inputs, so retractions
+            // indicate a bug. Report them on the sink's error stream rather
+            // than tripping the sink's assertion that multiplicities are
+            // non-negative.
+            let from_id = sink.from;
+            let (appends, retractions) = keyed.inner.branch(|_, (_, _, diff)| *diff < 0);
+            errs = retractions.as_collection().map(move |_| {
+                DataflowError::EvalError(EvalError::Internal(format!(
+                    "ENVELOPE NONE sink input {} retracted a row",
+                    from_id
+                )))
+            });
+            appends
+                .as_collection()
+                .map(|(key, value)| (key, Some(value)))
+        }
+        // The CDCv2 encoder and file sinks group updates by timestamp
+        // themselves, and need to observe retractions, so pass the updates
+        // through.
+        Some(SinkEnvelope::CdcV2) => keyed.map(|(_key, value)| (None, Some(value))),
         // No envelope, this can only happen for TAIL sinks, which work
         // on vanilla rows.
         None => keyed.map(|(key, value)| (key, Some(value))),
     };
 
+    (collection, errs)
+}
+
+/// Passes the updates of `collection` through until the first time at which
+/// `errs` contains an error, and holds back the frontier of the output at that
+/// time.
+///
+/// This is the sink's error stream: the sink that consumes the output neither
+/// writes nor reports progress past the first error.
+pub(crate) fn halt_on_errors<G, D>(
+    sink_id: GlobalId,
+    collection: Collection<G, D, Diff>,
+    errs: Collection<G, DataflowError, Diff>,
+) -> Collection<G, D, Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+    D: Data,
+{
+    let mut stash: Vec<(Capability<Timestamp>, Vec<(D, Timestamp, Diff)>)> = Vec::new();
+    let mut halted_at: Option<Capability<Timestamp>> = None;
+    let mut buffer = Vec::new();
     collection
+        .inner
+        .binary_frontier(&errs.inner, Pipeline, Pipeline, "HaltOnErrors", |_, _| {
+            move |ok_input, err_input, output| {
+                err_input.for_each(|cap, data| {
+                    for (err, time, _diff) in data.iter() {
+                        if halted_at.as_ref().map_or(true, |halt| time < halt.time()) {
+                            error!("sink {} halted at {}: {}", sink_id, time, err);
+                            halted_at = Some(cap.delayed(time));
+                        }
+                    }
+                });
+                ok_input.for_each(|cap, data| {
+                    data.swap(&mut buffer);
+                    stash.push((cap.retain(), std::mem::take(&mut buffer)));
+                });
+
+                // Updates can only be passed on once no more errors can arrive
+                // at or before their times.
+                let errs_frontier = err_input.frontier();
+                for (cap, updates) in stash.iter_mut() {
+                    let (ready, pending): (Vec<_>, Vec<_>) = updates
+                        .drain(..)
+                        .partition(|(_, time, _)| !errs_frontier.less_equal(time));
+                    *updates = pending;
+                    output
+                        .session(cap)
+                        .give_iterator(ready.into_iter().filter(|(_, time, _)| {
+                            halted_at.as_ref().map_or(true, |halt| time < halt.time())
+                        }));
+                }
+                stash.retain(|(_, updates)| !updates.is_empty());
+            }
+        })
+        .as_collection()
 }
 
 pub trait SinkRender<G>
@@ -230,6 +329,8 @@ where
     match connector {
         SinkConnector::Kafka(connector) => Box::new(connector.clone()),
         SinkConnector::AvroOcf(connector) => Box::new(connector.clone()),
+        SinkConnector::File(connector) => Box::new(connector.clone()),
+        SinkConnector::Postgres(connector) => Box::new(connector.clone()),
         SinkConnector::Tail(connector) => Box::new(connector.clone()),
     }
 }
@@ -10,14 +10,16 @@
 //! Logic related to the creation of dataflow sources.
 
 use std::any::Any;
+use std::cell::RefCell;
 use std::collections::HashMap;
 use std::rc::{Rc, Weak};
 
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::{collection, AsCollection, Collection, Hashable};
 use serde::{Deserialize, Serialize};
+use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::generic::operator;
-use timely::dataflow::operators::{Concat, Map, OkErr, Probe, UnorderedInput};
+use timely::dataflow::operators::{Concat, Map, OkErr, Operator, Probe, UnorderedInput};
 use timely::dataflow::{ProbeHandle, Scope, Stream};
 use tracing::debug;
 
@@ -30,7 +32,8 @@ use mz_persist_types::Codec;
 use mz_dataflow_types::sources::{encoding::*, persistence::*, *};
 use mz_dataflow_types::*;
 use mz_expr::{GlobalId, PartitionId, SourceInstanceId};
-use mz_repr::{Diff, Row, RowPacker, Timestamp};
+use mz_ore::cast::CastFrom;
+use mz_repr::{Datum, Diff, Row, RowPacker, Timestamp};
 use timely::progress::Antichain;
 
 use crate::decode::decode_cdcv2;
@@ -45,8 +48,8 @@ use crate::server::StorageState;
 use crate::source::timestamp::{AssignedTimestamp, SourceTimestamp};
 use crate::source::{
     self, DecodeResult, FileSourceReader, KafkaSourceReader, KinesisSourceReader,
-    PersistentTimestampBindingsConfig, PostgresSourceReader, PubNubSourceReader, S3SourceReader,
-    SourceConfig,
+    LoadGeneratorSourceReader, PersistentTimestampBindingsConfig, PostgresSourceReader,
+    PubNubSourceReader, S3SourceReader, SourceConfig, SourceOutput,
 };
 
 /// A type-level enum that holds one of two types of sources depending on their message type
@@ -192,9 +195,19 @@ where
             metadata_columns,
             ts_frequency,
             timeline: _,
+            decode_errors,
+            event_time,
         } => {
             // TODO(benesch): this match arm is hard to follow. Refactor.
 
+            // Sources bound to event time read their timestamps from the data,
+            // so the event time column must not be projected away.
+            if let (Some(event_time), Some(operators)) = (&event_time, &mut linear_operators) {
+                operators.projection.push(event_time.column);
+                operators.projection.sort();
+                operators.projection.dedup();
+            }
+
             // All sources should push their various error streams into this vector,
             // whose contents will be concatenated and inserted along the collection.
             let mut error_collections = Vec::<Collection<_, _, Diff>>::new();
@@ -223,6 +236,15 @@ where
                 )
             };
 
+            // Parquet files are stored column by column, so readers can skip the
+            // columns that are projected away.
+            let mut encoding = encoding;
+            if let (SourceDataEncoding::Single(DataEncoding::Parquet(parquet)), Some(operators)) =
+                (&mut encoding, &linear_operators)
+            {
+                parquet.projection = Some(operators.projection.clone());
+            }
+
             let timestamp_histories = storage_state
                 .ts_histories
                 .get(&src_id)
@@ -261,6 +283,19 @@ where
                         .as_collection(),
                 );
 
+                (ok_stream.as_collection(), capability)
+            } else if let ExternalSourceConnector::LoadGenerator(generator_connector) = connector {
+                let source = LoadGeneratorSourceReader::new(uid, generator_connector);
+                let ((ok_stream, err_stream), capability) =
+                    source::create_source_simple(source_config, source);
+
+                error_collections.push(
+                    err_stream
+                        .map(DataflowError::SourceError)
+                        .pass_through("source-errors")
+                        .as_collection(),
+                );
+
                 (ok_stream.as_collection(), capability)
             } else if let ExternalSourceConnector::Postgres(pg_connector) = connector {
                 let source =
@@ -323,8 +358,48 @@ where
                         );
                         ((SourceType::ByteStream(ok), ts, err), cap)
                     }
+                    ExternalSourceConnector::Webhook(webhook) => {
+                        // Requests are inserted by the coordinator, just like the rows of a
+                        // table, and then decoded like the messages of any other source.
+                        let (local_input, (requests, requests_err)) = import_table(
+                            as_of_frontier,
+                            storage_state,
+                            scope,
+                            uid,
+                            webhook.persisted_name,
+                        );
+                        storage_state.local_inputs.insert(src_id, local_input);
+                        error_collections.push(requests_err);
+                        let ok = requests.inner.unary(Pipeline, "WebhookRequests", |_, _| {
+                            move |input, output| {
+                                input.for_each(|cap, data| {
+                                    for (row, time, _diff) in data.iter() {
+                                        let (position, body, headers) =
+                                            WebhookSourceConnector::unpack_request(row);
+                                        output.session(&cap.delayed(time)).give(SourceOutput::new(
+                                            None,
+                                            Some(body),
+                                            position,
+                                            None,
+                                            PartitionId::None,
+                                            Some(headers),
+                                        ));
+                                    }
+                                });
+                            }
+                        });
+                        (
+                            (
+                                SourceType::Delimited(ok),
+                                operator::empty(scope),
+                                operator::empty(scope),
+                            ),
+                            None,
+                        )
+                    }
                     ExternalSourceConnector::Postgres(_) => unreachable!(),
                     ExternalSourceConnector::PubNub(_) => unreachable!(),
+                    ExternalSourceConnector::LoadGenerator(_) => unreachable!(),
                 };
 
                 // Include any source errors.
@@ -344,26 +419,13 @@ where
                     // CDCv2 can't quite be slotted in to the below code, since it determines
                     // its own diffs/timestamps as part of decoding.
                     if let SourceEnvelope::CdcV2 = &envelope {
-                        let AvroEncoding {
-                            schema,
-                            schema_registry_config,
-                            confluent_wire_format,
-                        } = match value_encoding {
-                            DataEncoding::Avro(enc) => enc,
-                            _ => unreachable!("Attempted to create non-Avro CDCv2 source"),
-                        };
                         let ok_source = match ok_source {
                             SourceType::Delimited(s) => s,
                             _ => unreachable!("Attempted to create non-delimited CDCv2 source"),
                         };
                         // TODO(petrosagg): this should move to the envelope section below and
-                        // made to work with a stream of Rows instead of decoding Avro directly
-                        let (oks, token) = decode_cdcv2(
-                            &ok_source,
-                            &schema,
-                            schema_registry_config,
-                            confluent_wire_format,
-                        );
+                        // made to work with a stream of Rows instead of decoding directly
+                        let (oks, token) = decode_cdcv2(&ok_source, value_encoding);
                         needed_tokens.push(Rc::new(token));
                         (oks, None)
                     } else {
@@ -391,6 +453,36 @@ where
                             needed_tokens.push(Rc::new(tok));
                         }
 
+                        let results = match decode_errors {
+                            DecodeErrorPolicy::Fail => results,
+                            DecodeErrorPolicy::Skip => drop_decode_errors(results, None),
+                            DecodeErrorPolicy::Redirect(table_id) => {
+                                // The source has restored its offsets by now,
+                                // and never reads the records before them
+                                // again.
+                                let restored_offsets: Vec<_> = storage_state
+                                    .ts_histories
+                                    .get(&src_id)
+                                    .map(|history| history.partitions())
+                                    .unwrap_or_default()
+                                    .into_iter()
+                                    .filter_map(|(pid, offset)| Some((pid, offset?)))
+                                    .collect();
+                                if !restored_offsets.is_empty() {
+                                    storage_state
+                                        .decode_error_restored_offsets
+                                        .insert(src_id, restored_offsets);
+                                }
+                                drop_decode_errors(
+                                    results,
+                                    Some((
+                                        (src_id, table_id),
+                                        Rc::clone(&storage_state.decode_error_redirects),
+                                    )),
+                                )
+                            }
+                        };
+
                         // render envelopes
                         match &envelope {
                             SourceEnvelope::Debezium(dbz_envelope) => {
@@ -556,6 +648,8 @@ where
                                 (stream.as_collection(), Some(errors))
                             }
                             SourceEnvelope::CdcV2 => unreachable!(),
+                            // Only produced by sources that are not decoded.
+                            SourceEnvelope::DifferentialRow => unreachable!(),
                         }
                     }
                 };
@@ -567,6 +661,20 @@ where
                 (stream, capability)
             };
 
+            // Rebind the records to their event times, replacing the timestamps
+            // assigned at ingestion.
+            if let Some(event_time) = event_time {
+                let worker = usize::cast_from(src_id.hashed()) % scope.peers();
+                let (oks, errs) = super::event_time::render(
+                    &collection.inner,
+                    event_time,
+                    worker,
+                    storage_state.now.clone(),
+                );
+                collection = oks.as_collection();
+                error_collections.push(errs.as_collection());
+            }
+
             // Force a shuffling of data in case sources are not uniformly distributed.
             use timely::dataflow::operators::Exchange;
             collection = collection.inner.exchange(|x| x.hashed()).as_collection();
@@ -981,6 +1089,43 @@ struct KV {
     val: Option<Result<Row, DecodeError>>,
 }
 
+/// Drops the records in `results` that failed to decode, for sources whose
+/// `ON DECODE ERROR` policy is not `FAIL`. If `redirect` is set, a row
+/// describing each dropped record is queued up to be sent to the coordinator,
+/// which inserts it into the table with the given ID.
+fn drop_decode_errors<G>(
+    results: timely::dataflow::Stream<G, DecodeResult>,
+    redirect: Option<(
+        (GlobalId, GlobalId),
+        Rc<RefCell<HashMap<(GlobalId, GlobalId), Vec<Row>>>>,
+    )>,
+) -> timely::dataflow::Stream<G, DecodeResult>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    results.flat_map(move |res| {
+        let error = match (&res.key, &res.value) {
+            (Some(Err(e)), _) | (_, Some(Err(e))) => e.to_string(),
+            _ => return Some(res),
+        };
+        if let Some((ids, redirects)) = &redirect {
+            let partition = match &res.partition {
+                PartitionId::None => None,
+                pid => Some(pid.to_string()),
+            };
+            let row = Row::pack_slice(&[
+                res.raw_key.as_deref().map_or(Datum::Null, Datum::Bytes),
+                res.raw_value.as_deref().map_or(Datum::Null, Datum::Bytes),
+                partition.as_deref().map_or(Datum::Null, Datum::String),
+                Datum::Int64(res.position),
+                Datum::String(&error),
+            ]);
+            redirects.borrow_mut().entry(*ids).or_default().push(row);
+        }
+        None
+    })
+}
+
 fn append_metadata_to_value<G>(
     results: timely::dataflow::Stream<G, DecodeResult>,
 ) -> timely::dataflow::Stream<G, KV>
@@ -338,6 +338,8 @@ where
                         upstream_time_millis: _,
                         partition: _,
                         metadata,
+                        raw_key: _,
+                        raw_value: _,
                     } in vector.drain(..)
                     {
                         let mut time = cap.time().clone();
@@ -179,6 +179,8 @@ pub fn serve_boundary<
                 aws_external_id: aws_external_id.clone(),
                 timely_worker_index,
                 timely_worker_peers,
+                decode_error_redirects: Rc::new(RefCell::new(HashMap::new())),
+                decode_error_restored_offsets: HashMap::new(),
             },
             storage_boundary,
             compute_boundary,
@@ -273,6 +275,7 @@ where
             self.activate_storage().update_rt_timestamps();
             self.activate_storage()
                 .report_conditional_frontier_progress();
+            self.activate_storage().report_decode_error_redirects();
 
             // Handle any received commands.
             let mut cmds = vec![];
@@ -306,6 +309,7 @@ where
                         reported_frontiers: HashMap::new(),
                         sink_metrics: (self.metrics_bundle.1).1.clone(),
                         materialized_logger: None,
+                        aws_external_id: self.storage_state.aws_external_id.clone(),
                     };
                     self.compute_state.insert(*instance_id, compute_instance);
                     compute_instances.push(*instance_id);
@@ -23,6 +23,7 @@ use tokio::sync::mpsc;
 
 use mz_dataflow_types::client::{ComputeCommand, ComputeInstanceId, ComputeResponse, Response};
 use mz_dataflow_types::logging::LoggingConfig;
+use mz_dataflow_types::sources::AwsExternalId;
 use mz_dataflow_types::{DataflowError, PeekResponse, TailResponse};
 use mz_expr::GlobalId;
 use mz_repr::{Diff, Timestamp};
@@ -62,6 +63,9 @@ pub struct ComputeState {
     pub sink_metrics: SinkBaseMetrics,
     /// The logger, from Timely's logging framework, if logs are enabled.
     pub materialized_logger: Option<logging::materialized::Logger>,
+    /// An external ID to use for all AWS AssumeRole operations, e.g. when
+    /// writing file sinks to S3.
+    pub aws_external_id: AwsExternalId,
 }
 
 /// A wrapper around [ComputeState] with a live timely worker and response channel.
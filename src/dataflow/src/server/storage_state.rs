@@ -20,12 +20,14 @@ use mz_dataflow_types::client::{
     CreateSourceCommand, Response, StorageCommand, StorageResponse, TimestampBindingFeedback,
 };
 use mz_dataflow_types::sources::AwsExternalId;
-use mz_dataflow_types::sources::{ExternalSourceConnector, SourceConnector};
+use mz_dataflow_types::sources::{
+    ExternalSourceConnector, FileSourceConnector, MzOffset, SourceConnector,
+};
 use mz_dataflow_types::SourceInstanceDesc;
 use mz_expr::{GlobalId, PartitionId};
 use mz_ore::now::NowFn;
 use mz_persist::client::RuntimeClient;
-use mz_repr::Timestamp;
+use mz_repr::{Row, Timestamp};
 
 use crate::metrics::Metrics;
 use crate::render::sources::PersistedSourceManager;
@@ -83,6 +85,14 @@ pub struct StorageState {
     pub timely_worker_index: usize,
     /// Peers in the associated timely dataflow worker.
     pub timely_worker_peers: usize,
+    /// Rows describing the records that sources with `ON DECODE ERROR REDIRECT`
+    /// failed to decode, keyed by the source and the table they are redirected
+    /// to, that have not yet been sent to the coordinator.
+    pub decode_error_redirects: Rc<RefCell<HashMap<(GlobalId, GlobalId), Vec<Row>>>>,
+    /// The offsets from which sources with `ON DECODE ERROR REDIRECT` resumed
+    /// reading their partitions, that have not yet been sent to the
+    /// coordinator.
+    pub decode_error_restored_offsets: HashMap<GlobalId, Vec<(PartitionId, MzOffset)>>,
 }
 
 /// A wrapper around [StorageState] with a live timely worker and response channel.
@@ -112,14 +122,20 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
             );
             match connector {
                 ExternalSourceConnector::AvroOcf(_)
-                | ExternalSourceConnector::File(_)
-                | ExternalSourceConnector::Kinesis(_)
+                | ExternalSourceConnector::File(FileSourceConnector { pattern: None, .. })
                 | ExternalSourceConnector::S3(_) => {
                     rt_default.add_partition(PartitionId::None, None);
                     Some(rt_default)
                 }
-                ExternalSourceConnector::Kafka(_) => Some(rt_default),
-                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::PubNub(_) => None,
+                // Kinesis shards and files matched by a glob are discovered by the source
+                // reader, just like Kafka partitions.
+                ExternalSourceConnector::Kafka(_)
+                | ExternalSourceConnector::Kinesis(_)
+                | ExternalSourceConnector::File(_) => Some(rt_default),
+                ExternalSourceConnector::Postgres(_)
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::Webhook(_)
+                | ExternalSourceConnector::LoadGenerator(_) => None,
             }
         } else {
             debug!(
@@ -131,7 +147,7 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
 
         // Add any timestamp bindings that we were already aware of on restart.
         if let Some(data) = source_timestamp_data {
-            for (pid, timestamp, offset) in source.ts_bindings.iter().cloned() {
+            for (pid, timestamp, offset, upstream_position) in source.ts_bindings.iter().cloned() {
                 if crate::source::responsible_for(
                     &source.id,
                     self.timely_worker.index(),
@@ -146,7 +162,7 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                         offset
                     );
                     data.add_partition(pid.clone(), None);
-                    data.add_binding(pid, timestamp, offset);
+                    data.add_binding(pid, timestamp, offset, upstream_position);
                 } else {
                     trace!(
                         "NOT adding partition/binding on worker {}: ({}, {}, {})",
@@ -191,6 +207,11 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                     );
                 }
             }
+            StorageCommand::AlterSources(sources) => {
+                for (id, desc) in sources {
+                    self.storage_state.source_descriptions.insert(id, desc);
+                }
+            }
             StorageCommand::RenderSources(sources) => self.build_storage_dataflow(sources),
             StorageCommand::AllowCompaction(list) => {
                 for (id, frontier) in list {
@@ -329,7 +350,9 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
                     history
                         .get_bindings_in_range(prev_frontier.borrow(), new_frontier.borrow())
                         .into_iter()
-                        .map(|(pid, ts, offset)| (*id, pid, ts, offset)),
+                        .map(|(pid, ts, offset, upstream_position)| {
+                            (*id, pid, ts, offset, upstream_position)
+                        }),
                 );
                 prev_frontier.clone_from(&new_frontier);
             }
@@ -371,6 +394,31 @@ impl<'a, A: Allocate, B: StorageCapture> ActiveStorageState<'a, A, B> {
         }
         self.storage_state.last_bindings_feedback = Instant::now();
     }
+
+    /// Sends the records that sources failed to decode to the coordinator,
+    /// which inserts them into the tables they are redirected to.
+    ///
+    /// Also sends the offsets from which those sources resumed reading, so
+    /// that the coordinator can forget the records before them.
+    pub fn report_decode_error_redirects(&mut self) {
+        let offsets = std::mem::take(&mut self.storage_state.decode_error_restored_offsets);
+        if !offsets.is_empty() {
+            self.send_storage_response(StorageResponse::DecodeErrorRestoredOffsets(
+                offsets.into_iter().collect(),
+            ));
+        }
+        let redirects =
+            std::mem::take(&mut *self.storage_state.decode_error_redirects.borrow_mut());
+        if !redirects.is_empty() {
+            self.send_storage_response(StorageResponse::DecodeErrorRedirects(
+                redirects
+                    .into_iter()
+                    .map(|((source_id, table_id), rows)| (source_id, table_id, rows))
+                    .collect(),
+            ));
+        }
+    }
+
     /// Instruct all real-time sources managed by the worker to close their current
     /// timestamp and move to the next wall clock time.
     ///
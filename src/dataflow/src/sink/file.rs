@@ -0,0 +1,485 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that writes the updates of closed timestamps to a directory of files,
+//! on the local filesystem or in S3.
+//!
+//! Updates are buffered until a file is full or has been open for long enough,
+//! and are then written as a single file that contains only complete
+//! timestamps. After each file is written, the sink rewrites `manifest.json`,
+//! which lists the files written so far along with the frontier they cover.
+//! Readers that only read the files listed in the manifest observe a consistent
+//! snapshot of the sinked collection.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use anyhow::Context;
+use aws_sdk_s3::types::ByteStream;
+use differential_dataflow::consolidation::consolidate;
+use differential_dataflow::{Collection, Hashable};
+use serde::Serialize;
+use serde_json::json;
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::Operator;
+use timely::dataflow::Scope;
+use timely::progress::{Antichain, Timestamp as _};
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tracing::error;
+
+use mz_dataflow_types::sinks::{FileSinkConnector, FileSinkFormat, FileSinkTarget, SinkDesc};
+use mz_dataflow_types::sources::AwsExternalId;
+use mz_expr::GlobalId;
+use mz_interchange::encode::column_names_and_types;
+use mz_interchange::json::encode_datums_as_json;
+use mz_interchange::parquet::ParquetEncoder;
+use mz_ore::cast::CastFrom;
+use mz_ore::retry::Retry;
+use mz_ore::task;
+use mz_persist::indexed::columnar::parquet::write_parquet_file;
+use mz_repr::{ColumnName, ColumnType, Diff, RelationDesc, Row, Timestamp};
+
+use crate::render::sinks::SinkRender;
+
+const MANIFEST_NAME: &str = "manifest.json";
+
+impl<G> SinkRender<G> for FileSinkConnector
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        false
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        compute_state: &mut crate::server::ComputeState,
+        sink: &SinkDesc,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // Only the active worker writes files, so all other workers report
+        // an empty write frontier.
+        let scope = sinked_collection.scope();
+        let active_write_worker =
+            usize::cast_from(sink_id.hashed()) % scope.peers() == scope.index();
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::minimum())
+        } else {
+            Antichain::new()
+        }));
+
+        file(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            compute_state.aws_external_id.clone(),
+            Rc::clone(&shared_frontier),
+        );
+
+        compute_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        // no sink token
+        None
+    }
+}
+
+/// The contents of a file sink's manifest.
+///
+/// Every update at a time not beyond `upper` is in exactly one of `files`. An
+/// empty `upper` indicates that the sink has written all of its input.
+#[derive(Debug, Serialize)]
+struct Manifest {
+    upper: Vec<Timestamp>,
+    files: Vec<ManifestFile>,
+}
+
+/// A file listed in a [`Manifest`], which contains exactly the updates at
+/// times beyond `lower` and not beyond `upper`.
+#[derive(Debug, Serialize)]
+struct ManifestFile {
+    name: String,
+    lower: Vec<Timestamp>,
+    upper: Vec<Timestamp>,
+    records: usize,
+}
+
+/// The updates buffered for the next file.
+struct OpenFile {
+    updates: Vec<(Row, Timestamp, Diff)>,
+    bytes: usize,
+    opened: Instant,
+}
+
+impl OpenFile {
+    fn new() -> Self {
+        OpenFile {
+            updates: vec![],
+            bytes: 0,
+            opened: Instant::now(),
+        }
+    }
+}
+
+enum FileEncoder {
+    Parquet(ParquetEncoder),
+    Json(Vec<(ColumnName, ColumnType)>),
+}
+
+impl FileEncoder {
+    fn new(format: FileSinkFormat, desc: RelationDesc) -> Self {
+        match format {
+            FileSinkFormat::Parquet => FileEncoder::Parquet(ParquetEncoder::new(desc)),
+            FileSinkFormat::Json => FileEncoder::Json(column_names_and_types(desc)),
+        }
+    }
+
+    fn encode(&self, updates: &[(Row, Timestamp, Diff)]) -> Result<Vec<u8>, anyhow::Error> {
+        let mut buf = vec![];
+        match self {
+            FileEncoder::Parquet(encoder) => write_parquet_file(
+                &mut buf,
+                encoder.schema(),
+                encoder.encode(updates)?.into_iter(),
+                None,
+            )?,
+            FileEncoder::Json(columns) => {
+                for (row, time, diff) in updates {
+                    let mut value = encode_datums_as_json(row.iter(), columns, false);
+                    if let serde_json::Value::Object(fields) = &mut value {
+                        fields.insert("time".into(), json!(time));
+                        fields.insert("diff".into(), json!(diff));
+                    }
+                    serde_json::to_writer(&mut buf, &value)?;
+                    buf.push(b'\n');
+                }
+            }
+        }
+        Ok(buf)
+    }
+}
+
+fn file<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connector: FileSinkConnector,
+    as_of: mz_dataflow_types::sinks::SinkAsOf,
+    aws_external_id: AwsExternalId,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    // We want exactly one worker to write the files and the manifest.
+    let hashed_id = id.hashed();
+    let is_active_worker =
+        usize::cast_from(hashed_id) % collection.scope().peers() == collection.scope().index();
+    let progress = Arc::new(Mutex::new(Antichain::from_elem(Timestamp::minimum())));
+    let writer = if is_active_worker {
+        let (tx, rx) = mpsc::unbounded_channel();
+        task::spawn(
+            || format!("file_sink_writer:{}", id),
+            write_files(
+                id,
+                connector.target.clone(),
+                aws_external_id,
+                Arc::clone(&progress),
+                rx,
+            ),
+        );
+        Some(tx)
+    } else {
+        None
+    };
+
+    let beyond_as_of = move |time: &Timestamp| {
+        if as_of.strict {
+            as_of.frontier.less_than(time)
+        } else {
+            as_of.frontier.less_equal(time)
+        }
+    };
+
+    let encoder = FileEncoder::new(connector.format, connector.value_desc.clone());
+    let mut pending: BTreeMap<Timestamp, Vec<(Row, Diff)>> = BTreeMap::new();
+    let mut open_file = OpenFile::new();
+    let mut manifest = Manifest {
+        upper: vec![Timestamp::minimum()],
+        files: vec![],
+    };
+    let mut halted = false;
+    let mut vector = vec![];
+
+    collection.inner.sink(
+        Exchange::new(move |_| hashed_id),
+        &format!("file-{}", id),
+        move |input| {
+            input.for_each(|_, rows| {
+                rows.swap(&mut vector);
+                for ((_key, value), time, diff) in vector.drain(..) {
+                    if beyond_as_of(&time) && !halted {
+                        let value = value.expect("file sinks always have a value");
+                        pending.entry(time).or_default().push((value, diff));
+                    }
+                }
+            });
+
+            let writer = match &writer {
+                Some(writer) => writer,
+                None => return,
+            };
+
+            // The sink has only made progress up to the last manifest that was
+            // written. Once writing fails, the sink is halted, and its write
+            // frontier stays at that manifest.
+            *write_frontier.borrow_mut() = progress.lock().expect("lock poisoned").clone();
+            if halted {
+                return;
+            }
+            let upper = input.frontier().frontier().to_owned();
+
+            // Move the updates at newly closed times into the open file.
+            while let Some(time) = pending.keys().next().copied() {
+                if upper.less_equal(&time) {
+                    break;
+                }
+                let mut updates = pending.remove(&time).expect("known to exist");
+                consolidate(&mut updates);
+                for (row, diff) in updates {
+                    open_file.bytes += row.data().len();
+                    open_file.updates.push((row, time, diff));
+                }
+            }
+
+            if upper.elements() == manifest.upper.as_slice() {
+                return;
+            }
+            let file_full = u64::cast_from(open_file.bytes) >= connector.max_file_bytes
+                || open_file.opened.elapsed() >= connector.max_file_interval;
+            if !file_full && !upper.is_empty() {
+                return;
+            }
+
+            if !open_file.updates.is_empty() {
+                let name = format!(
+                    "part-{:08}.{}",
+                    manifest.files.len(),
+                    connector.format.extension()
+                );
+                let encoded = match encoder.encode(&open_file.updates) {
+                    Ok(encoded) => encoded,
+                    Err(e) => {
+                        // Leave the manifest as is, so that it never claims
+                        // updates that were not written.
+                        error!(
+                            "encoding file {} for sink {} failed, halting sink: {:#}",
+                            name, id, e
+                        );
+                        halted = true;
+                        pending.clear();
+                        return;
+                    }
+                };
+                if writer
+                    .send(Message::File {
+                        name: name.clone(),
+                        contents: encoded,
+                    })
+                    .is_err()
+                {
+                    // The writer has stopped after failing to write a file,
+                    // and has already reported why.
+                    halted = true;
+                    pending.clear();
+                    return;
+                }
+                manifest.files.push(ManifestFile {
+                    name,
+                    lower: manifest.upper.clone(),
+                    upper: upper.elements().to_vec(),
+                    records: open_file.updates.len(),
+                });
+            }
+            manifest.upper = upper.elements().to_vec();
+            let encoded = serde_json::to_vec(&manifest).expect("manifests serialize to JSON");
+            if writer
+                .send(Message::Manifest {
+                    contents: encoded,
+                    upper,
+                })
+                .is_err()
+            {
+                halted = true;
+                pending.clear();
+                return;
+            }
+            open_file = OpenFile::new();
+        },
+    )
+}
+
+/// A message from the sink operator to its writer task.
+enum Message {
+    /// A file of updates to write.
+    File { name: String, contents: Vec<u8> },
+    /// A manifest that lists all preceding files, which covers every update at
+    /// a time not beyond `upper`.
+    Manifest {
+        contents: Vec<u8>,
+        upper: Antichain<Timestamp>,
+    },
+}
+
+impl Message {
+    fn into_parts(self) -> (String, Vec<u8>) {
+        match self {
+            Message::File { name, contents } => (name, contents),
+            Message::Manifest { contents, .. } => (MANIFEST_NAME.into(), contents),
+        }
+    }
+}
+
+/// Writes the files received on `rx`, in order, to `target`, and reports the
+/// upper of each manifest it has written in `progress`.
+///
+/// Writing stops at the first file that cannot be written, so that no manifest
+/// that lists a missing file is ever written. This halts the sink: the sink
+/// operator stops once it can no longer send files, and `progress` no longer
+/// advances.
+async fn write_files(
+    id: GlobalId,
+    target: FileSinkTarget,
+    aws_external_id: AwsExternalId,
+    progress: Arc<Mutex<Antichain<Timestamp>>>,
+    mut rx: UnboundedReceiver<Message>,
+) {
+    let mut writer = match FileWriter::new(target, aws_external_id).await {
+        Ok(writer) => writer,
+        Err(e) => {
+            error!(
+                "creating writer for sink {} failed, halting sink: {:#}",
+                id, e
+            );
+            return;
+        }
+    };
+    while let Some(message) = rx.recv().await {
+        let upper = match &message {
+            Message::File { .. } => None,
+            Message::Manifest { upper, .. } => Some(upper.clone()),
+        };
+        let (name, contents) = message.into_parts();
+        if let Err(e) = writer.write(&name, contents).await {
+            error!(
+                "writing file {} for sink {} failed, halting sink: {:#}",
+                name, id, e
+            );
+            return;
+        }
+        if let Some(upper) = upper {
+            *progress.lock().expect("lock poisoned") = upper;
+        }
+    }
+}
+
+/// Writes the files of a sink to its target.
+enum FileWriter {
+    Local {
+        path: PathBuf,
+    },
+    S3 {
+        client: aws_sdk_s3::Client,
+        bucket: String,
+        prefix: String,
+    },
+}
+
+impl FileWriter {
+    async fn new(
+        target: FileSinkTarget,
+        aws_external_id: AwsExternalId,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(match target {
+            FileSinkTarget::Local { path } => FileWriter::Local { path },
+            FileSinkTarget::S3 {
+                bucket,
+                prefix,
+                aws,
+            } => {
+                let config = aws.load(aws_external_id).await;
+                FileWriter::S3 {
+                    client: mz_aws_util::s3::client(&config),
+                    bucket,
+                    prefix,
+                }
+            }
+        })
+    }
+
+    /// Writes `contents` to the file `name`, replacing it if it exists.
+    async fn write(&self, name: &str, contents: Vec<u8>) -> Result<(), anyhow::Error> {
+        match self {
+            FileWriter::Local { path } => write_local_file(path, name, contents).await,
+            FileWriter::S3 {
+                client,
+                bucket,
+                prefix,
+            } => {
+                let key = format!("{}{}", prefix, name);
+                Retry::default()
+                    .retry_async(|_| {
+                        client
+                            .put_object()
+                            .bucket(bucket)
+                            .key(&key)
+                            .body(ByteStream::from(contents.clone()))
+                            .send()
+                    })
+                    .await
+                    .with_context(|| format!("writing s3://{}/{}", bucket, key))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Atomically replaces the file `name` in `directory` with `contents`.
+async fn write_local_file(
+    directory: &Path,
+    name: &str,
+    contents: Vec<u8>,
+) -> Result<(), anyhow::Error> {
+    let path = directory.join(name);
+    let tmp_path = PathBuf::from(format!("{}.tmp", path.display()));
+    tokio::fs::write(&tmp_path, contents)
+        .await
+        .with_context(|| format!("writing {}", tmp_path.display()))?;
+    tokio::fs::rename(&tmp_path, &path)
+        .await
+        .with_context(|| format!("renaming {} to {}", tmp_path.display(), path.display()))?;
+    Ok(())
+}
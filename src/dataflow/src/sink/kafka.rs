@@ -10,31 +10,36 @@
 use std::any::Any;
 use std::cell::{Cell, RefCell};
 use std::cmp;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::future::Future;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
+use differential_dataflow::capture::{Message as CdcV2Message, Progress};
+use differential_dataflow::consolidation::consolidate;
 use differential_dataflow::{AsCollection, Collection, Hashable};
 use futures::{StreamExt, TryFutureExt};
 use itertools::Itertools;
-use mz_interchange::json::JsonEncoder;
+use mz_interchange::json::{self, JsonEncoder};
+use mz_interchange::protobuf::{ProtobufEncoder, ProtobufSchemaGenerator};
 use rdkafka::client::ClientContext;
 use rdkafka::config::ClientConfig;
 use rdkafka::consumer::{BaseConsumer, Consumer};
 use rdkafka::error::{KafkaError, KafkaResult, RDKafkaErrorCode};
-use rdkafka::message::{Message, ToBytes};
+use rdkafka::message::{Message, OwnedHeaders, ToBytes};
 use rdkafka::producer::Producer;
 use rdkafka::producer::{BaseRecord, DeliveryResult, ProducerContext, ThreadedProducer};
 use rdkafka::{Offset, TopicPartitionList};
+use serde::{Deserialize, Serialize};
 use timely::dataflow::channels::pact::Exchange;
 use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::operators::generic::operator::empty;
 use timely::dataflow::operators::generic::{InputHandle, OutputHandle};
-use timely::dataflow::operators::{Capability, Map};
+use timely::dataflow::operators::{Capability, Map, Operator};
 use timely::dataflow::{Scope, Stream};
 use timely::progress::frontier::AntichainRef;
 use timely::progress::{Antichain, Timestamp as _};
@@ -43,25 +48,28 @@ use tracing::{debug, error, info};
 
 use mz_avro::types::Value;
 use mz_dataflow_types::sinks::{
-    KafkaSinkConnector, KafkaSinkConsistencyConnector, PublishedSchemaInfo, SinkAsOf, SinkDesc,
+    KafkaSinkConnector, KafkaSinkConsistencyConnector, KafkaSinkEncoding, KafkaSinkProgressRecord,
+    PublishedSchemaInfo, SinkAsOf, SinkDesc, SinkEnvelope,
 };
-use mz_expr::GlobalId;
+use mz_dataflow_types::DataflowError;
+use mz_expr::{EvalError, GlobalId, MirScalarExpr};
 use mz_interchange::avro::{
     self, get_debezium_transaction_schema, AvroEncoder, AvroSchemaGenerator,
 };
-use mz_interchange::encode::Encode;
+use mz_interchange::encode::{Encode, EncodeCdcV2};
 use mz_kafka_util::client::MzClientContext;
+use mz_kafka_util::partition::murmur2_partition;
 use mz_ore::cast::CastFrom;
 use mz_ore::collections::CollectionExt;
 use mz_ore::metrics::{CounterVecExt, DeleteOnDropCounter, DeleteOnDropGauge, GaugeVecExt};
 use mz_ore::retry::Retry;
 use mz_ore::task;
-use mz_repr::{Datum, Diff, RelationDesc, Row, RowPacker, Timestamp};
+use mz_repr::{Datum, Diff, RelationDesc, Row, RowArena, RowPacker, Timestamp};
 use mz_timely_util::async_op;
 use mz_timely_util::operators_async_ext::OperatorBuilderExt;
 
 use super::KafkaBaseMetrics;
-use crate::render::sinks::SinkRender;
+use crate::render::sinks::{halt_on_errors, SinkRender};
 use prometheus::core::{AtomicI64, AtomicU64};
 
 impl<G> SinkRender<G> for KafkaSinkConnector
@@ -137,6 +145,7 @@ where
                 .clone()
                 .map(|(desc, _indices)| desc),
             self.value_desc.clone(),
+            sink.envelope,
             sink.as_of.clone(),
             Rc::clone(&shared_frontier),
             &compute_state.sink_metrics.kafka,
@@ -306,7 +315,7 @@ impl KafkaTxProducer {
 #[derive(Debug, Clone)]
 struct KafkaConsistencyInitState {
     topic: String,
-    schema_id: i32,
+    schema_id: Option<i32>,
     consistency_client_config: rdkafka::ClientConfig,
 }
 
@@ -323,10 +332,20 @@ impl KafkaConsistencyInitState {
 #[derive(Debug, Clone)]
 struct KafkaConsistencyRunningState {
     topic: String,
-    schema_id: i32,
+    schema_id: Option<i32>,
     gate_ts: Rc<Cell<Option<Timestamp>>>,
 }
 
+/// The progress topic of an exactly-once sink. See
+/// [`KafkaSinkConnector::progress`].
+#[derive(Debug, Clone)]
+struct KafkaProgressState {
+    topic: String,
+    /// The key of this sink's records in the progress topic.
+    key: String,
+    client_config: rdkafka::ClientConfig,
+}
+
 #[derive(Debug, Clone)]
 enum KafkaSinkStateEnum {
     // Initialize ourselves as a transactional producer with Kafka
@@ -352,6 +371,11 @@ impl KafkaSinkStateEnum {
     }
 }
 
+/// How often a sink with `PARTITION BY` refreshes the partition count of its
+/// topic. Matches the default metadata refresh interval of Kafka's Java
+/// client, so that both pick up new partitions equally quickly.
+const PARTITION_COUNT_REFRESH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
 struct KafkaSinkState {
     name: String,
     topic: String,
@@ -364,18 +388,27 @@ struct KafkaSinkState {
     pending_rows: HashMap<Timestamp, Vec<EncodedRow>>,
     ready_rows: VecDeque<(Timestamp, Vec<EncodedRow>)>,
     sink_state: KafkaSinkStateEnum,
+    progress: Option<KafkaProgressState>,
+
+    /// Whether the sink chooses the partitions of its messages with
+    /// `PARTITION BY`.
+    partition_by: bool,
+    /// The number of partitions in the sink's topic, and when it was last
+    /// determined. Only needed if `partition_by` is set.
+    partition_count: Option<(i32, Instant)>,
 
-    /// Timestamp of the latest `END` record that was written out to Kafka.
+    /// Timestamp of the latest `END` or progress record that was written out
+    /// to Kafka.
     latest_progress_ts: Timestamp,
 
     /// Write frontier of this sink.
     ///
     /// The write frontier potentially blocks compaction of timestamp bindings
-    /// in upstream sources. The latest written `END` record is used when
-    /// restarting the sink to gate updates with a lower timestamp. We advance
-    /// the write frontier in lockstep with writing out END records. This
-    /// ensures that we don't write updates more than once, ensuring
-    /// exactly-once guaruantees.
+    /// in upstream sources. The latest written progress record, or `END`
+    /// record for sinks without one, is used when restarting the sink to gate
+    /// updates with a lower timestamp. We advance the write frontier in
+    /// lockstep with writing out these records. This ensures that we don't
+    /// write updates more than once, ensuring exactly-once guaruantees.
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
 }
 
@@ -412,6 +445,12 @@ impl KafkaSinkState {
             timeout: Duration::from_secs(5),
         };
 
+        let progress = connector.progress.map(|progress| KafkaProgressState {
+            topic: progress.topic,
+            key: progress.key,
+            client_config: consistency_client_config.clone(),
+        });
+
         let sink_state = KafkaSinkStateEnum::Init(connector.consistency.map(
             |KafkaSinkConsistencyConnector { topic, schema_id }| KafkaConsistencyInitState {
                 topic,
@@ -422,6 +461,8 @@ impl KafkaSinkState {
 
         KafkaSinkState {
             name: sink_name,
+            partition_by: connector.partition_by.is_some(),
+            partition_count: None,
             topic: connector.topic,
             topic_prefix: connector.topic_prefix,
             shutdown_flag,
@@ -432,6 +473,7 @@ impl KafkaSinkState {
             pending_rows: HashMap::new(),
             ready_rows: VecDeque::new(),
             sink_state,
+            progress,
             latest_progress_ts: Timestamp::minimum(),
             write_frontier,
         }
@@ -636,6 +678,33 @@ impl KafkaSinkState {
             .await
     }
 
+    /// Determines the number of partitions in the sink's topic, which
+    /// `PARTITION BY` needs to choose the partition of each message.
+    async fn determine_partition_count(&self) -> Result<i32, anyhow::Error> {
+        // Only actually used for retriable errors.
+        let partitions = Retry::default()
+            .max_tries(usize::MAX)
+            .clamp_backoff(Duration::from_secs(60 * 10))
+            .retry_async(|_| async {
+                let producer = Arc::clone(&self.producer.inner);
+                let topic = self.topic.clone();
+                task::spawn_blocking(
+                    || format!("get_partitions:{}", self.name),
+                    move || {
+                        mz_kafka_util::client::get_partitions(
+                            producer.client(),
+                            &topic,
+                            Duration::from_secs(10),
+                        )
+                    },
+                )
+                .await
+                .unwrap_or_else(|e| bail!(e))
+            })
+            .await?;
+        Ok(i32::try_from(partitions.len())?)
+    }
+
     async fn determine_latest_consistency_record(
         &self,
     ) -> Result<Option<Timestamp>, anyhow::Error> {
@@ -663,6 +732,7 @@ impl KafkaSinkState {
         // always be called on background thread
         fn get_latest_ts(
             consistency_topic: &str,
+            schema_id: Option<i32>,
             config: &ClientConfig,
             timeout: Duration,
         ) -> Result<Option<Timestamp>, anyhow::Error> {
@@ -731,7 +801,8 @@ impl KafkaSinkState {
                 debug_assert!(offset >= latest_offset.unwrap_or(0));
                 latest_offset = Some(offset);
 
-                if let Some(ts) = maybe_decode_consistency_end_record(&message, consistency_topic)?
+                if let Some(ts) =
+                    maybe_decode_consistency_end_record(&message, consistency_topic, schema_id)?
                 {
                     if ts >= latest_ts.unwrap_or(0) {
                         latest_ts = Some(ts);
@@ -755,7 +826,24 @@ impl KafkaSinkState {
         fn maybe_decode_consistency_end_record(
             bytes: &[u8],
             consistency_topic: &str,
+            schema_id: Option<i32>,
         ) -> Result<Option<Timestamp>, anyhow::Error> {
+            if schema_id.is_none() {
+                let id = json::decode_transaction_end_record(bytes)
+                    .context("Failed to decode consistency topic message")?;
+                return match id {
+                    Some(id) => match id.parse::<u64>() {
+                        Ok(ts) => Ok(Some(ts)),
+                        Err(_) => bail!(
+                            "Malformed consistency record, failed to parse timestamp {} in topic {}",
+                            id,
+                            consistency_topic
+                        ),
+                    },
+                    None => Ok(None),
+                };
+            }
+
             // The first 5 bytes are reserved for the schema id/schema registry information
             let mut bytes = bytes.get(5..).ok_or_else(|| {
                 anyhow!("Malformed consistency topic message.  Shorter than 5 bytes.")
@@ -789,8 +877,8 @@ impl KafkaSinkState {
 
         if let KafkaSinkStateEnum::Init(Some(KafkaConsistencyInitState {
             ref topic,
+            schema_id,
             ref consistency_client_config,
-            ..
         })) = self.sink_state
         {
             // Only actually used for retriable errors.
@@ -805,6 +893,7 @@ impl KafkaSinkState {
                         move || {
                             get_latest_ts(
                                 &topic,
+                                schema_id,
                                 &consistency_client_config,
                                 Duration::from_secs(10),
                             )
@@ -818,6 +907,112 @@ impl KafkaSinkState {
         return Ok(None);
     }
 
+    /// Determines the timestamp of the latest record for this sink in its
+    /// progress topic, if it has one.
+    async fn determine_latest_progress_record(&self) -> Result<Option<Timestamp>, anyhow::Error> {
+        // Retrieves the timestamp of the latest committed record with the given key from the
+        // progress topic. Blocking so should always be called on background thread.
+        fn get_latest_ts(
+            progress_topic: &str,
+            key: &str,
+            config: &ClientConfig,
+            timeout: Duration,
+        ) -> Result<Option<Timestamp>, anyhow::Error> {
+            let mut consumer = config
+                .create::<BaseConsumer>()
+                .context("creating consumer client failed")?;
+
+            let partitions =
+                mz_kafka_util::client::get_partitions(consumer.client(), progress_topic, timeout)
+                    .with_context(|| {
+                    format!(
+                        "Unable to fetch metadata about progress topic {}",
+                        progress_topic
+                    )
+                })?;
+            if partitions.len() != 1 {
+                bail!(
+                    "Progress topic {} should contain a single partition, but instead contains {} partitions",
+                    progress_topic, partitions.len(),
+                );
+            }
+            let partition = partitions.into_element();
+
+            // As with consistency topics, control batches and aborted transactions mean that we
+            // have to scan the topic from the beginning. Compaction keeps the topic small.
+            let mut tps = TopicPartitionList::new();
+            tps.add_partition(progress_topic, partition);
+            tps.set_partition_offset(progress_topic, partition, Offset::Beginning)?;
+            consumer.assign(&tps).with_context(|| {
+                format!(
+                    "Error seeking in progress topic {}:{}",
+                    progress_topic, partition
+                )
+            })?;
+
+            let (_lo, hi) = consumer
+                .fetch_watermarks(progress_topic, partition, timeout)
+                .map_err(|e| {
+                    anyhow!(
+                        "Failed to fetch metadata while reading from progress topic: {}",
+                        e
+                    )
+                })?;
+            if hi == 0 {
+                return Ok(None);
+            }
+
+            let mut latest_ts = None;
+            while let Some(result) = consumer.poll(timeout) {
+                let message = match result {
+                    Ok(message) => message,
+                    Err(KafkaError::PartitionEOF(_)) => break,
+                    Err(err) => bail!("Failed to process message {}", err),
+                };
+                if message.key() != Some(key.as_bytes()) {
+                    continue;
+                }
+                // A record written by `ALTER SINK ... RESUME FROM` may move the
+                // timestamp backwards, so the latest record wins.
+                latest_ts = match message.payload() {
+                    Some(payload) => {
+                        let record: KafkaSinkProgressRecord = serde_json::from_slice(payload)
+                            .with_context(|| {
+                                format!(
+                                    "Malformed record for sink {} in progress topic {}",
+                                    key, progress_topic
+                                )
+                            })?;
+                        Some(record.timestamp)
+                    }
+                    None => None,
+                };
+            }
+            Ok(latest_ts)
+        }
+
+        let progress = match &self.progress {
+            Some(progress) => progress,
+            None => return Ok(None),
+        };
+        // Only actually used for retriable errors.
+        Retry::default()
+            .max_tries(usize::MAX)
+            .clamp_backoff(Duration::from_secs(60 * 10))
+            .retry_async(|_| async {
+                let topic = progress.topic.clone();
+                let key = progress.key.clone();
+                let client_config = progress.client_config.clone();
+                task::spawn_blocking(
+                    || format!("get_latest_progress_ts:{}", self.name),
+                    move || get_latest_ts(&topic, &key, &client_config, Duration::from_secs(10)),
+                )
+                .await
+                .unwrap_or_else(|e| bail!(e))
+            })
+            .await
+    }
+
     async fn send_consistency_record(
         &self,
         transaction_id: &str,
@@ -825,13 +1020,21 @@ impl KafkaSinkState {
         message_count: Option<i64>,
         consistency: &KafkaConsistencyRunningState,
     ) -> KafkaResult<()> {
-        let encoded = avro::encode_debezium_transaction_unchecked(
-            consistency.schema_id,
-            &self.topic_prefix,
-            transaction_id,
-            status,
-            message_count,
-        );
+        let encoded = match consistency.schema_id {
+            Some(schema_id) => avro::encode_debezium_transaction_unchecked(
+                schema_id,
+                &self.topic_prefix,
+                transaction_id,
+                status,
+                message_count,
+            ),
+            None => json::encode_transaction_record(
+                &self.topic_prefix,
+                transaction_id,
+                status,
+                message_count,
+            ),
+        };
 
         let record = BaseRecord::to(&consistency.topic)
             .payload(&encoded)
@@ -840,6 +1043,23 @@ impl KafkaSinkState {
         self.send(record).await
     }
 
+    /// Records in the progress topic that the sink has written all of its
+    /// updates at or before `ts`.
+    async fn send_progress_record(
+        &self,
+        ts: Timestamp,
+        progress: &KafkaProgressState,
+    ) -> KafkaResult<()> {
+        let encoded = serde_json::to_vec(&KafkaSinkProgressRecord { timestamp: ts })
+            .expect("progress records serialize to JSON");
+
+        let record = BaseRecord::to(&progress.topic)
+            .payload(&encoded)
+            .key(&progress.key);
+
+        self.send(record).await
+    }
+
     /// Asserts that the write frontier has not yet advanced beyond `t`.
     fn assert_progress(&self, ts: &Timestamp) {
         assert!(self.write_frontier.borrow().less_equal(ts));
@@ -862,13 +1082,15 @@ impl KafkaSinkState {
     /// Updates the latest progress update timestamp based on the given
     /// input frontier and pending rows.
     ///
-    /// This will emit an `END` record to the consistency topic if the frontier
-    /// advanced and advance the maintained write frontier, which will in turn
-    /// unblock compaction of timestamp bindings in sources.
+    /// This will emit an `END` record to the consistency topic and a record
+    /// to the progress topic if the frontier advanced and advance the
+    /// maintained write frontier, which will in turn unblock compaction of
+    /// timestamp bindings in sources.
     ///
     /// *NOTE*: `END` records will only be emitted when
-    /// `KafkaSinkConnector.consistency` points to a consistency topic. The
-    /// write frontier will be advanced regardless.
+    /// `KafkaSinkConnector.consistency` points to a consistency topic, and
+    /// progress records only when `KafkaSinkConnector.progress` is set.
+    /// The write frontier will be advanced regardless.
     async fn maybe_emit_progress<'a>(
         &mut self,
         input_frontier: AntichainRef<'a, Timestamp>,
@@ -895,20 +1117,28 @@ impl KafkaSinkState {
             let min_frontier = min_frontier.saturating_sub(1);
 
             if min_frontier > self.latest_progress_ts {
-                // record the write frontier in the consistency topic.
-                if let Some(consistency_state) = self.sink_state.unwrap_running() {
+                // record the write frontier in the consistency and progress topics.
+                let consistency_state = self.sink_state.unwrap_running();
+                if consistency_state.is_some() || self.progress.is_some() {
                     if self.transactional {
                         self.retry_on_txn_error(|p| p.begin_transaction()).await?;
                     }
 
-                    self.send_consistency_record(
-                        &min_frontier.to_string(),
-                        "END",
-                        None,
-                        consistency_state,
-                    )
-                    .await
-                    .map_err(|_| anyhow::anyhow!("Error sending write frontier update."))?;
+                    if let Some(consistency_state) = consistency_state {
+                        self.send_consistency_record(
+                            &min_frontier.to_string(),
+                            "END",
+                            None,
+                            consistency_state,
+                        )
+                        .await
+                        .map_err(|_| anyhow::anyhow!("Error sending write frontier update."))?;
+                    }
+                    if let Some(progress) = &self.progress {
+                        self.send_progress_record(min_frontier, progress)
+                            .await
+                            .map_err(|_| anyhow::anyhow!("Error sending write frontier update."))?;
+                    }
 
                     if self.transactional {
                         self.retry_on_txn_error(|p| p.commit_transaction()).await?;
@@ -938,9 +1168,109 @@ impl KafkaSinkState {
 struct EncodedRow {
     key: Option<Vec<u8>>,
     value: Option<Vec<u8>>,
+    metadata: MessageMetadata,
     count: usize,
 }
 
+/// The partition key and headers of a message, as computed from the sink's
+/// `PARTITION BY` and `HEADERS` clauses.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct MessageMetadata {
+    /// The bytes whose hash chooses the partition of the message, if any.
+    partition_key: Option<Vec<u8>>,
+    /// The headers of the message. Headers whose value is null are omitted.
+    headers: Vec<(String, Vec<u8>)>,
+}
+
+/// Evaluates a sink's `PARTITION BY` and `HEADERS` expressions against the
+/// rows of the sink's input.
+#[derive(Clone)]
+struct MessageMetadataEvaluator {
+    partition_by: Option<MirScalarExpr>,
+    headers: Vec<(String, MirScalarExpr)>,
+    envelope: Option<SinkEnvelope>,
+    key_indices: Option<Vec<usize>>,
+    arity: usize,
+}
+
+impl MessageMetadataEvaluator {
+    fn new(connector: &KafkaSinkConnector, envelope: Option<SinkEnvelope>) -> Self {
+        MessageMetadataEvaluator {
+            partition_by: connector.partition_by.clone(),
+            headers: connector.headers.clone(),
+            envelope,
+            key_indices: connector
+                .key_desc_and_indices
+                .as_ref()
+                .map(|(_desc, indices)| indices.clone()),
+            arity: connector.value_desc.arity(),
+        }
+    }
+
+    /// Computes the metadata of the message with the given key and value.
+    ///
+    /// The expressions are evaluated against the input row that the message
+    /// describes: for the Debezium envelope, the `after` state of the row, or
+    /// the `before` state if the row was deleted. Upsert deletions only carry
+    /// the key of the row, so columns outside the key evaluate as null.
+    ///
+    /// Returns an error if evaluating any of the expressions fails.
+    fn evaluate(
+        &self,
+        key: Option<&Row>,
+        value: Option<&Row>,
+    ) -> Result<MessageMetadata, EvalError> {
+        if self.partition_by.is_none() && self.headers.is_empty() {
+            return Ok(MessageMetadata::default());
+        }
+
+        let datums: Vec<Datum> = match (self.envelope, value) {
+            (Some(SinkEnvelope::Debezium), Some(value)) => {
+                let mut datums = value.iter();
+                match (datums.next(), datums.next()) {
+                    (_, Some(Datum::List(after))) => after.iter().collect(),
+                    (Some(Datum::List(before)), _) => before.iter().collect(),
+                    _ => unreachable!("Debezium value without before or after: {:?}", value),
+                }
+            }
+            (_, Some(value)) => value.iter().collect(),
+            (_, None) => {
+                let mut datums = vec![Datum::Null; self.arity];
+                if let (Some(key), Some(key_indices)) = (key, &self.key_indices) {
+                    for (&idx, datum) in key_indices.iter().zip(key.iter()) {
+                        datums[idx] = datum;
+                    }
+                }
+                datums
+            }
+        };
+
+        let arena = RowArena::new();
+        let eval = |expr: &MirScalarExpr| -> Result<Option<Vec<u8>>, EvalError> {
+            match expr.eval(&datums, &arena)? {
+                Datum::Null => Ok(None),
+                Datum::String(s) => Ok(Some(s.as_bytes().to_vec())),
+                Datum::Bytes(b) => Ok(Some(b.to_vec())),
+                datum => unreachable!("Kafka sink expression evaluated to {:?}", datum),
+            }
+        };
+        let partition_key = match &self.partition_by {
+            Some(expr) => eval(expr)?,
+            None => None,
+        };
+        let mut headers = vec![];
+        for (name, expr) in &self.headers {
+            if let Some(value) = eval(expr)? {
+                headers.push((name.clone(), value));
+            }
+        }
+        Ok(MessageMetadata {
+            partition_key,
+            headers,
+        })
+    }
+}
+
 // TODO@jldlaughlin: What guarantees does this sink support? #1728
 fn kafka<G>(
     collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
@@ -948,6 +1278,7 @@ fn kafka<G>(
     connector: KafkaSinkConnector,
     key_desc: Option<RelationDesc>,
     value_desc: RelationDesc,
+    envelope: Option<SinkEnvelope>,
     as_of: SinkAsOf,
     write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
     metrics: &KafkaBaseMetrics,
@@ -961,11 +1292,39 @@ where
 
     let shared_gate_ts = Rc::new(Cell::new(None));
 
-    let encoded_stream = match connector.published_schema_info {
-        Some(PublishedSchemaInfo {
-            key_schema_id,
-            value_schema_id,
-        }) => {
+    let metadata_evaluator = MessageMetadataEvaluator::new(&connector, envelope);
+
+    let (encoded_stream, errs) = match (
+        envelope,
+        connector.encoding,
+        connector.published_schema_info.clone(),
+    ) {
+        (
+            Some(SinkEnvelope::CdcV2),
+            KafkaSinkEncoding::Avro,
+            Some(PublishedSchemaInfo {
+                value_schema_id, ..
+            }),
+        ) => {
+            let encoder = avro::cdc_v2::Encoder::new(value_desc, value_schema_id);
+            let encoded =
+                encode_cdc_v2_stream(stream, id, as_of.clone(), Box::new(encoder), name.clone());
+            (encoded, empty(&stream.scope()))
+        }
+        (Some(SinkEnvelope::CdcV2), KafkaSinkEncoding::Json, _) => {
+            let encoder = mz_interchange::json::cdc_v2::Encoder::new(value_desc);
+            let encoded =
+                encode_cdc_v2_stream(stream, id, as_of.clone(), Box::new(encoder), name.clone());
+            (encoded, empty(&stream.scope()))
+        }
+        (
+            _,
+            KafkaSinkEncoding::Avro,
+            Some(PublishedSchemaInfo {
+                key_schema_id,
+                value_schema_id,
+            }),
+        ) => {
             let schema_generator = AvroSchemaGenerator::new(
                 None,
                 None,
@@ -979,25 +1338,54 @@ where
                 as_of.clone(),
                 Rc::clone(&shared_gate_ts),
                 encoder,
+                metadata_evaluator,
+                connector.fuel,
+                name.clone(),
+            )
+        }
+        (Some(SinkEnvelope::CdcV2), KafkaSinkEncoding::Protobuf, _)
+        | (_, KafkaSinkEncoding::Avro, None) => {
+            unreachable!("invalid Kafka sink: {:?}", connector)
+        }
+        (_, KafkaSinkEncoding::Protobuf, published_schema_info) => {
+            let schema_generator =
+                ProtobufSchemaGenerator::new(key_desc, value_desc, connector.consistency.is_some())
+                    .expect("Protobuf schema validated during planning");
+            let encoder = match published_schema_info {
+                Some(PublishedSchemaInfo {
+                    key_schema_id,
+                    value_schema_id,
+                }) => ProtobufEncoder::new(schema_generator, key_schema_id, Some(value_schema_id)),
+                None => ProtobufEncoder::new(schema_generator, None, None),
+            };
+            encode_stream(
+                stream,
+                as_of.clone(),
+                Rc::clone(&shared_gate_ts),
+                encoder,
+                metadata_evaluator,
                 connector.fuel,
                 name.clone(),
             )
         }
-        None => {
+        (_, KafkaSinkEncoding::Json, _) => {
             let encoder = JsonEncoder::new(key_desc, value_desc, connector.consistency.is_some());
             encode_stream(
                 stream,
                 as_of.clone(),
                 Rc::clone(&shared_gate_ts),
                 encoder,
+                metadata_evaluator,
                 connector.fuel,
                 name.clone(),
             )
         }
     };
 
+    let encoded_stream = halt_on_errors(id, encoded_stream.as_collection(), errs.as_collection());
+
     produce_to_kafka(
-        encoded_stream,
+        encoded_stream.inner,
         id,
         name,
         connector,
@@ -1020,7 +1408,14 @@ where
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` in
 /// [`KafkaSinkConnector`] will be discarded without producing them.
 pub fn produce_to_kafka<G>(
-    stream: Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>,
+    stream: Stream<
+        G,
+        (
+            (Option<Vec<u8>>, Option<Vec<u8>>, MessageMetadata),
+            Timestamp,
+            Diff,
+        ),
+    >,
     id: GlobalId,
     name: String,
     connector: KafkaSinkConnector,
@@ -1098,12 +1493,40 @@ where
             }
 
             if is_active_worker {
+                // Partitions can be added to the topic while the sink runs, so
+                // the partition count is refreshed periodically.
+                let partition_count_stale = match s.partition_count {
+                    None => true,
+                    Some((_, determined_at)) => {
+                        determined_at.elapsed() >= PARTITION_COUNT_REFRESH_INTERVAL
+                    }
+                };
+                if s.partition_by && partition_count_stale {
+                    match s.determine_partition_count().await {
+                        Ok(partition_count) => {
+                            s.partition_count = Some((partition_count, Instant::now()))
+                        }
+                        Err(e) => {
+                            s.shutdown_flag.store(true, Ordering::SeqCst);
+                            info!("shutting down kafka sink while initializing: {}", e);
+                            return true;
+                        }
+                    }
+                }
                 if let KafkaSinkStateEnum::Init(ref init) = s.sink_state {
                     if s.transactional {
                         bail_err!(s.retry_on_txn_error(|p| p.init_transactions()).await);
                     }
 
-                    let latest_ts = match s.determine_latest_consistency_record().await {
+                    // Sinks resume from their latest progress record. Sinks
+                    // that have not written one yet, such as sinks created
+                    // before progress topics existed, fall back to the latest
+                    // `END` record in their consistency topic.
+                    let latest_ts = match s.determine_latest_progress_record().await {
+                        Ok(None) => s.determine_latest_consistency_record().await,
+                        result => result,
+                    };
+                    let latest_ts = match latest_ts {
                         Ok(ts) => ts,
                         Err(e) => {
                             s.shutdown_flag.store(true, Ordering::SeqCst);
@@ -1129,7 +1552,7 @@ where
             input.for_each(|_, rows| {
                 assert!(is_active_worker);
                 rows.swap(&mut vector);
-                for ((key, value), time, diff) in vector.drain(..) {
+                for ((key, value, metadata), time, diff) in vector.drain(..) {
                     let should_emit = if as_of.strict {
                         as_of.frontier.less_than(&time)
                     } else {
@@ -1154,6 +1577,7 @@ where
                     rows.push(EncodedRow {
                         key,
                         value,
+                        metadata,
                         count: diff,
                     });
                     s.metrics.rows_queued.inc();
@@ -1203,6 +1627,21 @@ where
                         Some(r) => record.key(r),
                         None => record,
                     };
+                    let record = match (&encoded_row.metadata.partition_key, s.partition_count) {
+                        (Some(partition_key), Some((partition_count, _))) => {
+                            record.partition(murmur2_partition(partition_key, partition_count))
+                        }
+                        _ => record,
+                    };
+                    let record = if encoded_row.metadata.headers.is_empty() {
+                        record
+                    } else {
+                        let headers = encoded_row.metadata.headers.iter().fold(
+                            OwnedHeaders::new_with_capacity(encoded_row.metadata.headers.len()),
+                            |headers, (name, value)| headers.add(name, value),
+                        );
+                        record.headers(headers)
+                    };
 
                     // Only fatal errors are returned from send
                     bail_err!(s.send(record).await);
@@ -1228,6 +1667,9 @@ where
                         .await
                     );
                 }
+                if let Some(progress) = &s.progress {
+                    bail_err!(s.send_progress_record(*ts, progress).await);
+                }
                 if s.transactional {
                     bail_err!(s.retry_on_txn_error(|p| p.commit_transaction()).await);
                 };
@@ -1296,7 +1738,9 @@ where
 /// This operator will only encode `fuel` number of updates per invocation. If necessary, it will
 /// stash updates and use an [`timely::scheduling::Activator`] to re-schedule future invocations.
 ///
-/// Input [`Row`] updates must me compatible with the given implementor of [`Encode`].
+/// Input [`Row`] updates must me compatible with the given implementor of [`Encode`]. Updates that
+/// the encoder cannot represent, or whose metadata fails to evaluate, are reported on the second
+/// output stream instead.
 ///
 /// Updates that are not beyond the given [`SinkAsOf`] and/or the `gate_ts` will be discarded
 /// without encoding them.
@@ -1313,9 +1757,20 @@ fn encode_stream<G>(
     as_of: SinkAsOf,
     shared_gate_ts: Rc<Cell<Option<Timestamp>>>,
     encoder: impl Encode + 'static,
+    metadata_evaluator: MessageMetadataEvaluator,
     fuel: usize,
     name_prefix: String,
-) -> Stream<G, ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff)>
+) -> (
+    Stream<
+        G,
+        (
+            (Option<Vec<u8>>, Option<Vec<u8>>, MessageMetadata),
+            Timestamp,
+            Diff,
+        ),
+    >,
+    Stream<G, (DataflowError, Timestamp, Diff)>,
+)
 where
     G: Scope<Timestamp = Timestamp>,
 {
@@ -1324,90 +1779,258 @@ where
     let mut builder = OperatorBuilder::new(name, input_stream.scope());
     let mut input = builder.new_input(&input_stream, Pipeline);
     let (mut output, output_stream) = builder.new_output();
+    let (mut err_output, err_stream) = builder.new_output();
+    let err_output_port = err_stream.name().port;
     builder.set_notify(false);
 
     let activator = input_stream
         .scope()
         .activator_for(&builder.operator_info().address[..]);
 
-    let mut stash: HashMap<Capability<Timestamp>, Vec<_>> = HashMap::new();
+    // Each stashed batch also holds a capability for the error output.
+    let mut stash: HashMap<Capability<Timestamp>, (Capability<Timestamp>, Vec<_>)> = HashMap::new();
     let mut vector = Vec::new();
-    let mut encode_logic = move |input: &mut InputHandle<
-        Timestamp,
-        ((Option<Row>, Option<Row>), Timestamp, Diff),
-        _,
-    >,
-                                 output: &mut OutputHandle<
-        _,
-        ((Option<Vec<u8>>, Option<Vec<u8>>), Timestamp, Diff),
-        _,
-    >| {
-        let mut fuel_remaining = fuel;
-        // stash away all the input we get, we want to be a nice citizen
-        input.for_each(|cap, data| {
-            data.swap(&mut vector);
-            let stashed = stash.entry(cap.retain()).or_default();
-            for update in vector.drain(..) {
-                let time = update.1;
-
-                let should_emit = if as_of.strict {
-                    as_of.frontier.less_than(&time)
-                } else {
-                    as_of.frontier.less_equal(&time)
-                };
-                let ts_gated = Some(time) <= shared_gate_ts.get();
+    let mut encode_logic =
+        move |input: &mut InputHandle<
+            Timestamp,
+            ((Option<Row>, Option<Row>), Timestamp, Diff),
+            _,
+        >,
+              output: &mut OutputHandle<
+            _,
+            (
+                (Option<Vec<u8>>, Option<Vec<u8>>, MessageMetadata),
+                Timestamp,
+                Diff,
+            ),
+            _,
+        >,
+              err_output: &mut OutputHandle<_, (DataflowError, Timestamp, Diff), _>| {
+            let mut fuel_remaining = fuel;
+            // stash away all the input we get, we want to be a nice citizen
+            input.for_each(|cap, data| {
+                data.swap(&mut vector);
+                let (_err_cap, stashed) = stash
+                    .entry(cap.delayed(cap.time()))
+                    .or_insert_with(move || (cap.retain_for_output(err_output_port), Vec::new()));
+                for update in vector.drain(..) {
+                    let time = update.1;
 
-                if !should_emit || ts_gated {
-                    // Skip stale data for already published timestamps
-                    continue;
+                    let should_emit = if as_of.strict {
+                        as_of.frontier.less_than(&time)
+                    } else {
+                        as_of.frontier.less_equal(&time)
+                    };
+                    let ts_gated = Some(time) <= shared_gate_ts.get();
+
+                    if !should_emit || ts_gated {
+                        // Skip stale data for already published timestamps
+                        continue;
+                    }
+                    stashed.push(update);
                 }
-                stashed.push(update);
-            }
-        });
+            });
 
-        // work off some of our data and then yield, can't be hogging
-        // the worker for minutes at a time
-
-        while fuel_remaining > 0 && !stash.is_empty() {
-            let lowest_ts = stash
-                .keys()
-                .min_by(|x, y| x.time().cmp(y.time()))
-                .expect("known to exist")
-                .clone();
-            let records = stash.get_mut(&lowest_ts).expect("known to exist");
-
-            let mut session = output.session(&lowest_ts);
-            let num_records_to_drain = cmp::min(records.len(), fuel_remaining);
-            records
-                .drain(..num_records_to_drain)
-                .for_each(|((key, value), time, diff)| {
-                    let key = key.map(|key| encoder.encode_key_unchecked(key));
-                    let value = value.map(|value| encoder.encode_value_unchecked(value));
-                    session.give(((key, value), time, diff));
-                });
+            // work off some of our data and then yield, can't be hogging
+            // the worker for minutes at a time
+
+            while fuel_remaining > 0 && !stash.is_empty() {
+                let lowest_ts = stash
+                    .keys()
+                    .min_by(|x, y| x.time().cmp(y.time()))
+                    .expect("known to exist")
+                    .clone();
+                let (err_cap, records) = stash.get_mut(&lowest_ts).expect("known to exist");
+
+                let num_records_to_drain = cmp::min(records.len(), fuel_remaining);
+                let mut session = output.session(&lowest_ts);
+                let mut err_session = err_output.session(err_cap);
+                records
+                    .drain(..num_records_to_drain)
+                    .for_each(|((key, value), time, diff)| {
+                        let encoded = (|| {
+                            let metadata = metadata_evaluator
+                                .evaluate(key.as_ref(), value.as_ref())
+                                .map_err(DataflowError::EvalError)?;
+                            let encode_err = |e: anyhow::Error| {
+                                DataflowError::EvalError(EvalError::Internal(format!(
+                                    "failed to encode update: {:#}",
+                                    e
+                                )))
+                            };
+                            let key = key
+                                .map(|key| encoder.encode_key(key))
+                                .transpose()
+                                .map_err(encode_err)?;
+                            let value = value
+                                .map(|value| encoder.encode_value(value))
+                                .transpose()
+                                .map_err(encode_err)?;
+                            Ok::<_, DataflowError>((key, value, metadata))
+                        })();
+                        match encoded {
+                            Ok(message) => session.give((message, time, diff)),
+                            Err(e) => err_session.give((e, time, 1)),
+                        }
+                    });
+                drop(err_session);
 
-            fuel_remaining -= num_records_to_drain;
+                fuel_remaining -= num_records_to_drain;
 
-            if records.is_empty() {
-                // drop our capability for this time
-                stash.remove(&lowest_ts);
+                if records.is_empty() {
+                    // drop our capability for this time
+                    stash.remove(&lowest_ts);
+                }
             }
-        }
 
-        if !stash.is_empty() {
-            activator.activate();
-            return true;
-        }
-        // signal that we're complete now
-        false
-    };
+            if !stash.is_empty() {
+                activator.activate();
+                return true;
+            }
+            // signal that we're complete now
+            false
+        };
 
     builder.build_reschedule(|_capabilities| {
         move |_frontiers| {
             let mut output_handle = output.activate();
-            encode_logic(&mut input, &mut output_handle)
+            let mut err_output_handle = err_output.activate();
+            encode_logic(&mut input, &mut output_handle, &mut err_output_handle)
         }
     });
 
-    output_stream
+    (output_stream, err_stream)
+}
+
+/// Encodes a stream of updates as messages of the CDCv2 protocol.
+///
+/// This operator exchanges all updates to the single worker that writes to the sink. Once the
+/// input frontier has passed a timestamp, the consolidated updates at that timestamp are encoded
+/// as one update batch. Whenever the frontier advances, a progress statement records the number
+/// of individual updates at each timestamp between the previous and the new frontier.
+///
+/// Each message is emitted at a timestamp at or beyond the updates it describes, so that
+/// downstream operators that emit in timestamp order write progress statements after the
+/// updates they cover. Updates that are not beyond the given [`SinkAsOf`] are discarded, and
+/// progress statements are held back until they can be emitted beyond it.
+fn encode_cdc_v2_stream<G>(
+    input_stream: &Stream<G, ((Option<Row>, Option<Row>), Timestamp, Diff)>,
+    id: GlobalId,
+    as_of: SinkAsOf,
+    encoder: Box<dyn EncodeCdcV2>,
+    name_prefix: String,
+) -> Stream<
+    G,
+    (
+        (Option<Vec<u8>>, Option<Vec<u8>>, MessageMetadata),
+        Timestamp,
+        Diff,
+    ),
+>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let name = format!("{}-{}_cdcv2_encode", name_prefix, encoder.get_format_name());
+    let hashed_id = id.hashed();
+    let is_active_worker =
+        usize::cast_from(hashed_id) % input_stream.scope().peers() == input_stream.scope().index();
+    let beyond_as_of = move |time: &Timestamp| {
+        if as_of.strict {
+            as_of.frontier.less_than(time)
+        } else {
+            as_of.frontier.less_equal(time)
+        }
+    };
+
+    input_stream.unary_frontier(
+        Exchange::new(move |_| hashed_id),
+        &name,
+        move |capability, _info| {
+            // Only the active worker receives updates, so the other workers never produce
+            // output and can give up their capability.
+            let mut capability = if is_active_worker {
+                Some(capability)
+            } else {
+                None
+            };
+            let mut pending: BTreeMap<Timestamp, Vec<(Row, Diff)>> = BTreeMap::new();
+            let mut lower = Antichain::from_elem(Timestamp::minimum());
+            let mut counts = vec![];
+            let mut vector = vec![];
+            move |input, output| {
+                input.for_each(|_cap, data| {
+                    data.swap(&mut vector);
+                    for ((_key, value), time, diff) in vector.drain(..) {
+                        if beyond_as_of(&time) {
+                            let value = value.expect("CDCv2 sinks always have a value");
+                            pending.entry(time).or_default().push((value, diff));
+                        }
+                    }
+                });
+
+                let cap = match &mut capability {
+                    Some(cap) => cap,
+                    None => return,
+                };
+                let upper = input.frontier().frontier().to_owned();
+                if upper == lower {
+                    return;
+                }
+
+                // Emit a batch of updates for each newly closed timestamp.
+                while let Some(time) = pending.keys().next().copied() {
+                    if upper.less_equal(&time) {
+                        break;
+                    }
+                    let mut updates = pending.remove(&time).expect("known to exist");
+                    consolidate(&mut updates);
+                    if updates.is_empty() {
+                        continue;
+                    }
+                    counts.push((time, updates.len()));
+                    let updates = updates
+                        .into_iter()
+                        .map(|(row, diff)| (row, time, diff))
+                        .collect();
+                    let encoded = encoder.encode_unchecked(&CdcV2Message::Updates(updates));
+                    output.session(&cap.delayed(&time)).give((
+                        (None, Some(encoded), MessageMetadata::default()),
+                        time,
+                        1,
+                    ));
+                }
+
+                // Describe the updates emitted between the previous and new frontiers. The
+                // statement is emitted at the last time it covers, which trails all of the
+                // updates it counts.
+                let progress_time = match upper.as_option() {
+                    Some(time) => time - 1,
+                    None => counts
+                        .iter()
+                        .map(|(time, _count)| *time)
+                        .max()
+                        .map_or(*cap.time(), |time| cmp::max(time, *cap.time())),
+                };
+                if beyond_as_of(&progress_time) {
+                    let progress = Progress {
+                        lower: lower.elements().to_vec(),
+                        upper: upper.elements().to_vec(),
+                        counts: std::mem::take(&mut counts),
+                    };
+                    let encoded = encoder.encode_unchecked(&CdcV2Message::Progress(progress));
+                    output.session(&cap.delayed(&progress_time)).give((
+                        (None, Some(encoded), MessageMetadata::default()),
+                        progress_time,
+                        1,
+                    ));
+                    lower = upper.clone();
+                }
+
+                match upper.as_option() {
+                    Some(time) => cap.downgrade(time),
+                    None => capability = None,
+                }
+            }
+        },
+    )
 }
@@ -8,8 +8,10 @@
 // by the Apache License, Version 2.0.
 
 mod avro_ocf;
+mod file;
 mod kafka;
 mod metrics;
+mod postgres;
 mod tail;
 
 pub(crate) use metrics::KafkaBaseMetrics;
@@ -0,0 +1,498 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A sink that mirrors its input into a table in an upstream Postgres database.
+//!
+//! The changes at each closed timestamp are applied as a single upstream
+//! transaction of upserts and deletes on the sink's key. The same transaction
+//! records, in the sink's progress table, the timestamp before which all
+//! changes have been applied. When the sink restarts, it skips the timestamps
+//! that it has already applied, and if it instead starts from a fresh snapshot
+//! of its input, it replaces the contents of the table with that snapshot.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use differential_dataflow::{Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::Operator;
+use timely::dataflow::Scope;
+use timely::progress::Antichain;
+use tokio::sync::mpsc::{self, UnboundedReceiver};
+use tokio_postgres::Client;
+use tracing::{error, info};
+
+use mz_dataflow_types::sinks::{PostgresSinkConnector, SinkAsOf, SinkDesc};
+use mz_expr::GlobalId;
+use mz_ore::cast::CastFrom;
+use mz_ore::task;
+use mz_postgres_util::quote_identifier;
+use mz_repr::{Diff, RelationDesc, Row, ScalarType, Timestamp};
+
+use crate::render::sinks::SinkRender;
+
+impl<G> SinkRender<G> for PostgresSinkConnector
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    fn uses_keys(&self) -> bool {
+        true
+    }
+
+    fn get_key_indices(&self) -> Option<&[usize]> {
+        Some(&self.key_desc_and_indices.1)
+    }
+
+    fn get_relation_key_indices(&self) -> Option<&[usize]> {
+        None
+    }
+
+    fn render_continuous_sink(
+        &self,
+        compute_state: &mut crate::server::ComputeState,
+        sink: &SinkDesc,
+        sink_id: GlobalId,
+        sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    ) -> Option<Rc<dyn Any>>
+    where
+        G: Scope<Timestamp = Timestamp>,
+    {
+        // Only the active worker writes upstream, so all other workers report
+        // an empty write frontier.
+        let scope = sinked_collection.scope();
+        let active_write_worker =
+            usize::cast_from(sink_id.hashed()) % scope.peers() == scope.index();
+        let shared_frontier = Rc::new(RefCell::new(if active_write_worker {
+            Antichain::from_elem(Timestamp::default())
+        } else {
+            Antichain::new()
+        }));
+
+        postgres(
+            sinked_collection,
+            sink_id,
+            self.clone(),
+            sink.as_of.clone(),
+            Rc::clone(&shared_frontier),
+        );
+
+        compute_state
+            .sink_write_frontiers
+            .insert(sink_id, shared_frontier);
+
+        // no sink token
+        None
+    }
+}
+
+/// A message from the sink operator to its writer task.
+enum Message {
+    /// The final value of each key that changed at `time`, or `None` if the key
+    /// was deleted.
+    Batch {
+        time: Timestamp,
+        updates: Vec<(Row, Option<Row>)>,
+    },
+    /// All preceding batches are complete, and no batches at times not beyond
+    /// this frontier will follow.
+    Upper(Antichain<Timestamp>),
+}
+
+fn postgres<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    id: GlobalId,
+    connector: PostgresSinkConnector,
+    as_of: SinkAsOf,
+    write_frontier: Rc<RefCell<Antichain<Timestamp>>>,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    // A sink with a snapshot receives the whole of its input at the `as_of`.
+    let snapshot_time = if as_of.strict {
+        None
+    } else {
+        as_of.frontier.elements().first().copied()
+    };
+
+    // We want exactly one worker to write upstream.
+    let hashed_id = id.hashed();
+    let is_active_worker =
+        usize::cast_from(hashed_id) % collection.scope().peers() == collection.scope().index();
+    let progress = Arc::new(Mutex::new(Antichain::from_elem(Timestamp::default())));
+    let writer = if is_active_worker {
+        let (tx, rx) = mpsc::unbounded_channel();
+        task::spawn(
+            || format!("postgres_sink_writer:{}", id),
+            write_transactions(id, connector, snapshot_time, Arc::clone(&progress), rx),
+        );
+        Some(tx)
+    } else {
+        None
+    };
+
+    let beyond_as_of = move |time: &Timestamp| {
+        if as_of.strict {
+            as_of.frontier.less_than(time)
+        } else {
+            as_of.frontier.less_equal(time)
+        }
+    };
+
+    let mut pending: BTreeMap<Timestamp, Vec<(Row, Option<Row>)>> = BTreeMap::new();
+    // The snapshot must be written even if it is empty, as it replaces
+    // whatever the upstream table contained.
+    if let Some(time) = snapshot_time {
+        pending.insert(time, vec![]);
+    }
+    let mut reported_upper = Antichain::from_elem(Timestamp::default());
+    let mut vector = vec![];
+
+    collection.inner.sink(
+        Exchange::new(move |_| hashed_id),
+        &format!("postgres-{}", id),
+        move |input| {
+            input.for_each(|_, rows| {
+                rows.swap(&mut vector);
+                for ((key, value), time, _diff) in vector.drain(..) {
+                    if beyond_as_of(&time) {
+                        let key = key.expect("Postgres sinks always have a key");
+                        pending.entry(time).or_default().push((key, value));
+                    }
+                }
+            });
+
+            let writer = match &writer {
+                Some(writer) => writer,
+                None => return,
+            };
+
+            *write_frontier.borrow_mut() = progress.lock().expect("lock poisoned").clone();
+
+            let upper = input.frontier().frontier().to_owned();
+            if upper == reported_upper {
+                return;
+            }
+
+            // If the writer has stopped, the sink is being dropped, so there is
+            // nobody to report failed sends to.
+            while let Some(time) = pending.keys().next().copied() {
+                if upper.less_equal(&time) {
+                    break;
+                }
+                let updates = pending.remove(&time).expect("known to exist");
+                let _ = writer.send(Message::Batch { time, updates });
+            }
+            let _ = writer.send(Message::Upper(upper.clone()));
+            reported_upper = upper;
+        },
+    )
+}
+
+/// Applies the batches received on `rx` upstream, in order, retrying each
+/// until it succeeds, and reports the frontier of applied batches in
+/// `progress`.
+///
+/// Like the other sinks, the sink halts at the first batch that fails with an
+/// error that retrying cannot fix, e.g. because a value violates a constraint
+/// of the upstream table: it neither writes nor reports progress past it.
+async fn write_transactions(
+    id: GlobalId,
+    connector: PostgresSinkConnector,
+    snapshot_time: Option<Timestamp>,
+    progress: Arc<Mutex<Antichain<Timestamp>>>,
+    mut rx: UnboundedReceiver<Message>,
+) {
+    let mut writer = Writer::new(id, connector);
+    let resume_upper = match writer.resume_upper().await {
+        Ok(resume_upper) => resume_upper,
+        Err(e) => {
+            error!("sink {} halted reading its progress: {}", id, e);
+            return;
+        }
+    };
+    if let Some(resume_upper) = resume_upper {
+        info!(
+            "Postgres sink {} resuming from timestamp {}",
+            id, resume_upper
+        );
+    }
+
+    let mut halted = false;
+    while let Some(message) = rx.recv().await {
+        match message {
+            Message::Batch { time, updates } => {
+                if halted || resume_upper.map_or(false, |upper| time < upper) {
+                    // Already applied before a restart, or never to be applied.
+                    continue;
+                }
+                let sql = writer.transaction_sql(time, &updates, Some(time) == snapshot_time);
+                if let Err(e) = writer.execute(&sql).await {
+                    error!("sink {} halted at {}: {}", id, time, e);
+                    halted = true;
+                }
+            }
+            Message::Upper(upper) if !halted => *progress.lock().expect("lock poisoned") = upper,
+            Message::Upper(_) => (),
+        }
+    }
+}
+
+/// The upstream connection of a Postgres sink, along with the SQL fragments
+/// it uses to build transactions.
+struct Writer {
+    id: GlobalId,
+    connector: PostgresSinkConnector,
+    client: Option<Client>,
+    /// The quoted, schema-qualified name of the sink's table.
+    table: String,
+    /// The quoted, schema-qualified name of the sink's progress table.
+    progress_table: String,
+    /// The quoted columns of the sink's table.
+    columns: Vec<String>,
+    /// The quoted columns of the sink's key.
+    key_columns: Vec<String>,
+}
+
+impl Writer {
+    fn new(id: GlobalId, connector: PostgresSinkConnector) -> Self {
+        let schema = quote_identifier(&connector.schema);
+        let table = format!("{}.{}", schema, quote_identifier(&connector.table));
+        let progress_table = format!("{}.{}", schema, quote_identifier(&connector.progress_table));
+        let quote_names = |desc: &RelationDesc| -> Vec<String> {
+            desc.iter_names()
+                .map(|name| quote_identifier(name.as_str()))
+                .collect()
+        };
+        let columns = quote_names(&connector.value_desc);
+        let key_columns = quote_names(&connector.key_desc_and_indices.0);
+        Writer {
+            id,
+            connector,
+            client: None,
+            table,
+            progress_table,
+            columns,
+            key_columns,
+        }
+    }
+
+    /// The value that identifies the sink's table in the progress table.
+    fn progress_key(&self) -> String {
+        format!("{}.{}", self.connector.schema, self.connector.table)
+    }
+
+    /// Returns the timestamp before which a previous incarnation of the sink
+    /// has applied all changes, if any.
+    ///
+    /// Retries on a fresh connection until the query succeeds or fails with an
+    /// error that retrying cannot fix, which is returned.
+    async fn resume_upper(&mut self) -> Result<Option<Timestamp>, tokio_postgres::Error> {
+        let sql = format!(
+            "SELECT upper FROM {} WHERE sink_table = $1",
+            self.progress_table
+        );
+        let progress_key = self.progress_key();
+        let mut backoff = Duration::from_millis(125);
+        loop {
+            let e = match self.client().await {
+                Ok(client) => match client.query_opt(sql.as_str(), &[&progress_key]).await {
+                    Ok(row) => {
+                        return Ok(row.map(|row| u64::try_from(row.get::<_, i64>(0)).unwrap_or(0)))
+                    }
+                    Err(e) if !is_transient(&e) => return Err(e),
+                    Err(e) => anyhow::Error::from(e),
+                },
+                Err(e) => e,
+            };
+            error!(
+                "reading progress of Postgres sink {} failed, retrying in {:?}: {:#}",
+                self.id, backoff, e
+            );
+            self.client = None;
+            tokio::time::sleep(backoff).await;
+            backoff = std::cmp::min(backoff * 2, Duration::from_secs(10));
+        }
+    }
+
+    /// Executes `sql`, retrying on a fresh connection until it succeeds or
+    /// fails with an error that retrying cannot fix, which is returned.
+    ///
+    /// Retrying is safe because each transaction only sets the final values
+    /// of keys, and so can be applied more than once.
+    async fn execute(&mut self, sql: &str) -> Result<(), tokio_postgres::Error> {
+        let mut backoff = Duration::from_millis(125);
+        loop {
+            let e = match self.client().await {
+                Ok(client) => match client.batch_execute(sql).await {
+                    Ok(()) => return Ok(()),
+                    Err(e) if !is_transient(&e) => return Err(e),
+                    Err(e) => anyhow::Error::from(e),
+                },
+                Err(e) => e,
+            };
+            error!(
+                "writing to Postgres sink {} failed, retrying in {:?}: {:#}",
+                self.id, backoff, e
+            );
+            // The connection may be left in an aborted transaction.
+            self.client = None;
+            tokio::time::sleep(backoff).await;
+            backoff = std::cmp::min(backoff * 2, Duration::from_secs(10));
+        }
+    }
+
+    async fn client(&mut self) -> Result<&Client, anyhow::Error> {
+        if self.client.is_none() {
+            let client = mz_postgres_util::connect(&self.connector.conn).await?;
+            client
+                .batch_execute("SET standard_conforming_strings = on")
+                .await?;
+            self.client = Some(client);
+        }
+        Ok(self.client.as_ref().expect("known to exist"))
+    }
+
+    /// Builds the transaction that applies `updates` at `time`, replacing the
+    /// contents of the table if `replace` is set.
+    fn transaction_sql(
+        &self,
+        time: Timestamp,
+        updates: &[(Row, Option<Row>)],
+        replace: bool,
+    ) -> String {
+        let mut sql = String::from("BEGIN;\n");
+        if replace {
+            writeln!(sql, "DELETE FROM {};", self.table).unwrap();
+        }
+        let value_types: Vec<_> = self
+            .connector
+            .value_desc
+            .typ()
+            .column_types
+            .iter()
+            .map(|typ| &typ.scalar_type)
+            .collect();
+        let key_types: Vec<_> = self
+            .connector
+            .key_desc_and_indices
+            .0
+            .typ()
+            .column_types
+            .iter()
+            .map(|typ| &typ.scalar_type)
+            .collect();
+        for (key, value) in updates {
+            match value {
+                Some(value) => {
+                    write!(
+                        sql,
+                        "INSERT INTO {} ({}) VALUES (",
+                        self.table,
+                        self.columns.join(", ")
+                    )
+                    .unwrap();
+                    for (i, (datum, typ)) in value.iter().zip(&value_types).enumerate() {
+                        if i > 0 {
+                            sql.push_str(", ");
+                        }
+                        push_literal(&mut sql, datum, typ);
+                    }
+                    write!(sql, ") ON CONFLICT ({}) DO ", self.key_columns.join(", ")).unwrap();
+                    let non_key_columns: Vec<_> = self
+                        .columns
+                        .iter()
+                        .filter(|column| !self.key_columns.contains(column))
+                        .map(|column| format!("{} = EXCLUDED.{}", column, column))
+                        .collect();
+                    if non_key_columns.is_empty() {
+                        sql.push_str("NOTHING;\n");
+                    } else {
+                        writeln!(sql, "UPDATE SET {};", non_key_columns.join(", ")).unwrap();
+                    }
+                }
+                None => {
+                    write!(sql, "DELETE FROM {} WHERE ", self.table).unwrap();
+                    for (i, ((column, datum), typ)) in self
+                        .key_columns
+                        .iter()
+                        .zip(key.iter())
+                        .zip(&key_types)
+                        .enumerate()
+                    {
+                        if i > 0 {
+                            sql.push_str(" AND ");
+                        }
+                        write!(sql, "{} = ", column).unwrap();
+                        push_literal(&mut sql, datum, typ);
+                    }
+                    sql.push_str(";\n");
+                }
+            }
+        }
+        write!(
+            sql,
+            "INSERT INTO {} (sink_table, upper) VALUES (",
+            self.progress_table
+        )
+        .unwrap();
+        push_string_literal(&mut sql, &self.progress_key());
+        writeln!(
+            sql,
+            ", {}) ON CONFLICT (sink_table) DO UPDATE SET upper = EXCLUDED.upper;",
+            time + 1
+        )
+        .unwrap();
+        sql.push_str("COMMIT;");
+        sql
+    }
+}
+
+/// Reports whether `e` may not recur if the statement that caused it is
+/// retried, e.g. because the connection was lost or the transaction lost a
+/// serialization conflict, rather than because the statement is invalid or
+/// violates a constraint.
+fn is_transient(e: &tokio_postgres::Error) -> bool {
+    match e.code() {
+        // Errors that the server did not report, e.g. I/O errors.
+        None => true,
+        Some(code) => {
+            let code = code.code();
+            // Connection exceptions, transaction rollbacks, insufficient
+            // resources, and operator interventions such as shutdowns.
+            code.starts_with("08")
+                || code.starts_with("40")
+                || code.starts_with("53")
+                || code.starts_with("57P")
+        }
+    }
+}
+
+/// Appends `datum` to `sql` as an untyped literal, which Postgres coerces to
+/// the type of the column it is assigned or compared to.
+fn push_literal(sql: &mut String, datum: mz_repr::Datum, typ: &ScalarType) {
+    match mz_pgrepr::Value::from_datum(datum, typ) {
+        None => sql.push_str("NULL"),
+        Some(value) => {
+            let mut text = String::new();
+            value.encode_text(&mut text);
+            push_string_literal(sql, &text);
+        }
+    }
+}
+
+fn push_string_literal(sql: &mut String, s: &str) {
+    sql.push('\'');
+    sql.push_str(&s.replace('\'', "''"));
+    sql.push('\'');
+}
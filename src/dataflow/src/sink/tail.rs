@@ -9,9 +9,12 @@
 
 use std::any::Any;
 use std::cell::RefCell;
+use std::iter;
 use std::ops::DerefMut;
 use std::rc::Rc;
 
+use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
+use differential_dataflow::operators::Reduce;
 use differential_dataflow::Collection;
 
 use timely::dataflow::channels::pact::Pipeline;
@@ -23,11 +26,13 @@ use timely::progress::Antichain;
 use timely::PartialOrder;
 
 use mz_dataflow_types::{
-    sinks::{SinkAsOf, SinkDesc, TailSinkConnector},
+    sinks::{SinkAsOf, SinkDesc, TailEnvelope, TailSinkConnector},
     TailResponse,
 };
 use mz_expr::GlobalId;
-use mz_repr::{Diff, Row, Timestamp};
+use mz_interchange::avro::DiffPair;
+use mz_interchange::envelopes::combine_at_timestamp;
+use mz_repr::{Datum, DatumVec, Diff, Row, RowPacker, Timestamp};
 
 use crate::render::sinks::SinkRender;
 
@@ -66,6 +71,11 @@ where
         })));
         let tail_protocol_weak = Rc::downgrade(&tail_protocol_handle);
 
+        let sinked_collection = match &self.envelope {
+            None => sinked_collection,
+            Some(envelope) => apply_envelope(sinked_collection, envelope, sink.from_desc.arity()),
+        };
+
         tail(
             sinked_collection,
             sink_id,
@@ -84,6 +94,143 @@ where
     }
 }
 
+/// Collapses the updates to each key at each timestamp into a single row,
+/// formatted according to `envelope`.
+///
+/// Each row consists of an `mz_state` column, the key columns, and the value
+/// columns (before and after the change, for Debezium). Keys that map to more
+/// than one row are reported as key violations rather than treated as errors,
+/// since nothing guarantees that the key the user picked is unique.
+fn apply_envelope<G>(
+    sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
+    envelope: &TailEnvelope,
+    arity: usize,
+) -> Collection<G, (Option<Row>, Option<Row>), Diff>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let key_indices = envelope.key_indices().to_vec();
+    let value_arity = arity - key_indices.len();
+    let mut datum_vec = DatumVec::new();
+    let keyed = sinked_collection.map(move |(_key, value)| {
+        let row = value.expect("tail must have values");
+        let datums = datum_vec.borrow_with(&row);
+        let key = Row::pack(key_indices.iter().map(|&idx| datums[idx]));
+        let value = Row::pack(
+            datums
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !key_indices.contains(idx))
+                .map(|(_, datum)| *datum),
+        );
+        (key, value)
+    });
+
+    // Reduce each key to a single state, so that a key's output is derived
+    // from all of its rows rather than just the ones that changed. The state
+    // is the value prefixed with `true` if the key maps to exactly one row,
+    // and just `false` otherwise.
+    let states: Collection<G, (Row, Row), Diff> =
+        keyed.reduce_named("TailEnvelope", |_key, input, output| {
+            let state = match input {
+                [(value, 1)] => Row::pack(iter::once(Datum::True).chain(value.iter())),
+                _ => Row::pack_slice(&[Datum::False]),
+            };
+            output.push((state, 1));
+        });
+
+    let combined = combine_at_timestamp(
+        states
+            .map(|(key, state)| (Some(key), state))
+            .arrange_by_key()
+            .stream,
+    );
+
+    let envelope = envelope.clone();
+    let mut row_buf = Row::default();
+    combined.flat_map(move |(key, dps)| {
+        let key = key.expect("tail envelopes are always keyed");
+        dps.into_iter()
+            .map(|dp| {
+                let mut packer = row_buf.packer();
+                match envelope {
+                    TailEnvelope::Upsert { .. } => pack_upsert(&mut packer, &key, dp, value_arity),
+                    TailEnvelope::Debezium { .. } => {
+                        pack_debezium(&mut packer, &key, dp, value_arity)
+                    }
+                }
+                (None, Some(row_buf.clone()))
+            })
+            .collect::<Vec<_>>()
+    })
+}
+
+/// The state of a key, as computed by [`apply_envelope`].
+enum KeyState<'a> {
+    /// The key maps to no rows.
+    Absent,
+    /// The key maps to exactly one row, whose value columns follow the
+    /// state marker.
+    Present(&'a Row),
+    /// The key maps to more than one row.
+    Violation,
+}
+
+impl<'a> KeyState<'a> {
+    fn new(state: &'a Option<Row>) -> Self {
+        match state {
+            None => KeyState::Absent,
+            Some(row) => match row.iter().next() {
+                Some(Datum::True) => KeyState::Present(row),
+                Some(Datum::False) => KeyState::Violation,
+                marker => unreachable!("invalid tail envelope state marker {:?}", marker),
+            },
+        }
+    }
+
+    /// Packs the value columns of the key, or NULLs if there is no single
+    /// value.
+    fn pack_values(&self, packer: &mut RowPacker, value_arity: usize) {
+        match self {
+            KeyState::Present(row) => packer.extend(row.iter().skip(1)),
+            KeyState::Absent | KeyState::Violation => {
+                packer.extend(iter::repeat(Datum::Null).take(value_arity))
+            }
+        }
+    }
+}
+
+fn pack_upsert(packer: &mut RowPacker, key: &Row, dp: DiffPair<Row>, value_arity: usize) {
+    let after = KeyState::new(&dp.after);
+    packer.push(Datum::String(match after {
+        KeyState::Absent => "delete",
+        KeyState::Present(_) => "upsert",
+        KeyState::Violation => "key_violation",
+    }));
+    packer.extend(key.iter());
+    after.pack_values(packer, value_arity);
+}
+
+fn pack_debezium(packer: &mut RowPacker, key: &Row, dp: DiffPair<Row>, value_arity: usize) {
+    let before = KeyState::new(&dp.before);
+    let after = KeyState::new(&dp.after);
+    let state = match (&before, &after) {
+        (_, KeyState::Violation) => "key_violation",
+        (_, KeyState::Absent) => "delete",
+        (KeyState::Present(_), KeyState::Present(_)) => "update",
+        (KeyState::Absent | KeyState::Violation, KeyState::Present(_)) => "insert",
+    };
+    packer.push(Datum::String(state));
+    packer.extend(key.iter());
+    if let KeyState::Violation = after {
+        // There is no single value to report on either side.
+        packer.extend(iter::repeat(Datum::Null).take(2 * value_arity));
+    } else {
+        before.pack_values(packer, value_arity);
+        after.pack_values(packer, value_arity);
+    }
+}
+
 fn tail<G>(
     sinked_collection: Collection<G, (Option<Row>, Option<Row>), Diff>,
     sink_id: GlobalId,
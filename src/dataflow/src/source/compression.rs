@@ -0,0 +1,190 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Compression codec detection and decompression for file and S3 sources.
+
+use std::io::{self, Read};
+
+use flate2::read::MultiGzDecoder;
+use mz_avro::{AvroRead, Skip};
+use mz_dataflow_types::sources::Compression;
+
+/// Magic bytes at the start of a Snappy framed stream: a stream identifier
+/// chunk containing the string "sNaPpY".
+const SNAPPY_MAGIC: &[u8] = b"\xff\x06\x00\x00sNaPpY";
+
+/// Determines the codec of the file or object `name` whose first bytes are
+/// `prefix`.
+///
+/// The extension of `name` takes precedence. If it is not a known compression
+/// extension, the codec is detected from the magic bytes in `prefix`. Data that
+/// matches neither is assumed to be uncompressed.
+pub fn detect(name: &str, prefix: &[u8]) -> Compression {
+    from_extension(name)
+        .or_else(|| from_magic(prefix))
+        .unwrap_or(Compression::None)
+}
+
+fn from_extension(name: &str) -> Option<Compression> {
+    let (_, extension) = name.rsplit_once('.')?;
+    match extension.to_ascii_lowercase().as_str() {
+        "gz" | "gzip" => Some(Compression::Gzip),
+        "zst" | "zstd" => Some(Compression::Zstd),
+        "bz2" => Some(Compression::Bzip2),
+        "xz" => Some(Compression::Xz),
+        "sz" => Some(Compression::Snappy),
+        _ => None,
+    }
+}
+
+fn from_magic(prefix: &[u8]) -> Option<Compression> {
+    if prefix.starts_with(b"\x1f\x8b") {
+        Some(Compression::Gzip)
+    } else if prefix.starts_with(b"\x28\xb5\x2f\xfd") {
+        Some(Compression::Zstd)
+    } else if prefix.starts_with(b"BZh") {
+        Some(Compression::Bzip2)
+    } else if prefix.starts_with(b"\xfd7zXZ\x00") {
+        Some(Compression::Xz)
+    } else if prefix.starts_with(SNAPPY_MAGIC) {
+        Some(Compression::Snappy)
+    } else {
+        None
+    }
+}
+
+/// Returns whether the `Content-Encoding` of an S3 object agrees with
+/// `compression`, or `None` if the encoding is not recognized.
+pub fn content_encoding_matches(content_encoding: &str, compression: Compression) -> Option<bool> {
+    let encoded = match content_encoding {
+        "identity" => Compression::None,
+        "gzip" => Compression::Gzip,
+        "zstd" => Compression::Zstd,
+        "bzip2" | "x-bzip2" => Compression::Bzip2,
+        "xz" | "x-xz" => Compression::Xz,
+        "x-snappy-framed" => Compression::Snappy,
+        _ => return None,
+    };
+    Some(compression == Compression::Auto || encoded == compression)
+}
+
+/// Wraps `reader` in a decoder for `compression`.
+///
+/// # Panics
+///
+/// Panics if `compression` is [`Compression::Auto`], which must be resolved
+/// with [`detect`] first.
+pub fn decompress<R>(reader: R, compression: Compression) -> io::Result<Box<dyn AvroRead + Send>>
+where
+    R: Read + Send + 'static,
+{
+    let reader: Box<dyn Read + Send> = match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(MultiGzDecoder::new(reader)),
+        Compression::Zstd => Box::new(zstd::stream::read::Decoder::new(reader)?),
+        Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(reader)),
+        Compression::Xz => Box::new(xz2::read::XzDecoder::new_multi_decoder(reader)),
+        Compression::Snappy => Box::new(snap::read::FrameDecoder::new(reader)),
+        Compression::Auto => unreachable!("AUTO compression must be resolved before decoding"),
+    };
+    Ok(Box::new(Decoder(reader)))
+}
+
+/// A reader over decompressed data.
+///
+/// Compressed streams cannot be seeked, so skipping reads and discards bytes.
+struct Decoder(Box<dyn Read + Send>);
+
+impl Read for Decoder {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+impl Skip for Decoder {}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::*;
+
+    #[test]
+    fn detect_prefers_extension() {
+        assert_eq!(detect("data.csv.gz", b""), Compression::Gzip);
+        assert_eq!(detect("data.JSON.ZST", b""), Compression::Zstd);
+        assert_eq!(detect("data.csv.bz2", b"\x1f\x8b"), Compression::Bzip2);
+        assert_eq!(detect("logs/2022.01.xz", b""), Compression::Xz);
+        assert_eq!(detect("data.sz", b""), Compression::Snappy);
+        assert_eq!(detect("data.csv", b"a,b,c"), Compression::None);
+        assert_eq!(detect("data", b""), Compression::None);
+    }
+
+    #[test]
+    fn detect_magic_bytes() -> Result<(), anyhow::Error> {
+        let mut gzip = flate2::write::GzEncoder::new(vec![], flate2::Compression::default());
+        gzip.write_all(b"hello")?;
+        assert_eq!(detect("data", &gzip.finish()?), Compression::Gzip);
+
+        let zstd = zstd::stream::encode_all(&b"hello"[..], 0)?;
+        assert_eq!(detect("data", &zstd), Compression::Zstd);
+
+        let mut bzip2 = bzip2::write::BzEncoder::new(vec![], bzip2::Compression::default());
+        bzip2.write_all(b"hello")?;
+        assert_eq!(detect("data", &bzip2.finish()?), Compression::Bzip2);
+
+        let mut xz = xz2::write::XzEncoder::new(vec![], 6);
+        xz.write_all(b"hello")?;
+        assert_eq!(detect("data", &xz.finish()?), Compression::Xz);
+
+        let mut snappy = vec![];
+        let mut encoder = snap::write::FrameEncoder::new(&mut snappy);
+        encoder.write_all(b"hello")?;
+        encoder.flush()?;
+        drop(encoder);
+        assert_eq!(detect("data", &snappy), Compression::Snappy);
+
+        // Detection needs more than one byte, but must not panic on short
+        // inputs.
+        assert_eq!(detect("data", b"\x1f"), Compression::None);
+        Ok(())
+    }
+
+    #[test]
+    fn decompress_roundtrip() -> Result<(), anyhow::Error> {
+        let mut snappy = vec![];
+        let mut encoder = snap::write::FrameEncoder::new(&mut snappy);
+        encoder.write_all(b"hello\nworld\n")?;
+        encoder.flush()?;
+        drop(encoder);
+
+        let mut out = String::new();
+        decompress(io::Cursor::new(snappy), Compression::Snappy)?.read_to_string(&mut out)?;
+        assert_eq!(out, "hello\nworld\n");
+        Ok(())
+    }
+
+    #[test]
+    fn decompress_corrupt_snappy() -> Result<(), anyhow::Error> {
+        let mut snappy = vec![];
+        let mut encoder = snap::write::FrameEncoder::new(&mut snappy);
+        encoder.write_all(b"hello\nworld\n")?;
+        encoder.flush()?;
+        drop(encoder);
+        // The checksum of the first data chunk follows the 10 byte stream
+        // identifier and the data chunk's 4 byte header.
+        snappy[14] ^= 0xff;
+
+        let mut out = String::new();
+        let res =
+            decompress(io::Cursor::new(snappy), Compression::Snappy)?.read_to_string(&mut out);
+        assert!(res.is_err());
+        Ok(())
+    }
+}
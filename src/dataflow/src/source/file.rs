@@ -7,31 +7,39 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::io::{self, BufRead, Read};
-use std::path::PathBuf;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Read, Seek, SeekFrom};
+use std::mem;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{Context, Error};
-use flate2::read::MultiGzDecoder;
+use globset::GlobMatcher;
 #[cfg(target_os = "linux")]
 use inotify::{EventMask, Inotify, WatchMask};
 use mz_dataflow_types::sources::AwsExternalId;
 use mz_repr::MessagePayload;
 use timely::scheduling::SyncActivator;
-use tracing::{debug, error, trace};
+use tracing::{debug, error, trace, warn};
 
 use mz_avro::Block;
 use mz_avro::BlockIter;
 use mz_avro::{AvroRead, Schema, Skip};
 use mz_dataflow_types::sources::{
-    encoding::AvroOcfEncoding, encoding::DataEncoding, encoding::SourceDataEncoding, Compression,
-    ExternalSourceConnector, MzOffset,
+    encoding::AvroOcfEncoding, encoding::DataEncoding, encoding::ParquetEncoding,
+    encoding::SourceDataEncoding, Compression, ExternalSourceConnector, MzOffset,
 };
 use mz_expr::{PartitionId, SourceInstanceId};
+use mz_interchange::parquet::ParquetReader;
+use mz_ore::cast::CastFrom;
 
 use crate::logging::materialized::Logger;
+use crate::source::compression;
 use crate::source::{NextMessage, SourceMessage, SourceReader};
 
 use super::metrics::SourceBaseMetrics;
@@ -40,11 +48,24 @@ use super::metrics::SourceBaseMetrics;
 pub struct FileSourceReader {
     /// Unique source ID
     id: SourceInstanceId,
-    /// Receiver channel that ingests records
-    receiver_stream: Receiver<Result<MessagePayload, Error>>,
-    /// Current File Offset. This corresponds to the offset of last processed message
-    /// (initially 0 if no records have been processed)
-    current_file_offset: FileOffset,
+    /// Receiver channel that ingests records, along with the partition they belong to
+    receiver_stream: Receiver<Result<(PartitionId, MessagePayload), Error>>,
+    /// Current File Offset of each partition. This corresponds to the offset of last processed
+    /// message (initially 0 if no records have been processed)
+    current_file_offsets: HashMap<PartitionId, FileOffset>,
+}
+
+impl FileSourceReader {
+    fn new_from_receiver(
+        id: SourceInstanceId,
+        receiver_stream: Receiver<Result<(PartitionId, MessagePayload), Error>>,
+    ) -> FileSourceReader {
+        FileSourceReader {
+            id,
+            receiver_stream,
+            current_file_offsets: HashMap::new(),
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -74,13 +95,53 @@ impl SourceReader for FileSourceReader {
         consumer_activator: SyncActivator,
         connector: ExternalSourceConnector,
         _: AwsExternalId,
-        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _upstream_positions: Vec<(PartitionId, MzOffset, String)>,
         encoding: SourceDataEncoding,
         _: Option<Logger>,
         _: SourceBaseMetrics,
     ) -> Result<Self, anyhow::Error> {
         let receiver = match connector {
             ExternalSourceConnector::File(fc) => {
+                if let Some(pattern) = fc.pattern {
+                    debug!("creating globbing FileSourceReader worker_id={}", worker_id);
+                    // Restored offsets are 1-indexed and point one past the
+                    // last line that was read, so they are also the number of
+                    // lines to skip.
+                    let start_offsets: HashMap<_, _> = restored_offsets
+                        .into_iter()
+                        .filter_map(|(pid, offset)| Some((pid, offset?.offset - 1)))
+                        .collect();
+                    let (tx, rx) = std::sync::mpsc::sync_channel(10000);
+                    let glob = pattern.compile_matcher();
+                    let mut reader = FileSourceReader::new_from_receiver(source_id, rx);
+                    reader.current_file_offsets = start_offsets
+                        .iter()
+                        .map(|(pid, offset)| (pid.clone(), FileOffset { offset: *offset }))
+                        .collect();
+                    std::thread::spawn(move || {
+                        read_glob_task(
+                            fc.path,
+                            glob,
+                            fc.tail,
+                            fc.compression,
+                            start_offsets,
+                            tx,
+                            consumer_activator,
+                        );
+                    });
+                    return Ok(reader);
+                }
+
+                if let DataEncoding::Parquet(encoding) = encoding.value() {
+                    debug!("creating Parquet FileSourceReader worker_id={}", worker_id);
+                    let (tx, rx) = std::sync::mpsc::sync_channel(10000);
+                    std::thread::spawn(move || {
+                        read_parquet_file_task(fc.path, tx, Some(consumer_activator), encoding);
+                    });
+                    return Ok(FileSourceReader::new_from_receiver(source_id, rx));
+                }
+
                 debug!("creating FileSourceReader worker_id={}", worker_id);
                 let ctor = |fi| {
                     let mut br = std::io::BufReader::new(fi);
@@ -160,23 +221,25 @@ impl SourceReader for FileSourceReader {
             _ => unreachable!(),
         };
 
-        Ok(FileSourceReader {
-            id: source_id,
-            receiver_stream: receiver,
-            current_file_offset: FileOffset { offset: 0 },
-        })
+        Ok(FileSourceReader::new_from_receiver(source_id, receiver))
     }
 
     fn get_next_message(&mut self) -> Result<NextMessage<Self::Key, Self::Value>, anyhow::Error> {
         match self.receiver_stream.try_recv() {
-            Ok(Ok(record)) => {
-                self.current_file_offset.offset += 1;
+            Ok(Ok((partition, record))) => {
+                let offset = self
+                    .current_file_offsets
+                    .entry(partition.clone())
+                    .or_insert(FileOffset { offset: 0 });
+                offset.offset += 1;
                 let message = SourceMessage {
-                    partition: PartitionId::None,
-                    offset: self.current_file_offset.into(),
+                    partition,
+                    offset: (*offset).into(),
                     upstream_time_millis: None,
+                    upstream_position: None,
                     key: (),
                     value: record,
+                    headers: None,
                 };
                 Ok(NextMessage::Ready(message))
             }
@@ -193,7 +256,7 @@ impl SourceReader for FileSourceReader {
 /// Blocking logic to read from a file, intended for its own thread.
 pub fn read_file_task<Ctor, I, Err>(
     path: PathBuf,
-    tx: std::sync::mpsc::SyncSender<Result<MessagePayload, anyhow::Error>>,
+    tx: std::sync::mpsc::SyncSender<Result<(PartitionId, MessagePayload), anyhow::Error>>,
     activator: Option<SyncActivator>,
     read_style: FileReadStyle,
     compression: Compression,
@@ -229,9 +292,34 @@ pub fn read_file_task<Ctor, I, Err>(
         }
     };
 
-    let file_stream: Box<dyn AvroRead + Send> = match compression {
-        Compression::Gzip => Box::new(MultiGzDecoder::new(file_stream)),
-        Compression::None => Box::new(file_stream),
+    let file_stream = match compression {
+        Compression::None => Ok(file_stream),
+        Compression::Auto => {
+            // Peek at the magic bytes without consuming them.
+            let mut file_stream = io::BufReader::new(file_stream);
+            match file_stream.fill_buf() {
+                Ok(prefix) => {
+                    let compression = compression::detect(&path.to_string_lossy(), prefix);
+                    compression::decompress(file_stream, compression)
+                }
+                Err(err) => Err(err),
+            }
+        }
+        compression => compression::decompress(file_stream, compression),
+    };
+    let file_stream = match file_stream.with_context(|| {
+        format!(
+            "file source: unable to decompress file at path {}",
+            path.to_string_lossy(),
+        )
+    }) {
+        Ok(f) => f,
+        Err(err) => {
+            // If we fail to send an error, it's likely due to a race condition
+            // with the source being closed.
+            let _ = tx.send(Err(err));
+            return;
+        }
     };
 
     let iter = iter_ctor(file_stream);
@@ -242,7 +330,12 @@ pub fn read_file_task<Ctor, I, Err>(
             path.to_string_lossy(),
         )
     }) {
-        Ok(i) => send_records(i, tx, activator),
+        Ok(i) => send_records(
+            i.into_iter()
+                .map(|record| record.map(|record| (PartitionId::None, record))),
+            tx,
+            activator,
+        ),
         Err(e) => {
             // If we fail to send an error, it's likely due to a race condition
             // with the source being closed.
@@ -251,6 +344,425 @@ pub fn read_file_task<Ctor, I, Err>(
     };
 }
 
+/// Blocking logic to read a Parquet file, intended for its own thread.
+///
+/// Unlike [`read_file_task`], this decodes the file while reading it, as
+/// Parquet files cannot be read front to back.
+fn read_parquet_file_task(
+    path: PathBuf,
+    tx: std::sync::mpsc::SyncSender<Result<(PartitionId, MessagePayload), anyhow::Error>>,
+    activator: Option<SyncActivator>,
+    encoding: ParquetEncoding,
+) {
+    trace!("reading Parquet file {}", path.display());
+    let reader = std::fs::File::open(&path)
+        .map_err(Error::from)
+        .and_then(|file| ParquetReader::new(file, encoding.columns, encoding.projection.as_deref()))
+        .with_context(|| {
+            format!(
+                "file source: unable to read Parquet file at path {}",
+                path.to_string_lossy(),
+            )
+        });
+    match reader {
+        Ok(reader) => send_records(
+            reader.map(|row| row.map(|row| (PartitionId::None, MessagePayload::Row(row)))),
+            tx,
+            activator,
+        ),
+        Err(err) => {
+            // If we fail to send an error, it's likely due to a race condition
+            // with the source being closed.
+            let _ = tx.send(Err(err));
+        }
+    }
+}
+
+/// How often a glob file source checks its files for new data when tailing.
+const GLOB_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How often a glob file source looks for new files when tailing.
+const GLOB_SCAN_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The number of bytes read from a file in one go.
+const GLOB_CHUNK_SIZE: usize = 8192;
+
+/// The most bytes read from one file before moving on to the next, so that a
+/// busy file cannot starve the others.
+const GLOB_READ_BUDGET: usize = 1 << 20;
+
+/// Identifies a file independently of its path, by device and inode number,
+/// and by creation time where the platform records it, so that a file that
+/// reuses the inode of a deleted file is not mistaken for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct FileId {
+    dev: u64,
+    ino: u64,
+    created: Option<SystemTime>,
+}
+
+impl FileId {
+    fn new(metadata: &std::fs::Metadata) -> FileId {
+        FileId {
+            dev: metadata.dev(),
+            ino: metadata.ino(),
+            created: metadata.created().ok(),
+        }
+    }
+}
+
+/// A file that is read by a glob file source.
+struct GlobFile {
+    id: FileId,
+    /// The number of times the file has been truncated.
+    generation: u64,
+    /// The partition to which the file's records are assigned. See
+    /// [`GlobFile::partition`].
+    partition: PartitionId,
+    /// The number of lines at the start of the partition that were read
+    /// before a restart, and must not be read again.
+    skip: i64,
+    /// The path at which the file was last seen.
+    path: PathBuf,
+    reader: GlobFileReader,
+    /// Data read from the file that does not yet form a complete line.
+    partial_line: Vec<u8>,
+    /// Whether the file was found at a path matching the glob in the latest
+    /// scan. Files that have been rotated away or deleted are read to their end
+    /// and then forgotten.
+    matched: bool,
+}
+
+enum GlobFileReader {
+    /// An uncompressed file, which is tailed, and read again from its start if
+    /// it is truncated.
+    Plain { file: std::fs::File, position: u64 },
+    /// A compressed file, which is assumed to be complete and is read once.
+    Compressed(Box<dyn AvroRead + Send>),
+}
+
+impl Read for GlobFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            GlobFileReader::Plain { file, position } => {
+                let n = file.read(buf)?;
+                *position += u64::cast_from(n);
+                Ok(n)
+            }
+            GlobFileReader::Compressed(reader) => reader.read(buf),
+        }
+    }
+}
+
+impl GlobFile {
+    fn open(
+        path: PathBuf,
+        id: FileId,
+        compression: Compression,
+        start_offsets: &HashMap<PartitionId, i64>,
+    ) -> Result<GlobFile, io::Error> {
+        let mut file = std::fs::File::open(&path)?;
+        let compression = match compression {
+            Compression::Auto => {
+                let mut prefix = vec![];
+                (&mut file).take(16).read_to_end(&mut prefix)?;
+                file.seek(SeekFrom::Start(0))?;
+                compression::detect(&path.to_string_lossy(), &prefix)
+            }
+            compression => compression,
+        };
+        let reader = match compression {
+            Compression::None => GlobFileReader::Plain { file, position: 0 },
+            compression => GlobFileReader::Compressed(compression::decompress(file, compression)?),
+        };
+        let partition = GlobFile::partition(id, 0);
+        Ok(GlobFile {
+            id,
+            skip: start_offsets.get(&partition).copied().unwrap_or(0),
+            partition,
+            generation: 0,
+            path,
+            reader,
+            partial_line: vec![],
+            matched: true,
+        })
+    }
+
+    /// Names the partition for a generation of the file with the given ID.
+    ///
+    /// The offsets of a partition only ever describe one sequence of lines, so
+    /// a file that replaces another at the same path, or that is truncated and
+    /// rewritten, is assigned a new partition rather than continuing the
+    /// offsets of the data it replaced. The name does not include the file's
+    /// path, so that a file that is renamed while the source is not running
+    /// resumes its partition after a restart. A file that is truncated while
+    /// the source is not running is not detected, however.
+    fn partition(id: FileId, generation: u64) -> PartitionId {
+        let created = id
+            .created
+            .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+            .map_or(0, |d| d.as_nanos());
+        PartitionId::File(format!("{}.{}.{}.{}", id.dev, id.ino, created, generation))
+    }
+
+    /// Reads the data that is currently available from the file.
+    ///
+    /// Returns the complete lines that were read, if any, and whether the file
+    /// has been read to its end and can be forgotten. When it has, any
+    /// trailing partial line is returned as a complete line.
+    fn read(&mut self, tail: bool) -> Result<(Option<Vec<u8>>, bool), io::Error> {
+        if let GlobFileReader::Plain { file, position } = &mut self.reader {
+            if tail && file.metadata()?.len() < *position {
+                warn!(
+                    "file source: {} was truncated; reading it again from the start",
+                    self.path.display()
+                );
+                file.seek(SeekFrom::Start(0))?;
+                *position = 0;
+                self.partial_line.clear();
+                self.generation += 1;
+                self.partition = GlobFile::partition(self.id, self.generation);
+                self.skip = 0;
+            }
+        }
+
+        let start = self.partial_line.len();
+        let mut eof = false;
+        while self.partial_line.len() - start < GLOB_READ_BUDGET {
+            let len = self.partial_line.len();
+            self.partial_line.resize(len + GLOB_CHUNK_SIZE, 0);
+            let n = self.reader.read(&mut self.partial_line[len..])?;
+            self.partial_line.truncate(len + n);
+            if n == 0 {
+                eof = true;
+                break;
+            }
+        }
+
+        let followed = tail && self.matched && matches!(self.reader, GlobFileReader::Plain { .. });
+        if eof && !followed {
+            let mut lines = mem::take(&mut self.partial_line);
+            if lines.is_empty() {
+                return Ok((None, true));
+            }
+            if lines.last() != Some(&b'\n') {
+                lines.push(b'\n');
+            }
+            return Ok((self.skip_lines(lines), true));
+        }
+
+        match self.partial_line.iter().rposition(|b| *b == b'\n') {
+            Some(i) => {
+                let partial_line = self.partial_line.split_off(i + 1);
+                let lines = mem::replace(&mut self.partial_line, partial_line);
+                Ok((self.skip_lines(lines), false))
+            }
+            None => Ok((None, false)),
+        }
+    }
+
+    /// Drops the lines that were read before a restart from the start of
+    /// `lines`, which must end with a newline.
+    fn skip_lines(&mut self, mut lines: Vec<u8>) -> Option<Vec<u8>> {
+        let mut start = 0;
+        while self.skip > 0 && start < lines.len() {
+            let line_len = lines[start..]
+                .iter()
+                .position(|b| *b == b'\n')
+                .expect("lines end with a newline");
+            start += line_len + 1;
+            self.skip -= 1;
+        }
+        lines.drain(..start);
+        if lines.is_empty() {
+            None
+        } else {
+            Some(lines)
+        }
+    }
+}
+
+/// Blocking logic to read every file under `root` that matches `glob`,
+/// intended for its own thread.
+///
+/// Each file is a separate partition. Files are identified by device, inode and
+/// creation time rather than by path, so that a file that is renamed, as by
+/// `logrotate`, is read to its end rather than read again under its new name,
+/// while a new file created in its place is read from its start. Each line is a
+/// separate record, so that offsets count lines and reading can resume from
+/// `start_offsets` after a restart, and lines from different files are never
+/// spliced together.
+///
+/// When tailing, the files are followed forever, and new files are picked up
+/// as they appear.
+fn read_glob_task(
+    root: PathBuf,
+    glob: GlobMatcher,
+    tail: bool,
+    compression: Compression,
+    start_offsets: HashMap<PartitionId, i64>,
+    tx: std::sync::mpsc::SyncSender<Result<(PartitionId, MessagePayload), anyhow::Error>>,
+    activator: SyncActivator,
+) {
+    trace!("reading files matching {}", glob.glob());
+    let mut files: HashMap<FileId, GlobFile> = HashMap::new();
+    // Files that were read to their end, and must not be read again.
+    let mut finished: HashSet<FileId> = HashSet::new();
+    let mut last_scan: Option<Instant> = None;
+    loop {
+        if last_scan.map_or(true, |t| tail && t.elapsed() >= GLOB_SCAN_INTERVAL) {
+            if let Err(err) = scan_glob(
+                &root,
+                &glob,
+                compression,
+                &start_offsets,
+                &mut files,
+                &finished,
+            ) {
+                // If we fail to send an error, it's likely due to a race condition
+                // with the source being closed.
+                let _ = tx.send(Err(err));
+                return;
+            }
+            last_scan = Some(Instant::now());
+        }
+
+        let mut made_progress = false;
+        let mut done = vec![];
+        for (id, file) in files.iter_mut() {
+            let (lines, eof) = match file.read(tail).with_context(|| {
+                format!(
+                    "file source: unable to read file at path {}",
+                    file.path.display()
+                )
+            }) {
+                Ok(result) => result,
+                Err(err) => {
+                    let _ = tx.send(Err(err));
+                    return;
+                }
+            };
+            if let Some(lines) = lines {
+                made_progress = true;
+                for line in lines.split_inclusive(|b| *b == b'\n') {
+                    if tx
+                        .send(Ok((
+                            file.partition.clone(),
+                            MessagePayload::Data(line.to_vec()),
+                        )))
+                        .is_err()
+                    {
+                        // The receiver went away, probably due to `DROP SOURCE`
+                        return;
+                    }
+                }
+                activator.activate().expect("activation failed");
+            }
+            if eof {
+                done.push(*id);
+            }
+        }
+        for id in done {
+            files.remove(&id);
+            finished.insert(id);
+        }
+
+        if !tail && files.is_empty() {
+            return;
+        }
+        if !made_progress {
+            thread::sleep(GLOB_POLL_INTERVAL);
+        }
+    }
+}
+
+/// Walks `root` and starts reading the files that match `glob`, unless they
+/// are already being read or have been read to their end.
+fn scan_glob(
+    root: &Path,
+    glob: &GlobMatcher,
+    compression: Compression,
+    start_offsets: &HashMap<PartitionId, i64>,
+    files: &mut HashMap<FileId, GlobFile>,
+    finished: &HashSet<FileId>,
+) -> Result<(), anyhow::Error> {
+    // Only descend as deep as the glob can match, unless it contains `**`.
+    let max_depth = if glob.glob().glob().contains("**") {
+        usize::MAX
+    } else {
+        Path::new(glob.glob().glob()).components().count() - root.components().count()
+    };
+
+    for file in files.values_mut() {
+        file.matched = false;
+    }
+
+    let mut dirs = vec![(root.to_path_buf(), 1)];
+    while let Some((dir, depth)) = dirs.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            // Subdirectories may be removed while we are walking them.
+            Err(err) if err.kind() == io::ErrorKind::NotFound && dir != root => continue,
+            Err(err) => {
+                return Err(err).with_context(|| {
+                    format!("file source: unable to list directory {}", dir.display())
+                })
+            }
+        };
+        for entry in entries {
+            let entry = entry?;
+            let path = entry.path();
+            if entry.file_type()?.is_dir() {
+                if depth < max_depth {
+                    dirs.push((path, depth + 1));
+                }
+                continue;
+            }
+            if !glob.is_match(&path) {
+                continue;
+            }
+            // Follows symlinks, unlike `entry.metadata()`.
+            let metadata = match std::fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err.into()),
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            let id = FileId::new(&metadata);
+            if finished.contains(&id) {
+                continue;
+            }
+            match files.entry(id) {
+                Entry::Occupied(mut entry) => {
+                    let file = entry.get_mut();
+                    file.matched = true;
+                    file.path = path;
+                }
+                Entry::Vacant(entry) => {
+                    debug!("file source: discovered file {}", path.display());
+                    match GlobFile::open(path.clone(), id, compression, start_offsets) {
+                        Ok(file) => {
+                            entry.insert(file);
+                        }
+                        Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                        Err(err) => {
+                            return Err(err).with_context(|| {
+                                format!(
+                                    "file source: unable to open file at path {}",
+                                    path.display()
+                                )
+                            })
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 fn open_file_stream(
     _path: PathBuf,
     file: std::fs::File,
@@ -421,3 +933,127 @@ fn send_records<I, Out, Err>(
     }
     trace!("sent {} records to reader", records);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+
+    use globset::Glob;
+
+    use super::*;
+
+    fn glob_matcher(dir: &Path, glob: &str) -> Result<GlobMatcher, anyhow::Error> {
+        Ok(Glob::new(&format!("{}/{}", dir.display(), glob))?.compile_matcher())
+    }
+
+    /// Reads the lines currently available from `file`.
+    fn read_lines(file: &mut GlobFile) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(file.read(true)?.0.unwrap_or_default())
+    }
+
+    #[test]
+    fn truncated_file_starts_new_partition() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("data.log");
+        fs::write(&path, "a\nb\n")?;
+
+        let id = FileId::new(&fs::metadata(&path)?);
+        let mut file = GlobFile::open(path.clone(), id, Compression::None, &HashMap::new())?;
+        assert_eq!(read_lines(&mut file)?, b"a\nb\n");
+        let partition = file.partition.clone();
+
+        // Truncating the file in place keeps its identity, but its new
+        // contents must not continue the offsets of the old ones.
+        fs::write(&path, "c\n")?;
+        assert_eq!(FileId::new(&fs::metadata(&path)?), id);
+        assert_eq!(read_lines(&mut file)?, b"c\n");
+        assert_ne!(file.partition, partition);
+
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)?
+            .write_all(b"d\n")?;
+        let partition = file.partition.clone();
+        assert_eq!(read_lines(&mut file)?, b"d\n");
+        assert_eq!(file.partition, partition);
+        Ok(())
+    }
+
+    #[test]
+    fn rotated_file_is_finished_and_replacement_is_read() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let glob = glob_matcher(dir.path(), "*.log")?;
+        let path = dir.path().join("app.log");
+        fs::write(&path, "old 1\n")?;
+
+        let mut files = HashMap::new();
+        let finished = HashSet::new();
+        scan_glob(
+            dir.path(),
+            &glob,
+            Compression::None,
+            &HashMap::new(),
+            &mut files,
+            &finished,
+        )?;
+        assert_eq!(files.len(), 1);
+        let old_id = *files.keys().next().unwrap();
+        assert_eq!(
+            read_lines(files.get_mut(&old_id).unwrap())?,
+            b"old 1\n".to_vec()
+        );
+
+        // Rotate the file, as `logrotate` does: the old file is renamed and
+        // finished by its writer, and a new file is created at the same path.
+        let rotated = dir.path().join("app.log.1");
+        fs::rename(&path, &rotated)?;
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&rotated)?
+            .write_all(b"old 2\n")?;
+        fs::write(&path, "new 1\n")?;
+
+        scan_glob(
+            dir.path(),
+            &glob,
+            Compression::None,
+            &HashMap::new(),
+            &mut files,
+            &finished,
+        )?;
+        assert_eq!(files.len(), 2);
+        let new_id = *files.keys().find(|id| **id != old_id).unwrap();
+
+        let old = files.get_mut(&old_id).unwrap();
+        assert!(!old.matched);
+        assert_eq!(old.read(true)?, (Some(b"old 2\n".to_vec()), true));
+        let old_partition = old.partition.clone();
+
+        let new = files.get_mut(&new_id).unwrap();
+        assert!(new.matched);
+        assert_eq!(read_lines(new)?, b"new 1\n".to_vec());
+        assert_ne!(new.partition, old_partition);
+        Ok(())
+    }
+
+    #[test]
+    fn renamed_file_resumes_from_start_offset() -> Result<(), anyhow::Error> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("app.log");
+        fs::write(&path, "a\nb\nc\n")?;
+        let id = FileId::new(&fs::metadata(&path)?);
+
+        // The file was renamed while the source was not running, after the
+        // first two lines were read.
+        let renamed = dir.path().join("app.log.1");
+        fs::rename(&path, &renamed)?;
+        let start_offsets = HashMap::from([(GlobFile::partition(id, 0), 2)]);
+
+        let renamed_id = FileId::new(&fs::metadata(&renamed)?);
+        assert_eq!(renamed_id, id);
+        let mut file = GlobFile::open(renamed, renamed_id, Compression::None, &start_offsets)?;
+        assert_eq!(read_lines(&mut file)?, b"c\n");
+        Ok(())
+    }
+}
@@ -16,9 +16,10 @@ use mz_dataflow_types::sources::AwsExternalId;
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
 use rdkafka::error::KafkaError;
-use rdkafka::message::BorrowedMessage;
+use rdkafka::message::{BorrowedMessage, Headers};
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientConfig, ClientContext, Message, TopicPartitionList};
+use regex::Regex;
 use timely::scheduling::activate::SyncActivator;
 
 use mz_dataflow_types::sources::{
@@ -38,14 +39,19 @@ use super::metrics::SourceBaseMetrics;
 
 /// Contains all information necessary to ingest data from Kafka
 pub struct KafkaSourceReader {
-    /// Name of the topic on which this source is backed on
+    /// Name of the topic on which this source is backed on, or the pattern the
+    /// names of its topics match
     topic_name: String,
+    /// If present, the source reads every topic whose name matches the pattern
+    topic_pattern: Option<Regex>,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
     /// Source instance ID
     id: SourceInstanceId,
     /// Kafka consumer for this source
     consumer: Arc<BaseConsumer<GlueConsumerContext>>,
+    /// Whether the source includes message headers, and so must read them
+    include_headers: bool,
     /// List of consumers. A consumer should be assigned per partition to guarantee fairness
     partition_consumers: VecDeque<PartitionConsumer>,
     /// Worker ID
@@ -53,9 +59,9 @@ pub struct KafkaSourceReader {
     /// Total count of workers
     worker_count: usize,
     /// Map from partition -> most recently read offset
-    last_offsets: HashMap<i32, i64>,
+    last_offsets: HashMap<PartitionId, i64>,
     /// Map from partition -> offset to start reading at
-    start_offsets: HashMap<i32, i64>,
+    start_offsets: HashMap<PartitionId, i64>,
     /// Timely worker logger for source events
     logger: Option<Logger>,
     /// Channel to receive Kafka statistics JSON blobs from the stats callback.
@@ -63,7 +69,7 @@ pub struct KafkaSourceReader {
     // The last statistics JSON blob received.
     last_stats: Option<Jsonb>,
     /// The last partition we received
-    partition_info: Arc<Mutex<Option<Vec<i32>>>>,
+    partition_info: Arc<Mutex<Option<Vec<PartitionId>>>>,
     /// A handle to the spawned metadata thread
     // Drop order is important here, we want the thread to be unparked after the `partition_info`
     // Arc has been dropped, so that the unpacked thread notices it and exits immediately
@@ -92,6 +98,7 @@ impl SourceReader for KafkaSourceReader {
         connector: ExternalSourceConnector,
         _: AwsExternalId,
         restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        _upstream_positions: Vec<(PartitionId, MzOffset, String)>,
         _: SourceDataEncoding,
         logger: Option<Logger>,
         _: SourceBaseMetrics,
@@ -104,9 +111,12 @@ impl SourceReader for KafkaSourceReader {
         let KafkaSourceConnector {
             addrs,
             topic,
+            topic_pattern,
             config_options,
             group_id_prefix,
             cluster_id,
+            include_headers,
+            include_header,
             ..
         } = kc;
         let kafka_config = create_kafka_config(
@@ -125,18 +135,27 @@ impl SourceReader for KafkaSourceReader {
             .expect("Failed to create Kafka Consumer");
         let consumer = Arc::new(consumer);
 
+        let topic_pattern = topic_pattern.map(|pattern| pattern.0);
+
         // Start offsets is a map from pid -> next 0-indexed offset to read from,
-        // which is equivalent to 1 + the last 0-indexed offset read.
-        let mut start_offsets: HashMap<_, _> = kc.start_offsets.into_iter().collect();
+        // which is equivalent to 1 + the last 0-indexed offset read. Start offsets
+        // can only be specified for single-topic sources.
+        let mut start_offsets: HashMap<_, _> = if topic_pattern.is_none() {
+            kc.start_offsets
+                .into_iter()
+                .map(|(pid, offset)| (PartitionId::Kafka(pid), offset))
+                .collect()
+        } else {
+            HashMap::new()
+        };
 
         // Restored offsets are 1-indexed, so convert to 0-indexed offsets by
         // subtracting 1. The bindings in sqlite already encode 1 offset past the
         // last read offset.
         for (pid, offset) in restored_offsets {
-            let pid = match pid {
-                PartitionId::Kafka(id) => id,
-                _ => panic!("unexpected partition id type"),
-            };
+            if !matches!(pid, PartitionId::Kafka(_) | PartitionId::KafkaTopic(..)) {
+                panic!("unexpected partition id type");
+            }
             if let Some(offset) = offset {
                 if let Some(start_offset) = start_offsets.get_mut(&pid) {
                     *start_offset = std::cmp::max(offset.offset - 1, *start_offset);
@@ -150,6 +169,7 @@ impl SourceReader for KafkaSourceReader {
         let metadata_thread_handle = {
             let partition_info = Arc::downgrade(&partition_info);
             let topic = topic.clone();
+            let topic_pattern = topic_pattern.clone();
             let consumer = Arc::clone(&consumer);
             let metadata_refresh_frequency = config_options
                 .get("topic.metadata.refresh.interval.ms")
@@ -163,7 +183,19 @@ impl SourceReader for KafkaSourceReader {
                 .name("kafka-metadata".to_string())
                 .spawn(move || {
                     while let Some(partition_info) = partition_info.upgrade() {
-                        match get_kafka_partitions(&consumer, &topic, Duration::from_secs(30)) {
+                        // Refreshing the metadata of a pattern source is also how we
+                        // discover topics that were created after the source.
+                        let partitions = match &topic_pattern {
+                            None => {
+                                get_kafka_partitions(&consumer, &topic, Duration::from_secs(30))
+                            }
+                            Some(pattern) => get_kafka_topic_partitions(
+                                &consumer,
+                                pattern,
+                                Duration::from_secs(30),
+                            ),
+                        };
+                        match partitions {
                             Ok(info) => {
                                 *partition_info.lock().unwrap() = Some(info);
                                 std::thread::park_timeout(metadata_refresh_frequency);
@@ -178,10 +210,12 @@ impl SourceReader for KafkaSourceReader {
 
         Ok(KafkaSourceReader {
             topic_name: topic,
+            topic_pattern,
             source_name,
             id: source_id,
             partition_consumers: VecDeque::new(),
             consumer,
+            include_headers: include_headers.is_some() || !include_header.is_empty(),
             worker_id,
             worker_count,
             last_offsets: HashMap::new(),
@@ -204,7 +238,7 @@ impl SourceReader for KafkaSourceReader {
         let partition_info = self.partition_info.lock().unwrap().take();
         if let Some(partitions) = partition_info {
             for pid in partitions {
-                self.add_partition(PartitionId::Kafka(pid));
+                self.add_partition(pid);
             }
         }
         let mut next_message = NextMessage::Pending;
@@ -223,7 +257,9 @@ impl SourceReader for KafkaSourceReader {
                     self.source_name, self.topic_name, e
                 ),
                 Ok(message) => {
-                    let source_message = SourceMessage::from(&message);
+                    let mut source_message = SourceMessage::from(&message);
+                    source_message.partition =
+                        self.partition_id(message.topic(), message.partition());
                     next_message = self.handle_message(source_message);
                 }
             }
@@ -254,6 +290,26 @@ impl SourceReader for KafkaSourceReader {
 }
 
 impl KafkaSourceReader {
+    /// Returns the ID of the given partition of the given topic.
+    ///
+    /// Only sources with a topic pattern need to tell apart the partitions of
+    /// different topics.
+    fn partition_id(&self, topic: &str, partition: i32) -> PartitionId {
+        match self.topic_pattern {
+            None => PartitionId::Kafka(partition),
+            Some(_) => PartitionId::KafkaTopic(topic.to_owned(), partition),
+        }
+    }
+
+    /// Returns the topic and partition number identified by `pid`.
+    fn topic_partition(&self, pid: &PartitionId) -> (String, i32) {
+        match pid {
+            PartitionId::Kafka(partition) => (self.topic_name.clone(), *partition),
+            PartitionId::KafkaTopic(topic, partition) => (topic.clone(), *partition),
+            _ => unreachable!(),
+        }
+    }
+
     /// Ensures that a partition queue for `pid` exists.
     /// In Kafka, partitions are assigned contiguously. This function consequently
     /// creates partition queues for every p <= pid
@@ -266,10 +322,6 @@ impl KafkaSourceReader {
         ) {
             return;
         }
-        let pid = match pid {
-            PartitionId::Kafka(p) => p,
-            _ => unreachable!(),
-        };
         if self.last_offsets.contains_key(&pid) {
             return;
         }
@@ -279,7 +331,7 @@ impl KafkaSourceReader {
             None => 0,
         };
 
-        self.create_partition_queue(pid, Offset::Offset(start_offset));
+        self.create_partition_queue(pid.clone(), Offset::Offset(start_offset));
 
         // Indicate a last offset of -1 if we have not been instructed to have a specific start
         // offset for this topic.
@@ -295,25 +347,27 @@ impl KafkaSourceReader {
         self.partition_consumers.len().try_into().unwrap()
     }
 
-    /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: i32, initial_offset: Offset) {
+    /// Creates a new partition queue for `pid`.
+    fn create_partition_queue(&mut self, pid: PartitionId, initial_offset: Offset) {
+        let (topic, partition_id) = self.topic_partition(&pid);
         info!(
             "Activating Kafka queue for {} [{}] (source {}) on worker {}",
-            self.topic_name, partition_id, self.id, self.worker_id
+            topic, partition_id, self.id, self.worker_id
         );
 
-        // Collect old partition assignments
+        // Collect old partition assignments. The consumer is only ever assigned
+        // partitions of this source's topics.
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, initial_offset)
+            .add_partition_offset(&topic, partition_id, initial_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -325,7 +379,7 @@ impl KafkaSourceReader {
         for pc in &mut self.partition_consumers {
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(&pc.topic, pc.partition)
                 .expect("partition known to be valid");
             pc.partition_queue.set_nonempty_callback({
                 let context = Arc::clone(&context);
@@ -335,17 +389,18 @@ impl KafkaSourceReader {
 
         let mut partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(&topic, partition_id)
             .expect("partition known to be valid");
         partition_queue.set_nonempty_callback(move || context.activate());
-        self.partition_consumers
-            .push_front(PartitionConsumer::new(partition_id, partition_queue));
+        self.partition_consumers.push_front(PartitionConsumer::new(
+            pid,
+            topic,
+            partition_id,
+            partition_queue,
+            self.include_headers,
+        ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().elements().len(),
             self.partition_consumers.len()
         );
     }
@@ -353,22 +408,21 @@ impl KafkaSourceReader {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: i32, next_offset: i64) {
+    fn fast_forward_consumer(&self, pid: &PartitionId, next_offset: i64) {
+        let (topic, partition) = self.topic_partition(pid);
         let res = self.consumer.seek(
-            &self.topic_name,
-            pid,
+            &topic,
+            partition,
             Offset::Offset(next_offset),
             Duration::from_secs(1),
         );
         match res {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
-                let position = res
-                    .get(&(self.topic_name.clone(), pid))
-                    .and_then(|p| match p {
-                        Offset::Offset(o) => Some(o),
-                        _ => None,
-                    });
+                let position = res.get(&(topic, partition)).and_then(|p| match p {
+                    Offset::Offset(o) => Some(o),
+                    _ => None,
+                });
                 if let Some(position) = position {
                     if *position != next_offset {
                         warn!("Did not fast-forward consumer on partition PID: {} to the correct Kafka offset. Currently at offset: {} Expected offset: {}",
@@ -415,7 +469,7 @@ impl KafkaSourceReader {
                 let pid = partition_queue.pid();
                 let last_offset = self
                     .last_offsets
-                    .get(&pid)
+                    .get(pid)
                     .expect("partition known to be installed");
 
                 error!(
@@ -442,10 +496,7 @@ impl KafkaSourceReader {
         &mut self,
         message: SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>>,
     ) -> NextMessage<Option<Vec<u8>>, Option<Vec<u8>>> {
-        let partition = match message.partition {
-            PartitionId::Kafka(pid) => pid,
-            _ => unreachable!(),
-        };
+        let partition = message.partition.clone();
 
         // Convert the received offset back from a 1-indexed MzOffset to the correct offset.
         let offset = message.offset.offset - 1;
@@ -480,7 +531,7 @@ impl KafkaSourceReader {
                 last_offset + 1,
             );
             // Seek to the *next* 0 indexed offset that we have not yet processed
-            self.fast_forward_consumer(partition, last_offset + 1);
+            self.fast_forward_consumer(&partition, last_offset + 1);
             // We explicitly should not consume the message as we have already processed it
             // However, we make sure to activate the source to make sure that we get a chance
             // to read from this consumer again (even if no new data arrives)
@@ -589,8 +640,10 @@ impl<'a> From<&BorrowedMessage<'a>> for SourceMessage<Option<Vec<u8>>, Option<Ve
             partition: PartitionId::Kafka(msg.partition()),
             offset: kafka_offset.into(),
             upstream_time_millis: msg.timestamp().to_millis(),
+            upstream_position: None,
             key: msg.key().map(|k| k.to_vec()),
             value: msg.payload().map(|p| p.to_vec()),
+            headers: None,
         }
     }
 }
@@ -598,17 +651,32 @@ impl<'a> From<&BorrowedMessage<'a>> for SourceMessage<Option<Vec<u8>>, Option<Ve
 /// Wrapper around a partition containing the underlying consumer
 struct PartitionConsumer {
     /// the partition id with which this consumer is associated
-    pid: i32,
+    pid: PartitionId,
+    /// the topic of the partition
+    topic: String,
+    /// the partition number within the topic
+    partition: i32,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<GlueConsumerContext>,
+    /// Whether to read the headers of each message
+    include_headers: bool,
 }
 
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
-    fn new(pid: i32, partition_queue: PartitionQueue<GlueConsumerContext>) -> Self {
+    fn new(
+        pid: PartitionId,
+        topic: String,
+        partition: i32,
+        partition_queue: PartitionQueue<GlueConsumerContext>,
+        include_headers: bool,
+    ) -> Self {
         PartitionConsumer {
             pid,
+            topic,
+            partition,
             partition_queue,
+            include_headers,
         }
     }
 
@@ -618,8 +686,21 @@ impl PartitionConsumer {
     ) -> Result<Option<SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>>>, KafkaError> {
         match self.partition_queue.poll(Duration::from_millis(0)) {
             Some(Ok(msg)) => {
-                let result = SourceMessage::from(&msg);
-                assert_eq!(result.partition, PartitionId::Kafka(self.pid));
+                assert_eq!(
+                    (msg.topic(), msg.partition()),
+                    (&*self.topic, self.partition)
+                );
+                let mut result = SourceMessage::from(&msg);
+                result.partition = self.pid.clone();
+                if self.include_headers {
+                    let headers = msg.headers().map_or_else(Vec::new, |headers| {
+                        (0..headers.count())
+                            .filter_map(|i| headers.get(i))
+                            .map(|(key, value)| (key.to_string(), Some(value.to_vec())))
+                            .collect()
+                    });
+                    result.headers = Some(headers);
+                }
                 Ok(Some(result))
             }
             Some(Err(err)) => Err(err),
@@ -628,8 +709,8 @@ impl PartitionConsumer {
     }
 
     /// Return the partition id for this PartitionConsumer
-    fn pid(&self) -> i32 {
-        self.pid
+    fn pid(&self) -> &PartitionId {
+        &self.pid
     }
 }
 
@@ -678,12 +759,35 @@ fn get_kafka_partitions(
     consumer: &BaseConsumer<GlueConsumerContext>,
     topic: &str,
     timeout: Duration,
-) -> Result<Vec<i32>, anyhow::Error> {
+) -> Result<Vec<PartitionId>, anyhow::Error> {
     let metadata = consumer.fetch_metadata(Some(topic), timeout)?;
     Ok(metadata.topics()[0]
         .partitions()
         .iter()
-        .map(|x| x.id())
+        .map(|x| PartitionId::Kafka(x.id()))
+        .collect())
+}
+
+/// Return the list of partition ids associated with every topic whose name matches `pattern`.
+///
+/// Like Kafka's own pattern subscriptions, this skips internal topics such as
+/// `__consumer_offsets`.
+fn get_kafka_topic_partitions(
+    consumer: &BaseConsumer<GlueConsumerContext>,
+    pattern: &Regex,
+    timeout: Duration,
+) -> Result<Vec<PartitionId>, anyhow::Error> {
+    let metadata = consumer.fetch_metadata(None, timeout)?;
+    Ok(metadata
+        .topics()
+        .iter()
+        .filter(|topic| !topic.name().starts_with("__") && pattern.is_match(topic.name()))
+        .flat_map(|topic| {
+            topic
+                .partitions()
+                .iter()
+                .map(move |x| PartitionId::KafkaTopic(topic.name().to_owned(), x.id()))
+        })
         .collect())
 }
 
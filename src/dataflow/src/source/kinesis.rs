@@ -20,7 +20,7 @@ use futures::executor::block_on;
 use mz_dataflow_types::sources::AwsExternalId;
 use prometheus::core::AtomicI64;
 use timely::scheduling::SyncActivator;
-use tracing::error;
+use tracing::{error, warn};
 
 use mz_aws_util::kinesis;
 use mz_dataflow_types::sources::{
@@ -47,7 +47,7 @@ pub struct KinesisSourceReader {
     /// The name of the stream
     stream_name: String,
     /// The set of active shards
-    shard_set: HashMap<String, ShardMetrics>,
+    shard_set: HashMap<String, ShardState>,
     /// A queue representing the next shard to read from. This is necessary
     /// to ensure that all shards are read from uniformly
     shard_queue: VecDeque<(String, Option<String>)>,
@@ -56,12 +56,35 @@ pub struct KinesisSourceReader {
     last_checked_shards: Instant,
     /// Storage for messages that have not yet been timestamped
     buffered_messages: VecDeque<SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>>>,
-    /// Count of processed message
-    processed_message_count: i64,
     /// Metrics from which per-shard metrics get created.
     base_metrics: KinesisMetrics,
 }
 
+/// Per-shard reading state.
+struct ShardState {
+    metrics: ShardMetrics,
+    /// The offset assigned to the last record read from this shard. Kinesis
+    /// sequence numbers do not fit into an `MzOffset`, so records are instead
+    /// numbered consecutively per shard, starting at 1.
+    last_offset: i64,
+    /// The sequence number of the record at `last_offset`, if known.
+    last_sequence_number: Option<String>,
+}
+
+impl ShardState {
+    fn new(metrics: ShardMetrics, resume_position: Option<(i64, String)>) -> Self {
+        let (last_offset, last_sequence_number) = match resume_position {
+            Some((offset, sequence_number)) => (offset, Some(sequence_number)),
+            None => (0, None),
+        };
+        Self {
+            metrics,
+            last_offset,
+            last_sequence_number,
+        }
+    }
+}
+
 struct ShardMetrics {
     millis_behind_latest: DeleteOnDropGauge<'static, AtomicI64, Vec<String>>,
 }
@@ -87,9 +110,14 @@ impl KinesisSourceReader {
             .difference(&known_shards)
             .map(|shard_id| shard_id.to_owned());
         for shard_id in new_shards {
+            // Shards that appear after the source was created have never been read
+            // from, so they are always read from the beginning.
             self.shard_set.insert(
                 shard_id.to_string(),
-                ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id),
+                ShardState::new(
+                    ShardMetrics::new(&self.base_metrics, &self.stream_name, &shard_id),
+                    None,
+                ),
             );
             self.shard_queue.push_back((
                 shard_id.to_string(),
@@ -125,7 +153,8 @@ impl SourceReader for KinesisSourceReader {
         _consumer_activator: SyncActivator,
         connector: ExternalSourceConnector,
         aws_external_id: AwsExternalId,
-        _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        upstream_positions: Vec<(PartitionId, MzOffset, String)>,
         _encoding: SourceDataEncoding,
         _: Option<Logger>,
         base_metrics: SourceBaseMetrics,
@@ -135,7 +164,13 @@ impl SourceReader for KinesisSourceReader {
             _ => unreachable!(),
         };
 
-        let state = block_on(create_state(&base_metrics.kinesis, kc, aws_external_id));
+        let resume_positions = resume_positions(restored_offsets, upstream_positions);
+        let state = block_on(create_state(
+            &base_metrics.kinesis,
+            kc,
+            aws_external_id,
+            resume_positions,
+        ));
         match state {
             Ok((kinesis_client, stream_name, shard_set, shard_queue)) => Ok(KinesisSourceReader {
                 kinesis_client,
@@ -144,7 +179,6 @@ impl SourceReader for KinesisSourceReader {
                 buffered_messages: VecDeque::new(),
                 shard_set,
                 stream_name,
-                processed_message_count: 0,
                 base_metrics: base_metrics.kinesis,
             }),
             Err(e) => Err(anyhow!("{}", e)),
@@ -175,6 +209,7 @@ impl SourceReader for KinesisSourceReader {
                                 self.shard_set
                                     .get(&shard_id)
                                     .unwrap()
+                                    .metrics
                                     .millis_behind_latest
                                     .set(millis);
                             }
@@ -190,9 +225,17 @@ impl SourceReader for KinesisSourceReader {
                         Err(SdkError::ServiceError { err, .. })
                             if err.is_expired_iterator_exception() =>
                         {
-                            // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
-                            error!("{}", err);
-                            return Err(anyhow::Error::msg(err));
+                            // Resume right after the last record we read from this shard.
+                            warn!("{}; acquiring a new shard iterator", err);
+                            let shard = self.shard_set.get(&shard_id).unwrap();
+                            let iterator = block_on(get_shard_iterator(
+                                &self.kinesis_client,
+                                &self.stream_name,
+                                &shard_id,
+                                shard.last_sequence_number.as_deref(),
+                            ))?;
+                            self.shard_queue.push_back((shard_id, iterator));
+                            return Ok(NextMessage::TransientDelay);
                         }
                         Err(SdkError::ServiceError { err, .. })
                             if err.is_provisioned_throughput_exceeded_exception() =>
@@ -219,21 +262,24 @@ impl SourceReader for KinesisSourceReader {
                         }
                     };
 
+                    let shard = self.shard_set.get_mut(&shard_id).unwrap();
                     for record in get_records_output.records.unwrap_or_default() {
                         let data = record
                             .data
                             .map(|blob| blob.into_inner())
                             .unwrap_or_else(Vec::new);
-                        self.processed_message_count += 1;
+                        shard.last_offset += 1;
+                        shard.last_sequence_number = record.sequence_number;
                         let source_message = SourceMessage {
-                            partition: PartitionId::None,
+                            partition: PartitionId::Kinesis(shard_id.clone()),
                             offset: MzOffset {
-                                //TODO: should MzOffset be modified to be a string?
-                                offset: self.processed_message_count,
+                                offset: shard.last_offset,
                             },
                             upstream_time_millis: None,
                             key: None,
                             value: Some(data),
+                            upstream_position: shard.last_sequence_number.clone(),
+                            headers: None,
                         };
                         self.buffered_messages.push_back(source_message);
                     }
@@ -248,17 +294,90 @@ impl SourceReader for KinesisSourceReader {
     }
 }
 
+/// Determines, for each shard, the offset and sequence number of the last record
+/// that was bound to a timestamp in a previous incarnation of the source.
+///
+/// Returns a map from shard ID to `(offset, sequence number)`. Reading a shard
+/// resumes right after that sequence number. Records before the restored offset
+/// that are read again are discarded by the source machinery.
+fn resume_positions(
+    restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+    upstream_positions: Vec<(PartitionId, MzOffset, String)>,
+) -> HashMap<String, (i64, String)> {
+    let restored_offsets: HashMap<_, _> = restored_offsets
+        .into_iter()
+        .filter_map(|(pid, offset)| match (pid, offset) {
+            (PartitionId::Kinesis(shard_id), Some(offset)) => Some((shard_id, offset)),
+            _ => None,
+        })
+        .collect();
+
+    let mut resume_positions: HashMap<String, (i64, String)> = HashMap::new();
+    for (pid, offset, sequence_number) in upstream_positions {
+        let shard_id = match pid {
+            PartitionId::Kinesis(shard_id) => shard_id,
+            _ => continue,
+        };
+        // Only positions at or before the restored offset are safe to resume
+        // from. The position recorded at offset `o` belongs to the record at
+        // offset `o - 1`.
+        match restored_offsets.get(&shard_id) {
+            Some(restored) if offset <= *restored => (),
+            _ => continue,
+        }
+        let last_offset = offset.offset - 1;
+        match resume_positions.get(&shard_id) {
+            Some((existing, _)) if *existing >= last_offset => (),
+            _ => {
+                resume_positions.insert(shard_id, (last_offset, sequence_number));
+            }
+        }
+    }
+
+    for shard_id in restored_offsets.keys() {
+        if !resume_positions.contains_key(shard_id) {
+            warn!(
+                "no sequence number recorded for restored Kinesis shard {}; \
+                 reading it from the beginning",
+                shard_id
+            );
+        }
+    }
+
+    resume_positions
+}
+
+/// Constructs an iterator over a shard that starts right after
+/// `sequence_number`, or at the oldest available record if no sequence number
+/// is given.
+async fn get_shard_iterator(
+    client: &KinesisClient,
+    stream_name: &str,
+    shard_id: &str,
+    sequence_number: Option<&str>,
+) -> Result<Option<String>, anyhow::Error> {
+    let iterator = match sequence_number {
+        Some(sequence_number) => {
+            kinesis::get_shard_iterator_after(client, stream_name, shard_id, sequence_number)
+                .await?
+        }
+        None => kinesis::get_shard_iterator(client, stream_name, shard_id).await?,
+    };
+    Ok(iterator)
+}
+
 /// Creates the necessary data-structures for shard management
 // todo: Better error handling here! Not all errors mean we're done/can't progress.
 async fn create_state(
     base_metrics: &KinesisMetrics,
     c: KinesisSourceConnector,
     aws_external_id: AwsExternalId,
+    mut resume_positions: HashMap<String, (i64, String)>,
 ) -> Result<
     (
         KinesisClient,
         String,
-        HashMap<String, ShardMetrics>,
+        HashMap<String, ShardState>,
         VecDeque<(String, Option<String>)>,
     ),
     anyhow::Error,
@@ -270,13 +389,23 @@ async fn create_state(
     let mut shard_queue: VecDeque<(String, Option<String>)> = VecDeque::new();
     let mut shard_map = HashMap::new();
     for shard_id in shard_set {
+        let resume_position = resume_positions.remove(&shard_id);
         shard_queue.push_back((
             shard_id.clone(),
-            kinesis::get_shard_iterator(&kinesis_client, &c.stream_name, &shard_id).await?,
+            get_shard_iterator(
+                &kinesis_client,
+                &c.stream_name,
+                &shard_id,
+                resume_position.as_ref().map(|(_, seq)| seq.as_str()),
+            )
+            .await?,
         ));
         shard_map.insert(
             shard_id.clone(),
-            ShardMetrics::new(base_metrics, &c.stream_name, &shard_id),
+            ShardState::new(
+                ShardMetrics::new(base_metrics, &c.stream_name, &shard_id),
+                resume_position,
+            ),
         );
     }
 
@@ -287,3 +416,49 @@ async fn create_state(
         shard_queue,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resume_positions_use_latest_bound_record() {
+        let shard = |id: &str| PartitionId::Kinesis(id.into());
+        let restored_offsets = vec![
+            (shard("shardId-000000000000"), Some(MzOffset { offset: 6 })),
+            (shard("shardId-000000000001"), Some(MzOffset { offset: 3 })),
+            (shard("shardId-000000000002"), None),
+        ];
+        let upstream_positions = vec![
+            (
+                shard("shardId-000000000000"),
+                MzOffset { offset: 4 },
+                "40".into(),
+            ),
+            (
+                shard("shardId-000000000000"),
+                MzOffset { offset: 6 },
+                "60".into(),
+            ),
+            // Positions beyond the restored offset must not be resumed from.
+            (
+                shard("shardId-000000000001"),
+                MzOffset { offset: 5 },
+                "50".into(),
+            ),
+            (
+                shard("shardId-000000000002"),
+                MzOffset { offset: 2 },
+                "20".into(),
+            ),
+        ];
+
+        let positions = resume_positions(restored_offsets, upstream_positions);
+        assert_eq!(
+            positions.get("shardId-000000000000"),
+            Some(&(5, "60".to_string()))
+        );
+        assert_eq!(positions.get("shardId-000000000001"), None);
+        assert_eq!(positions.get("shardId-000000000002"), None);
+    }
+}
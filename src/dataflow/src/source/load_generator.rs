@@ -0,0 +1,292 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Sources that generate deterministic data on their own.
+//!
+//! Each generator emits one batch of changes per offset. The changes at an
+//! offset depend only on the offsets that precede it, so a generator that is
+//! restarted replays exactly the same changes.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use mz_dataflow_types::sources::{LoadGenerator, LoadGeneratorSourceConnector};
+use mz_dataflow_types::SourceErrorDetails;
+use mz_expr::SourceInstanceId;
+use mz_repr::adt::numeric::Numeric;
+use mz_repr::{Datum, Diff, Row};
+
+use crate::source::{SimpleSource, SourceError, Timestamper};
+
+/// Information required to run a load generator.
+pub struct LoadGeneratorSourceReader {
+    source_id: SourceInstanceId,
+    connector: LoadGeneratorSourceConnector,
+}
+
+impl LoadGeneratorSourceReader {
+    /// Constructs a new instance
+    pub fn new(source_id: SourceInstanceId, connector: LoadGeneratorSourceConnector) -> Self {
+        Self {
+            source_id,
+            connector,
+        }
+    }
+}
+
+#[async_trait]
+impl SimpleSource for LoadGeneratorSourceReader {
+    async fn start(self, timestamper: &Timestamper) -> Result<(), SourceError> {
+        let mut generator: Box<dyn Generator> = match self.connector.generator {
+            LoadGenerator::Counter { max_cardinality } => Box::new(Counter {
+                max_cardinality,
+                counter: 0,
+            }),
+            LoadGenerator::Auction => Box::new(Auction {
+                rng: StdRng::seed_from_u64(0),
+                offset: 0,
+                open: BTreeMap::new(),
+            }),
+            LoadGenerator::Tpch { count_orders } => Box::new(Tpch {
+                count_orders,
+                loaded: 0,
+                offset: 0,
+            }),
+        };
+
+        let mut interval = tokio::time::interval(self.connector.tick_interval);
+        loop {
+            if !generator.in_snapshot() {
+                interval.tick().await;
+            }
+            let batch = match generator.next_batch() {
+                Some(batch) => batch,
+                None => return Ok(()),
+            };
+            let tx = timestamper.start_tx().await;
+            for (row, diff) in batch {
+                let res = if diff > 0 {
+                    tx.insert(row).await
+                } else {
+                    tx.delete(row).await
+                };
+                res.map_err(|e| SourceError {
+                    source_id: self.source_id,
+                    error: SourceErrorDetails::Other(e.to_string()),
+                })?;
+            }
+        }
+    }
+}
+
+/// A deterministic source of changes.
+trait Generator: Send {
+    /// Returns the changes at the next offset, or `None` if the generator is
+    /// exhausted.
+    fn next_batch(&mut self) -> Option<Vec<(Row, Diff)>>;
+
+    /// Reports whether the generator is still producing its initial snapshot,
+    /// whose batches are emitted without waiting for a tick.
+    fn in_snapshot(&self) -> bool {
+        false
+    }
+}
+
+/// Inserts the next integer at every tick.
+struct Counter {
+    max_cardinality: Option<u64>,
+    counter: i64,
+}
+
+impl Generator for Counter {
+    fn next_batch(&mut self) -> Option<Vec<(Row, Diff)>> {
+        self.counter += 1;
+        let mut batch = vec![(Row::pack_slice(&[Datum::Int64(self.counter)]), 1)];
+        if let Some(max_cardinality) = self.max_cardinality {
+            let oldest = self.counter - max_cardinality as i64;
+            if oldest > 0 {
+                batch.push((Row::pack_slice(&[Datum::Int64(oldest)]), -1));
+            }
+        }
+        Some(batch)
+    }
+}
+
+/// The number of ticks for which an auction accepts bids.
+const AUCTION_TICKS: i64 = 10;
+
+const AUCTION_ITEMS: &[&str] = &[
+    "Signed Memorabilia",
+    "City Bar Crawl",
+    "Best Pizza in Town",
+    "Gift Basket",
+    "Custom Art",
+];
+
+/// Opens an auction at every tick, places a bid on a random open auction, and
+/// closes the auction that was opened `AUCTION_TICKS` ticks ago.
+struct Auction {
+    rng: StdRng,
+    offset: i64,
+    open: BTreeMap<i64, AuctionState>,
+}
+
+struct AuctionState {
+    item: &'static str,
+    seller: i64,
+    /// The highest bid and its bidder.
+    bid: Option<(i32, i64)>,
+}
+
+impl AuctionState {
+    fn pack(&self, id: i64) -> Row {
+        let (amount, bidder) = match self.bid {
+            Some((amount, bidder)) => (Datum::Int32(amount), Datum::Int64(bidder)),
+            None => (Datum::Null, Datum::Null),
+        };
+        Row::pack_slice(&[
+            Datum::Int64(id),
+            Datum::String(self.item),
+            Datum::Int64(self.seller),
+            amount,
+            bidder,
+        ])
+    }
+}
+
+impl Generator for Auction {
+    fn next_batch(&mut self) -> Option<Vec<(Row, Diff)>> {
+        self.offset += 1;
+        let mut batch = vec![];
+
+        let closed = self.offset - AUCTION_TICKS;
+        if let Some(state) = self.open.remove(&closed) {
+            batch.push((state.pack(closed), -1));
+        }
+
+        if !self.open.is_empty() {
+            let idx = self.rng.gen_range(0..self.open.len());
+            let (id, state) = self.open.iter_mut().nth(idx).unwrap();
+            batch.push((state.pack(*id), -1));
+            let amount = state.bid.map_or(0, |(amount, _)| amount) + self.rng.gen_range(1..=100);
+            let bidder = self.rng.gen_range(1..=1000);
+            state.bid = Some((amount, bidder));
+            batch.push((state.pack(*id), 1));
+        }
+
+        let state = AuctionState {
+            item: AUCTION_ITEMS[self.rng.gen_range(0..AUCTION_ITEMS.len())],
+            seller: self.rng.gen_range(1..=1000),
+            bid: None,
+        };
+        batch.push((state.pack(self.offset), 1));
+        self.open.insert(self.offset, state);
+
+        Some(batch)
+    }
+}
+
+/// The number of orders inserted per batch of the TPC-H snapshot.
+const TPCH_SNAPSHOT_BATCH: u64 = 10_000;
+
+const TPCH_PRIORITIES: &[&str] = &["1-URGENT", "2-HIGH", "3-MEDIUM", "4-NOT SPECIFIED", "5-LOW"];
+
+const TPCH_WORDS: &[&str] = &[
+    "furiously",
+    "quickly",
+    "carefully",
+    "blithely",
+    "final",
+    "regular",
+    "pending",
+    "ironic",
+    "express",
+    "deposits",
+    "requests",
+    "accounts",
+    "packages",
+    "theodolites",
+];
+
+/// Loads `count_orders` TPC-H orders, and then, at every tick, inserts a new
+/// order, fulfills an open order, and deletes the oldest order.
+///
+/// The first half of the initial orders are fulfilled (status `F`) and the
+/// rest are open (status `O`). The order fulfilled at tick `t` is the one with
+/// key `t + count_orders / 2`, and the order deleted at tick `t` is the one
+/// with key `t`, which by then is always fulfilled.
+struct Tpch {
+    count_orders: u64,
+    loaded: u64,
+    offset: u64,
+}
+
+impl Tpch {
+    /// Generates the order with the given key. All columns but the status are
+    /// a function of the key alone.
+    fn order(&self, key: u64, status: &str) -> Row {
+        let mut rng = StdRng::seed_from_u64(key);
+        let count_customers = std::cmp::max(1, self.count_orders / 10);
+        let count_clerks = std::cmp::max(1, self.count_orders / 1500);
+        let mut price = Numeric::from(rng.gen_range(90_000..=50_000_000i64));
+        price.set_exponent(-2);
+        let date = NaiveDate::from_ymd(1992, 1, 1) + chrono::Duration::days(rng.gen_range(0..2406));
+        let priority = TPCH_PRIORITIES[rng.gen_range(0..TPCH_PRIORITIES.len())];
+        let clerk = format!("Clerk#{:09}", rng.gen_range(1..=count_clerks));
+        let comment = (0..3)
+            .map(|_| TPCH_WORDS[rng.gen_range(0..TPCH_WORDS.len())])
+            .collect::<Vec<_>>()
+            .join(" ");
+        Row::pack_slice(&[
+            Datum::Int64(key as i64),
+            Datum::Int64(rng.gen_range(1..=count_customers) as i64),
+            Datum::String(status),
+            Datum::from(price),
+            Datum::Date(date),
+            Datum::String(priority),
+            Datum::String(&clerk),
+            Datum::Int32(0),
+            Datum::String(&comment),
+        ])
+    }
+}
+
+impl Generator for Tpch {
+    fn next_batch(&mut self) -> Option<Vec<(Row, Diff)>> {
+        let half = self.count_orders / 2;
+        if self.in_snapshot() {
+            let start = self.loaded + 1;
+            self.loaded = std::cmp::min(self.loaded + TPCH_SNAPSHOT_BATCH, self.count_orders);
+            let batch = (start..=self.loaded)
+                .map(|key| {
+                    let status = if key <= half { "F" } else { "O" };
+                    (self.order(key, status), 1)
+                })
+                .collect();
+            return Some(batch);
+        }
+
+        self.offset += 1;
+        let fulfilled = self.offset + half;
+        Some(vec![
+            (self.order(self.count_orders + self.offset, "O"), 1),
+            (self.order(fulfilled, "O"), -1),
+            (self.order(fulfilled, "F"), 1),
+            (self.order(self.offset, "F"), -1),
+        ])
+    }
+
+    fn in_snapshot(&self) -> bool {
+        self.loaded < self.count_orders
+    }
+}
@@ -64,10 +64,12 @@ use crate::source::timestamp::TimestampBindingRc;
 use crate::source::timestamp::TimestampBindingUpdater;
 use crate::source::timestamp::{AssignedTimestamp, SourceTimestamp};
 
+mod compression;
 mod file;
 mod gen;
 mod kafka;
 mod kinesis;
+mod load_generator;
 pub(super) mod metrics;
 mod postgres;
 mod pubnub;
@@ -82,6 +84,7 @@ pub use file::FileReadStyle;
 pub use file::FileSourceReader;
 pub use kafka::KafkaSourceReader;
 pub use kinesis::KinesisSourceReader;
+pub use load_generator::LoadGeneratorSourceReader;
 pub use postgres::PostgresSourceReader;
 pub use pubnub::PubNubSourceReader;
 pub use s3::S3SourceReader;
@@ -144,6 +147,9 @@ where
     pub upstream_time_millis: Option<i64>,
     /// The partition of this message, present iff the partition comes from Kafka
     pub partition: PartitionId,
+    /// The headers that accompanied the record upstream, if the source has the
+    /// concept of headers
+    pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
 }
 
 /// The output of the decoding operator
@@ -163,6 +169,11 @@ pub struct DecodeResult {
     // TODO(bwm): This should probably be statically different for different streams, or we should
     // propagate whether metadata is requested into the decoder
     pub metadata: Row,
+    /// The raw key of the record, retained only if the record failed to decode
+    pub raw_key: Option<Vec<u8>>,
+    /// The raw value of the record, retained only if the record failed to
+    /// decode and the source delimits its records itself
+    pub raw_value: Option<Vec<u8>>,
 }
 
 /// Kafka-specific information about the event
@@ -188,6 +199,7 @@ where
         position: i64,
         upstream_time_millis: Option<i64>,
         partition: PartitionId,
+        headers: Option<Vec<(String, Option<Vec<u8>>)>>,
     ) -> SourceOutput<K, V> {
         SourceOutput {
             key,
@@ -195,6 +207,7 @@ where
             position,
             upstream_time_millis,
             partition,
+            headers,
         }
     }
 }
@@ -306,6 +319,7 @@ impl MaybeLength for MessagePayload {
     fn len(&self) -> Option<usize> {
         match self {
             MessagePayload::Data(data) => Some(data.len()),
+            MessagePayload::Row(row) => Some(row.data().len()),
             MessagePayload::EOF => None,
         }
     }
@@ -340,6 +354,11 @@ pub(crate) trait SourceReader {
     /// This function returns the source reader and optionally, any "partition" it's
     /// already reading. In practice, the partition is only non-None for static sources
     /// that either don't truly have partitions or have a fixed number of partitions.
+    ///
+    /// `upstream_positions` lists the source-native positions recorded alongside the
+    /// known timestamp bindings, as `(partition, offset, position)` triples, where
+    /// `position` is the upstream position of the message at `offset - 1`. It is only
+    /// populated for sources that emit [`SourceMessage::upstream_position`].
     fn new(
         source_name: String,
         source_id: SourceInstanceId,
@@ -349,6 +368,7 @@ pub(crate) trait SourceReader {
         connector: ExternalSourceConnector,
         aws_external_id: AwsExternalId,
         restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
+        upstream_positions: Vec<(PartitionId, MzOffset, String)>,
         encoding: SourceDataEncoding,
         logger: Option<Logger>,
         metrics: crate::source::metrics::SourceBaseMetrics,
@@ -381,10 +401,16 @@ pub struct SourceMessage<Key, Value> {
     ///
     /// Milliseconds since the unix epoch
     pub upstream_time_millis: Option<i64>,
+    /// The source-native position of the message, for sources whose positions cannot be
+    /// represented as an `MzOffset` (e.g. Kinesis sequence numbers). It is durably recorded
+    /// alongside timestamp bindings so that the source can resume right after the message.
+    pub upstream_position: Option<String>,
     /// The message key
     pub key: Key,
     /// The message value
     pub value: Value,
+    /// The message headers, for sources that carry them and include them
+    pub headers: Option<Vec<(String, Option<Vec<u8>>)>>,
 }
 
 impl fmt::Debug for SourceMessage<(), MessagePayload> {
@@ -393,6 +419,7 @@ impl fmt::Debug for SourceMessage<(), MessagePayload> {
             .field("partition", &self.partition)
             .field("offset", &self.offset)
             .field("upstream_time_millis", &self.upstream_time_millis)
+            .field("upstream_position", &self.upstream_position)
             .finish()
     }
 }
@@ -403,6 +430,7 @@ impl fmt::Debug for SourceMessage<Option<Vec<u8>>, Option<Vec<u8>>> {
             .field("partition", &self.partition)
             .field("offset", &self.offset)
             .field("upstream_time_millis", &self.upstream_time_millis)
+            .field("upstream_position", &self.upstream_position)
             .field("key[present]", &self.key.is_some())
             .field("value[present]", &self.value.is_some())
             .finish()
@@ -418,7 +446,9 @@ pub fn responsible_for(
     pid: &PartitionId,
 ) -> bool {
     match pid {
-        PartitionId::None => {
+        // Kinesis shards and globbed files are read by a single worker, so they must all be
+        // assigned to the same worker as `PartitionId::None`.
+        PartitionId::None | PartitionId::Kinesis(_) | PartitionId::File(_) => {
             // All workers are responsible for reading in Kafka sources. Other sources
             // support single-threaded ingestion only. Note that in all cases we want all
             // readers of the same source or same partition to reside on the same worker,
@@ -439,6 +469,13 @@ pub fn responsible_for(
             let hash = (source_id.hashed() >> 32) + *p as u64;
             (hash % worker_count as u64) == worker_id as u64
         }
+        PartitionId::KafkaTopic(topic, p) => {
+            // As above, but additionally offset by a hash of the topic name so that the
+            // partitions of different topics matched by the same pattern are not all
+            // assigned starting from the same worker.
+            let hash = (source_id.hashed() >> 32) + (topic.hashed() >> 32) + *p as u64;
+            (hash % worker_count as u64) == worker_id as u64
+        }
     }
 }
 
@@ -1020,13 +1057,14 @@ where
                     // bindings here.
                     let existing_binding = timestamp_histories
                         .get_bindings_in_range(lower.borrow(), upper.borrow()).into_iter()
-                        .filter(|(pid, _ts, offset)| *pid == source_ts.partition && *offset >= source_ts.offset).next();
+                        .filter(|(pid, _ts, offset, _position)| *pid == source_ts.partition && *offset >= source_ts.offset).next();
                     match existing_binding {
                         None => {
                             timestamp_histories.add_binding(
                                 source_ts.partition.clone(),
                                 assigned_ts.0,
                                 source_ts.offset,
+                                source_ts.upstream_position.clone(),
                             );
                         },
                         Some(existing_binding) => {
@@ -1083,6 +1121,10 @@ where
             .as_mut()
             .map(|ts| ts.partitions())
             .unwrap_or_default();
+        let upstream_positions = timestamp_histories
+            .as_mut()
+            .map(|ts| ts.upstream_positions())
+            .unwrap_or_default();
         let mut partition_cursors: HashMap<_, _> = restored_offsets
             .iter()
             .cloned()
@@ -1101,6 +1143,7 @@ where
                 source_connector.clone(),
                 aws_external_id.clone(),
                 restored_offsets,
+                upstream_positions,
                 encoding,
                 logger,
                 base_metrics.clone(),
@@ -1534,7 +1577,11 @@ fn handle_message<S: SourceReader>(
     let offset = message.offset;
 
     // Determine the timestamp to which we need to assign this message
-    let ts = timestamp_bindings.get_or_propose_binding(&partition, offset);
+    let ts = timestamp_bindings.get_or_propose_binding(
+        &partition,
+        offset,
+        message.upstream_position.as_deref(),
+    );
     // Note: empty and null payload/keys are currently
     // treated as the same thing.
     let key = message.key;
@@ -1556,6 +1603,7 @@ fn handle_message<S: SourceReader>(
         offset.offset,
         message.upstream_time_millis,
         message.partition,
+        message.headers,
     )));
 
     match metric_updates.entry(partition) {
@@ -31,14 +31,18 @@ use std::collections::{HashMap, HashSet};
 use std::convert::{From, TryInto};
 use std::default::Default;
 use std::fmt::Formatter;
+use std::io::{Read, Seek, SeekFrom};
 use std::ops::AddAssign;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 
-use async_compression::tokio::bufread::GzipDecoder;
+use async_compression::tokio::bufread::{BzDecoder, GzipDecoder, XzDecoder, ZstdDecoder};
 use aws_sdk_s3::error::{GetObjectError, ListObjectsV2Error};
 use aws_sdk_s3::types::SdkError;
 use aws_sdk_s3::Client as S3Client;
 use aws_sdk_sqs::model::{ChangeMessageVisibilityBatchRequestEntry, Message as SqsMessage};
 use aws_sdk_sqs::Client as SqsClient;
+use bytes::Bytes;
 use futures::{FutureExt, StreamExt, TryStreamExt};
 use globset::GlobMatcher;
 use timely::scheduling::SyncActivator;
@@ -48,16 +52,19 @@ use tokio::time::{self, Duration};
 use tokio_util::io::{ReaderStream, StreamReader};
 
 use mz_dataflow_types::sources::{
-    encoding::SourceDataEncoding, AwsConfig, AwsExternalId, Compression, ExternalSourceConnector,
-    MzOffset, S3KeySource,
+    encoding::{DataEncoding, ParquetEncoding, SourceDataEncoding},
+    AwsConfig, AwsExternalId, Compression, ExternalSourceConnector, MzOffset, S3KeySource,
 };
 use mz_expr::{PartitionId, SourceInstanceId};
+use mz_interchange::parquet::ParquetReader;
+use mz_ore::cast::CastFrom;
 use mz_ore::retry::{Retry, RetryReader};
 use mz_ore::task;
 use mz_repr::MessagePayload;
 use tracing::{debug, error, trace, warn};
 
 use crate::logging::materialized::Logger;
+use crate::source::compression;
 use crate::source::{NextMessage, SourceMessage, SourceReader};
 
 use self::metrics::{BucketMetrics, ScanBucketMetrics};
@@ -132,6 +139,7 @@ async fn download_objects_task(
     aws_external_id: AwsExternalId,
     activator: SyncActivator,
     compression: Compression,
+    parquet: Option<ParquetEncoding>,
     metrics: SourceBaseMetrics,
 ) {
     let config = aws_config.load(aws_external_id).await;
@@ -192,6 +200,7 @@ async fn download_objects_task(
                     &msg_ref.bucket,
                     &msg_ref.key,
                     compression,
+                    parquet.as_ref(),
                     sid,
                 )
                 .await;
@@ -653,8 +662,15 @@ async fn download_object(
     bucket: &str,
     key: &str,
     compression: Compression,
+    parquet: Option<&ParquetEncoding>,
     source_id: &str,
 ) -> Result<DownloadMetricUpdate, DownloadError> {
+    if let Some(parquet) = parquet {
+        let download_result =
+            read_parquet_object(source_id, client, bucket, key, parquet, tx).await;
+        return finish_download(tx, activator, bucket, key, source_id, download_result).await;
+    }
+
     let retry_reader: RetryReader<_, _, _> = RetryReader::new(|state, offset| async move {
         let range = if offset == 0 {
             None
@@ -684,13 +700,10 @@ async fn download_object(
         // If the Content-Encoding does not match the compression specified for this
         // source, emit a debug message and trust the user-specified compression
         if let Some(s) = obj.content_encoding.as_deref() {
-            match (s, compression) {
-                ("gzip", Compression::Gzip) => (),
-                ("identity", Compression::None) => (),
-                ("identity" | "gzip", _) => {
-                    debug!("object {} has mismatched Content-Encoding: {}", key, s)
-                }
-                _ => debug!("object {} has unrecognized Content-Encoding: {}", key, s),
+            match compression::content_encoding_matches(s, compression) {
+                Some(true) => (),
+                Some(false) => debug!("object {} has mismatched Content-Encoding: {}", key, s),
+                None => debug!("object {} has unrecognized Content-Encoding: {}", key, s),
             }
         }
 
@@ -701,25 +714,56 @@ async fn download_object(
 
     let mut reader = Box::pin(BufReader::new(retry_reader));
 
-    // Check for empty files by filling up the buffer of bufreader and checking if it got any bytes
-    match reader.fill_buf().await {
+    // Check for empty files by filling up the buffer of bufreader and checking if it got any bytes.
+    // The same bytes are used to detect the codec of the object for `COMPRESSION AUTO`.
+    let compression = match reader.fill_buf().await {
         Ok(buf) => {
             if buf.is_empty() {
                 trace!("source_id={} empty object {}/{}", source_id, bucket, key);
                 return Ok(Default::default());
             }
+            match compression {
+                Compression::Auto => compression::detect(key, buf),
+                compression => compression,
+            }
         }
         Err(err) => return Err(DownloadError::Failed { err }),
     };
 
-    let mut download_result = match compression {
+    let download_result = match compression {
         Compression::None => read_object_chunked(source_id, reader, tx).await,
         Compression::Gzip => {
             let decoder = GzipDecoder::new(reader);
             read_object_chunked(source_id, decoder, tx).await
         }
+        Compression::Zstd => {
+            let decoder = ZstdDecoder::new(reader);
+            read_object_chunked(source_id, decoder, tx).await
+        }
+        Compression::Bzip2 => {
+            let decoder = BzDecoder::new(reader);
+            read_object_chunked(source_id, decoder, tx).await
+        }
+        Compression::Xz => {
+            let decoder = XzDecoder::new(reader);
+            read_object_chunked(source_id, decoder, tx).await
+        }
+        Compression::Snappy => read_snappy_object(source_id, reader, tx).await,
+        Compression::Auto => unreachable!("AUTO compression resolved above"),
     };
 
+    finish_download(tx, activator, bucket, key, source_id, download_result).await
+}
+
+/// Marks the end of an object for dataflow, if it was downloaded successfully.
+async fn finish_download(
+    tx: &Sender<S3Result<InternalMessage>>,
+    activator: &SyncActivator,
+    bucket: &str,
+    key: &str,
+    source_id: &str,
+    mut download_result: Result<DownloadMetricUpdate, DownloadError>,
+) -> Result<DownloadMetricUpdate, DownloadError> {
     debug!(
         "source_id={} {}/{} download_result={:?}",
         source_id, bucket, key, download_result,
@@ -783,6 +827,295 @@ where
     });
 }
 
+/// Decodes an object in the Snappy framing format and sends it to dataflow
+/// one chunk at a time.
+///
+/// `async-compression` does not support Snappy, so the object is streamed to a
+/// blocking task that decodes it with [`snap::read::FrameDecoder`], which also
+/// verifies the checksum of each chunk.
+async fn read_snappy_object<R>(
+    source_id: &str,
+    reader: R,
+    tx: &Sender<Result<InternalMessage, S3Error>>,
+) -> Result<DownloadMetricUpdate, DownloadError>
+where
+    R: Unpin + AsyncRead,
+{
+    let (compressed_tx, compressed_rx) = tokio::sync::mpsc::channel(1);
+    let decode = task::spawn_blocking(|| format!("s3_read_snappy:{}", source_id), {
+        let source_id = source_id.to_string();
+        let tx = tx.clone();
+        move || {
+            let mut decoder = snap::read::FrameDecoder::new(ChannelReader {
+                rx: compressed_rx,
+                buf: Bytes::new(),
+            });
+            let (mut bytes_read, mut chunks) = (0, 0);
+            loop {
+                let mut chunk = vec![0; CHUNK_SIZE];
+                let n = match decoder.read(&mut chunk) {
+                    Ok(0) => break,
+                    Ok(n) => n,
+                    Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(err) => return Err(DownloadError::Failed { err }),
+                };
+                chunk.truncate(n);
+                bytes_read += n;
+                chunks += 1;
+                if tx
+                    .blocking_send(Ok(InternalMessage {
+                        record: MessagePayload::Data(chunk),
+                    }))
+                    .is_err()
+                {
+                    return Err(DownloadError::SendFailed);
+                }
+            }
+
+            trace!(
+                "source_id={} finished sending snappy object to dataflow chunks={} bytes={}",
+                source_id,
+                chunks,
+                bytes_read
+            );
+            Ok(DownloadMetricUpdate {
+                bytes: bytes_read.try_into().expect("usize <= u64"),
+                messages: chunks,
+            })
+        }
+    });
+
+    let mut stream = ReaderStream::with_capacity(reader, CHUNK_SIZE);
+    while let Some(result) = stream.next().await {
+        let failed = result.is_err();
+        // The decoder stops reading when it fails, in which case it reports
+        // its error below.
+        if compressed_tx.send(result).await.is_err() || failed {
+            break;
+        }
+    }
+    drop(compressed_tx);
+
+    decode.await.map_err(|e| DownloadError::Failed {
+        err: std::io::Error::new(std::io::ErrorKind::Other, e),
+    })?
+}
+
+/// A blocking reader over the chunks of an object that an async task
+/// downloads.
+struct ChannelReader {
+    rx: Receiver<std::io::Result<Bytes>>,
+    /// The unread part of the last received chunk.
+    buf: Bytes,
+}
+
+impl Read for ChannelReader {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        while self.buf.is_empty() {
+            match self.rx.blocking_recv() {
+                Some(chunk) => self.buf = chunk?,
+                None => return Ok(0),
+            }
+        }
+        let n = std::cmp::min(out.len(), self.buf.len());
+        out[..n].copy_from_slice(&self.buf.split_to(n));
+        Ok(n)
+    }
+}
+
+/// Decodes a Parquet object and sends its rows to dataflow.
+///
+/// Parquet files can only be decoded with random access, so rather than
+/// streaming the object the footer and then each row group's demanded column
+/// chunks are fetched with ranged requests, from a blocking task. Only the row
+/// group that is being decoded is held in memory.
+async fn read_parquet_object(
+    source_id: &str,
+    client: &S3Client,
+    bucket: &str,
+    key: &str,
+    encoding: &ParquetEncoding,
+    tx: &Sender<S3Result<InternalMessage>>,
+) -> Result<DownloadMetricUpdate, DownloadError> {
+    let len = client
+        .head_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .map_err(|e| DownloadError::Failed {
+            err: std::io::Error::new(std::io::ErrorKind::Other, e),
+        })?
+        .content_length;
+    if len <= 0 {
+        trace!("source_id={} empty object {}/{}", source_id, bucket, key);
+        return Ok(Default::default());
+    }
+
+    let bytes_read = Arc::new(AtomicU64::new(0));
+    let reader = S3RangeReader {
+        client: client.clone(),
+        bucket: bucket.to_string(),
+        key: key.to_string(),
+        runtime: tokio::runtime::Handle::current(),
+        len: len.try_into().expect("positive"),
+        pos: 0,
+        buf_start: 0,
+        buf: Bytes::new(),
+        bytes_read: Arc::clone(&bytes_read),
+    };
+    let encoding = encoding.clone();
+    let tx = tx.clone();
+    task::spawn_blocking(|| format!("s3_read_parquet:{}", source_id), {
+        let source_id = source_id.to_string();
+        move || {
+            let to_io_error =
+                |e: anyhow::Error| std::io::Error::new(std::io::ErrorKind::InvalidData, e);
+            let rows = ParquetReader::new(reader, encoding.columns, encoding.projection.as_deref())
+                .map_err(|e| DownloadError::Failed {
+                    err: to_io_error(e),
+                })?;
+
+            let mut messages = 0;
+            for row in rows {
+                let row = row.map_err(|e| DownloadError::Failed {
+                    err: to_io_error(e),
+                })?;
+                messages += 1;
+                if tx
+                    .blocking_send(Ok(InternalMessage {
+                        record: MessagePayload::Row(row),
+                    }))
+                    .is_err()
+                {
+                    return Err(DownloadError::SendFailed);
+                }
+            }
+
+            let bytes_read = bytes_read.load(Ordering::Relaxed);
+            trace!(
+                "source_id={} finished sending Parquet object to dataflow rows={} bytes={}",
+                source_id,
+                messages,
+                bytes_read
+            );
+            Ok(DownloadMetricUpdate {
+                bytes: bytes_read,
+                messages,
+            })
+        }
+    })
+    .await
+    .map_err(|e| DownloadError::Failed {
+        err: std::io::Error::new(std::io::ErrorKind::Other, e),
+    })?
+}
+
+/// The number of bytes of an object that an [`S3RangeReader`] fetches at once.
+const RANGE_READ_SIZE: u64 = 1 << 20;
+
+/// A blocking, seekable reader of an S3 object that fetches the parts of the
+/// object that are read with ranged requests.
+///
+/// Must only be used from a blocking task, as each request blocks on
+/// `runtime`.
+struct S3RangeReader {
+    client: S3Client,
+    bucket: String,
+    key: String,
+    runtime: tokio::runtime::Handle,
+    /// The length of the object.
+    len: u64,
+    /// The position in the object of the next read.
+    pos: u64,
+    /// The position in the object of the first byte of `buf`.
+    buf_start: u64,
+    buf: Bytes,
+    /// The number of bytes downloaded so far.
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl S3RangeReader {
+    /// Fetches the part of the object that starts at `self.pos` into `buf`.
+    fn fetch(&mut self) -> std::io::Result<()> {
+        let end = std::cmp::min(self.pos + RANGE_READ_SIZE, self.len) - 1;
+        let range = format!("bytes={}-{}", self.pos, end);
+        let (client, bucket, key) = (&self.client, &self.bucket, &self.key);
+        let buf = self.runtime.block_on(async {
+            let obj = Retry::default()
+                .retry_async(|_| {
+                    client
+                        .get_object()
+                        .bucket(bucket)
+                        .key(key)
+                        .range(range.clone())
+                        .send()
+                })
+                .await
+                .map_err(|err| S3Error::GetObjectError {
+                    bucket: bucket.clone(),
+                    key: key.clone(),
+                    err,
+                })?;
+            obj.body
+                .collect()
+                .await
+                .map(|body| body.into_bytes())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
+        })?;
+        self.buf_start = self.pos;
+        self.bytes_read
+            .fetch_add(u64::cast_from(buf.len()), Ordering::Relaxed);
+        self.buf = buf;
+        Ok(())
+    }
+}
+
+impl Read for S3RangeReader {
+    fn read(&mut self, out: &mut [u8]) -> std::io::Result<usize> {
+        if self.pos >= self.len || out.is_empty() {
+            return Ok(0);
+        }
+        let buf_end = self.buf_start + u64::cast_from(self.buf.len());
+        if self.pos < self.buf_start || self.pos >= buf_end {
+            self.fetch()?;
+        }
+        let start = usize::cast_from(self.pos - self.buf_start);
+        let n = std::cmp::min(out.len(), self.buf.len() - start);
+        out[..n].copy_from_slice(&self.buf[start..start + n]);
+        self.pos += u64::cast_from(n);
+        Ok(n)
+    }
+}
+
+impl Seek for S3RangeReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::End(n) => checked_offset(self.len, n),
+            SeekFrom::Current(n) => checked_offset(self.pos, n),
+        };
+        match pos {
+            Some(pos) => {
+                self.pos = pos;
+                Ok(pos)
+            }
+            None => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+fn checked_offset(base: u64, offset: i64) -> Option<u64> {
+    if offset >= 0 {
+        base.checked_add(offset.unsigned_abs())
+    } else {
+        base.checked_sub(offset.unsigned_abs())
+    }
+}
+
 impl SourceReader for S3SourceReader {
     type Key = ();
     type Value = MessagePayload;
@@ -796,7 +1129,8 @@ impl SourceReader for S3SourceReader {
         connector: ExternalSourceConnector,
         aws_external_id: AwsExternalId,
         _restored_offsets: Vec<(PartitionId, Option<MzOffset>)>,
-        _encoding: SourceDataEncoding,
+        _upstream_positions: Vec<(PartitionId, MzOffset, String)>,
+        encoding: SourceDataEncoding,
         _: Option<Logger>,
         metrics: SourceBaseMetrics,
     ) -> Result<Self, anyhow::Error> {
@@ -813,6 +1147,10 @@ impl SourceReader for S3SourceReader {
             let (keys_tx, keys_rx) = tokio::sync::mpsc::channel(10_000);
             let (shutdowner, shutdown_rx) = tokio::sync::watch::channel(DataflowStatus::Running);
             let glob = s3_conn.pattern.map(|g| g.compile_matcher());
+            let parquet = match encoding.value() {
+                DataEncoding::Parquet(parquet) => Some(parquet),
+                _ => None,
+            };
 
             task::spawn(
                 || format!("s3_download:{}", source_id),
@@ -825,6 +1163,7 @@ impl SourceReader for S3SourceReader {
                     aws_external_id.clone(),
                     consumer_activator,
                     s3_conn.compression,
+                    parquet,
                     metrics.clone(),
                 ),
             );
@@ -891,8 +1230,10 @@ impl SourceReader for S3SourceReader {
                     partition: PartitionId::None,
                     offset: self.offset.into(),
                     upstream_time_millis: None,
+                    upstream_position: None,
                     key: (),
                     value: record,
+                    headers: None,
                 }))
             }
             Some(Some(Err(e))) => match e {
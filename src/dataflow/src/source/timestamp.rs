@@ -42,15 +42,16 @@ use mz_ore::now::NowFn;
 use mz_repr::Timestamp;
 
 use crate::source::gen::source::{
-    proto_source_timestamp, ProtoAssignedTimestamp, ProtoSourceTimestamp,
+    proto_source_timestamp, ProtoAssignedTimestamp, ProtoKafkaTopicPartition, ProtoSourceTimestamp,
 };
 
 /// This struct holds state for proposed timestamps and
 /// proposed bindings from offsets to timestamps.
 #[derive(Debug)]
 pub struct TimestampProposer {
-    /// Working set of proposed offsets to assign to a new timestamp.
-    bindings: HashMap<PartitionId, MzOffset>,
+    /// Working set of proposed offsets to assign to a new timestamp, along with the upstream
+    /// position of the message at the proposed offset, if the source provides one.
+    bindings: HashMap<PartitionId, (MzOffset, Option<String>)>,
     /// Current timestamp we are assigning new data to.
     timestamp: Timestamp,
     /// Last time we updated the timestamp.
@@ -77,16 +78,25 @@ impl TimestampProposer {
     ///
     /// This proposal is ignored if there is already a proposed binding for this
     /// partition to an offset > 'offset'.
-    fn propose_binding(&mut self, partition: PartitionId, offset: MzOffset) -> Timestamp {
+    fn propose_binding(
+        &mut self,
+        partition: PartitionId,
+        offset: MzOffset,
+        upstream_position: Option<&str>,
+    ) -> Timestamp {
         // Propose one past the current offset, as bindings store one past the
         // maximum offset bound to that time..
         let next_offset = offset + 1;
+        let upstream_position = upstream_position.map(|p| p.to_owned());
 
         // Only use the proposal if it further ahead than any existing proposals
         // for that partition.
-        let current_proposal = self.bindings.entry(partition).or_insert(next_offset);
-        if next_offset > *current_proposal {
-            *current_proposal = next_offset;
+        let current_proposal = self
+            .bindings
+            .entry(partition)
+            .or_insert_with(|| (next_offset, upstream_position.clone()));
+        if next_offset > current_proposal.0 {
+            *current_proposal = (next_offset, upstream_position);
         }
         self.timestamp
     }
@@ -96,7 +106,9 @@ impl TimestampProposer {
     ///
     /// This function needs to be called periodically in order for RT sources to
     /// make progress.
-    fn update_timestamp(&mut self) -> Option<(Timestamp, Vec<(PartitionId, MzOffset)>)> {
+    fn update_timestamp(
+        &mut self,
+    ) -> Option<(Timestamp, Vec<(PartitionId, MzOffset, Option<String>)>)> {
         if self.last_update_time.elapsed().as_millis() < self.update_interval.into() {
             return None;
         }
@@ -107,7 +119,11 @@ impl TimestampProposer {
 
         if self.timestamp < new_ts {
             // Now we need to fetch all of the existing bindings
-            let bindings: Vec<_> = self.bindings.iter().map(|(p, o)| (p.clone(), *o)).collect();
+            let bindings: Vec<_> = self
+                .bindings
+                .iter()
+                .map(|(p, (o, pos))| (p.clone(), *o, pos.clone()))
+                .collect();
             let old_timestamp = self.timestamp;
 
             self.timestamp = new_ts;
@@ -141,10 +157,16 @@ impl TimestampProposer {
 /// - offsets in [o1, o2) are bound to t2.
 /// - offsets in [o2, inf) have not been assigned a timestamp yet.
 /// - no offsets are bound to t3, and no offsets will be bound to t3.
+///
+/// Sources whose upstream positions cannot be represented as an `MzOffset`
+/// (e.g. Kinesis sequence numbers) additionally record, for each bound offset
+/// `o`, the upstream position of the message at offset `o - 1`. This allows
+/// them to resume reading right after a binding.
 #[derive(Debug)]
 pub struct PartitionTimestamps {
     id: PartitionId,
     bindings: Vec<(Timestamp, MzOffset)>,
+    upstream_positions: HashMap<MzOffset, String>,
 }
 
 impl PartitionTimestamps {
@@ -152,6 +174,7 @@ impl PartitionTimestamps {
         Self {
             id,
             bindings: Vec::new(),
+            upstream_positions: HashMap::new(),
         }
     }
 
@@ -180,9 +203,21 @@ impl PartitionTimestamps {
         // We always keep the last binding around.
         new_bindings.push(*self.bindings.last().expect("known to exist"));
         self.bindings = new_bindings;
+
+        // Upstream positions are only interesting for offsets that are still bound.
+        if !self.upstream_positions.is_empty() {
+            let bindings = &self.bindings;
+            self.upstream_positions
+                .retain(|offset, _| bindings.iter().any(|(_, o)| o == offset));
+        }
     }
 
-    fn add_binding(&mut self, timestamp: Timestamp, offset: MzOffset) {
+    fn add_binding(
+        &mut self,
+        timestamp: Timestamp,
+        offset: MzOffset,
+        upstream_position: Option<String>,
+    ) {
         if let Some((last_ts, last_offset)) = self.bindings.last() {
             // TODO(rkhaitan): remove this error log and change the assertion
             // below to be strictly greater than once we fix 10742.
@@ -207,6 +242,9 @@ impl PartitionTimestamps {
             );
         }
         self.bindings.push((timestamp, offset));
+        if let Some(upstream_position) = upstream_position {
+            self.upstream_positions.insert(offset, upstream_position);
+        }
     }
 
     /// Gets the timestamp binding for `offset`.
@@ -256,14 +294,25 @@ impl PartitionTimestamps {
         &self,
         lower: AntichainRef<Timestamp>,
         upper: AntichainRef<Timestamp>,
-        bindings: &mut Vec<(PartitionId, Timestamp, MzOffset)>,
+        bindings: &mut Vec<(PartitionId, Timestamp, MzOffset, Option<String>)>,
     ) {
         for (time, offset) in self.bindings.iter() {
             if lower.less_equal(time) && !upper.less_equal(time) {
-                bindings.push((self.id.clone(), *time, *offset));
+                bindings.push((
+                    self.id.clone(),
+                    *time,
+                    *offset,
+                    self.upstream_positions.get(offset).cloned(),
+                ));
             }
         }
     }
+
+    fn upstream_positions(&self) -> impl Iterator<Item = (MzOffset, &str)> {
+        self.upstream_positions
+            .iter()
+            .map(|(offset, position)| (*offset, position.as_str()))
+    }
 }
 
 /// This struct holds per-source timestamp state in a way that can be shared across
@@ -375,13 +424,19 @@ impl TimestampBindingBox {
             .or_insert_with(|| PartitionTimestamps::new(partition));
     }
 
-    fn add_binding(&mut self, partition: PartitionId, timestamp: Timestamp, offset: MzOffset) {
+    fn add_binding(
+        &mut self,
+        partition: PartitionId,
+        timestamp: Timestamp,
+        offset: MzOffset,
+        upstream_position: Option<String>,
+    ) {
         if !self.partitions.contains_key(&partition) {
             panic!("missing partition {:?} when adding binding", partition);
         }
 
         let partition = self.partitions.get_mut(&partition).expect("known to exist");
-        partition.add_binding(timestamp, offset);
+        partition.add_binding(timestamp, offset, upstream_position);
     }
 
     fn downgrade(&self, cap: &mut Capability<Timestamp>, cursors: &HashMap<PartitionId, MzOffset>) {
@@ -395,7 +450,12 @@ impl TimestampBindingBox {
         cap.downgrade(&ts);
     }
 
-    fn get_or_propose_binding(&mut self, partition: &PartitionId, offset: MzOffset) -> Timestamp {
+    fn get_or_propose_binding(
+        &mut self,
+        partition: &PartitionId,
+        offset: MzOffset,
+        upstream_position: Option<&str>,
+    ) -> Timestamp {
         if !self.partitions.contains_key(partition) {
             self.add_partition(partition.clone(), None);
         }
@@ -403,7 +463,8 @@ impl TimestampBindingBox {
         if let Some(time) = partition_timestamps.get_binding(offset) {
             time
         } else {
-            self.proposer.propose_binding(partition.clone(), offset)
+            self.proposer
+                .propose_binding(partition.clone(), offset, upstream_position)
         }
     }
 
@@ -411,7 +472,7 @@ impl TimestampBindingBox {
         &self,
         lower: AntichainRef<Timestamp>,
         upper: AntichainRef<Timestamp>,
-    ) -> Vec<(PartitionId, Timestamp, MzOffset)> {
+    ) -> Vec<(PartitionId, Timestamp, MzOffset, Option<String>)> {
         let mut ret = Vec::new();
 
         for (_, partition) in self.partitions.iter() {
@@ -421,6 +482,17 @@ impl TimestampBindingBox {
         ret
     }
 
+    fn upstream_positions(&self) -> Vec<(PartitionId, MzOffset, String)> {
+        self.partitions
+            .iter()
+            .flat_map(|(pid, partition)| {
+                partition
+                    .upstream_positions()
+                    .map(move |(offset, position)| (pid.clone(), offset, position.to_owned()))
+            })
+            .collect()
+    }
+
     fn upper(&self) -> Timestamp {
         self.proposer.upper()
     }
@@ -445,8 +517,8 @@ impl TimestampBindingBox {
     fn update_timestamp(&mut self) {
         let result = self.proposer.update_timestamp();
         if let Some((time, bindings)) = result {
-            for (partition, offset) in bindings {
-                self.add_binding(partition, time, offset);
+            for (partition, offset, upstream_position) in bindings {
+                self.add_binding(partition, time, offset, upstream_position);
             }
         }
     }
@@ -513,10 +585,19 @@ impl TimestampBindingRc {
     /// the largest previously bound offset for that partition. If `proposed` is true,
     /// the binding is treated as tentative and may be overwritten by other, overlapping
     /// bindings
-    pub fn add_binding(&self, partition: PartitionId, timestamp: Timestamp, offset: MzOffset) {
+    ///
+    /// The optional `upstream_position` is the source-native position of the message at
+    /// `offset - 1`, for sources that need it to resume reading after this binding.
+    pub fn add_binding(
+        &self,
+        partition: PartitionId,
+        timestamp: Timestamp,
+        offset: MzOffset,
+        upstream_position: Option<String>,
+    ) {
         self.wrapper
             .borrow_mut()
-            .add_binding(partition, timestamp, offset);
+            .add_binding(partition, timestamp, offset, upstream_position);
     }
 
     /// Tell timestamping machinery to look out for `partition`
@@ -533,10 +614,15 @@ impl TimestampBindingRc {
     ///
     /// This function returns the timestamp and the maximum offset for which it is
     /// valid.
-    pub fn get_or_propose_binding(&self, partition: &PartitionId, offset: MzOffset) -> Timestamp {
+    pub fn get_or_propose_binding(
+        &self,
+        partition: &PartitionId,
+        offset: MzOffset,
+        upstream_position: Option<&str>,
+    ) -> Timestamp {
         self.wrapper
             .borrow_mut()
-            .get_or_propose_binding(partition, offset)
+            .get_or_propose_binding(partition, offset, upstream_position)
     }
 
     /// Get the timestamp that all messages beyond the minted bindings will be assigned to. This is
@@ -573,6 +659,13 @@ impl TimestampBindingRc {
         self.wrapper.borrow().partitions()
     }
 
+    /// Returns the upstream positions recorded alongside the currently known bindings, as
+    /// `(partition, offset, position)` triples where `position` is the source-native position
+    /// of the message at `offset - 1`.
+    pub fn upstream_positions(&self) -> Vec<(PartitionId, MzOffset, String)> {
+        self.wrapper.borrow().upstream_positions()
+    }
+
     /// Instructs RT sources to try and move forward to the next timestamp if
     /// possible
     pub fn update_timestamp(&self) {
@@ -584,7 +677,7 @@ impl TimestampBindingRc {
         &self,
         lower: AntichainRef<Timestamp>,
         upper: AntichainRef<Timestamp>,
-    ) -> Vec<(PartitionId, Timestamp, MzOffset)> {
+    ) -> Vec<(PartitionId, Timestamp, MzOffset, Option<String>)> {
         self.wrapper.borrow().get_bindings_in_range(lower, upper)
     }
 
@@ -636,6 +729,9 @@ pub struct SourceTimestamp {
     pub partition: PartitionId,
     /// Materialize offset of the message (1-indexed)
     pub offset: MzOffset,
+    /// Source-native position of the message at `offset - 1`, for sources whose upstream
+    /// positions cannot be represented as an `MzOffset`.
+    pub upstream_position: Option<String>,
 }
 
 // TODO: See comment on Ord below.
@@ -646,17 +742,60 @@ impl PartialOrd for SourceTimestamp {
                 self.offset.offset.cmp(&other.offset.offset)
             }
             (PartitionId::Kafka(a), PartitionId::Kafka(b)) => a.cmp(b),
+            (PartitionId::KafkaTopic(a, p), PartitionId::KafkaTopic(b, q)) if (a, p) == (b, q) => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::KafkaTopic(a, p), PartitionId::KafkaTopic(b, q)) => (a, p).cmp(&(b, q)),
+            (PartitionId::Kinesis(a), PartitionId::Kinesis(b)) if a == b => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::Kinesis(a), PartitionId::Kinesis(b)) => a.cmp(b),
+            (PartitionId::File(a), PartitionId::File(b)) if a == b => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::File(a), PartitionId::File(b)) => a.cmp(b),
             (PartitionId::None, PartitionId::None) => self.offset.offset.cmp(&other.offset.offset),
             // We're not using a wildcard pattern here, to make sure this fails when someone adds
             // new types of partition ID.
-            (PartitionId::None, PartitionId::Kafka(_)) => {
-                unreachable!("PartitionId types must match")
-            }
-            (PartitionId::Kafka(_), PartitionId::None) => {
+            (
+                PartitionId::None,
+                PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::Kafka(_),
+                PartitionId::None
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::KafkaTopic(..),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::Kinesis(_),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::File(_),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_),
+            ) => {
                 unreachable!("PartitionId types must match")
             }
         };
-        Some(result)
+        Some(result.then_with(|| self.upstream_position.cmp(&other.upstream_position)))
     }
 }
 
@@ -670,17 +809,60 @@ impl Ord for SourceTimestamp {
                 self.offset.offset.cmp(&other.offset.offset)
             }
             (PartitionId::Kafka(a), PartitionId::Kafka(b)) => a.cmp(b),
+            (PartitionId::KafkaTopic(a, p), PartitionId::KafkaTopic(b, q)) if (a, p) == (b, q) => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::KafkaTopic(a, p), PartitionId::KafkaTopic(b, q)) => (a, p).cmp(&(b, q)),
+            (PartitionId::Kinesis(a), PartitionId::Kinesis(b)) if a == b => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::Kinesis(a), PartitionId::Kinesis(b)) => a.cmp(b),
+            (PartitionId::File(a), PartitionId::File(b)) if a == b => {
+                self.offset.offset.cmp(&other.offset.offset)
+            }
+            (PartitionId::File(a), PartitionId::File(b)) => a.cmp(b),
             (PartitionId::None, PartitionId::None) => self.offset.offset.cmp(&other.offset.offset),
             // We're not using a wildcard pattern here, to make sure this fails when someone adds
             // new types of partition ID.
-            (PartitionId::None, PartitionId::Kafka(_)) => {
-                unreachable!("PartitionId types must match")
-            }
-            (PartitionId::Kafka(_), PartitionId::None) => {
+            (
+                PartitionId::None,
+                PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::Kafka(_),
+                PartitionId::None
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::KafkaTopic(..),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::Kinesis(_)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::Kinesis(_),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::File(_),
+            )
+            | (
+                PartitionId::File(_),
+                PartitionId::None
+                | PartitionId::Kafka(_)
+                | PartitionId::KafkaTopic(..)
+                | PartitionId::Kinesis(_),
+            ) => {
                 unreachable!("PartitionId types must match")
             }
         };
-        result
+        result.then_with(|| self.upstream_position.cmp(&other.upstream_position))
     }
 }
 
@@ -693,9 +875,18 @@ impl From<&SourceTimestamp> for ProtoSourceTimestamp {
         ProtoSourceTimestamp {
             partition_id: Some(match &x.partition {
                 PartitionId::Kafka(x) => proto_source_timestamp::PartitionId::Kafka(*x),
+                PartitionId::KafkaTopic(topic, partition) => {
+                    proto_source_timestamp::PartitionId::KafkaTopic(ProtoKafkaTopicPartition {
+                        topic: topic.clone(),
+                        partition: *partition,
+                    })
+                }
+                PartitionId::Kinesis(x) => proto_source_timestamp::PartitionId::Kinesis(x.clone()),
+                PartitionId::File(x) => proto_source_timestamp::PartitionId::File(x.clone()),
                 PartitionId::None => proto_source_timestamp::PartitionId::None(()),
             }),
             mz_offset: x.offset.offset,
+            upstream_position: x.upstream_position.clone().unwrap_or_default(),
         }
     }
 }
@@ -706,14 +897,25 @@ impl TryFrom<ProtoSourceTimestamp> for SourceTimestamp {
     fn try_from(x: ProtoSourceTimestamp) -> Result<Self, Self::Error> {
         let partition = match x.partition_id {
             Some(proto_source_timestamp::PartitionId::Kafka(x)) => PartitionId::Kafka(x),
+            Some(proto_source_timestamp::PartitionId::KafkaTopic(x)) => {
+                PartitionId::KafkaTopic(x.topic, x.partition)
+            }
+            Some(proto_source_timestamp::PartitionId::Kinesis(x)) => PartitionId::Kinesis(x),
+            Some(proto_source_timestamp::PartitionId::File(x)) => PartitionId::File(x),
             Some(proto_source_timestamp::PartitionId::None(_)) => PartitionId::None,
             None => return Err("unknown partition_id".into()),
         };
+        let upstream_position = if x.upstream_position.is_empty() {
+            None
+        } else {
+            Some(x.upstream_position)
+        };
         Ok(SourceTimestamp {
             partition,
             offset: MzOffset {
                 offset: x.mz_offset,
             },
+            upstream_position,
         })
     }
 }
@@ -846,10 +1048,14 @@ impl TimestampBindingUpdater {
                 self.current_bindings_frontier.borrow(),
             )
             .into_iter()
-            .map(|(partition, assigned_ts, offset)| {
+            .map(|(partition, assigned_ts, offset, upstream_position)| {
                 (
                     (
-                        SourceTimestamp { partition, offset },
+                        SourceTimestamp {
+                            partition,
+                            offset,
+                            upstream_position,
+                        },
                         AssignedTimestamp(assigned_ts),
                     ),
                     1,
@@ -883,7 +1089,45 @@ mod tests {
     fn source_timestamp_roundtrip() -> Result<(), String> {
         let partition = PartitionId::Kafka(42);
         let offset = MzOffset { offset: 17 };
-        let original = SourceTimestamp { partition, offset };
+        let original = SourceTimestamp {
+            partition,
+            offset,
+            upstream_position: None,
+        };
+        let mut encoded = Vec::new();
+        original.encode(&mut encoded);
+        let decoded = SourceTimestamp::decode(&encoded)?;
+
+        assert_eq!(decoded, original);
+
+        Ok(())
+    }
+
+    #[test]
+    fn kinesis_source_timestamp_roundtrip() -> Result<(), String> {
+        let original = SourceTimestamp {
+            partition: PartitionId::Kinesis("shardId-000000000001".into()),
+            offset: MzOffset { offset: 17 },
+            upstream_position: Some(
+                "49590338271490256608559692538361571095921575989136588898".into(),
+            ),
+        };
+        let mut encoded = Vec::new();
+        original.encode(&mut encoded);
+        let decoded = SourceTimestamp::decode(&encoded)?;
+
+        assert_eq!(decoded, original);
+
+        Ok(())
+    }
+
+    #[test]
+    fn kafka_topic_source_timestamp_roundtrip() -> Result<(), String> {
+        let original = SourceTimestamp {
+            partition: PartitionId::KafkaTopic("events.clicks".into(), 3),
+            offset: MzOffset { offset: 17 },
+            upstream_position: None,
+        };
         let mut encoded = Vec::new();
         original.encode(&mut encoded);
         let decoded = SourceTimestamp::decode(&encoded)?;
@@ -912,7 +1156,7 @@ mod tests {
 
         timestamp_histories.add_partition(PartitionId::Kafka(0), None);
 
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 });
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 }, None);
 
         let actual_updates = timestamp_binding_updater
             .update(&timestamp_histories)
@@ -922,6 +1166,7 @@ mod tests {
                 SourceTimestamp {
                     partition: PartitionId::Kafka(0),
                     offset: MzOffset { offset: 4 },
+                    upstream_position: None,
                 },
                 AssignedTimestamp(42),
             ),
@@ -929,7 +1174,7 @@ mod tests {
         )];
         assert_eq!(actual_updates, expected_updates);
 
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 43, MzOffset { offset: 5 });
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 43, MzOffset { offset: 5 }, None);
 
         let actual_updates = timestamp_binding_updater
             .update(&timestamp_histories)
@@ -939,6 +1184,7 @@ mod tests {
                 SourceTimestamp {
                     partition: PartitionId::Kafka(0),
                     offset: MzOffset { offset: 5 },
+                    upstream_position: None,
                 },
                 AssignedTimestamp(43),
             ),
@@ -956,7 +1202,7 @@ mod tests {
 
         timestamp_histories.add_partition(PartitionId::Kafka(0), None);
 
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 });
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 }, None);
 
         let actual_updates = timestamp_binding_updater
             .update(&timestamp_histories)
@@ -966,6 +1212,7 @@ mod tests {
                 SourceTimestamp {
                     partition: PartitionId::Kafka(0),
                     offset: MzOffset { offset: 4 },
+                    upstream_position: None,
                 },
                 AssignedTimestamp(42),
             ),
@@ -988,9 +1235,9 @@ mod tests {
 
         timestamp_histories.add_partition(PartitionId::Kafka(0), None);
 
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 });
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 43, MzOffset { offset: 5 });
-        timestamp_histories.add_binding(PartitionId::Kafka(0), 44, MzOffset { offset: 6 });
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 42, MzOffset { offset: 4 }, None);
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 43, MzOffset { offset: 5 }, None);
+        timestamp_histories.add_binding(PartitionId::Kafka(0), 44, MzOffset { offset: 6 }, None);
 
         let mut actual_updates = timestamp_binding_updater
             .update(&timestamp_histories)
@@ -1001,6 +1248,7 @@ mod tests {
                     SourceTimestamp {
                         partition: PartitionId::Kafka(0),
                         offset: MzOffset { offset: 4 },
+                        upstream_position: None,
                     },
                     AssignedTimestamp(42),
                 ),
@@ -1011,6 +1259,7 @@ mod tests {
                     SourceTimestamp {
                         partition: PartitionId::Kafka(0),
                         offset: MzOffset { offset: 5 },
+                        upstream_position: None,
                     },
                     AssignedTimestamp(43),
                 ),
@@ -1021,6 +1270,7 @@ mod tests {
                     SourceTimestamp {
                         partition: PartitionId::Kafka(0),
                         offset: MzOffset { offset: 6 },
+                        upstream_position: None,
                     },
                     AssignedTimestamp(44),
                 ),
@@ -1042,6 +1292,7 @@ mod tests {
                     SourceTimestamp {
                         partition: PartitionId::Kafka(0),
                         offset: MzOffset { offset: 4 },
+                        upstream_position: None,
                     },
                     AssignedTimestamp(42),
                 ),
@@ -1052,6 +1303,7 @@ mod tests {
                     SourceTimestamp {
                         partition: PartitionId::Kafka(0),
                         offset: MzOffset { offset: 5 },
+                        upstream_position: None,
                     },
                     AssignedTimestamp(43),
                 ),
@@ -1075,7 +1327,7 @@ mod tests {
         ];
 
         for (time, offset) in bindings.iter() {
-            pt.add_binding(*time, offset.clone());
+            pt.add_binding(*time, offset.clone(), None);
         }
 
         let test_cases = vec![
@@ -1093,4 +1345,22 @@ mod tests {
             }
         }
     }
+
+    // Upstream positions are retained for exactly the offsets that survive compaction.
+    #[test]
+    fn partition_timestamps_upstream_positions() {
+        let mut pt = PartitionTimestamps::new(PartitionId::Kinesis("shardId-000000000000".into()));
+        pt.add_binding(1, MzOffset { offset: 2 }, Some("11".into()));
+        pt.add_binding(2, MzOffset { offset: 5 }, Some("14".into()));
+        pt.add_binding(3, MzOffset { offset: 6 }, Some("17".into()));
+
+        pt.compact(Antichain::from_elem(2).borrow());
+
+        let mut positions = pt
+            .upstream_positions()
+            .map(|(offset, position)| (offset.offset, position.to_owned()))
+            .collect::<Vec<_>>();
+        positions.sort();
+        assert_eq!(positions, vec![(5, "14".into()), (6, "17".into())]);
+    }
 }
@@ -130,10 +130,23 @@ impl fmt::Display for SourceInstanceId {
 
 /// Unique identifier for each part of a whole source.
 ///     Kafka -> partition
+///     Kafka with a topic pattern -> (topic, partition)
+///     Kinesis -> shard
 ///     None -> sources that have no notion of partitioning (e.g file sources)
+///
+/// The display form of a partition ID does not identify its variant, so
+/// partition IDs must be stored in their serialized form.
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum PartitionId {
     Kafka(i32),
+    /// A partition of one of the topics matched by a Kafka source's topic
+    /// pattern, identified by the topic name and partition ID.
+    KafkaTopic(String, i32),
+    /// A Kinesis shard, identified by its shard ID (e.g. `shardId-000000000000`).
+    Kinesis(String),
+    /// A file read by a file source with a glob, identified by the path at
+    /// which it was discovered and the identity of the file.
+    File(String),
     None,
 }
 
@@ -141,6 +154,9 @@ impl fmt::Display for PartitionId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             PartitionId::Kafka(id) => write!(f, "{}", id),
+            PartitionId::KafkaTopic(topic, id) => write!(f, "{}:{}", topic, id),
+            PartitionId::Kinesis(shard_id) => write!(f, "{}", shard_id),
+            PartitionId::File(path) => write!(f, "{}", path),
             PartitionId::None => write!(f, "none"),
         }
     }
@@ -154,17 +170,3 @@ impl From<&PartitionId> for Option<String> {
         }
     }
 }
-
-impl FromStr for PartitionId {
-    type Err = Error;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s {
-            "none" => Ok(PartitionId::None),
-            s => {
-                let val: i32 = s.parse()?;
-                Ok(PartitionId::Kafka(val))
-            }
-        }
-    }
-}
@@ -10,7 +10,8 @@
 //! Logic for the Avro representation of the CDCv2 protocol.
 
 use mz_avro::schema::{FullName, SchemaNode};
-use mz_repr::{Diff, Row, Timestamp};
+use mz_avro::types::Value;
+use mz_repr::{ColumnName, ColumnType, Diff, RelationDesc, Row, Timestamp};
 use serde_json::json;
 
 use anyhow::anyhow;
@@ -25,6 +26,9 @@ use mz_avro_derive::AvroDecodable;
 use std::{cell::RefCell, rc::Rc};
 
 use super::decode::RowWrapper;
+use super::encode::{encode_avro_header, encode_datums_as_avro};
+use crate::encode::{column_names_and_types, EncodeCdcV2};
+use crate::json::build_row_schema_json;
 
 pub fn extract_data_columns<'a>(schema: &'a Schema) -> anyhow::Result<SchemaNode<'a>> {
     let data_name = FullName::from_parts("data", Some("com.materialize.cdc"), "");
@@ -125,6 +129,7 @@ pub fn build_schema(row_schema: serde_json::Value) -> Schema {
         "type": "array",
         "items": {
             "name" : "update",
+            "namespace": "com.materialize.cdc",
             "type" : "record",
             "fields" : [
                 {
@@ -145,6 +150,7 @@ pub fn build_schema(row_schema: serde_json::Value) -> Schema {
 
     let progress_schema = json!({
         "name" : "progress",
+        "namespace": "com.materialize.cdc",
         "type" : "record",
         "fields" : [
             {
@@ -188,87 +194,126 @@ pub fn build_schema(row_schema: serde_json::Value) -> Schema {
     Schema::parse(&message_schema).expect("schema constrution failed")
 }
 
-#[cfg(test)]
-mod tests {
-
-    use super::*;
-    use crate::avro::encode_datums_as_avro;
-    use crate::encode::column_names_and_types;
-    use mz_avro::types::Value;
-    use mz_avro::AvroDeserializer;
-    use mz_avro::GeneralDeserializer;
-    use mz_repr::{ColumnName, ColumnType, RelationDesc, Row, ScalarType};
+/// Constructs the CDCv2 schema for the rows described by `desc`.
+pub fn build_schema_for_desc(desc: RelationDesc) -> Schema {
+    let columns = column_names_and_types(desc);
+    build_schema(build_row_schema_json(&columns, "data"))
+}
 
-    use crate::json::build_row_schema_json;
+/// Collected state to encode update batches and progress statements.
+#[derive(Debug)]
+pub struct Encoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    schema: Schema,
+    schema_id: i32,
+}
 
-    /// Collected state to encode update batches and progress statements.
-    #[derive(Debug)]
-    struct Encoder {
-        columns: Vec<(ColumnName, ColumnType)>,
+impl Encoder {
+    /// Creates a new CDCv2 encoder from a relation description and the ID
+    /// under which its schema was published.
+    pub fn new(desc: RelationDesc, schema_id: i32) -> Self {
+        Self {
+            columns: column_names_and_types(desc.clone()),
+            schema: build_schema_for_desc(desc),
+            schema_id,
+        }
     }
 
-    impl Encoder {
-        /// Creates a new CDCv2 encoder from a relation description.
-        pub fn new(desc: RelationDesc) -> Self {
-            let columns = column_names_and_types(desc);
-            Self { columns }
+    /// Encodes a batch of updates as an Avro value.
+    pub fn encode_updates(&self, updates: &[(Row, Timestamp, Diff)]) -> Value {
+        let mut enc_updates = Vec::new();
+        for (data, time, diff) in updates {
+            let enc_data = encode_datums_as_avro(&**data, &self.columns);
+            let enc_time = Value::Long(encode_timestamp(*time));
+            let enc_diff = Value::Long(*diff);
+            enc_updates.push(Value::Record(vec![
+                ("data".to_string(), enc_data),
+                ("time".to_string(), enc_time),
+                ("diff".to_string(), enc_diff),
+            ]));
         }
-
-        /// Encodes a batch of updates as an Avro value.
-        pub fn encode_updates(&self, updates: &[(Row, i64, i64)]) -> Value {
-            let mut enc_updates = Vec::new();
-            for (data, time, diff) in updates {
-                let enc_data = encode_datums_as_avro(&**data, &self.columns);
-                let enc_time = Value::Long(time.clone());
-                let enc_diff = Value::Long(diff.clone());
-                enc_updates.push(Value::Record(vec![
-                    ("data".to_string(), enc_data),
-                    ("time".to_string(), enc_time),
-                    ("diff".to_string(), enc_diff),
-                ]));
-            }
-            Value::Union {
-                index: 0,
-                inner: Box::new(Value::Array(enc_updates)),
-                n_variants: 2,
-                null_variant: None,
-            }
+        Value::Union {
+            index: 0,
+            inner: Box::new(Value::Array(enc_updates)),
+            n_variants: 2,
+            null_variant: None,
         }
+    }
 
-        /// Encodes the contents of a progress statement as an Avro value.
-        pub fn encode_progress(
-            &self,
-            lower: &[i64],
-            upper: &[i64],
-            counts: &[(i64, i64)],
-        ) -> Value {
-            let enc_lower = Value::Array(lower.iter().cloned().map(Value::Long).collect());
-            let enc_upper = Value::Array(upper.iter().cloned().map(Value::Long).collect());
-            let enc_counts = Value::Array(
-                counts
+    /// Encodes the contents of a progress statement as an Avro value.
+    pub fn encode_progress(
+        &self,
+        lower: &[Timestamp],
+        upper: &[Timestamp],
+        counts: &[(Timestamp, usize)],
+    ) -> Value {
+        let encode_frontier = |frontier: &[Timestamp]| {
+            Value::Array(
+                frontier
                     .iter()
-                    .map(|(time, count)| {
-                        Value::Record(vec![
-                            ("time".to_string(), Value::Long(time.clone())),
-                            ("count".to_string(), Value::Long(count.clone())),
-                        ])
-                    })
+                    .map(|time| Value::Long(encode_timestamp(*time)))
                     .collect(),
-            );
-            let enc_progress = Value::Record(vec![
-                ("lower".to_string(), enc_lower),
-                ("upper".to_string(), enc_upper),
-                ("counts".to_string(), enc_counts),
-            ]);
+            )
+        };
+        let enc_counts = Value::Array(
+            counts
+                .iter()
+                .map(|(time, count)| {
+                    Value::Record(vec![
+                        ("time".to_string(), Value::Long(encode_timestamp(*time))),
+                        (
+                            "count".to_string(),
+                            Value::Long(i64::try_from(*count).expect("count fits in an i64")),
+                        ),
+                    ])
+                })
+                .collect(),
+        );
+        let enc_progress = Value::Record(vec![
+            ("lower".to_string(), encode_frontier(lower)),
+            ("upper".to_string(), encode_frontier(upper)),
+            ("counts".to_string(), enc_counts),
+        ]);
 
-            Value::Union {
-                index: 1,
-                inner: Box::new(enc_progress),
-                n_variants: 2,
-                null_variant: None,
-            }
+        Value::Union {
+            index: 1,
+            inner: Box::new(enc_progress),
+            n_variants: 2,
+            null_variant: None,
         }
     }
+}
+
+impl EncodeCdcV2 for Encoder {
+    fn get_format_name(&self) -> &str {
+        "avro"
+    }
+
+    fn encode_unchecked(&self, message: &Message<Row, Timestamp, Diff>) -> Vec<u8> {
+        let value = match message {
+            Message::Updates(updates) => self.encode_updates(updates),
+            Message::Progress(progress) => {
+                self.encode_progress(&progress.lower, &progress.upper, &progress.counts)
+            }
+        };
+        let mut buf = vec![];
+        encode_avro_header(&mut buf, self.schema_id);
+        mz_avro::encode_unchecked(&value, &self.schema, &mut buf);
+        buf
+    }
+}
+
+fn encode_timestamp(time: Timestamp) -> i64 {
+    i64::try_from(time).expect("timestamp fits in an i64")
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use mz_avro::AvroDeserializer;
+    use mz_avro::GeneralDeserializer;
+    use mz_repr::ScalarType;
 
     #[test]
     fn test_roundtrip() {
@@ -276,10 +321,8 @@ mod tests {
             .with_column("id", ScalarType::Int64.nullable(false))
             .with_column("price", ScalarType::Float64.nullable(true));
 
-        let encoder = Encoder::new(desc.clone());
-        let row_schema =
-            build_row_schema_json(&crate::encode::column_names_and_types(desc), "data");
-        let schema = build_schema(row_schema);
+        let encoder = Encoder::new(desc.clone(), 0);
+        let schema = build_schema_for_desc(desc);
 
         let values = vec![
             encoder.encode_updates(&[]),
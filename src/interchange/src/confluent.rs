@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+//! Logic for the framing that the Confluent stack places around serialized data.
+
 use anyhow::{bail, Result};
 use byteorder::{BigEndian, ByteOrder};
 
@@ -52,21 +54,103 @@ pub fn extract_avro_header(buf: &[u8]) -> Result<(i32, &[u8])> {
     extract_schema_id(buf, "avro")
 }
 
-pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, &[u8])> {
-    let (schema_id, buf) = extract_schema_id(buf, "protobuf")?;
+/// Extracts the schema ID and the message index path placed in front of a
+/// serialized Protobuf message by the Confluent stack.
+///
+/// The message index path identifies the message within the schema that the
+/// datum was written with: the first index selects a top-level message of the
+/// schema, and each subsequent index selects a message nested within the
+/// previous one. It is encoded as a zig-zag varint count followed by that many
+/// zig-zag varint indexes, except that the common path `[0]` is encoded as a
+/// single `0` byte.
+///
+/// This function returns the schema ID, the message index path, and a subslice
+/// of the rest of the buffer.
+pub fn extract_protobuf_header(buf: &[u8]) -> Result<(i32, Vec<i32>, &[u8])> {
+    let (schema_id, mut buf) = extract_schema_id(buf, "protobuf")?;
 
-    match buf.get(0) {
-        Some(0) => Ok((schema_id, &buf[1..])),
-        Some(message_id) => bail!(
-            "unsupported Confluent-style protobuf message descriptor id: \
-                expected 0, but found: {}. \
-                See https://github.com/MaterializeInc/materialize/issues/9250",
-            message_id
+    let count = read_zigzag_varint(&mut buf)?;
+    let message_indexes = match count {
+        0 => vec![0],
+        _ if count < 0 || count as usize > buf.len() => bail!(
+            "invalid Confluent-style protobuf message index count: {}",
+            count
         ),
-        None => bail!(
-            "Confluent-style protobuf datum is too few bytes: expected a message id after magic \
-            and schema id, got a buffer of length {}",
-            buf.len()
+        _ => (0..count)
+            .map(|_| read_zigzag_varint(&mut buf))
+            .collect::<Result<_>>()?,
+    };
+
+    Ok((schema_id, message_indexes, buf))
+}
+
+/// Writes the header that the Confluent stack places in front of a serialized
+/// Protobuf message: the magic byte, the schema ID, and the message index path.
+/// See [`extract_protobuf_header`] for details.
+pub fn encode_protobuf_header(buf: &mut Vec<u8>, schema_id: i32, message_indexes: &[i32]) {
+    buf.push(0);
+    buf.extend_from_slice(&schema_id.to_be_bytes());
+    encode_protobuf_message_indexes(buf, message_indexes);
+}
+
+/// Encodes a message index path as it appears in the header of a Confluent
+/// Protobuf datum. See [`extract_protobuf_header`] for details.
+pub fn encode_protobuf_message_indexes(buf: &mut Vec<u8>, message_indexes: &[i32]) {
+    if message_indexes == [0] {
+        buf.push(0);
+    } else {
+        let count = i32::try_from(message_indexes.len()).expect("message index path too long");
+        write_zigzag_varint(buf, count);
+        for index in message_indexes {
+            write_zigzag_varint(buf, *index);
+        }
+    }
+}
+
+fn read_zigzag_varint(buf: &mut &[u8]) -> Result<i32> {
+    let n = match prost::encoding::decode_varint(buf) {
+        Ok(n) => n,
+        Err(_) => bail!(
+            "Confluent-style protobuf datum is too few bytes: expected a message index path \
+            after magic and schema id"
         ),
+    };
+    let n = ((n >> 1) as i64) ^ -((n & 1) as i64);
+    match i32::try_from(n) {
+        Ok(n) => Ok(n),
+        Err(_) => bail!("Confluent-style protobuf message index {} out of range", n),
+    }
+}
+
+fn write_zigzag_varint(buf: &mut Vec<u8>, n: i32) {
+    let n = ((n << 1) ^ (n >> 31)) as u32;
+    prost::encoding::encode_varint(u64::from(n), buf);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protobuf_header() {
+        for message_indexes in [vec![0], vec![1], vec![0, 2], vec![3, 0, 64]] {
+            let mut buf = vec![];
+            encode_protobuf_header(&mut buf, 42, &message_indexes);
+            buf.push(0xff);
+            let (schema_id, decoded, rest) = extract_protobuf_header(&buf).unwrap();
+            assert_eq!(schema_id, 42);
+            assert_eq!(decoded, message_indexes);
+            assert_eq!(rest, [0xff]);
+        }
+
+        // The path `[0]` has a compact encoding.
+        let mut buf = vec![];
+        encode_protobuf_message_indexes(&mut buf, &[0]);
+        assert_eq!(buf, [0]);
+
+        // Truncated and negative counts are rejected.
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 1]).is_err());
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 1, 3]).is_err());
+        assert!(extract_protobuf_header(&[0, 0, 0, 0, 1, 4]).is_err());
     }
 }
@@ -9,7 +9,9 @@
 
 use std::collections::HashSet;
 
-use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row};
+use differential_dataflow::capture::Message;
+
+use mz_repr::{ColumnName, ColumnType, Datum, Diff, RelationDesc, Row, Timestamp};
 
 pub trait Encode {
     fn get_format_name(&self) -> &str;
@@ -17,6 +19,25 @@ pub trait Encode {
     fn encode_key_unchecked(&self, row: Row) -> Vec<u8>;
 
     fn encode_value_unchecked(&self, row: Row) -> Vec<u8>;
+
+    /// Encodes a key, or returns an error if the format cannot represent
+    /// the row.
+    fn encode_key(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_key_unchecked(row))
+    }
+
+    /// Encodes a value, or returns an error if the format cannot represent
+    /// the row.
+    fn encode_value(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        Ok(self.encode_value_unchecked(row))
+    }
+}
+
+/// Encodes the update batches and progress statements of the CDCv2 protocol.
+pub trait EncodeCdcV2 {
+    fn get_format_name(&self) -> &str;
+
+    fn encode_unchecked(&self, message: &Message<Row, Timestamp, Diff>) -> Vec<u8>;
 }
 
 /// Bundled information sufficient to encode Datums.
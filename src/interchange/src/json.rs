@@ -19,6 +19,10 @@ use serde_json::{json, Map};
 
 use crate::encode::{column_names_and_types, Encode, TypedDatum};
 
+pub mod envelope_cdc_v2;
+
+pub use envelope_cdc_v2 as cdc_v2;
+
 // Manages encoding of JSON-encoded bytes
 pub struct JsonEncoder {
     key_columns: Option<Vec<(ColumnName, ColumnType)>>,
@@ -122,6 +126,47 @@ where
     serde_json::value::Value::Object(value_fields)
 }
 
+/// Encodes a transaction record for a consistency topic as JSON.
+///
+/// The record has the same shape as the Avro record described by
+/// [`crate::avro::get_debezium_transaction_schema`], so consumers can treat
+/// Avro and JSON consistency topics alike.
+pub fn encode_transaction_record(
+    collection: &str,
+    id: &str,
+    status: &str,
+    message_count: Option<i64>,
+) -> Vec<u8> {
+    let data_collections = message_count.map(|count| {
+        json!([{
+            "data_collection": collection,
+            "event_count": count,
+        }])
+    });
+    json!({
+        "id": id,
+        "status": status,
+        "event_count": message_count,
+        "data_collections": data_collections,
+    })
+    .to_string()
+    .into_bytes()
+}
+
+/// Decodes a transaction record produced by [`encode_transaction_record`],
+/// returning the transaction ID if the record has status `END`.
+pub fn decode_transaction_end_record(bytes: &[u8]) -> Result<Option<String>, anyhow::Error> {
+    let record: serde_json::Value = serde_json::from_slice(bytes)?;
+    match (record.get("status"), record.get("id")) {
+        (Some(serde_json::Value::String(status)), Some(serde_json::Value::String(id)))
+            if status == "END" =>
+        {
+            Ok(Some(id.clone()))
+        }
+        _ => Ok(None),
+    }
+}
+
 pub trait ToJson {
     /// Transforms this value to a JSON value.
     fn json<F: FnMut() -> String>(self, namer: &mut F) -> serde_json::value::Value;
@@ -393,3 +438,40 @@ pub fn build_row_schema_json(
         "name": name
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transaction_record_roundtrip() {
+        let begin = encode_transaction_record("topic", "42", "BEGIN", None);
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&begin).unwrap(),
+            json!({
+                "id": "42",
+                "status": "BEGIN",
+                "event_count": null,
+                "data_collections": null,
+            })
+        );
+        assert_eq!(decode_transaction_end_record(&begin).unwrap(), None);
+
+        let end = encode_transaction_record("topic", "42", "END", Some(3));
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&end).unwrap(),
+            json!({
+                "id": "42",
+                "status": "END",
+                "event_count": 3,
+                "data_collections": [{"data_collection": "topic", "event_count": 3}],
+            })
+        );
+        assert_eq!(
+            decode_transaction_end_record(&end).unwrap(),
+            Some("42".into())
+        );
+
+        assert!(decode_transaction_end_record(b"not json").is_err());
+    }
+}
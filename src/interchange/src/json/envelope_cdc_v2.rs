@@ -0,0 +1,194 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logic for the JSON representation of the CDCv2 protocol.
+//!
+//! Each message is a JSON object with exactly one of the following keys:
+//!
+//! ```json
+//! {"updates": [{"data": {"id": 5, "price": 10}, "time": 5, "diff": 1}]}
+//! {"progress": {"lower": [0], "upper": [3], "counts": [{"time": 1, "count": 1}]}}
+//! ```
+//!
+//! As JSON carries no schema, the `data` field of each update may be any JSON
+//! value, and is decoded into a row with a single `jsonb` column. When
+//! encoding, `data` is the JSON object describing the row's columns.
+
+use anyhow::Context;
+use differential_dataflow::capture::{Message, Progress};
+use serde::{Deserialize, Serialize};
+
+use mz_repr::adt::jsonb::Jsonb;
+use mz_repr::{ColumnName, ColumnType, Diff, RelationDesc, Row, Timestamp};
+
+use super::encode_datums_as_json;
+use crate::encode::{column_names_and_types, EncodeCdcV2};
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "lowercase", deny_unknown_fields)]
+enum CdcV2Message {
+    Updates(Vec<Update>),
+    Progress(CdcV2Progress),
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Update {
+    data: serde_json::Value,
+    time: Timestamp,
+    diff: Diff,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct CdcV2Progress {
+    lower: Vec<Timestamp>,
+    upper: Vec<Timestamp>,
+    counts: Vec<Count>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct Count {
+    time: Timestamp,
+    count: usize,
+}
+
+/// Decodes a JSON-encoded CDCv2 message.
+pub fn decode(bytes: &[u8]) -> Result<Message<Row, Timestamp, Diff>, anyhow::Error> {
+    let message: CdcV2Message =
+        serde_json::from_slice(bytes).context("decoding JSON CDCv2 message")?;
+    Ok(match message {
+        CdcV2Message::Updates(updates) => Message::Updates(
+            updates
+                .into_iter()
+                .map(|update| {
+                    let data = Jsonb::from_serde_json(update.data)?.into_row();
+                    Ok((data, update.time, update.diff))
+                })
+                .collect::<Result<_, anyhow::Error>>()?,
+        ),
+        CdcV2Message::Progress(progress) => Message::Progress(Progress {
+            lower: progress.lower,
+            upper: progress.upper,
+            counts: progress
+                .counts
+                .into_iter()
+                .map(|count| (count.time, count.count))
+                .collect(),
+        }),
+    })
+}
+
+/// Encodes CDCv2 messages as JSON.
+#[derive(Debug)]
+pub struct Encoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+}
+
+impl Encoder {
+    /// Creates a new CDCv2 encoder from a relation description.
+    pub fn new(desc: RelationDesc) -> Self {
+        Self {
+            columns: column_names_and_types(desc),
+        }
+    }
+}
+
+impl EncodeCdcV2 for Encoder {
+    fn get_format_name(&self) -> &str {
+        "json"
+    }
+
+    fn encode_unchecked(&self, message: &Message<Row, Timestamp, Diff>) -> Vec<u8> {
+        let message = match message {
+            Message::Updates(updates) => CdcV2Message::Updates(
+                updates
+                    .iter()
+                    .map(|(data, time, diff)| Update {
+                        data: encode_datums_as_json(data.iter(), &self.columns, false),
+                        time: *time,
+                        diff: *diff,
+                    })
+                    .collect(),
+            ),
+            Message::Progress(progress) => CdcV2Message::Progress(CdcV2Progress {
+                lower: progress.lower.clone(),
+                upper: progress.upper.clone(),
+                counts: progress
+                    .counts
+                    .iter()
+                    .map(|(time, count)| Count {
+                        time: *time,
+                        count: *count,
+                    })
+                    .collect(),
+            }),
+        };
+        serde_json::to_vec(&message).expect("CDCv2 messages serialize to JSON")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::{Datum, ScalarType};
+
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        let message =
+            decode(br#"{"updates": [{"data": {"id": 5}, "time": 4, "diff": -1}]}"#).unwrap();
+        match message {
+            Message::Updates(updates) => {
+                assert_eq!(updates.len(), 1);
+                let (data, time, diff) = &updates[0];
+                assert!(matches!(data.unpack_first(), Datum::Map(_)));
+                assert_eq!((*time, *diff), (4, -1));
+            }
+            Message::Progress(_) => panic!("expected updates"),
+        }
+
+        let message = decode(
+            br#"{"progress": {"lower": [3], "upper": [10], "counts": [{"time": 4, "count": 1}]}}"#,
+        )
+        .unwrap();
+        match message {
+            Message::Progress(progress) => {
+                assert_eq!(progress.lower, vec![3]);
+                assert_eq!(progress.upper, vec![10]);
+                assert_eq!(progress.counts, vec![(4, 1)]);
+            }
+            Message::Updates(_) => panic!("expected progress"),
+        }
+
+        assert!(decode(br#"{"updates": [], "progress": null}"#).is_err());
+        assert!(decode(br#"{"array": []}"#).is_err());
+    }
+
+    #[test]
+    fn test_encode() {
+        let desc = RelationDesc::empty().with_column("id", ScalarType::Int64.nullable(false));
+        let encoder = Encoder::new(desc);
+
+        let updates = Message::Updates(vec![(Row::pack_slice(&[Datum::Int64(5)]), 4, -1)]);
+        assert_eq!(
+            encoder.encode_unchecked(&updates),
+            br#"{"updates":[{"data":{"id":5},"time":4,"diff":-1}]}"#
+        );
+
+        let progress = Message::Progress(Progress {
+            lower: vec![3],
+            upper: vec![10],
+            counts: vec![(4, 1)],
+        });
+        let encoded = encoder.encode_unchecked(&progress);
+        assert!(matches!(decode(&encoded).unwrap(), Message::Progress(_)));
+    }
+}
@@ -12,8 +12,9 @@
 #![warn(missing_debug_implementations)]
 
 pub mod avro;
-mod confluent;
+pub mod confluent;
 pub mod encode;
 pub mod envelopes;
 pub mod json;
+pub mod parquet;
 pub mod protobuf;
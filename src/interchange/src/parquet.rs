@@ -0,0 +1,699 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Decoding and encoding of Apache Parquet files.
+//!
+//! Parquet files are self-describing: their footer contains the schema of the
+//! data. When a Parquet source is created, the schema of a sample file is
+//! converted into a list of [`ParquetColumn`]s, which determines the
+//! [`RelationDesc`] of the source. Every file that is subsequently ingested is
+//! checked against that list of columns.
+//!
+//! File sinks encode updates with a [`ParquetEncoder`], and write them with
+//! persist's Parquet writer into files that Parquet sources can read back.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::{Read, Seek};
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use arrow2::array::{Array, BinaryArray, BooleanArray, PrimitiveArray, Utf8Array};
+use arrow2::datatypes::{DataType, Field, Schema, TimeUnit};
+use arrow2::io::parquet::read::{infer_schema, read_metadata, RecordReader};
+use arrow2::record_batch::RecordBatch;
+use arrow2::types::NativeType;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use mz_repr::{ColumnName, ColumnType, Datum, Diff, RelationDesc, Row, ScalarType, Timestamp};
+
+use crate::encode::{column_names_and_types, TypedDatum};
+use crate::json::ToJson;
+
+/// Parquet dates count days since the Unix epoch, which is day 719,163 of the
+/// common era.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// A column of a Parquet source.
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub struct ParquetColumn {
+    /// The name of the column in the Parquet schema.
+    pub name: String,
+    /// The type of the column.
+    #[serde(rename = "type")]
+    pub ty: ParquetColumnType,
+    /// Whether the column may contain nulls.
+    pub nullable: bool,
+}
+
+/// The types of Parquet columns that can be ingested.
+///
+/// Each variant covers all Parquet logical types that are decoded into the
+/// same [`ScalarType`].
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetColumnType {
+    Boolean,
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Text,
+    Bytes,
+    Date,
+    Timestamp,
+    TimestampTz,
+}
+
+impl ParquetColumnType {
+    /// Determines the column type for an Arrow data type, as produced by
+    /// reading a Parquet file.
+    fn from_arrow(data_type: &DataType) -> Option<Self> {
+        Some(match data_type {
+            DataType::Boolean => ParquetColumnType::Boolean,
+            DataType::Int8 | DataType::Int16 | DataType::UInt8 => ParquetColumnType::Int16,
+            DataType::Int32 | DataType::UInt16 => ParquetColumnType::Int32,
+            DataType::Int64 | DataType::UInt32 => ParquetColumnType::Int64,
+            DataType::Float32 => ParquetColumnType::Float32,
+            DataType::Float64 => ParquetColumnType::Float64,
+            DataType::Utf8 | DataType::LargeUtf8 => ParquetColumnType::Text,
+            DataType::Binary | DataType::LargeBinary => ParquetColumnType::Bytes,
+            DataType::Date32 => ParquetColumnType::Date,
+            DataType::Timestamp(_, None) => ParquetColumnType::Timestamp,
+            DataType::Timestamp(_, Some(_)) => ParquetColumnType::TimestampTz,
+            _ => return None,
+        })
+    }
+
+    /// Returns the scalar type that values of this column are decoded into.
+    pub fn scalar_type(&self) -> ScalarType {
+        match self {
+            ParquetColumnType::Boolean => ScalarType::Bool,
+            ParquetColumnType::Int16 => ScalarType::Int16,
+            ParquetColumnType::Int32 => ScalarType::Int32,
+            ParquetColumnType::Int64 => ScalarType::Int64,
+            ParquetColumnType::Float32 => ScalarType::Float32,
+            ParquetColumnType::Float64 => ScalarType::Float64,
+            ParquetColumnType::Text => ScalarType::String,
+            ParquetColumnType::Bytes => ScalarType::Bytes,
+            ParquetColumnType::Date => ScalarType::Date,
+            ParquetColumnType::Timestamp => ScalarType::Timestamp,
+            ParquetColumnType::TimestampTz => ScalarType::TimestampTz,
+        }
+    }
+}
+
+impl fmt::Display for ParquetColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            ParquetColumnType::Boolean => "boolean",
+            ParquetColumnType::Int16 => "int16",
+            ParquetColumnType::Int32 => "int32",
+            ParquetColumnType::Int64 => "int64",
+            ParquetColumnType::Float32 => "float32",
+            ParquetColumnType::Float64 => "float64",
+            ParquetColumnType::Text => "text",
+            ParquetColumnType::Bytes => "bytes",
+            ParquetColumnType::Date => "date",
+            ParquetColumnType::Timestamp => "timestamp",
+            ParquetColumnType::TimestampTz => "timestamptz",
+        })
+    }
+}
+
+/// Converts the schema of a Parquet file into a list of columns.
+fn schema_to_columns(schema: &Schema) -> Result<Vec<ParquetColumn>, anyhow::Error> {
+    schema
+        .fields()
+        .iter()
+        .map(|field| {
+            let ty = ParquetColumnType::from_arrow(field.data_type()).ok_or_else(|| {
+                anyhow!(
+                    "Parquet column {} has unsupported type {:?}",
+                    field.name(),
+                    field.data_type()
+                )
+            })?;
+            Ok(ParquetColumn {
+                name: field.name().clone(),
+                ty,
+                nullable: field.is_nullable(),
+            })
+        })
+        .collect()
+}
+
+/// Reads the columns of the Parquet file in `reader` from its footer.
+pub fn read_schema<R: Read + Seek>(reader: &mut R) -> Result<Vec<ParquetColumn>, anyhow::Error> {
+    let metadata = read_metadata(reader)?;
+    schema_to_columns(&infer_schema(&metadata)?)
+}
+
+/// Serializes a list of columns, e.g. for storage in the catalog.
+pub fn encode_schema(columns: &[ParquetColumn]) -> String {
+    serde_json::to_string(columns).expect("serializing Parquet columns cannot fail")
+}
+
+/// Parses a list of columns previously serialized by [`encode_schema`].
+pub fn parse_schema(schema: &str) -> Result<Vec<ParquetColumn>, anyhow::Error> {
+    let columns: Vec<ParquetColumn> = serde_json::from_str(schema)
+        .map_err(|e| anyhow!("invalid Parquet schema {}: {}", schema, e))?;
+    if columns.is_empty() {
+        bail!("Parquet schema must contain at least one column");
+    }
+    Ok(columns)
+}
+
+/// Computes the [`RelationDesc`] of a Parquet source with the given columns.
+pub fn columns_to_relationdesc(columns: &[ParquetColumn]) -> RelationDesc {
+    columns.iter().fold(RelationDesc::empty(), |desc, column| {
+        desc.with_column(
+            column.name.as_str(),
+            column.ty.scalar_type().nullable(column.nullable),
+        )
+    })
+}
+
+/// Decodes the rows of a Parquet file, one row group at a time.
+///
+/// Only the columns that are demanded are read from the file. Columns that are
+/// not demanded, and nullable columns that are missing from the file, are
+/// decoded as nulls.
+pub struct ParquetReader<R: Read + Seek> {
+    records: RecordReader<R>,
+    /// The columns to decode, in output order.
+    columns: Vec<ParquetColumn>,
+    /// For each column in `columns`, its position in the record batches
+    /// produced by `records`, if it is read at all.
+    positions: Vec<Option<usize>>,
+    batch: Option<RecordBatch>,
+    next_row: usize,
+    row_buf: Row,
+}
+
+impl<R: Read + Seek> fmt::Debug for ParquetReader<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ParquetReader")
+            .field("columns", &self.columns)
+            .field("positions", &self.positions)
+            .field("next_row", &self.next_row)
+            .finish()
+    }
+}
+
+impl<R: Read + Seek> ParquetReader<R> {
+    /// Prepares reading the Parquet file in `reader`.
+    ///
+    /// `demanded` lists the indexes of the columns that will be used, or
+    /// `None` if all columns are used. Indexes that do not refer to a column
+    /// are ignored.
+    pub fn new(
+        mut reader: R,
+        columns: Vec<ParquetColumn>,
+        demanded: Option<&[usize]>,
+    ) -> Result<Self, anyhow::Error> {
+        let file_columns = read_schema(&mut reader)?;
+        let file_positions: HashMap<_, _> = file_columns
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.name.as_str(), (i, c)))
+            .collect();
+
+        // Determine which columns of the file to read.
+        let mut projection = vec![];
+        for (i, column) in columns.iter().enumerate() {
+            match file_positions.get(column.name.as_str()) {
+                Some((pos, file_column)) => {
+                    if file_column.ty != column.ty {
+                        bail!(
+                            "Parquet column {} has type {}, but {} was expected",
+                            column.name,
+                            file_column.ty,
+                            column.ty
+                        );
+                    }
+                    if demanded.map(|d| d.contains(&i)).unwrap_or(true) {
+                        projection.push(*pos);
+                    }
+                }
+                None if column.nullable => (),
+                None => bail!(
+                    "Parquet file is missing non-nullable column {}",
+                    column.name
+                ),
+            }
+        }
+        projection.sort_unstable();
+
+        let positions = columns
+            .iter()
+            .map(|column| {
+                file_positions
+                    .get(column.name.as_str())
+                    .and_then(|(pos, _)| projection.iter().position(|p| p == pos))
+            })
+            .collect();
+
+        let records = RecordReader::try_new(reader, Some(projection), None, None, None)?;
+        Ok(ParquetReader {
+            records,
+            columns,
+            positions,
+            batch: None,
+            next_row: 0,
+            row_buf: Row::default(),
+        })
+    }
+
+    fn decode_row(&mut self) -> Result<Row, anyhow::Error> {
+        let batch = self.batch.as_ref().expect("known to exist");
+        let row = self.next_row;
+        self.next_row += 1;
+        {
+            let mut packer = self.row_buf.packer();
+            for (column, pos) in self.columns.iter().zip(self.positions.iter()) {
+                match pos {
+                    None => packer.push(Datum::Null),
+                    Some(pos) => {
+                        let array = &*batch.columns()[*pos];
+                        let datum = array_datum(array, row).map_err(|e| {
+                            anyhow!("decoding Parquet column {}: {}", column.name, e)
+                        })?;
+                        if datum.is_null() && !column.nullable {
+                            bail!("Parquet column {} contains unexpected null", column.name);
+                        }
+                        packer.push(datum);
+                    }
+                }
+            }
+        }
+        Ok(self.row_buf.clone())
+    }
+}
+
+impl<R: Read + Seek> Iterator for ParquetReader<R> {
+    type Item = Result<Row, anyhow::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let exhausted = match &self.batch {
+                Some(batch) => self.next_row >= batch.num_rows(),
+                None => true,
+            };
+            if !exhausted {
+                return Some(self.decode_row());
+            }
+            // Move on to the next row group.
+            match self.records.next()? {
+                Ok(batch) => {
+                    self.batch = Some(batch);
+                    self.next_row = 0;
+                }
+                Err(e) => return Some(Err(e.into())),
+            }
+        }
+    }
+}
+
+fn downcast<T: 'static>(array: &dyn Array) -> Result<&T, anyhow::Error> {
+    array
+        .as_any()
+        .downcast_ref::<T>()
+        .ok_or_else(|| anyhow!("unexpected array for type {:?}", array.data_type()))
+}
+
+fn primitive<T: NativeType>(array: &dyn Array, i: usize) -> Result<T, anyhow::Error> {
+    Ok(downcast::<PrimitiveArray<T>>(array)?.value(i))
+}
+
+fn timestamp(unit: &TimeUnit, value: i64) -> Result<NaiveDateTime, anyhow::Error> {
+    let (secs, nanos) = match unit {
+        TimeUnit::Second => (value, 0),
+        TimeUnit::Millisecond => (value.div_euclid(1_000), value.rem_euclid(1_000) * 1_000_000),
+        TimeUnit::Microsecond => (
+            value.div_euclid(1_000_000),
+            value.rem_euclid(1_000_000) * 1_000,
+        ),
+        TimeUnit::Nanosecond => (
+            value.div_euclid(1_000_000_000),
+            value.rem_euclid(1_000_000_000),
+        ),
+    };
+    NaiveDateTime::from_timestamp_opt(secs, nanos as u32)
+        .ok_or_else(|| anyhow!("timestamp out of range"))
+}
+
+/// Decodes the value at index `i` of `array`.
+///
+/// The array's type must be one that [`ParquetColumnType::from_arrow`]
+/// accepts.
+fn array_datum(array: &dyn Array, i: usize) -> Result<Datum, anyhow::Error> {
+    if array.is_null(i) {
+        return Ok(Datum::Null);
+    }
+    Ok(match array.data_type() {
+        DataType::Boolean => Datum::from(downcast::<BooleanArray>(array)?.value(i)),
+        DataType::Int8 => Datum::from(i16::from(primitive::<i8>(array, i)?)),
+        DataType::Int16 => Datum::from(primitive::<i16>(array, i)?),
+        DataType::UInt8 => Datum::from(i16::from(primitive::<u8>(array, i)?)),
+        DataType::Int32 => Datum::from(primitive::<i32>(array, i)?),
+        DataType::UInt16 => Datum::from(i32::from(primitive::<u16>(array, i)?)),
+        DataType::Int64 => Datum::from(primitive::<i64>(array, i)?),
+        DataType::UInt32 => Datum::from(i64::from(primitive::<u32>(array, i)?)),
+        DataType::Float32 => Datum::from(primitive::<f32>(array, i)?),
+        DataType::Float64 => Datum::from(primitive::<f64>(array, i)?),
+        DataType::Utf8 => Datum::from(downcast::<Utf8Array<i32>>(array)?.value(i)),
+        DataType::LargeUtf8 => Datum::from(downcast::<Utf8Array<i64>>(array)?.value(i)),
+        DataType::Binary => Datum::from(downcast::<BinaryArray<i32>>(array)?.value(i)),
+        DataType::LargeBinary => Datum::from(downcast::<BinaryArray<i64>>(array)?.value(i)),
+        DataType::Date32 => {
+            let days = primitive::<i32>(array, i)?;
+            let date = NaiveDate::from_num_days_from_ce_opt(days + UNIX_EPOCH_DAYS_FROM_CE)
+                .ok_or_else(|| anyhow!("date out of range"))?;
+            Datum::from(date)
+        }
+        DataType::Timestamp(unit, tz) => {
+            let ts = timestamp(unit, primitive::<i64>(array, i)?)?;
+            match tz {
+                None => Datum::from(ts),
+                Some(_) => Datum::from(DateTime::<Utc>::from_utc(ts, Utc)),
+            }
+        }
+        data_type => bail!("unsupported type {:?}", data_type),
+    })
+}
+
+/// The number of updates in each row group of an encoded file.
+const ROW_GROUP_SIZE: usize = 65_536;
+
+/// Encodes batches of updates as Arrow record batches, to be written as
+/// Parquet files.
+///
+/// Each batch has one column per column of the relation, followed by a `time`
+/// and a `diff` column. Types without a native Parquet representation are
+/// written as text, using their JSON encoding.
+#[derive(Debug)]
+pub struct ParquetEncoder {
+    columns: Vec<(ColumnName, ColumnType)>,
+    schema: Arc<Schema>,
+}
+
+impl ParquetEncoder {
+    /// Creates a new encoder for updates to a relation with the given
+    /// description.
+    pub fn new(desc: RelationDesc) -> Self {
+        let columns = column_names_and_types(desc);
+        let mut fields: Vec<_> = columns
+            .iter()
+            .map(|(name, typ)| {
+                Field::new(name.as_str(), arrow_type(&typ.scalar_type), typ.nullable)
+            })
+            .collect();
+        fields.push(Field::new("time", DataType::Int64, false));
+        fields.push(Field::new("diff", DataType::Int64, false));
+        ParquetEncoder {
+            columns,
+            schema: Arc::new(Schema::new(fields)),
+        }
+    }
+
+    /// Returns the schema of the record batches produced by this encoder.
+    pub fn schema(&self) -> &Schema {
+        &self.schema
+    }
+
+    /// Encodes `updates` as record batches of at most `ROW_GROUP_SIZE` rows,
+    /// each of which makes up one row group of a Parquet file.
+    pub fn encode(
+        &self,
+        updates: &[(Row, Timestamp, Diff)],
+    ) -> Result<Vec<RecordBatch>, anyhow::Error> {
+        updates
+            .chunks(ROW_GROUP_SIZE)
+            .map(|chunk| self.encode_batch(chunk))
+            .collect()
+    }
+
+    fn encode_batch(
+        &self,
+        updates: &[(Row, Timestamp, Diff)],
+    ) -> Result<RecordBatch, anyhow::Error> {
+        let rows: Vec<Vec<Datum>> = updates.iter().map(|(row, _, _)| row.unpack()).collect();
+        let mut arrays: Vec<Arc<dyn Array>> = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, (_name, typ))| encode_column(typ, rows.iter().map(|datums| datums[i])))
+            .collect();
+        arrays.push(Arc::new(PrimitiveArray::<i64>::from(
+            updates
+                .iter()
+                .map(|(_, time, _)| Some(*time as i64))
+                .collect::<Vec<_>>(),
+        )));
+        arrays.push(Arc::new(PrimitiveArray::<i64>::from(
+            updates
+                .iter()
+                .map(|(_, _, diff)| Some(*diff as i64))
+                .collect::<Vec<_>>(),
+        )));
+        Ok(RecordBatch::try_new(Arc::clone(&self.schema), arrays)?)
+    }
+}
+
+/// Determines the Arrow type that values of a scalar type are encoded as.
+///
+/// The result is always a type that [`ParquetColumnType::from_arrow`] accepts.
+fn arrow_type(scalar_type: &ScalarType) -> DataType {
+    match scalar_type {
+        ScalarType::Bool => DataType::Boolean,
+        ScalarType::Int16 => DataType::Int16,
+        ScalarType::Int32 => DataType::Int32,
+        ScalarType::Int64 => DataType::Int64,
+        ScalarType::Float32 => DataType::Float32,
+        ScalarType::Float64 => DataType::Float64,
+        ScalarType::Bytes => DataType::Binary,
+        ScalarType::Date => DataType::Date32,
+        ScalarType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        ScalarType::TimestampTz => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+        }
+        _ => DataType::Utf8,
+    }
+}
+
+/// Encodes a column of datums of the given type into an array of the type
+/// chosen by [`arrow_type`].
+fn encode_column<'a, I>(typ: &ColumnType, datums: I) -> Arc<dyn Array>
+where
+    I: Iterator<Item = Datum<'a>>,
+{
+    fn values<'a, I, T, F>(datums: I, mut f: F) -> Vec<Option<T>>
+    where
+        I: Iterator<Item = Datum<'a>>,
+        F: FnMut(Datum<'a>) -> T,
+    {
+        datums
+            .map(|datum| (!datum.is_null()).then(|| f(datum)))
+            .collect()
+    }
+
+    fn micros(ts: NaiveDateTime) -> i64 {
+        ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros())
+    }
+
+    match &typ.scalar_type {
+        ScalarType::Bool => Arc::new(BooleanArray::from(values(datums, |d| d.unwrap_bool()))),
+        ScalarType::Int16 => Arc::new(PrimitiveArray::<i16>::from(values(datums, |d| {
+            d.unwrap_int16()
+        }))),
+        ScalarType::Int32 => Arc::new(PrimitiveArray::<i32>::from(values(datums, |d| {
+            d.unwrap_int32()
+        }))),
+        ScalarType::Int64 => Arc::new(PrimitiveArray::<i64>::from(values(datums, |d| {
+            d.unwrap_int64()
+        }))),
+        ScalarType::Float32 => Arc::new(PrimitiveArray::<f32>::from(values(datums, |d| {
+            d.unwrap_float32()
+        }))),
+        ScalarType::Float64 => Arc::new(PrimitiveArray::<f64>::from(values(datums, |d| {
+            d.unwrap_float64()
+        }))),
+        ScalarType::Bytes => Arc::new(BinaryArray::<i32>::from(values(datums, |d| {
+            d.unwrap_bytes()
+        }))),
+        ScalarType::Date => Arc::new(
+            PrimitiveArray::<i32>::from(values(datums, |d| {
+                d.unwrap_date().num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE
+            }))
+            .to(DataType::Date32),
+        ),
+        ScalarType::Timestamp => Arc::new(
+            PrimitiveArray::<i64>::from(values(datums, |d| micros(d.unwrap_timestamp())))
+                .to(arrow_type(&typ.scalar_type)),
+        ),
+        ScalarType::TimestampTz => Arc::new(
+            PrimitiveArray::<i64>::from(values(datums, |d| {
+                micros(d.unwrap_timestamptz().naive_utc())
+            }))
+            .to(arrow_type(&typ.scalar_type)),
+        ),
+        _ => {
+            let mut namer = || "record".to_owned();
+            Arc::new(Utf8Array::<i32>::from(values(
+                datums,
+                |d| match TypedDatum::new(d, typ.clone()).json(&mut namer) {
+                    serde_json::Value::String(s) => s,
+                    value => value.to_string(),
+                },
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_persist::indexed::columnar::parquet::write_parquet_file;
+
+    use super::*;
+
+    #[test]
+    fn schema_roundtrip() {
+        let columns = vec![
+            ParquetColumn {
+                name: "id".into(),
+                ty: ParquetColumnType::Int64,
+                nullable: false,
+            },
+            ParquetColumn {
+                name: "created_at".into(),
+                ty: ParquetColumnType::TimestampTz,
+                nullable: true,
+            },
+        ];
+        let encoded = encode_schema(&columns);
+        assert_eq!(
+            encoded,
+            r#"[{"name":"id","type":"int64","nullable":false},{"name":"created_at","type":"timestamptz","nullable":true}]"#
+        );
+        assert_eq!(parse_schema(&encoded).unwrap(), columns);
+
+        let desc = columns_to_relationdesc(&columns);
+        assert_eq!(
+            desc.typ().column_types,
+            vec![
+                ScalarType::Int64.nullable(false),
+                ScalarType::TimestampTz.nullable(true)
+            ]
+        );
+    }
+
+    #[test]
+    fn timestamp_units() {
+        let expected = NaiveDate::from_ymd(1969, 12, 31).and_hms_milli(23, 59, 59, 500);
+        assert_eq!(timestamp(&TimeUnit::Millisecond, -500).unwrap(), expected);
+        assert_eq!(
+            timestamp(&TimeUnit::Microsecond, -500_000).unwrap(),
+            expected
+        );
+        assert_eq!(
+            timestamp(&TimeUnit::Nanosecond, -500_000_000).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn encode_roundtrip() {
+        let desc = RelationDesc::empty()
+            .with_column("id", ScalarType::Int64.nullable(false))
+            .with_column("name", ScalarType::String.nullable(true))
+            .with_column("born", ScalarType::Date.nullable(false))
+            .with_column(
+                "balance",
+                ScalarType::Numeric { max_scale: None }.nullable(false),
+            );
+        let born = NaiveDate::from_ymd(1969, 7, 20);
+        let updates = vec![
+            (
+                Row::pack_slice(&[
+                    Datum::Int64(1),
+                    Datum::String("buzz"),
+                    Datum::Date(born),
+                    Datum::from(mz_repr::adt::numeric::Numeric::from(12)),
+                ]),
+                5,
+                1,
+            ),
+            (
+                Row::pack_slice(&[
+                    Datum::Int64(2),
+                    Datum::Null,
+                    Datum::Date(born),
+                    Datum::from(mz_repr::adt::numeric::Numeric::from(-3)),
+                ]),
+                7,
+                -1,
+            ),
+        ];
+
+        let encoder = ParquetEncoder::new(desc);
+        let mut buf = vec![];
+        write_parquet_file(
+            &mut buf,
+            encoder.schema(),
+            encoder.encode(&updates).unwrap().into_iter(),
+            None,
+        )
+        .unwrap();
+
+        let mut reader = std::io::Cursor::new(buf);
+        let columns = read_schema(&mut reader).unwrap();
+        assert_eq!(
+            columns
+                .iter()
+                .map(|c| (c.name.as_str(), c.ty))
+                .collect::<Vec<_>>(),
+            vec![
+                ("id", ParquetColumnType::Int64),
+                ("name", ParquetColumnType::Text),
+                ("born", ParquetColumnType::Date),
+                ("balance", ParquetColumnType::Text),
+                ("time", ParquetColumnType::Int64),
+                ("diff", ParquetColumnType::Int64),
+            ]
+        );
+        let rows = ParquetReader::new(reader, columns, None)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                Row::pack_slice(&[
+                    Datum::Int64(1),
+                    Datum::String("buzz"),
+                    Datum::Date(born),
+                    Datum::String("12"),
+                    Datum::Int64(5),
+                    Datum::Int64(1),
+                ]),
+                Row::pack_slice(&[
+                    Datum::Int64(2),
+                    Datum::Null,
+                    Datum::Date(born),
+                    Datum::String("-3"),
+                    Datum::Int64(7),
+                    Datum::Int64(-1),
+                ]),
+            ]
+        );
+    }
+}
@@ -10,21 +10,30 @@
 use std::collections::HashSet;
 
 use anyhow::{anyhow, bail, Context};
-
+use prost::Message;
 use prost_reflect::{
     Cardinality, DynamicMessage, FieldDescriptor, FileDescriptor, Kind, MessageDescriptor,
     ReflectMessage, Value,
 };
+use prost_types::{DescriptorProto, FileDescriptorSet};
 
 use mz_ore::str::StrExt;
 use mz_repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
 
+mod encode;
+pub mod envelope_cdc_v2;
+
+pub use envelope_cdc_v2 as cdc_v2;
+
+pub use self::encode::{ProtobufEncoder, ProtobufSchemaGenerator};
+
 /// A decoded description of the schema of a Protobuf message.
 #[derive(Debug, PartialEq)]
 pub struct DecodedDescriptors {
     message_descriptor: MessageDescriptor,
     columns: Vec<(ColumnName, ColumnType)>,
     message_name: String,
+    message_index_path: Vec<i32>,
 }
 
 impl DecodedDescriptors {
@@ -46,13 +55,39 @@ impl DecodedDescriptors {
             let ty = derive_column_type(&mut seen_messages, &field)?;
             columns.push((name, ty))
         }
+        let message_index_path = message_index_path(bytes, &message_name)?;
         Ok(DecodedDescriptors {
             message_descriptor,
             columns,
             message_name,
+            message_index_path,
         })
     }
 
+    /// Returns the path of indexes that identifies the message within its
+    /// file in the Confluent wire format.
+    pub fn message_index_path(&self) -> &[i32] {
+        &self.message_index_path
+    }
+
+    /// Checks that the message index path of a Confluent-style datum refers to
+    /// the message described by these descriptors.
+    pub(crate) fn check_message_index_path(
+        &self,
+        message_index_path: &[i32],
+    ) -> Result<(), anyhow::Error> {
+        if message_index_path != self.message_index_path {
+            bail!(
+                "Confluent-style protobuf message index path {:?} does not refer to message {}, \
+                whose path is {:?}",
+                message_index_path,
+                self.message_name.quoted(),
+                self.message_index_path,
+            );
+        }
+        Ok(())
+    }
+
     /// Describes the columns in the message.
     ///
     /// In other words, the return value describes the shape of the rows that
@@ -103,7 +138,14 @@ impl Decoder {
             // allocations).
             //
             // [0]: https://developers.google.com/protocol-buffers/docs/overview
-            let (_schema_id, adjusted_bytes) = crate::confluent::extract_protobuf_header(bytes)?;
+            //
+            // The message index path, on the other hand, must identify the
+            // message we know about, as the schema may contain several
+            // messages.
+            let (_schema_id, message_index_path, adjusted_bytes) =
+                crate::confluent::extract_protobuf_header(bytes)?;
+            self.descriptors
+                .check_message_index_path(&message_index_path)?;
             bytes = adjusted_bytes;
         }
         let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
@@ -113,6 +155,43 @@ impl Decoder {
     }
 }
 
+/// Computes the path of indexes that identifies the message named
+/// `message_name` in an encoded `FileDescriptorSet`, as used by the Confluent
+/// wire format: the index of the top-level message within the file that
+/// declares it, followed by the indexes of each nested message.
+pub fn message_index_path(bytes: &[u8], message_name: &str) -> Result<Vec<i32>, anyhow::Error> {
+    fn find(messages: &[DescriptorProto], prefix: &str, message_name: &str) -> Option<Vec<i32>> {
+        for (i, message) in messages.iter().enumerate() {
+            let name = if prefix.is_empty() {
+                message.name().to_owned()
+            } else {
+                format!("{}.{}", prefix, message.name())
+            };
+            let i = i32::try_from(i).expect("too many protobuf messages");
+            if name == message_name {
+                return Some(vec![i]);
+            }
+            if let Some(mut path) = find(&message.nested_type, &name, message_name) {
+                path.insert(0, i);
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    let fds = FileDescriptorSet::decode(bytes).context("decoding file descriptor set")?;
+    let name = message_name.strip_prefix('.').unwrap_or(message_name);
+    fds.file
+        .iter()
+        .find_map(|file| find(&file.message_type, file.package(), name))
+        .ok_or_else(|| {
+            anyhow!(
+                "protobuf message {} not found in file descriptor set",
+                message_name.quoted(),
+            )
+        })
+}
+
 fn derive_column_type(
     seen_messages: &mut HashSet<String>,
     field: &FieldDescriptor,
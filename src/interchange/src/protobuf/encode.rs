@@ -0,0 +1,841 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logic for encoding rows as Protobuf messages.
+//!
+//! The schema of the messages is derived from the columns being encoded. Each
+//! column becomes a field, numbered in column order starting at 1. Nullable
+//! columns become `optional` fields that are omitted when null, records become
+//! messages, lists and arrays become `repeated` fields, and maps become
+//! `map<string, V>` fields. Protobuf has no way to represent null elements of
+//! lists or null values of maps, so rows that contain them fail to encode.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Write};
+
+use anyhow::bail;
+use chrono::{Datelike, NaiveDateTime, Timelike};
+use prost::encoding::{encode_key, encode_varint, WireType};
+use prost::Message;
+use prost_types::field_descriptor_proto::{Label, Type};
+use prost_types::{
+    DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet, MessageOptions,
+    OneofDescriptorProto,
+};
+
+use mz_repr::adt::char;
+use mz_repr::adt::jsonb::JsonbRef;
+use mz_repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::encode::{column_names_and_types, Encode};
+
+/// The number of days between 0001-01-01 and the Unix epoch.
+const UNIX_EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// The largest field number below the range that Protobuf reserves for its own
+/// use.
+const MAX_FIELD_NUMBER: usize = 18_999;
+
+/// The schema of a message, along with the columns it was derived from.
+struct MessageInfo {
+    columns: Vec<(ColumnName, ColumnType)>,
+    file: FileDescriptorProto,
+}
+
+impl MessageInfo {
+    fn new(name: &str, columns: Vec<(ColumnName, ColumnType)>) -> Result<Self, anyhow::Error> {
+        let mut builder = SchemaBuilder {
+            messages: vec![],
+            seen: HashMap::new(),
+            next_record: 0,
+        };
+        builder.seen.insert(name.to_owned(), columns.clone());
+        builder.add_message(name.to_owned(), &columns)?;
+        let file = FileDescriptorProto {
+            name: Some(format!("{}.proto", name)),
+            syntax: Some("proto3".into()),
+            message_type: builder.messages,
+            ..Default::default()
+        };
+        Ok(MessageInfo { columns, file })
+    }
+
+    fn descriptors(&self) -> Vec<u8> {
+        FileDescriptorSet {
+            file: vec![self.file.clone()],
+        }
+        .encode_to_vec()
+    }
+}
+
+/// Generates key and value Protobuf schemas.
+pub struct ProtobufSchemaGenerator {
+    key_info: Option<MessageInfo>,
+    value_info: MessageInfo,
+}
+
+impl ProtobufSchemaGenerator {
+    /// Derives the schemas for the given key and value relations.
+    ///
+    /// Returns an error if a relation contains a type that cannot be
+    /// represented in Protobuf, like a list of lists.
+    pub fn new(
+        key_desc: Option<RelationDesc>,
+        value_desc: RelationDesc,
+        include_transaction: bool,
+    ) -> Result<Self, anyhow::Error> {
+        let mut value_columns = column_names_and_types(value_desc);
+        if include_transaction {
+            value_columns.push((
+                "transaction".into(),
+                ColumnType {
+                    nullable: false,
+                    scalar_type: ScalarType::Record {
+                        fields: vec![(
+                            "id".into(),
+                            ColumnType {
+                                scalar_type: ScalarType::String,
+                                nullable: false,
+                            },
+                        )],
+                        custom_oid: None,
+                        custom_name: Some("transaction".to_string()),
+                    },
+                },
+            ));
+        }
+        let value_info = MessageInfo::new("envelope", value_columns)?;
+        let key_info = key_desc
+            .map(|key_desc| MessageInfo::new("row", column_names_and_types(key_desc)))
+            .transpose()?;
+        Ok(ProtobufSchemaGenerator {
+            key_info,
+            value_info,
+        })
+    }
+
+    /// Returns the `.proto` source of the value schema. The first message in
+    /// the schema describes the values.
+    pub fn value_schema(&self) -> String {
+        render_proto(&self.value_info.file)
+    }
+
+    /// Returns the `.proto` source of the key schema, if there is a key. The
+    /// first message in the schema describes the keys.
+    pub fn key_schema(&self) -> Option<String> {
+        self.key_info.as_ref().map(|info| render_proto(&info.file))
+    }
+
+    /// Returns the value schema as an encoded `FileDescriptorSet`.
+    pub fn value_descriptors(&self) -> Vec<u8> {
+        self.value_info.descriptors()
+    }
+
+    /// Returns the key schema as an encoded `FileDescriptorSet`, if there is a
+    /// key.
+    pub fn key_descriptors(&self) -> Option<Vec<u8>> {
+        self.key_info.as_ref().map(|info| info.descriptors())
+    }
+}
+
+impl fmt::Debug for ProtobufSchemaGenerator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProtobufSchemaGenerator")
+            .field("value_schema", &self.value_schema())
+            .finish()
+    }
+}
+
+/// Manages encoding of Protobuf-encoded bytes.
+#[derive(Debug)]
+pub struct ProtobufEncoder {
+    schema_generator: ProtobufSchemaGenerator,
+    key_schema_id: Option<i32>,
+    value_schema_id: Option<i32>,
+}
+
+impl ProtobufEncoder {
+    /// Constructs a new encoder.
+    ///
+    /// If schema IDs are provided, messages are framed in the Confluent wire
+    /// format.
+    pub fn new(
+        schema_generator: ProtobufSchemaGenerator,
+        key_schema_id: Option<i32>,
+        value_schema_id: Option<i32>,
+    ) -> Self {
+        ProtobufEncoder {
+            schema_generator,
+            key_schema_id,
+            value_schema_id,
+        }
+    }
+}
+
+impl Encode for ProtobufEncoder {
+    fn get_format_name(&self) -> &str {
+        "protobuf"
+    }
+
+    fn encode_key_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_key(row).expect("key must be encodable")
+    }
+
+    fn encode_value_unchecked(&self, row: Row) -> Vec<u8> {
+        self.encode_value(row).expect("value must be encodable")
+    }
+
+    fn encode_key(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let info = self
+            .schema_generator
+            .key_info
+            .as_ref()
+            .expect("key schema must exist");
+        encode_message(self.key_schema_id, &info.columns, row)
+    }
+
+    fn encode_value(&self, row: Row) -> Result<Vec<u8>, anyhow::Error> {
+        let info = &self.schema_generator.value_info;
+        encode_message(self.value_schema_id, &info.columns, row)
+    }
+}
+
+fn encode_message(
+    schema_id: Option<i32>,
+    columns: &[(ColumnName, ColumnType)],
+    row: Row,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut buf = vec![];
+    if let Some(schema_id) = schema_id {
+        // The encoded message is always the first message in its schema.
+        crate::confluent::encode_protobuf_header(&mut buf, schema_id, &[0]);
+    }
+    encode_fields(&mut buf, columns, row.iter())?;
+    Ok(buf)
+}
+
+/// Builds the messages of a Protobuf schema.
+struct SchemaBuilder {
+    messages: Vec<DescriptorProto>,
+    /// The fields of each message that has been added, so that identical
+    /// record types share a message.
+    seen: HashMap<String, Vec<(ColumnName, ColumnType)>>,
+    next_record: usize,
+}
+
+impl SchemaBuilder {
+    /// Adds a message named `name` with a field for each of the columns,
+    /// followed by any messages for records nested within the columns.
+    fn add_message(
+        &mut self,
+        name: String,
+        columns: &[(ColumnName, ColumnType)],
+    ) -> Result<(), anyhow::Error> {
+        if columns.len() > MAX_FIELD_NUMBER {
+            bail!(
+                "Protobuf messages can have at most {} fields, but {} would have {}",
+                MAX_FIELD_NUMBER,
+                name,
+                columns.len()
+            );
+        }
+
+        // Reserve the position of the message before any nested messages are
+        // added.
+        let index = self.messages.len();
+        self.messages.push(DescriptorProto::default());
+
+        let mut message = DescriptorProto {
+            name: Some(name),
+            ..Default::default()
+        };
+        let mut field_names = HashSet::new();
+        for (i, (column_name, column_type)) in columns.iter().enumerate() {
+            let field_name = unique_field_name(column_name, &mut field_names);
+            let mut field = FieldDescriptorProto {
+                name: Some(field_name.clone()),
+                number: Some(i32::try_from(i + 1).expect("bounded by MAX_FIELD_NUMBER")),
+                ..Default::default()
+            };
+            match &column_type.scalar_type {
+                ty @ (ScalarType::Array(_) | ScalarType::List { .. } | ScalarType::Int2Vector) => {
+                    field.set_label(Label::Repeated);
+                    self.set_type(&mut field, ty.unwrap_collection_element_type())?;
+                }
+                ScalarType::Map { value_type, .. } => {
+                    let entry_name = map_entry_name(&field_name);
+                    let mut key = FieldDescriptorProto {
+                        name: Some("key".into()),
+                        number: Some(1),
+                        ..Default::default()
+                    };
+                    key.set_label(Label::Optional);
+                    key.set_type(Type::String);
+                    let mut value = FieldDescriptorProto {
+                        name: Some("value".into()),
+                        number: Some(2),
+                        ..Default::default()
+                    };
+                    value.set_label(Label::Optional);
+                    self.set_type(&mut value, value_type)?;
+                    message.nested_type.push(DescriptorProto {
+                        name: Some(entry_name.clone()),
+                        field: vec![key, value],
+                        options: Some(MessageOptions {
+                            map_entry: Some(true),
+                            ..Default::default()
+                        }),
+                        ..Default::default()
+                    });
+                    field.set_label(Label::Repeated);
+                    field.set_type(Type::Message);
+                    field.type_name = Some(format!(".{}.{}", message.name(), entry_name));
+                }
+                ty => {
+                    field.set_label(Label::Optional);
+                    self.set_type(&mut field, ty)?;
+                    // Message fields can always be absent, but other fields
+                    // need explicit presence to distinguish null from the
+                    // default value.
+                    if column_type.nullable && field.r#type() != Type::Message {
+                        let oneof_index = i32::try_from(message.oneof_decl.len())
+                            .expect("bounded by MAX_FIELD_NUMBER");
+                        field.proto3_optional = Some(true);
+                        field.oneof_index = Some(oneof_index);
+                        message.oneof_decl.push(OneofDescriptorProto {
+                            name: Some(format!("_{}", field_name)),
+                            ..Default::default()
+                        });
+                    }
+                }
+            }
+            message.field.push(field);
+        }
+
+        self.messages[index] = message;
+        Ok(())
+    }
+
+    /// Sets the type of `field` to hold a single value of type `ty`.
+    fn set_type(
+        &mut self,
+        field: &mut FieldDescriptorProto,
+        ty: &ScalarType,
+    ) -> Result<(), anyhow::Error> {
+        let proto_type = match ty {
+            ScalarType::Bool => Type::Bool,
+            ScalarType::Int16 | ScalarType::Int32 | ScalarType::Date => Type::Int32,
+            ScalarType::Int64
+            | ScalarType::Time
+            | ScalarType::Timestamp
+            | ScalarType::TimestampTz => Type::Int64,
+            ScalarType::Oid | ScalarType::RegClass | ScalarType::RegProc | ScalarType::RegType => {
+                Type::Uint32
+            }
+            ScalarType::Float32 => Type::Float,
+            ScalarType::Float64 => Type::Double,
+            ScalarType::Numeric { .. }
+            | ScalarType::Interval
+            | ScalarType::String
+            | ScalarType::Char { .. }
+            | ScalarType::VarChar { .. }
+            | ScalarType::Jsonb
+            | ScalarType::Uuid => Type::String,
+            ScalarType::Bytes => Type::Bytes,
+            ScalarType::Record {
+                fields,
+                custom_name,
+                ..
+            } => {
+                let name = self.record_message(custom_name.as_deref(), fields)?;
+                field.type_name = Some(format!(".{}", name));
+                Type::Message
+            }
+            ScalarType::Array(_)
+            | ScalarType::List { .. }
+            | ScalarType::Int2Vector
+            | ScalarType::Map { .. } => {
+                bail!("Protobuf cannot represent lists, arrays, or maps nested within lists, arrays, or maps")
+            }
+        };
+        field.set_type(proto_type);
+        Ok(())
+    }
+
+    /// Returns the name of the message for a record type, adding the message
+    /// if no identical record type has been seen yet.
+    fn record_message(
+        &mut self,
+        custom_name: Option<&str>,
+        fields: &[(ColumnName, ColumnType)],
+    ) -> Result<String, anyhow::Error> {
+        if let Some(name) = custom_name {
+            let name = sanitize_identifier(name);
+            match self.seen.get(&name) {
+                Some(seen_fields) if seen_fields == fields => return Ok(name),
+                Some(_) => (),
+                None => {
+                    self.seen.insert(name.clone(), fields.to_vec());
+                    self.add_message(name.clone(), fields)?;
+                    return Ok(name);
+                }
+            }
+        }
+        loop {
+            let name = format!("record{}", self.next_record);
+            self.next_record += 1;
+            if !self.seen.contains_key(&name) {
+                self.seen.insert(name.clone(), fields.to_vec());
+                self.add_message(name.clone(), fields)?;
+                return Ok(name);
+            }
+        }
+    }
+}
+
+/// Converts `name` into a valid Protobuf identifier.
+fn sanitize_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if !identifier.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        identifier.insert(0, '_');
+    }
+    identifier
+}
+
+/// Converts a column name into a field name that is distinct from the names
+/// in `seen`.
+fn unique_field_name(name: &ColumnName, seen: &mut HashSet<String>) -> String {
+    let stem = sanitize_identifier(name.as_str());
+    let mut name = stem.clone();
+    let mut i = 1;
+    while !seen.insert(name.clone()) {
+        name = format!("{}_{}", stem, i);
+        i += 1;
+    }
+    name
+}
+
+/// Returns the name that `protoc` gives to the entry message of a map field.
+fn map_entry_name(field_name: &str) -> String {
+    let mut name = String::new();
+    let mut capitalize = true;
+    for c in field_name.chars() {
+        if c == '_' {
+            capitalize = true;
+        } else if capitalize {
+            name.push(c.to_ascii_uppercase());
+            capitalize = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name.push_str("Entry");
+    name
+}
+
+/// Renders a file descriptor as the source of a `.proto` file.
+fn render_proto(file: &FileDescriptorProto) -> String {
+    let mut proto = String::new();
+    writeln!(proto, "syntax = \"proto3\";").unwrap();
+    for message in &file.message_type {
+        writeln!(proto).unwrap();
+        writeln!(proto, "message {} {{", message.name()).unwrap();
+        for field in &message.field {
+            let map_entry = message.nested_type.iter().find(|nested| {
+                field.type_name() == format!(".{}.{}", message.name(), nested.name())
+                    && nested.options.as_ref().map_or(false, |o| o.map_entry())
+            });
+            match map_entry {
+                Some(entry) => writeln!(
+                    proto,
+                    "  map<{}, {}> {} = {};",
+                    field_type_name(&entry.field[0]),
+                    field_type_name(&entry.field[1]),
+                    field.name(),
+                    field.number()
+                )
+                .unwrap(),
+                None => {
+                    let label = match field.label() {
+                        Label::Repeated => "repeated ",
+                        _ if field.proto3_optional() => "optional ",
+                        _ => "",
+                    };
+                    writeln!(
+                        proto,
+                        "  {}{} {} = {};",
+                        label,
+                        field_type_name(field),
+                        field.name(),
+                        field.number()
+                    )
+                    .unwrap()
+                }
+            }
+        }
+        writeln!(proto, "}}").unwrap();
+    }
+    proto
+}
+
+fn field_type_name(field: &FieldDescriptorProto) -> &str {
+    match field.r#type() {
+        Type::Bool => "bool",
+        Type::Int32 => "int32",
+        Type::Int64 => "int64",
+        Type::Uint32 => "uint32",
+        Type::Float => "float",
+        Type::Double => "double",
+        Type::String => "string",
+        Type::Bytes => "bytes",
+        Type::Message => field.type_name().trim_start_matches('.'),
+        other => unreachable!("unexpected protobuf field type {:?}", other),
+    }
+}
+
+/// Encodes the fields of a message, one for each of the columns.
+fn encode_fields<'a, I>(
+    buf: &mut Vec<u8>,
+    columns: &[(ColumnName, ColumnType)],
+    datums: I,
+) -> Result<(), anyhow::Error>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    for (i, ((name, typ), datum)) in columns.iter().zip(datums).enumerate() {
+        let tag = u32::try_from(i + 1).expect("bounded by MAX_FIELD_NUMBER");
+        if datum.is_null() {
+            continue;
+        }
+        match &typ.scalar_type {
+            ScalarType::Array(element_type) => {
+                for datum in datum.unwrap_array().elements() {
+                    encode_element(buf, tag, name, element_type, datum)?;
+                }
+            }
+            ScalarType::Int2Vector => {
+                for datum in datum.unwrap_array().elements() {
+                    encode_element(buf, tag, name, &ScalarType::Int16, datum)?;
+                }
+            }
+            ScalarType::List { element_type, .. } => {
+                for datum in datum.unwrap_list() {
+                    encode_element(buf, tag, name, element_type, datum)?;
+                }
+            }
+            ScalarType::Map { value_type, .. } => {
+                for (key, value) in datum.unwrap_map() {
+                    if value.is_null() {
+                        bail!(
+                            "Protobuf cannot represent the null map value in column {}",
+                            name.as_str()
+                        );
+                    }
+                    let mut entry = vec![];
+                    encode_length_delimited(&mut entry, 1, key.as_bytes());
+                    encode_value(&mut entry, 2, value_type, value)?;
+                    encode_length_delimited(buf, tag, &entry);
+                }
+            }
+            ty => encode_value(buf, tag, ty, datum)?,
+        }
+    }
+    Ok(())
+}
+
+/// Encodes an element of a repeated field. Null elements cannot be omitted
+/// without shifting the positions of later elements, nor encoded as the
+/// default value without losing data, so they are rejected.
+fn encode_element(
+    buf: &mut Vec<u8>,
+    tag: u32,
+    name: &ColumnName,
+    ty: &ScalarType,
+    datum: Datum,
+) -> Result<(), anyhow::Error> {
+    if datum.is_null() {
+        bail!(
+            "Protobuf cannot represent the null element in column {}",
+            name.as_str()
+        );
+    }
+    encode_value(buf, tag, ty, datum)
+}
+
+/// Encodes a non-null value of a field that is not repeated.
+fn encode_value(
+    buf: &mut Vec<u8>,
+    tag: u32,
+    ty: &ScalarType,
+    datum: Datum,
+) -> Result<(), anyhow::Error> {
+    match ty {
+        ScalarType::Bool => encode_int(buf, tag, i64::from(datum.unwrap_bool())),
+        ScalarType::Int16 => encode_int(buf, tag, i64::from(datum.unwrap_int16())),
+        ScalarType::Int32 => encode_int(buf, tag, i64::from(datum.unwrap_int32())),
+        ScalarType::Int64 => encode_int(buf, tag, datum.unwrap_int64()),
+        ScalarType::Date => {
+            let days = datum.unwrap_date().num_days_from_ce() - UNIX_EPOCH_DAYS_FROM_CE;
+            encode_int(buf, tag, i64::from(days))
+        }
+        ScalarType::Time => {
+            let time = datum.unwrap_time();
+            let micros = i64::from(time.num_seconds_from_midnight()) * 1_000_000
+                + i64::from(time.nanosecond()) / 1_000;
+            encode_int(buf, tag, micros)
+        }
+        ScalarType::Timestamp => encode_int(buf, tag, timestamp_micros(datum.unwrap_timestamp())),
+        ScalarType::TimestampTz => encode_int(
+            buf,
+            tag,
+            timestamp_micros(datum.unwrap_timestamptz().naive_utc()),
+        ),
+        ScalarType::Oid | ScalarType::RegClass | ScalarType::RegProc | ScalarType::RegType => {
+            encode_key(tag, WireType::Varint, buf);
+            encode_varint(u64::from(datum.unwrap_uint32()), buf);
+        }
+        ScalarType::Float32 => {
+            encode_key(tag, WireType::ThirtyTwoBit, buf);
+            buf.extend_from_slice(&datum.unwrap_float32().to_le_bytes());
+        }
+        ScalarType::Float64 => {
+            encode_key(tag, WireType::SixtyFourBit, buf);
+            buf.extend_from_slice(&datum.unwrap_float64().to_le_bytes());
+        }
+        ScalarType::Numeric { .. } => {
+            let s = datum.unwrap_numeric().0.to_standard_notation_string();
+            encode_length_delimited(buf, tag, s.as_bytes())
+        }
+        ScalarType::Interval => {
+            let s = datum.unwrap_interval().to_string();
+            encode_length_delimited(buf, tag, s.as_bytes())
+        }
+        ScalarType::Bytes => encode_length_delimited(buf, tag, datum.unwrap_bytes()),
+        ScalarType::String | ScalarType::VarChar { .. } => {
+            encode_length_delimited(buf, tag, datum.unwrap_str().as_bytes())
+        }
+        ScalarType::Char { length } => {
+            let s = char::format_str_pad(datum.unwrap_str(), *length);
+            encode_length_delimited(buf, tag, s.as_bytes())
+        }
+        ScalarType::Jsonb => {
+            let s = JsonbRef::from_datum(datum).to_string();
+            encode_length_delimited(buf, tag, s.as_bytes())
+        }
+        ScalarType::Uuid => {
+            let s = datum.unwrap_uuid().to_string();
+            encode_length_delimited(buf, tag, s.as_bytes())
+        }
+        ScalarType::Record { fields, .. } => {
+            let mut message = vec![];
+            encode_fields(&mut message, fields, datum.unwrap_list())?;
+            encode_length_delimited(buf, tag, &message)
+        }
+        ScalarType::Array(_)
+        | ScalarType::List { .. }
+        | ScalarType::Int2Vector
+        | ScalarType::Map { .. } => {
+            unreachable!("nested collections are rejected by ProtobufSchemaGenerator")
+        }
+    }
+    Ok(())
+}
+
+fn encode_int(buf: &mut Vec<u8>, tag: u32, value: i64) {
+    // Negative values of 32-bit fields are sign extended to 64 bits, as
+    // required by the Protobuf encoding.
+    encode_key(tag, WireType::Varint, buf);
+    encode_varint(value as u64, buf);
+}
+
+fn encode_length_delimited(buf: &mut Vec<u8>, tag: u32, bytes: &[u8]) {
+    encode_key(tag, WireType::LengthDelimited, buf);
+    encode_varint(u64::try_from(bytes.len()).expect("usize fits in u64"), buf);
+    buf.extend_from_slice(bytes);
+}
+
+fn timestamp_micros(ts: NaiveDateTime) -> i64 {
+    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros())
+}
+
+#[cfg(test)]
+mod tests {
+    use mz_repr::RelationType;
+
+    use super::*;
+    use crate::protobuf::{DecodedDescriptors, Decoder};
+
+    fn record(fields: Vec<(&str, ScalarType)>, custom_name: Option<&str>) -> ScalarType {
+        ScalarType::Record {
+            fields: fields
+                .into_iter()
+                .map(|(name, ty)| (name.into(), ty.nullable(false)))
+                .collect(),
+            custom_oid: None,
+            custom_name: custom_name.map(|name| name.to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_schema() {
+        let row = record(vec![("x", ScalarType::Int32)], Some("row"));
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                row.clone().nullable(true),
+                row.nullable(true),
+                ScalarType::Int64.nullable(true),
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_oid: None,
+                }
+                .nullable(false),
+                ScalarType::Map {
+                    value_type: Box::new(ScalarType::Float64),
+                    custom_oid: None,
+                }
+                .nullable(false),
+                ScalarType::Timestamp.nullable(false),
+            ]),
+            ["before", "after", "?column?", "tags", "my_map", "ts"],
+        );
+        let generator = ProtobufSchemaGenerator::new(None, desc, true).unwrap();
+        assert_eq!(
+            generator.value_schema(),
+            r#"syntax = "proto3";
+
+message envelope {
+  row before = 1;
+  row after = 2;
+  optional int64 _column_ = 3;
+  repeated string tags = 4;
+  map<string, double> my_map = 5;
+  int64 ts = 6;
+  transaction transaction = 7;
+}
+
+message row {
+  int32 x = 1;
+}
+
+message transaction {
+  string id = 1;
+}
+"#
+        );
+        assert_eq!(generator.key_schema(), None);
+
+        let nested = RelationDesc::new(
+            RelationType::new(vec![ScalarType::List {
+                element_type: Box::new(ScalarType::List {
+                    element_type: Box::new(ScalarType::Int32),
+                    custom_oid: None,
+                }),
+                custom_oid: None,
+            }
+            .nullable(false)]),
+            ["a"],
+        );
+        assert!(ProtobufSchemaGenerator::new(None, nested, false).is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let inner = record(vec![("x", ScalarType::Int32)], None);
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                ScalarType::Int32.nullable(false),
+                ScalarType::Int64.nullable(true),
+                ScalarType::String.nullable(false),
+                inner.clone().nullable(true),
+                inner.nullable(true),
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Int64),
+                    custom_oid: None,
+                }
+                .nullable(false),
+                ScalarType::Float64.nullable(false),
+                ScalarType::Bool.nullable(false),
+            ]),
+            ["a", "b", "c", "d", "e", "f", "g", "h"],
+        );
+        let generator = ProtobufSchemaGenerator::new(None, desc, false).unwrap();
+        let descriptors =
+            DecodedDescriptors::from_bytes(&generator.value_descriptors(), "envelope".into())
+                .unwrap();
+        let encoder = ProtobufEncoder::new(generator, None, Some(7));
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push(Datum::Int32(-3));
+        packer.push(Datum::Int64(0));
+        packer.push(Datum::String("hello"));
+        packer.push_list(vec![Datum::Int32(42)]);
+        packer.push(Datum::Null);
+        packer.push_list(vec![Datum::Int64(1), Datum::Int64(-2)]);
+        packer.push(Datum::Float64(1.5.into()));
+        packer.push(Datum::True);
+
+        let encoded = encoder.encode_value_unchecked(row.clone());
+        let mut decoder = Decoder::new(descriptors, true).unwrap();
+        let decoded = futures::executor::block_on(decoder.decode(&encoded))
+            .unwrap()
+            .unwrap();
+        assert_eq!(decoded, row);
+    }
+
+    #[test]
+    fn test_null_elements() {
+        let desc = RelationDesc::new(
+            RelationType::new(vec![
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::Int64),
+                    custom_oid: None,
+                }
+                .nullable(false),
+                ScalarType::Map {
+                    value_type: Box::new(ScalarType::String),
+                    custom_oid: None,
+                }
+                .nullable(false),
+            ]),
+            ["l", "m"],
+        );
+        let generator = ProtobufSchemaGenerator::new(None, desc, false).unwrap();
+        let encoder = ProtobufEncoder::new(generator, None, None);
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push_list(vec![Datum::Int64(1), Datum::Null]);
+        packer.push_dict(vec![("k", Datum::String("v"))]);
+        let err = encoder.encode_value(row).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Protobuf cannot represent the null element in column l"
+        );
+
+        let mut row = Row::default();
+        let mut packer = row.packer();
+        packer.push_list(vec![Datum::Int64(1)]);
+        packer.push_dict(vec![("k", Datum::Null)]);
+        let err = encoder.encode_value(row).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Protobuf cannot represent the null map value in column m"
+        );
+    }
+}
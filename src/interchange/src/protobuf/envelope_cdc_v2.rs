@@ -0,0 +1,225 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Logic for the Protobuf representation of the CDCv2 protocol.
+//!
+//! The message that a CDCv2 source is declared with must have the following
+//! shape, where `Row` is any message describing the columns of the source:
+//!
+//! ```protobuf
+//! message Envelope {
+//!     repeated Update updates = 1;
+//!     Progress progress = 2;
+//! }
+//!
+//! message Update {
+//!     Row data = 1;
+//!     int64 time = 2;
+//!     int64 diff = 3;
+//! }
+//!
+//! message Progress {
+//!     repeated int64 lower = 1;
+//!     repeated int64 upper = 2;
+//!     repeated Count counts = 3;
+//! }
+//!
+//! message Count {
+//!     int64 time = 1;
+//!     int64 count = 2;
+//! }
+//! ```
+//!
+//! A message with `progress` set is a progress statement; any other message
+//! is a (possibly empty) batch of updates.
+
+use anyhow::{anyhow, bail};
+use differential_dataflow::capture::{Message, Progress};
+use prost_reflect::{DynamicMessage, Kind, MessageDescriptor, Value};
+
+use mz_repr::{Diff, Row, Timestamp};
+
+use super::{pack_message, DecodedDescriptors};
+
+/// Decodes Protobuf-encoded CDCv2 messages.
+#[derive(Debug)]
+pub struct Decoder {
+    descriptors: DecodedDescriptors,
+    row: Row,
+    confluent_wire_format: bool,
+}
+
+impl Decoder {
+    /// Constructs a decoder for a CDCv2 envelope message, validating that the
+    /// message has the shape described in the module documentation.
+    pub fn new(
+        descriptors: DecodedDescriptors,
+        confluent_wire_format: bool,
+    ) -> Result<Self, anyhow::Error> {
+        validate_envelope(&descriptors.message_descriptor)?;
+        Ok(Decoder {
+            descriptors,
+            row: Row::default(),
+            confluent_wire_format,
+        })
+    }
+
+    /// Decodes the encoded Protobuf message into a CDCv2 message.
+    pub fn decode(
+        &mut self,
+        mut bytes: &[u8],
+    ) -> Result<Message<Row, Timestamp, Diff>, anyhow::Error> {
+        if self.confluent_wire_format {
+            let (_schema_id, message_index_path, adjusted_bytes) =
+                crate::confluent::extract_protobuf_header(bytes)?;
+            self.descriptors
+                .check_message_index_path(&message_index_path)?;
+            bytes = adjusted_bytes;
+        }
+        let message = DynamicMessage::decode(self.descriptors.message_descriptor.clone(), bytes)?;
+        if message.has_field_by_name("progress") {
+            let progress = get_field(&message, "progress")?;
+            let progress = as_message(&progress)?;
+            let counts = get_field(progress, "counts")?;
+            let counts = as_list(&counts)?
+                .iter()
+                .map(|count| {
+                    let count = as_message(count)?;
+                    let time = get_timestamp(&*get_field(count, "time")?)?;
+                    let count = usize::try_from(as_i64(&*get_field(count, "count")?)?)?;
+                    Ok((time, count))
+                })
+                .collect::<Result<_, anyhow::Error>>()?;
+            Ok(Message::Progress(Progress {
+                lower: get_timestamps(&*get_field(progress, "lower")?)?,
+                upper: get_timestamps(&*get_field(progress, "upper")?)?,
+                counts,
+            }))
+        } else {
+            let updates = get_field(&message, "updates")?;
+            let updates = as_list(&updates)?
+                .iter()
+                .map(|update| {
+                    let update = as_message(update)?;
+                    if !update.has_field_by_name("data") {
+                        bail!("CDCv2 update is missing its data");
+                    }
+                    let data = get_field(update, "data")?;
+                    let mut packer = self.row.packer();
+                    pack_message(&mut packer, as_message(&data)?)?;
+                    let time = get_timestamp(&*get_field(update, "time")?)?;
+                    let diff = as_i64(&*get_field(update, "diff")?)?;
+                    Ok((self.row.clone(), time, diff))
+                })
+                .collect::<Result<_, anyhow::Error>>()?;
+            Ok(Message::Updates(updates))
+        }
+    }
+}
+
+fn validate_envelope(envelope: &MessageDescriptor) -> Result<(), anyhow::Error> {
+    let fields: Vec<_> = envelope.fields().map(|f| f.name().to_owned()).collect();
+    if fields != ["updates", "progress"] {
+        bail!(
+            "ENVELOPE MATERIALIZE message {} must have exactly the fields updates and progress, in that order",
+            envelope.full_name()
+        );
+    }
+    let update = expect_message_field(envelope, "updates", true)?;
+    expect_message_field(&update, "data", false)?;
+    expect_int64_field(&update, "time", false)?;
+    expect_int64_field(&update, "diff", false)?;
+    let progress = expect_message_field(envelope, "progress", false)?;
+    expect_int64_field(&progress, "lower", true)?;
+    expect_int64_field(&progress, "upper", true)?;
+    let count = expect_message_field(&progress, "counts", true)?;
+    expect_int64_field(&count, "time", false)?;
+    expect_int64_field(&count, "count", false)?;
+    Ok(())
+}
+
+fn expect_message_field(
+    message: &MessageDescriptor,
+    name: &str,
+    repeated: bool,
+) -> Result<MessageDescriptor, anyhow::Error> {
+    match message.get_field_by_name(name) {
+        Some(field) if field.is_list() == repeated => match field.kind() {
+            Kind::Message(m) => Ok(m),
+            _ => bail!(
+                "ENVELOPE MATERIALIZE field {}.{} must be a message",
+                message.full_name(),
+                name
+            ),
+        },
+        _ => bail!(
+            "ENVELOPE MATERIALIZE message {} must have a {}message field {}",
+            message.full_name(),
+            if repeated { "repeated " } else { "" },
+            name
+        ),
+    }
+}
+
+fn expect_int64_field(
+    message: &MessageDescriptor,
+    name: &str,
+    repeated: bool,
+) -> Result<(), anyhow::Error> {
+    match message.get_field_by_name(name) {
+        Some(field)
+            if field.is_list() == repeated
+                && matches!(field.kind(), Kind::Int64 | Kind::Sint64 | Kind::Sfixed64) =>
+        {
+            Ok(())
+        }
+        _ => bail!(
+            "ENVELOPE MATERIALIZE message {} must have a {}int64 field {}",
+            message.full_name(),
+            if repeated { "repeated " } else { "" },
+            name
+        ),
+    }
+}
+
+fn get_field<'a>(
+    message: &'a DynamicMessage,
+    name: &str,
+) -> Result<std::borrow::Cow<'a, Value>, anyhow::Error> {
+    message
+        .get_field_by_name(name)
+        .ok_or_else(|| anyhow!("internal error: CDCv2 message missing field {}", name))
+}
+
+fn as_message(value: &Value) -> Result<&DynamicMessage, anyhow::Error> {
+    value
+        .as_message()
+        .ok_or_else(|| anyhow!("internal error: expected message in CDCv2 message"))
+}
+
+fn as_list(value: &Value) -> Result<&[Value], anyhow::Error> {
+    value
+        .as_list()
+        .ok_or_else(|| anyhow!("internal error: expected list in CDCv2 message"))
+}
+
+fn as_i64(value: &Value) -> Result<i64, anyhow::Error> {
+    value
+        .as_i64()
+        .ok_or_else(|| anyhow!("internal error: expected int64 in CDCv2 message"))
+}
+
+fn get_timestamp(value: &Value) -> Result<Timestamp, anyhow::Error> {
+    let time = as_i64(value)?;
+    Timestamp::try_from(time).map_err(|_| anyhow!("CDCv2 timestamp {} is negative", time))
+}
+
+fn get_timestamps(value: &Value) -> Result<Vec<Timestamp>, anyhow::Error> {
+    as_list(value)?.iter().map(get_timestamp).collect()
+}
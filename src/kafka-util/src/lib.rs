@@ -15,5 +15,6 @@ mod addr;
 
 pub mod admin;
 pub mod client;
+pub mod partition;
 
 pub use addr::{KafkaAddrs, KafkaAddrsParseError};
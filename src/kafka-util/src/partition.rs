@@ -0,0 +1,92 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Partitioning compatible with Kafka's Java client.
+
+/// Returns the partition that the default partitioner of Kafka's Java client
+/// assigns to a message with the given key, if the topic has
+/// `partition_count` partitions.
+///
+/// Producers in other systems that use the Java client, or librdkafka's
+/// `murmur2_random` partitioner, assign messages with the same key to the
+/// same partition.
+///
+/// # Panics
+///
+/// Panics if `partition_count` is not positive.
+pub fn murmur2_partition(key: &[u8], partition_count: i32) -> i32 {
+    assert!(partition_count > 0, "partition count must be positive");
+    (murmur2(key) & 0x7fffffff) % partition_count
+}
+
+/// Computes the 32-bit MurmurHash2 of `data`, with the seed and byte order
+/// used by Kafka's Java client.
+fn murmur2(data: &[u8]) -> i32 {
+    const SEED: u32 = 0x9747b28c;
+    const M: u32 = 0x5bd1e995;
+    const R: u32 = 24;
+
+    let mut h = SEED ^ (data.len() as u32);
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+    let tail = chunks.remainder();
+    if tail.len() >= 3 {
+        h ^= u32::from(tail[2]) << 16;
+    }
+    if tail.len() >= 2 {
+        h ^= u32::from(tail[1]) << 8;
+    }
+    if !tail.is_empty() {
+        h ^= u32::from(tail[0]);
+        h = h.wrapping_mul(M);
+    }
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_murmur2() {
+        // Test vectors from Kafka's Java client.
+        let cases: &[(&[u8], i32)] = &[
+            (b"21", -973932308),
+            (b"foobar", -790332482),
+            (b"a-little-bit-long-string", -985981536),
+            (b"a-little-bit-longer-string", -1486304829),
+            (
+                b"lkjh234lh9fiuh90y23oiuhsafujhadof229phr9h19h89h8",
+                -58897971,
+            ),
+            (b"abc", 479470107),
+        ];
+        for (data, expected) in cases {
+            assert_eq!(murmur2(data), *expected, "murmur2({:?})", data);
+        }
+    }
+
+    #[test]
+    fn test_murmur2_partition() {
+        assert_eq!(murmur2_partition(b"21", 1), 0);
+        // -973932308 & 0x7fffffff = 1173551340
+        assert_eq!(murmur2_partition(b"21", 7), 1173551340 % 7);
+        assert_eq!(murmur2_partition(b"abc", 10), 479470107 % 10);
+    }
+}
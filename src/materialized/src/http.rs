@@ -43,9 +43,13 @@ mod prof;
 mod root;
 mod sql;
 mod util;
+mod webhook;
 
 const SYSTEM_USER: &str = "mz_system";
 
+/// The user as which requests to webhook sources are executed.
+const WEBHOOK_USER: &str = "mz_webhook";
+
 const METHODS: &[&[u8]] = &[
     b"OPTIONS", b"GET", b"HEAD", b"POST", b"PUT", b"DELETE", b"TRACE", b"CONNECT",
 ];
@@ -171,7 +175,16 @@ impl Server {
                 // We verify that if any of these are present, they must match any other that
                 // is also present.
 
-                let user = if let Err(e) = cert_user {
+                let is_webhook = req.method() == Method::POST
+                    && req.uri().path().starts_with(webhook::PATH_PREFIX);
+                let user = if is_webhook {
+                    // The services that push to webhook sources cannot log in,
+                    // so their requests are instead authorized by the
+                    // signatures that every webhook source requires, and run
+                    // as an unprivileged user regardless of any credentials
+                    // they present.
+                    cert_user.map(|_| WEBHOOK_USER.to_string())
+                } else if let Err(e) = cert_user {
                     Err(e)
                 } else if let Some(frontegg) = &frontegg {
                     // If we require mzcloud auth, fetch credentials from the http auth
@@ -237,6 +250,9 @@ impl Server {
                     (&Method::GET, "/internal/catalog") => {
                         catalog::handle_internal_catalog(req, &mut coord_client).await
                     }
+                    (&Method::POST, _) if is_webhook => {
+                        webhook::handle_webhook(req, &mut coord_client).await
+                    }
                     _ => root::handle_static(req, &mut coord_client),
                 };
                 coord_client.terminate().await;
@@ -0,0 +1,130 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Endpoints for webhook sources.
+
+use std::collections::BTreeMap;
+
+use http_body::{LengthLimitError, Limited};
+use hyper::header::CONTENT_LENGTH;
+use hyper::{Body, Request, Response, StatusCode};
+
+use mz_coord::session::EndTransactionAction;
+use mz_coord::CoordError;
+use mz_sql::catalog::CatalogError;
+use mz_sql::names::PartialName;
+
+use crate::http::util;
+
+/// The path under which webhook sources are exposed, as
+/// `/api/webhook/[[<database>/]<schema>/]<source>`.
+pub const PATH_PREFIX: &str = "/api/webhook/";
+
+/// The maximum size of the body of a request to a webhook source, in bytes.
+///
+/// Bodies are buffered in full before their signatures can be verified, so
+/// larger requests are rejected before they are read.
+pub const MAX_REQUEST_BYTES: usize = 1 << 20;
+
+/// Appends the body of `req` to the webhook source named by its path.
+///
+/// The response is only sent once the request has been recorded and
+/// timestamped, so a successful response means that the request will be
+/// reflected in the source, and, if user tables are persisted, that it will
+/// survive restarts.
+pub async fn handle_webhook(
+    req: Request<Body>,
+    coord_client: &mut mz_coord::SessionClient,
+) -> Result<Response<Body>, anyhow::Error> {
+    let name = match parse_name(req.uri().path()) {
+        Some(name) => name,
+        None => {
+            return Ok(util::error_response(
+                StatusCode::NOT_FOUND,
+                "expected path of the form /api/webhook/[[<database>/]<schema>/]<source>",
+            ))
+        }
+    };
+
+    // Header names are case insensitive, so normalize them to lowercase, and
+    // fold repeated headers into a single comma-separated value, as permitted
+    // by RFC 7230.
+    let mut headers = BTreeMap::<String, String>::new();
+    for (key, value) in req.headers() {
+        let value = String::from_utf8_lossy(value.as_bytes());
+        headers
+            .entry(key.as_str().to_lowercase())
+            .and_modify(|v| {
+                v.push_str(", ");
+                v.push_str(&value);
+            })
+            .or_insert_with(|| value.into_owned());
+    }
+    let too_large = || {
+        util::error_response(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("request body exceeds {} bytes", MAX_REQUEST_BYTES),
+        )
+    };
+    let content_length = headers
+        .get(CONTENT_LENGTH.as_str())
+        .and_then(|len| len.parse::<usize>().ok());
+    if matches!(content_length, Some(len) if len > MAX_REQUEST_BYTES) {
+        return Ok(too_large());
+    }
+    // The declared length may be absent or wrong, so limit the body as it is
+    // read, too.
+    let body = match hyper::body::to_bytes(Limited::new(req.into_body(), MAX_REQUEST_BYTES)).await {
+        Ok(body) => body.to_vec(),
+        Err(e) if e.is::<LengthLimitError>() => return Ok(too_large()),
+        Err(e) => return Err(anyhow::anyhow!(e)),
+    };
+
+    let res = async {
+        coord_client.start_transaction(Some(1)).await?;
+        if let Err(e) = coord_client.append_webhook(name, body, headers).await {
+            coord_client
+                .end_transaction(EndTransactionAction::Rollback)
+                .await?;
+            return Err(e);
+        }
+        coord_client
+            .end_transaction(EndTransactionAction::Commit)
+            .await
+    }
+    .await;
+    Ok(match res {
+        Ok(_) => Response::new(Body::empty()),
+        Err(e) => {
+            let code = match &e {
+                CoordError::SqlCatalog(CatalogError::UnknownItem(_))
+                | CoordError::SqlCatalog(CatalogError::UnknownSchema(_))
+                | CoordError::SqlCatalog(CatalogError::UnknownDatabase(_)) => StatusCode::NOT_FOUND,
+                CoordError::UnauthorizedWebhookRequest(_) => StatusCode::UNAUTHORIZED,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            util::error_response(code, e.to_string())
+        }
+    })
+}
+
+fn parse_name(path: &str) -> Option<PartialName> {
+    let mut parts: Vec<_> = path.strip_prefix(PATH_PREFIX)?.split('/').collect();
+    if parts.len() > 3 || parts.iter().any(|p| p.is_empty()) {
+        return None;
+    }
+    let item = parts.pop()?.to_string();
+    let schema = parts.pop().map(|s| s.to_string());
+    let database = parts.pop().map(|s| s.to_string());
+    Some(PartialName {
+        database,
+        schema,
+        item,
+    })
+}
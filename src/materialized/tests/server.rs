@@ -208,9 +208,9 @@ fn test_safe_mode() -> Result<(), Box<dyn Error>> {
         .unwrap_db_error();
     assert_eq!(err.message(), "cannot create file source in safe mode");
     let err = client
-        .batch_execute("CREATE SINK snk FROM mz_sources INTO FILE '/ignored' FORMAT BYTES")
+        .batch_execute("CREATE SINK snk FROM mz_sources INTO FILE '/ignored' FORMAT JSON")
         .unwrap_db_error();
-    assert_eq!(err.message(), "Expected one of KAFKA or AVRO, found FILE");
+    assert_eq!(err.message(), "cannot create file sink in safe mode");
 
     // No Avro OCF sources or sinks.
     let err = client
@@ -301,6 +301,33 @@ fn test_http_sql() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_http_webhook_limits() -> Result<(), Box<dyn Error>> {
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+    client.batch_execute(
+        "CREATE MATERIALIZED SOURCE hook FROM WEBHOOK
+         WITH (hmac_secret = 'shh', hmac_header = 'x-signature') FORMAT BYTES",
+    )?;
+    let url = Url::parse(&format!(
+        "http://{}/api/webhook/hook",
+        server.inner.local_addr()
+    ))?;
+
+    // Oversized bodies are rejected before their signature is checked.
+    let res = Client::new()
+        .post(url.clone())
+        .body(vec![0; (1 << 20) + 1])
+        .send()?;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Other requests are only authorized by their signature.
+    let res = Client::new().post(url).body("unsigned").send()?;
+    assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+    Ok(())
+}
+
 #[test]
 fn test_metrics_registry_hygiene() -> Result<(), Box<dyn Error>> {
     // Minor setup chores to ensure the server has done at least a little work:
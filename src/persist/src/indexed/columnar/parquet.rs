@@ -11,8 +11,10 @@
 
 use std::io::{Read, Seek, Write};
 
+use arrow2::datatypes::Schema;
 use arrow2::io::parquet::read::RecordReader;
 use arrow2::io::parquet::write::RowGroupIterator;
+use arrow2::record_batch::RecordBatch;
 use differential_dataflow::trace::Description;
 use parquet2::compression::Compression;
 use parquet2::encoding::Encoding;
@@ -121,32 +123,44 @@ fn encode_parquet_kvtd<W: Write>(
     inline_base64: String,
     iter: &[ColumnarRecords],
 ) -> Result<(), Error> {
-    let iter = iter.into_iter().map(|x| Ok(encode_arrow_batch_kvtd(x)));
+    let metadata = vec![KeyValue {
+        key: INLINE_METADATA_KEY.into(),
+        value: Some(inline_base64),
+    }];
+    write_parquet_file(
+        w,
+        &SCHEMA_ARROW_KVTD,
+        iter.iter().map(encode_arrow_batch_kvtd),
+        Some(metadata),
+    )
+}
 
-    let schema = SCHEMA_ARROW_KVTD.clone();
+/// Writes `batches`, which must all match `schema`, to `w` as a Parquet file
+/// with one row group per batch and the given key-value metadata.
+pub fn write_parquet_file<W, I>(
+    w: &mut W,
+    schema: &Schema,
+    batches: I,
+    metadata: Option<Vec<KeyValue>>,
+) -> Result<(), Error>
+where
+    W: Write,
+    I: Iterator<Item = RecordBatch>,
+{
     let options = WriteOptions {
         write_statistics: false,
         compression: Compression::Uncompressed,
         version: Version::V2,
     };
     let row_groups = RowGroupIterator::try_new(
-        iter,
-        &schema,
+        batches.map(Ok),
+        schema,
         options,
-        vec![
-            Encoding::Plain,
-            Encoding::Plain,
-            Encoding::Plain,
-            Encoding::Plain,
-        ],
+        vec![Encoding::Plain; schema.fields().len()],
     )?;
 
     let parquet_schema = row_groups.parquet_schema().clone();
-    let metadata = vec![KeyValue {
-        key: INLINE_METADATA_KEY.into(),
-        value: Some(inline_base64),
-    }];
-    write_file(w, row_groups, parquet_schema, options, None, Some(metadata))
+    write_file(w, row_groups, parquet_schema, options, None, metadata)
         .map_err(|err| err.to_string())?;
 
     Ok(())
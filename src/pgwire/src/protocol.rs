@@ -1077,6 +1077,9 @@ where
                 created!(existed, SqlState::DUPLICATE_OBJECT, "source")
             }
             ExecuteResponse::CreatedSources => command_complete!("CREATE SOURCES"),
+            ExecuteResponse::CreatedSubscription { existed } => {
+                created!(existed, SqlState::DUPLICATE_OBJECT, "subscription")
+            }
             ExecuteResponse::CreatedSink { existed } => {
                 created!(existed, SqlState::DUPLICATE_OBJECT, "sink")
             }
@@ -1097,6 +1100,7 @@ where
             ExecuteResponse::DroppedSource => command_complete!("DROP SOURCE"),
             ExecuteResponse::DroppedIndex => command_complete!("DROP INDEX"),
             ExecuteResponse::DroppedSink => command_complete!("DROP SINK"),
+            ExecuteResponse::DroppedSubscription => command_complete!("DROP SUBSCRIPTION"),
             ExecuteResponse::DroppedTable => command_complete!("DROP TABLE"),
             ExecuteResponse::DroppedView => command_complete!("DROP VIEW"),
             ExecuteResponse::DroppedType => command_complete!("DROP TYPE"),
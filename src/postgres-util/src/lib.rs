@@ -7,7 +7,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-//! Provides convenience functions for working with upstream Postgres sources from the `sql` package.
+//! Provides convenience functions for working with upstream Postgres sources and sinks.
 
 use anyhow::{anyhow, bail};
 use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode};
@@ -240,3 +240,17 @@ pub async fn connect_replication(conn: &str) -> Result<Client, anyhow::Error> {
     );
     Ok(client)
 }
+
+/// Starts a regular, non-replication connection to the upstream database
+pub async fn connect(conn: &str) -> Result<Client, anyhow::Error> {
+    let config: Config = conn.parse()?;
+    let tls = make_tls(&config)?;
+    let (client, connection) = config.connect(tls).await?;
+    task::spawn(|| format!("postgres_connect:{conn}"), connection);
+    Ok(client)
+}
+
+/// Quotes `ident` for use as an identifier in an upstream Postgres statement.
+pub fn quote_identifier(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
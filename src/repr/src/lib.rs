@@ -55,6 +55,11 @@ pub enum MessagePayload {
     /// Data from the source connector.
     // TODO(guswynn): Determine if `Vec` needs to be non-empty.
     Data(Vec<u8>),
+    /// A record that the source connector has already decoded.
+    ///
+    /// Used for formats that cannot be decoded from a stream of bytes, like
+    /// Parquet, whose files need to be read with random access.
+    Row(Row),
     /// Forces the decoder to consider this a delimiter.
     ///
     /// For example, CSV records are normally terminated by a newline,
@@ -27,7 +27,7 @@ use std::path::PathBuf;
 use enum_kinds::EnumKind;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
-use crate::ast::{AstInfo, Expr, Ident, SqlOption, UnresolvedObjectName, WithOption};
+use crate::ast::{AstInfo, Expr, Ident, SqlOption, UnresolvedObjectName, Value, WithOption};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Schema {
@@ -98,6 +98,9 @@ pub enum ProtobufSchema<T: AstInfo> {
         message_name: String,
         schema: Schema,
     },
+    /// No schema was specified. Sinks derive the schema from the sinked
+    /// relation.
+    Derived,
 }
 
 impl<T: AstInfo> AstDisplay for ProtobufSchema<T> {
@@ -115,6 +118,7 @@ impl<T: AstInfo> AstDisplay for ProtobufSchema<T> {
                 f.write_str("' USING ");
                 f.write_str(schema);
             }
+            Self::Derived => (),
         }
     }
 }
@@ -148,12 +152,20 @@ impl_display_t!(CsrConnectorAvro);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CsrConnectorProto<T: AstInfo> {
     pub url: String,
+    /// The fully qualified name of the message to decode, if the schema
+    /// contains more than one message.
+    pub message_name: Option<String>,
     pub seed: Option<CsrSeedCompiledOrLegacy>,
     pub with_options: Vec<SqlOption<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for CsrConnectorProto<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        if let Some(message_name) = &self.message_name {
+            f.write_str("MESSAGE '");
+            f.write_node(&display::escape_single_quote_string(message_name));
+            f.write_str("' ");
+        }
         f.write_str("USING CONFLUENT SCHEMA REGISTRY '");
         f.write_node(&display::escape_single_quote_string(&self.url));
         f.write_str("'");
@@ -288,6 +300,7 @@ pub enum Format<T: AstInfo> {
     },
     Json,
     Text,
+    Parquet,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -324,6 +337,12 @@ pub enum SourceIncludeMetadataType {
     Partition,
     Topic,
     Offset,
+    Headers,
+    Header {
+        key: String,
+        /// Whether to include the header's value as `bytea` rather than `text`.
+        use_bytes: bool,
+    },
 }
 
 impl AstDisplay for SourceIncludeMetadataType {
@@ -334,6 +353,12 @@ impl AstDisplay for SourceIncludeMetadataType {
             SourceIncludeMetadataType::Partition => f.write_str("PARTITION"),
             SourceIncludeMetadataType::Topic => f.write_str("TOPIC"),
             SourceIncludeMetadataType::Offset => f.write_str("OFFSET"),
+            SourceIncludeMetadataType::Headers => f.write_str("HEADERS"),
+            SourceIncludeMetadataType::Header { key, .. } => {
+                f.write_str("HEADER '");
+                f.write_node(&display::escape_single_quote_string(key));
+                f.write_str("'");
+            }
         }
     }
 }
@@ -352,6 +377,12 @@ impl AstDisplay for SourceIncludeMetadata {
             f.write_str(" AS ");
             f.write_node(alias);
         }
+        if let SourceIncludeMetadataType::Header {
+            use_bytes: true, ..
+        } = &self.ty
+        {
+            f.write_str(" BYTES");
+        }
     }
 }
 impl_display!(SourceIncludeMetadata);
@@ -386,6 +417,31 @@ impl AstDisplay for Envelope {
 }
 impl_display!(Envelope);
 
+/// What a source does with records that it fails to decode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DecodeErrorPolicy {
+    /// Put the source into an error state.
+    Fail,
+    /// Drop the record.
+    Skip,
+    /// Drop the record and write its raw contents into the named table.
+    Redirect(UnresolvedObjectName),
+}
+
+impl AstDisplay for DecodeErrorPolicy {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            Self::Fail => f.write_str("FAIL"),
+            Self::Skip => f.write_str("SKIP"),
+            Self::Redirect(name) => {
+                f.write_str("REDIRECT TO ");
+                f.write_node(name);
+            }
+        }
+    }
+}
+impl_display!(DecodeErrorPolicy);
+
 impl<T: AstInfo> AstDisplay for Format<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
@@ -394,6 +450,7 @@ impl<T: AstInfo> AstDisplay for Format<T> {
                 f.write_str("AVRO ");
                 f.write_node(inner);
             }
+            Self::Protobuf(ProtobufSchema::Derived) => f.write_str("PROTOBUF"),
             Self::Protobuf(inner) => {
                 f.write_str("PROTOBUF ");
                 f.write_node(inner);
@@ -415,6 +472,7 @@ impl<T: AstInfo> AstDisplay for Format<T> {
             }
             Self::Json => f.write_str("JSON"),
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet => f.write_str("PARQUET"),
         }
     }
 }
@@ -423,6 +481,11 @@ impl_display_t!(Format);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+    Snappy,
+    Auto,
     None,
 }
 
@@ -430,6 +493,11 @@ impl AstDisplay for Compression {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         match self {
             Self::Gzip => f.write_str("GZIP"),
+            Self::Zstd => f.write_str("ZSTD"),
+            Self::Bzip2 => f.write_str("BZIP2"),
+            Self::Xz => f.write_str("XZ"),
+            Self::Snappy => f.write_str("SNAPPY"),
+            Self::Auto => f.write_str("AUTO"),
             Self::None => f.write_str("NONE"),
         }
     }
@@ -464,6 +532,9 @@ pub enum CreateSourceConnector {
     Kafka {
         broker: String,
         topic: String,
+        /// Whether `topic` is a regular expression matching the names of the
+        /// topics to read: `TOPIC PATTERN 'events\..*'`
+        pattern: bool,
         key: Option<Vec<Ident>>,
     },
     Kinesis {
@@ -496,6 +567,12 @@ pub enum CreateSourceConnector {
         /// The PubNub channel to subscribe to
         channel: String,
     },
+    /// An HTTP endpoint to which each request body is pushed.
+    Webhook,
+    /// A built-in generator of deterministic data.
+    LoadGenerator {
+        generator: LoadGenerator,
+    },
 }
 
 impl AstDisplay for CreateSourceConnector {
@@ -508,11 +585,20 @@ impl AstDisplay for CreateSourceConnector {
                 f.write_str(" COMPRESSION ");
                 f.write_node(compression);
             }
-            CreateSourceConnector::Kafka { broker, topic, key } => {
+            CreateSourceConnector::Kafka {
+                broker,
+                topic,
+                pattern,
+                key,
+            } => {
                 f.write_str("KAFKA BROKER '");
                 f.write_node(&display::escape_single_quote_string(broker));
                 f.write_str("'");
-                f.write_str(" TOPIC '");
+                f.write_str(" TOPIC ");
+                if *pattern {
+                    f.write_str("PATTERN ");
+                }
+                f.write_str("'");
                 f.write_node(&display::escape_single_quote_string(topic));
                 f.write_str("'");
                 if let Some(key) = key.as_ref() {
@@ -577,16 +663,50 @@ impl AstDisplay for CreateSourceConnector {
                 f.write_str(&display::escape_single_quote_string(channel));
                 f.write_str("'");
             }
+            CreateSourceConnector::Webhook => f.write_str("WEBHOOK"),
+            CreateSourceConnector::LoadGenerator { generator } => {
+                f.write_str("LOAD GENERATOR ");
+                f.write_node(generator);
+            }
         }
     }
 }
 impl_display!(CreateSourceConnector);
 
+/// The kind of data produced by a `LOAD GENERATOR` source.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LoadGenerator {
+    Counter,
+    Auction,
+    Tpch {
+        /// The argument to `SCALE FACTOR`.
+        scale_factor: Value,
+    },
+}
+
+impl AstDisplay for LoadGenerator {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        match self {
+            LoadGenerator::Counter => f.write_str("COUNTER"),
+            LoadGenerator::Auction => f.write_str("AUCTION"),
+            LoadGenerator::Tpch { scale_factor } => {
+                f.write_str("TPCH (SCALE FACTOR ");
+                f.write_node(scale_factor);
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display!(LoadGenerator);
+
 impl<T: AstInfo> From<&CreateSinkConnector<T>> for ConnectorType {
     fn from(connector: &CreateSinkConnector<T>) -> ConnectorType {
         match connector {
             CreateSinkConnector::Kafka { .. } => ConnectorType::Kafka,
             CreateSinkConnector::AvroOcf { .. } => ConnectorType::AvroOcf,
+            CreateSinkConnector::File { .. } => ConnectorType::File,
+            CreateSinkConnector::S3 { .. } => ConnectorType::S3,
+            CreateSinkConnector::Postgres { .. } => ConnectorType::Postgres,
         }
     }
 }
@@ -598,10 +718,27 @@ pub enum CreateSinkConnector<T: AstInfo> {
         broker: String,
         topic: String,
         key: Option<KafkaSinkKey>,
+        /// An expression whose value chooses the partition of each message
+        partition_by: Option<Expr<T>>,
+        /// Headers to attach to each message
+        headers: Vec<KafkaSinkHeader<T>>,
         consistency: Option<KafkaConsistency<T>>,
     },
     /// Avro Object Container File
     AvroOcf { path: String },
+    /// A directory on the local filesystem
+    File { path: String },
+    /// A bucket in S3, optionally restricted to the keys under a prefix
+    S3 {
+        bucket: String,
+        prefix: Option<String>,
+    },
+    /// A table in an upstream Postgres database
+    Postgres {
+        conn: String,
+        table: String,
+        key: KafkaSinkKey,
+    },
 }
 
 impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
@@ -611,6 +748,8 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
                 broker,
                 topic,
                 key,
+                partition_by,
+                headers,
                 consistency,
             } => {
                 f.write_str("KAFKA BROKER '");
@@ -622,6 +761,15 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
                 if let Some(key) = key.as_ref() {
                     f.write_node(key);
                 }
+                if let Some(partition_by) = partition_by.as_ref() {
+                    f.write_str(" PARTITION BY ");
+                    f.write_node(partition_by);
+                }
+                if !headers.is_empty() {
+                    f.write_str(" HEADERS (");
+                    f.write_node(&display::comma_separated(headers));
+                    f.write_str(")");
+                }
                 if let Some(consistency) = consistency.as_ref() {
                     f.write_node(consistency);
                 }
@@ -631,6 +779,29 @@ impl<T: AstInfo> AstDisplay for CreateSinkConnector<T> {
                 f.write_node(&display::escape_single_quote_string(path));
                 f.write_str("'");
             }
+            CreateSinkConnector::File { path } => {
+                f.write_str("FILE '");
+                f.write_node(&display::escape_single_quote_string(path));
+                f.write_str("'");
+            }
+            CreateSinkConnector::S3 { bucket, prefix } => {
+                f.write_str("S3 BUCKET '");
+                f.write_node(&display::escape_single_quote_string(bucket));
+                f.write_str("'");
+                if let Some(prefix) = prefix {
+                    f.write_str(" PREFIX '");
+                    f.write_node(&display::escape_single_quote_string(prefix));
+                    f.write_str("'");
+                }
+            }
+            CreateSinkConnector::Postgres { conn, table, key } => {
+                f.write_str("POSTGRES CONNECTION '");
+                f.write_node(&display::escape_single_quote_string(conn));
+                f.write_str("' TABLE '");
+                f.write_node(&display::escape_single_quote_string(table));
+                f.write_str("'");
+                f.write_node(key);
+            }
         }
     }
 }
@@ -657,6 +828,23 @@ impl<T: AstInfo> AstDisplay for KafkaConsistency<T> {
 }
 impl_display_t!(KafkaConsistency);
 
+/// A header to attach to each message written by a Kafka sink.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KafkaSinkHeader<T: AstInfo> {
+    pub name: String,
+    pub value: Expr<T>,
+}
+
+impl<T: AstInfo> AstDisplay for KafkaSinkHeader<T> {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("'");
+        f.write_node(&display::escape_single_quote_string(&self.name));
+        f.write_str("' = ");
+        f.write_node(&self.value);
+    }
+}
+impl_display_t!(KafkaSinkHeader);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct KafkaSinkKey {
     pub key_columns: Vec<Ident>,
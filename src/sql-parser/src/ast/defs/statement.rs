@@ -22,9 +22,9 @@ use std::fmt;
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
-    AstInfo, ColumnDef, CreateSinkConnector, CreateSourceConnector, CreateSourceFormat, Envelope,
-    Expr, Format, Ident, KeyConstraint, Query, SourceIncludeMetadata, TableAlias, TableConstraint,
-    TableWithJoins, UnresolvedObjectName, Value,
+    AstInfo, ColumnDef, CreateSinkConnector, CreateSourceConnector, CreateSourceFormat, CsrSeed,
+    DecodeErrorPolicy, Envelope, Expr, Format, Ident, KeyConstraint, Query, SourceIncludeMetadata,
+    TableAlias, TableConstraint, TableWithJoins, UnresolvedObjectName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -46,8 +46,12 @@ pub enum Statement<T: AstInfo> {
     CreateIndex(CreateIndexStatement<T>),
     CreateType(CreateTypeStatement<T>),
     CreateRole(CreateRoleStatement),
+    CreateSubscription(CreateSubscriptionStatement),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndex(AlterIndexStatement),
+    AlterSource(AlterSourceStatement),
+    AlterSink(AlterSinkStatement),
+    AlterSubscription(AlterSubscriptionStatement),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropObjects(DropObjectsStatement),
@@ -95,8 +99,12 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateIndex(stmt) => f.write_node(stmt),
             Statement::CreateRole(stmt) => f.write_node(stmt),
             Statement::CreateType(stmt) => f.write_node(stmt),
+            Statement::CreateSubscription(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndex(stmt) => f.write_node(stmt),
+            Statement::AlterSource(stmt) => f.write_node(stmt),
+            Statement::AlterSink(stmt) => f.write_node(stmt),
+            Statement::AlterSubscription(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
@@ -373,6 +381,7 @@ pub struct CreateSourceStatement<T: AstInfo> {
     pub if_not_exists: bool,
     pub materialized: bool,
     pub key_constraint: Option<KeyConstraint>,
+    pub on_decode_error: DecodeErrorPolicy,
 }
 
 impl<T: AstInfo> AstDisplay for CreateSourceStatement<T> {
@@ -420,6 +429,14 @@ impl<T: AstInfo> AstDisplay for CreateSourceStatement<T> {
                 f.write_node(&self.envelope);
             }
         }
+
+        match self.on_decode_error {
+            DecodeErrorPolicy::Fail => (),
+            _ => {
+                f.write_str(" ON DECODE ERROR ");
+                f.write_node(&self.on_decode_error);
+            }
+        }
     }
 }
 impl_display_t!(CreateSourceStatement);
@@ -476,6 +493,33 @@ impl<T: AstInfo> AstDisplay for CreateSinkStatement<T> {
 }
 impl_display_t!(CreateSinkStatement);
 
+/// `CREATE SUBSCRIPTION`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CreateSubscriptionStatement {
+    pub name: UnresolvedObjectName,
+    pub from: UnresolvedObjectName,
+    pub with_options: Vec<WithOption>,
+    pub if_not_exists: bool,
+}
+
+impl AstDisplay for CreateSubscriptionStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("CREATE SUBSCRIPTION ");
+        if self.if_not_exists {
+            f.write_str("IF NOT EXISTS ");
+        }
+        f.write_node(&self.name);
+        f.write_str(" FOR ");
+        f.write_node(&self.from);
+        if !self.with_options.is_empty() {
+            f.write_str(" WITH (");
+            f.write_node(&display::comma_separated(&self.with_options));
+            f.write_str(")");
+        }
+    }
+}
+impl_display!(CreateSubscriptionStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ViewDefinition<T: AstInfo> {
     /// View name
@@ -885,6 +929,113 @@ impl AstDisplay for AlterIndexStatement {
 
 impl_display!(AlterIndexStatement);
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterSourceAction {
+    /// Adopt the latest schema from the schema registry, or, once purified,
+    /// the schema given by `seed`.
+    RefreshSchema { seed: Option<CsrSeed> },
+}
+
+/// `ALTER SOURCE ... REFRESH SCHEMA`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSourceStatement {
+    pub source_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterSourceAction,
+}
+
+impl AstDisplay for AlterSourceStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SOURCE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.source_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterSourceAction::RefreshSchema { seed } => {
+                f.write_str("REFRESH SCHEMA");
+                if let Some(seed) = seed {
+                    f.write_str(" ");
+                    f.write_node(seed);
+                }
+            }
+        }
+    }
+}
+
+impl_display!(AlterSourceStatement);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterSinkAction {
+    /// Resume writing with the updates after `timestamp`.
+    ResumeFrom { timestamp: u64 },
+}
+
+/// `ALTER SINK ... RESUME FROM`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSinkStatement {
+    pub sink_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterSinkAction,
+}
+
+impl AstDisplay for AlterSinkStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SINK ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.sink_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterSinkAction::ResumeFrom { timestamp } => {
+                f.write_str("RESUME FROM ");
+                f.write_str(timestamp);
+            }
+        }
+    }
+}
+
+impl_display!(AlterSinkStatement);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AlterSubscriptionAction {
+    /// Record that the consumer has processed all updates at or before
+    /// `timestamp`.
+    Acknowledge { timestamp: u64 },
+}
+
+/// `ALTER SUBSCRIPTION ... ACKNOWLEDGE`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSubscriptionStatement {
+    pub subscription_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub action: AlterSubscriptionAction,
+}
+
+impl AstDisplay for AlterSubscriptionStatement {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        f.write_str("ALTER SUBSCRIPTION ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.subscription_name);
+        f.write_str(" ");
+
+        match &self.action {
+            AlterSubscriptionAction::Acknowledge { timestamp } => {
+                f.write_str("ACKNOWLEDGE ");
+                f.write_str(timestamp);
+            }
+        }
+    }
+}
+
+impl_display!(AlterSubscriptionStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -1064,6 +1215,7 @@ impl<T: AstInfo> AstDisplay for ShowObjectsStatement<T> {
             ObjectType::View => "VIEWS",
             ObjectType::Source => "SOURCES",
             ObjectType::Sink => "SINKS",
+            ObjectType::Subscription => "SUBSCRIPTIONS",
             ObjectType::Type => "TYPES",
             ObjectType::Role => "ROLES",
             ObjectType::Object => "OBJECTS",
@@ -1275,6 +1427,7 @@ impl_display!(RollbackStatement);
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TailStatement<T: AstInfo> {
     pub relation: TailRelation<T>,
+    pub envelope: Option<TailEnvelope>,
     pub options: Vec<WithOption>,
     pub as_of: Option<Expr<T>>,
 }
@@ -1283,6 +1436,10 @@ impl<T: AstInfo> AstDisplay for TailStatement<T> {
     fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
         f.write_str("TAIL ");
         f.write_node(&self.relation);
+        if let Some(envelope) = &self.envelope {
+            f.write_str(" ENVELOPE ");
+            f.write_node(envelope);
+        }
         if !self.options.is_empty() {
             f.write_str(" WITH (");
             f.write_node(&display::comma_separated(&self.options));
@@ -1316,6 +1473,35 @@ impl<T: AstInfo> AstDisplay for TailRelation<T> {
 }
 impl_display_t!(TailRelation);
 
+/// The envelope of a `TAIL` statement, which determines how the changes to
+/// each key are presented.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TailEnvelope {
+    /// `ENVELOPE UPSERT (KEY (...))`
+    Upsert { key_columns: Vec<Ident> },
+    /// `ENVELOPE DEBEZIUM (KEY (...))`
+    Debezium { key_columns: Vec<Ident> },
+}
+
+impl AstDisplay for TailEnvelope {
+    fn fmt<W: fmt::Write>(&self, f: &mut AstFormatter<W>) {
+        let key_columns = match self {
+            TailEnvelope::Upsert { key_columns } => {
+                f.write_str("UPSERT");
+                key_columns
+            }
+            TailEnvelope::Debezium { key_columns } => {
+                f.write_str("DEBEZIUM");
+                key_columns
+            }
+        };
+        f.write_str(" (KEY (");
+        f.write_node(&display::comma_separated(key_columns));
+        f.write_str("))");
+    }
+}
+impl_display!(TailEnvelope);
+
 /// `EXPLAIN ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct ExplainStatement<T: AstInfo> {
@@ -1365,6 +1551,7 @@ pub enum ObjectType {
     View,
     Source,
     Sink,
+    Subscription,
     Index,
     Type,
     Role,
@@ -1379,6 +1566,7 @@ impl AstDisplay for ObjectType {
             ObjectType::View => "VIEW",
             ObjectType::Source => "SOURCE",
             ObjectType::Sink => "SINK",
+            ObjectType::Subscription => "SUBSCRIPTION",
             ObjectType::Index => "INDEX",
             ObjectType::Type => "TYPE",
             ObjectType::Role => "ROLE",
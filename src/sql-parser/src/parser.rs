@@ -1561,6 +1561,8 @@ impl<'a> Parser<'a> {
             self.parse_create_schema()
         } else if self.peek_keyword(SINK) {
             self.parse_create_sink()
+        } else if self.peek_keyword(SUBSCRIPTION) {
+            self.parse_create_subscription()
         } else if self.peek_keyword(TYPE) {
             self.parse_create_type()
         } else if self.peek_keyword(ROLE) || self.peek_keyword(USER) {
@@ -1591,7 +1593,7 @@ impl<'a> Parser<'a> {
             } else {
                 self.expected(
                     self.peek_pos(),
-                    "DATABASE, SCHEMA, ROLE, USER, TYPE, INDEX, SINK, SOURCE, TABLE or [OR REPLACE] [TEMPORARY] [MATERIALIZED] VIEW or VIEWS after CREATE",
+                    "DATABASE, SCHEMA, ROLE, USER, TYPE, INDEX, SINK, SUBSCRIPTION, SOURCE, TABLE or [OR REPLACE] [TEMPORARY] [MATERIALIZED] VIEW or VIEWS after CREATE",
                     self.peek_token(),
                 )
             }
@@ -1654,10 +1656,12 @@ impl<'a> Parser<'a> {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(PARQUET) {
+            Format::Parquet
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
@@ -1695,44 +1699,28 @@ impl<'a> Parser<'a> {
 
     fn parse_protobuf_schema(&mut self) -> Result<ProtobufSchema<Raw>, ParserError> {
         if self.parse_keywords(&[USING, CONFLUENT, SCHEMA, REGISTRY]) {
-            let csr_connector = self.parse_csr_connector_proto()?;
+            let csr_connector = self.parse_csr_connector_proto(None)?;
             Ok(ProtobufSchema::Csr { csr_connector })
         } else if self.parse_keyword(MESSAGE) {
             let message_name = self.parse_literal_string()?;
             self.expect_keyword(USING)?;
+            if self.parse_keywords(&[CONFLUENT, SCHEMA, REGISTRY]) {
+                let csr_connector = self.parse_csr_connector_proto(Some(message_name))?;
+                return Ok(ProtobufSchema::Csr { csr_connector });
+            }
             let schema = self.parse_schema()?;
             Ok(ProtobufSchema::InlineSchema {
                 message_name,
                 schema,
             })
         } else {
-            return self.expected(
-                self.peek_pos(),
-                "CONFLUENT SCHEMA REGISTRY or MESSSAGE",
-                self.peek_token(),
-            );
+            Ok(ProtobufSchema::Derived)
         }
     }
 
     fn parse_csr_connector_avro(&mut self) -> Result<CsrConnectorAvro<Raw>, ParserError> {
         let url = self.parse_literal_string()?;
-
-        let seed = if self.parse_keyword(SEED) {
-            let key_schema = if self.parse_keyword(KEY) {
-                self.expect_keyword(SCHEMA)?;
-                Some(self.parse_literal_string()?)
-            } else {
-                None
-            };
-            self.expect_keywords(&[VALUE, SCHEMA])?;
-            let value_schema = self.parse_literal_string()?;
-            Some(CsrSeed {
-                key_schema,
-                value_schema,
-            })
-        } else {
-            None
-        };
+        let seed = self.parse_csr_seed()?;
 
         // Look ahead to avoid erroring on `WITH SNAPSHOT`; we only want to
         // accept `WITH (...)` here.
@@ -1749,7 +1737,28 @@ impl<'a> Parser<'a> {
         })
     }
 
-    fn parse_csr_connector_proto(&mut self) -> Result<CsrConnectorProto<Raw>, ParserError> {
+    fn parse_csr_seed(&mut self) -> Result<Option<CsrSeed>, ParserError> {
+        if !self.parse_keyword(SEED) {
+            return Ok(None);
+        }
+        let key_schema = if self.parse_keyword(KEY) {
+            self.expect_keyword(SCHEMA)?;
+            Some(self.parse_literal_string()?)
+        } else {
+            None
+        };
+        self.expect_keywords(&[VALUE, SCHEMA])?;
+        let value_schema = self.parse_literal_string()?;
+        Ok(Some(CsrSeed {
+            key_schema,
+            value_schema,
+        }))
+    }
+
+    fn parse_csr_connector_proto(
+        &mut self,
+        message_name: Option<String>,
+    ) -> Result<CsrConnectorProto<Raw>, ParserError> {
         let url = self.parse_literal_string()?;
 
         let seed = if self.parse_keyword(SEED) {
@@ -1805,6 +1814,7 @@ impl<'a> Parser<'a> {
 
         Ok(CsrConnectorProto {
             url,
+            message_name,
             seed,
             with_options,
         })
@@ -1849,8 +1859,22 @@ impl<'a> Parser<'a> {
             Compression::None
         } else if self.parse_keyword(GZIP) {
             Compression::Gzip
+        } else if self.parse_keyword(ZSTD) {
+            Compression::Zstd
+        } else if self.parse_keyword(BZIP2) {
+            Compression::Bzip2
+        } else if self.parse_keyword(XZ) {
+            Compression::Xz
+        } else if self.parse_keyword(SNAPPY) {
+            Compression::Snappy
+        } else if self.parse_keyword(AUTO) {
+            Compression::Auto
         } else {
-            return self.expected(self.peek_pos(), "NONE or GZIP", self.peek_token());
+            return self.expected(
+                self.peek_pos(),
+                "NONE, GZIP, ZSTD, BZIP2, XZ, SNAPPY, or AUTO",
+                self.peek_token(),
+            );
         };
         Ok(compression)
     }
@@ -1902,6 +1926,20 @@ impl<'a> Parser<'a> {
             Envelope::None
         };
 
+        let on_decode_error = if self.parse_keywords(&[ON, DECODE, ERROR]) {
+            match self.expect_one_of_keywords(&[FAIL, SKIP, REDIRECT])? {
+                FAIL => DecodeErrorPolicy::Fail,
+                SKIP => DecodeErrorPolicy::Skip,
+                REDIRECT => {
+                    self.expect_keyword(TO)?;
+                    DecodeErrorPolicy::Redirect(self.parse_object_name()?)
+                }
+                _ => unreachable!(),
+            }
+        } else {
+            DecodeErrorPolicy::Fail
+        };
+
         Ok(Statement::CreateSource(CreateSourceStatement {
             name,
             col_names,
@@ -1913,6 +1951,7 @@ impl<'a> Parser<'a> {
             if_not_exists,
             materialized,
             key_constraint,
+            on_decode_error,
         }))
     }
 
@@ -2009,8 +2048,42 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_create_subscription(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(SUBSCRIPTION)?;
+        let if_not_exists = self.parse_if_not_exists()?;
+        let name = self.parse_object_name()?;
+        self.expect_keyword(FOR)?;
+        let from = self.parse_object_name()?;
+        let with_options = self.parse_opt_with_options()?;
+        Ok(Statement::CreateSubscription(CreateSubscriptionStatement {
+            name,
+            from,
+            with_options,
+            if_not_exists,
+        }))
+    }
+
     fn parse_create_source_connector(&mut self) -> Result<CreateSourceConnector, ParserError> {
-        match self.expect_one_of_keywords(&[FILE, KAFKA, KINESIS, AVRO, S3, POSTGRES, PUBNUB])? {
+        match self.expect_one_of_keywords(&[
+            FILE, KAFKA, KINESIS, AVRO, S3, POSTGRES, PUBNUB, WEBHOOK, LOAD,
+        ])? {
+            WEBHOOK => Ok(CreateSourceConnector::Webhook),
+            LOAD => {
+                self.expect_keyword(GENERATOR)?;
+                let generator = match self.expect_one_of_keywords(&[COUNTER, AUCTION, TPCH])? {
+                    COUNTER => LoadGenerator::Counter,
+                    AUCTION => LoadGenerator::Auction,
+                    TPCH => {
+                        self.expect_token(&Token::LParen)?;
+                        self.expect_keywords(&[SCALE, FACTOR])?;
+                        let scale_factor = self.parse_number_value()?;
+                        self.expect_token(&Token::RParen)?;
+                        LoadGenerator::Tpch { scale_factor }
+                    }
+                    _ => unreachable!(),
+                };
+                Ok(CreateSourceConnector::LoadGenerator { generator })
+            }
             PUBNUB => {
                 self.expect_keywords(&[SUBSCRIBE, KEY])?;
                 let subscribe_key = self.parse_literal_string()?;
@@ -2058,6 +2131,7 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(BROKER)?;
                 let broker = self.parse_literal_string()?;
                 self.expect_keyword(TOPIC)?;
+                let pattern = self.parse_keyword(PATTERN);
                 let topic = self.parse_literal_string()?;
                 // one token of lookahead:
                 // * `KEY (` means we're parsing a list of columns for the key
@@ -2070,7 +2144,12 @@ impl<'a> Parser<'a> {
                 } else {
                     None
                 };
-                Ok(CreateSourceConnector::Kafka { broker, topic, key })
+                Ok(CreateSourceConnector::Kafka {
+                    broker,
+                    topic,
+                    pattern,
+                    key,
+                })
             }
             KINESIS => {
                 self.expect_keyword(ARN)?;
@@ -2132,11 +2211,12 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_create_sink_connector(&mut self) -> Result<CreateSinkConnector<Raw>, ParserError> {
-        match self.expect_one_of_keywords(&[KAFKA, AVRO])? {
+        match self.expect_one_of_keywords(&[KAFKA, AVRO, FILE, S3, POSTGRES])? {
             KAFKA => {
                 self.expect_keyword(BROKER)?;
                 let broker = self.parse_literal_string()?;
                 self.expect_keyword(TOPIC)?;
+                let pattern = self.parse_keyword(PATTERN);
                 let topic = self.parse_literal_string()?;
                 // one token of lookahead:
                 // * `KEY (` means we're parsing a list of columns for the key
@@ -2160,11 +2240,26 @@ impl<'a> Parser<'a> {
                 } else {
                     None
                 };
+                let partition_by = if self.parse_keywords(&[PARTITION, BY]) {
+                    Some(self.parse_expr()?)
+                } else {
+                    None
+                };
+                let headers = if self.parse_keyword(HEADERS) {
+                    self.expect_token(&Token::LParen)?;
+                    let headers = self.parse_comma_separated(Parser::parse_kafka_sink_header)?;
+                    self.expect_token(&Token::RParen)?;
+                    headers
+                } else {
+                    vec![]
+                };
                 let consistency = self.parse_kafka_consistency()?;
                 Ok(CreateSinkConnector::Kafka {
                     broker,
                     topic,
                     key,
+                    partition_by,
+                    headers,
                     consistency,
                 })
             }
@@ -2173,10 +2268,48 @@ impl<'a> Parser<'a> {
                 let path = self.parse_literal_string()?;
                 Ok(CreateSinkConnector::AvroOcf { path })
             }
+            FILE => {
+                let path = self.parse_literal_string()?;
+                Ok(CreateSinkConnector::File { path })
+            }
+            S3 => {
+                self.expect_keyword(BUCKET)?;
+                let bucket = self.parse_literal_string()?;
+                let prefix = if self.parse_keyword(PREFIX) {
+                    Some(self.parse_literal_string()?)
+                } else {
+                    None
+                };
+                Ok(CreateSinkConnector::S3 { bucket, prefix })
+            }
+            POSTGRES => {
+                self.expect_keyword(CONNECTION)?;
+                let conn = self.parse_literal_string()?;
+                self.expect_keyword(TABLE)?;
+                let table = self.parse_literal_string()?;
+                self.expect_keyword(KEY)?;
+                let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+                let not_enforced = self.parse_keywords(&[NOT, ENFORCED]);
+                Ok(CreateSinkConnector::Postgres {
+                    conn,
+                    table,
+                    key: KafkaSinkKey {
+                        key_columns,
+                        not_enforced,
+                    },
+                })
+            }
             _ => unreachable!(),
         }
     }
 
+    fn parse_kafka_sink_header(&mut self) -> Result<KafkaSinkHeader<Raw>, ParserError> {
+        let name = self.parse_literal_string()?;
+        self.expect_token(&Token::Eq)?;
+        let value = self.parse_expr()?;
+        Ok(KafkaSinkHeader { name, value })
+    }
+
     fn parse_kafka_consistency(&mut self) -> Result<Option<KafkaConsistency<Raw>>, ParserError> {
         if self.parse_keyword(CONSISTENCY) {
             // We would prefer for all consistency parameters to be
@@ -2433,20 +2566,28 @@ impl<'a> Parser<'a> {
     fn parse_source_include_metadata(&mut self) -> Result<Vec<SourceIncludeMetadata>, ParserError> {
         if self.parse_keyword(INCLUDE) {
             self.parse_comma_separated(|parser| {
-                let ty = match parser
-                    .expect_one_of_keywords(&[KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET])?
-                {
+                let mut ty = match parser.expect_one_of_keywords(&[
+                    KEY, TIMESTAMP, PARTITION, TOPIC, OFFSET, HEADERS, HEADER,
+                ])? {
                     KEY => SourceIncludeMetadataType::Key,
                     TIMESTAMP => SourceIncludeMetadataType::Timestamp,
                     PARTITION => SourceIncludeMetadataType::Partition,
                     TOPIC => SourceIncludeMetadataType::Topic,
                     OFFSET => SourceIncludeMetadataType::Offset,
+                    HEADERS => SourceIncludeMetadataType::Headers,
+                    HEADER => SourceIncludeMetadataType::Header {
+                        key: parser.parse_literal_string()?,
+                        use_bytes: false,
+                    },
                     _ => unreachable!("only explicitly allowed items can be parsed"),
                 };
                 let alias = parser
                     .parse_keyword(AS)
                     .then(|| parser.parse_identifier())
                     .transpose()?;
+                if let SourceIncludeMetadataType::Header { use_bytes, .. } = &mut ty {
+                    *use_bytes = parser.parse_keyword(BYTES);
+                }
                 Ok(SourceIncludeMetadata { ty, alias })
             })
         } else {
@@ -2469,7 +2610,17 @@ impl<'a> Parser<'a> {
         let materialized = self.parse_keyword(MATERIALIZED);
 
         let object_type = match self.parse_one_of_keywords(&[
-            DATABASE, INDEX, ROLE, SCHEMA, SINK, SOURCE, TABLE, TYPE, USER, VIEW,
+            DATABASE,
+            INDEX,
+            ROLE,
+            SCHEMA,
+            SINK,
+            SOURCE,
+            SUBSCRIPTION,
+            TABLE,
+            TYPE,
+            USER,
+            VIEW,
         ]) {
             Some(DATABASE) => {
                 let if_exists = self.parse_if_exists()?;
@@ -2489,6 +2640,7 @@ impl<'a> Parser<'a> {
             Some(SCHEMA) => ObjectType::Schema,
             Some(SINK) => ObjectType::Sink,
             Some(SOURCE) => ObjectType::Source,
+            Some(SUBSCRIPTION) => ObjectType::Subscription,
             Some(TABLE) => ObjectType::Table,
             Some(TYPE) => ObjectType::Type,
             Some(VIEW) => ObjectType::View,
@@ -2496,7 +2648,7 @@ impl<'a> Parser<'a> {
                 return self.expected(
                     self.peek_pos(),
                     "DATABASE, INDEX, ROLE, SCHEMA, SINK, SOURCE, \
-                     TABLE, TYPE, USER, VIEW after DROP",
+                     SUBSCRIPTION, TABLE, TYPE, USER, VIEW after DROP",
                     self.peek_token(),
                 );
             }
@@ -2773,14 +2925,16 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_alter(&mut self) -> Result<Statement<Raw>, ParserError> {
-        let object_type = match self.expect_one_of_keywords(&[INDEX, SINK, SOURCE, VIEW, TABLE])? {
-            INDEX => ObjectType::Index,
-            SINK => ObjectType::Sink,
-            SOURCE => ObjectType::Source,
-            VIEW => ObjectType::View,
-            TABLE => ObjectType::Table,
-            _ => unreachable!(),
-        };
+        let object_type =
+            match self.expect_one_of_keywords(&[INDEX, SINK, SOURCE, SUBSCRIPTION, VIEW, TABLE])? {
+                INDEX => ObjectType::Index,
+                SINK => ObjectType::Sink,
+                SOURCE => ObjectType::Source,
+                SUBSCRIPTION => ObjectType::Subscription,
+                VIEW => ObjectType::View,
+                TABLE => ObjectType::Table,
+                _ => unreachable!(),
+            };
 
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
@@ -2817,6 +2971,39 @@ impl<'a> Parser<'a> {
             }
         }
 
+        // We support `ALTER SOURCE ... REFRESH SCHEMA`.
+        if object_type == ObjectType::Source && self.parse_keyword(REFRESH) {
+            self.expect_keyword(SCHEMA)?;
+            let seed = self.parse_csr_seed()?;
+            return Ok(Statement::AlterSource(AlterSourceStatement {
+                source_name: name,
+                if_exists,
+                action: AlterSourceAction::RefreshSchema { seed },
+            }));
+        }
+
+        // We support `ALTER SINK ... RESUME FROM`.
+        if object_type == ObjectType::Sink && self.parse_keyword(RESUME) {
+            self.expect_keyword(FROM)?;
+            let timestamp = self.parse_literal_uint()?;
+            return Ok(Statement::AlterSink(AlterSinkStatement {
+                sink_name: name,
+                if_exists,
+                action: AlterSinkAction::ResumeFrom { timestamp },
+            }));
+        }
+
+        // We support `ALTER SUBSCRIPTION ... ACKNOWLEDGE`.
+        if object_type == ObjectType::Subscription {
+            self.expect_keyword(ACKNOWLEDGE)?;
+            let timestamp = self.parse_literal_uint()?;
+            return Ok(Statement::AlterSubscription(AlterSubscriptionStatement {
+                subscription_name: name,
+                if_exists,
+                action: AlterSubscriptionAction::Acknowledge { timestamp },
+            }));
+        }
+
         self.expect_keywords(&[RENAME, TO])?;
         let to_item_name = self.parse_identifier()?;
 
@@ -4322,15 +4509,40 @@ impl<'a> Parser<'a> {
         } else {
             TailRelation::Name(self.parse_object_name()?)
         };
+        let envelope = if self.parse_keyword(ENVELOPE) {
+            Some(self.parse_tail_envelope()?)
+        } else {
+            None
+        };
         let options = self.parse_opt_with_options()?;
         let as_of = self.parse_optional_as_of()?;
         Ok(Statement::Tail(TailStatement {
             relation,
+            envelope,
             options,
             as_of,
         }))
     }
 
+    /// Parse the envelope of a `TAIL` statement, assuming that the `ENVELOPE`
+    /// token has already been consumed.
+    fn parse_tail_envelope(&mut self) -> Result<TailEnvelope, ParserError> {
+        let upsert = match self.expect_one_of_keywords(&[UPSERT, DEBEZIUM])? {
+            UPSERT => true,
+            DEBEZIUM => false,
+            _ => unreachable!(),
+        };
+        self.expect_token(&Token::LParen)?;
+        self.expect_keyword(KEY)?;
+        let key_columns = self.parse_parenthesized_column_list(Mandatory)?;
+        self.expect_token(&Token::RParen)?;
+        Ok(if upsert {
+            TailEnvelope::Upsert { key_columns }
+        } else {
+            TailEnvelope::Debezium { key_columns }
+        })
+    }
+
     /// Parse an `EXPLAIN` statement, assuming that the `EXPLAIN` token
     /// has already been consumed.
     fn parse_explain(&mut self) -> Result<Statement<Raw>, ParserError> {
@@ -12,14 +12,16 @@
 use std::collections::{HashMap, HashSet};
 
 use mz_ore::str::StrExt;
+use mz_repr::ColumnName;
 use mz_sql_parser::ast::RawName;
 
 use crate::ast::visit::{self, Visit};
 use crate::ast::visit_mut::{self, VisitMut};
 use crate::ast::{
     AstInfo, CreateIndexStatement, CreateSinkStatement, CreateSourceStatement,
-    CreateTableStatement, CreateViewStatement, Expr, Ident, Query, Raw, Statement,
-    UnresolvedObjectName, ViewDefinition,
+    CreateSubscriptionStatement, CreateTableStatement, CreateViewStatement, DecodeErrorPolicy,
+    Expr, Ident, Query, Raw, Select, SelectItem, Statement, TableAlias, TableFactor,
+    TableWithJoins, UnresolvedObjectName, ViewDefinition,
 };
 use crate::names::FullName;
 
@@ -33,6 +35,7 @@ pub fn create_stmt_rename(create_stmt: &mut Statement<Raw>, to_item_name: String
             *name = Some(Ident::new(to_item_name));
         }
         Statement::CreateSink(CreateSinkStatement { name, .. })
+        | Statement::CreateSubscription(CreateSubscriptionStatement { name, .. })
         | Statement::CreateSource(CreateSourceStatement { name, .. })
         | Statement::CreateView(CreateViewStatement {
             definition: ViewDefinition { name, .. },
@@ -82,7 +85,8 @@ pub fn create_stmt_rename_refs(
         Statement::CreateIndex(CreateIndexStatement { on_name, .. }) => {
             maybe_update_object_name(on_name);
         }
-        Statement::CreateSink(CreateSinkStatement { from, .. }) => {
+        Statement::CreateSink(CreateSinkStatement { from, .. })
+        | Statement::CreateSubscription(CreateSubscriptionStatement { from, .. }) => {
             maybe_update_object_name(from);
         }
         Statement::CreateView(CreateViewStatement {
@@ -91,13 +95,84 @@ pub fn create_stmt_rename_refs(
         }) => {
             rewrite_query(from_name, to_item_name, query)?;
         }
-        Statement::CreateSource(_) | Statement::CreateTable(_) => {}
+        Statement::CreateSource(CreateSourceStatement {
+            on_decode_error, ..
+        }) => {
+            if let DecodeErrorPolicy::Redirect(table) = on_decode_error {
+                maybe_update_object_name(table);
+            }
+        }
+        Statement::CreateTable(_) => {}
         _ => unreachable!("Internal error: only catalog items need to update item refs"),
     }
 
     Ok(())
 }
 
+/// Replaces every reference to `name` in a `CREATE VIEW` statement with a
+/// subquery that selects only `columns` from it, under an alias that keeps
+/// the reference's existing name.
+///
+/// This pins the view to the columns it was originally planned with, e.g. when
+/// columns are later appended to the item that `name` refers to.
+pub fn create_stmt_project_refs(
+    create_stmt: &mut Statement<Raw>,
+    name: FullName,
+    columns: &[ColumnName],
+) {
+    match create_stmt {
+        Statement::CreateView(CreateViewStatement {
+            definition: ViewDefinition { query, .. },
+            ..
+        }) => {
+            let mut v = RefProjector {
+                name: UnresolvedObjectName::from(name),
+                columns,
+            };
+            v.visit_query_mut(query);
+        }
+        _ => unreachable!("Internal error: only views need to project item refs"),
+    }
+}
+
+struct RefProjector<'a> {
+    name: UnresolvedObjectName,
+    columns: &'a [ColumnName],
+}
+
+impl<'a, 'ast> VisitMut<'ast, Raw> for RefProjector<'a> {
+    fn visit_table_factor_mut(&mut self, table_factor: &'ast mut TableFactor<Raw>) {
+        match table_factor {
+            TableFactor::Table { name, alias } if name.name() == &self.name => {
+                let alias = alias.take().unwrap_or_else(|| TableAlias {
+                    name: self.name.0[self.name.0.len() - 1].clone(),
+                    columns: vec![],
+                    strict: false,
+                });
+                let mut select = Select::default().from(TableWithJoins {
+                    relation: TableFactor::Table {
+                        name: name.clone(),
+                        alias: None,
+                    },
+                    joins: vec![],
+                });
+                for column in self.columns {
+                    select = select.project(SelectItem::Expr {
+                        expr: Expr::Identifier(vec![Ident::new(column.as_str())]),
+                        alias: None,
+                    });
+                }
+                *table_factor = TableFactor::Derived {
+                    lateral: false,
+                    subquery: Box::new(Query::select(select)),
+                    alias: Some(alias),
+                };
+            }
+            _ => visit_mut::visit_table_factor_mut(self, table_factor),
+        }
+    }
+}
+
 /// Rewrites `query`'s references of `from` to `to` or errors if too ambiguous.
 fn rewrite_query(from: FullName, to: String, query: &mut Query<Raw>) -> Result<(), String> {
     let from_ident = Ident::new(from.item.clone());
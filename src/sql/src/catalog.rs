@@ -286,6 +286,8 @@ pub enum CatalogItemType {
     Type,
     /// A func.
     Func,
+    /// A subscription.
+    Subscription,
 }
 
 impl fmt::Display for CatalogItemType {
@@ -298,6 +300,7 @@ impl fmt::Display for CatalogItemType {
             CatalogItemType::Index => f.write_str("index"),
             CatalogItemType::Type => f.write_str("type"),
             CatalogItemType::Func => f.write_str("func"),
+            CatalogItemType::Subscription => f.write_str("subscription"),
         }
     }
 }
@@ -26,6 +26,7 @@ use reqwest::Url;
 use tokio::time::Duration;
 
 use mz_ccsr::tls::{Certificate, Identity};
+use mz_repr::strconv;
 use mz_sql_parser::ast::Value;
 
 enum ValType {
@@ -252,6 +253,9 @@ pub fn extract_config(
 ///
 /// Expected to test the output of `extract_security_config`.
 ///
+/// The connection is tested by fetching the metadata of `topic`, or of all
+/// topics if `topic` is `None`.
+///
 /// # Errors
 ///
 /// - `librdkafka` cannot create a BaseConsumer using the provided `options`.
@@ -259,7 +263,7 @@ pub fn extract_config(
 ///   exist.
 pub async fn create_consumer(
     broker: &str,
-    topic: &str,
+    topic: Option<&str>,
     options: &BTreeMap<String, String>,
 ) -> Result<Arc<BaseConsumer<KafkaErrCheckContext>>, anyhow::Error> {
     let mut config = rdkafka::ClientConfig::new();
@@ -272,18 +276,23 @@ pub async fn create_consumer(
         Ok(consumer) => {
             let consumer: Arc<BaseConsumer<KafkaErrCheckContext>> = Arc::new(consumer);
             let context = Arc::clone(&consumer.context());
-            let owned_topic = String::from(topic);
+            let owned_topic = topic.map(String::from);
             // Wait for a metadata request for up to one second. This greatly
             // increases the probability that we'll see a connection error if
             // e.g. the hostname was mistyped. librdkafka doesn't expose a
             // better API for asking whether a connection succeeded or failed,
             // unfortunately.
-            task::spawn_blocking(move || format!("kafka_set_metadata:{broker}:{topic}"), {
-                let consumer = Arc::clone(&consumer);
-                move || {
-                    let _ = consumer.fetch_metadata(Some(&owned_topic), Duration::from_secs(1));
-                }
-            })
+            let task_topic = topic.unwrap_or("*");
+            task::spawn_blocking(
+                move || format!("kafka_set_metadata:{broker}:{task_topic}"),
+                {
+                    let consumer = Arc::clone(&consumer);
+                    move || {
+                        let _ =
+                            consumer.fetch_metadata(owned_topic.as_deref(), Duration::from_secs(1));
+                    }
+                },
+            )
             .await?;
             let error = context.error.lock().expect("lock poisoned");
             if let Some(error) = &*error {
@@ -315,52 +324,52 @@ pub async fn create_consumer(
     }
 }
 
+/// The `WITH` options that resolve to start offsets by timestamp.
+pub const START_TIMESTAMP_OPTIONS: [&str; 2] = ["start_timestamp", "kafka_time_offset"];
+
 /// Returns start offsets for the partitions of `topic` and the provided
-/// `kafka_time_offset` option.
+/// `start_timestamp` or `kafka_time_offset` option.
 ///
 /// For each partition, the returned offset is the earliest offset whose
 /// timestamp is greater than or equal to the given timestamp for the
 /// partition. If no such message exists (or the Kafka broker is before
 /// 0.10.0), the current end offset is returned for the partition.
 ///
-/// The provided `kafka_time_offset` option must be a non-zero number:
+/// Both options accept a non-zero number:
 /// * Non-Negative numbers will used as is (e.g. `1622659034343`)
 /// * Negative numbers will be translated to a timestamp in millis
 ///   before now (e.g. `-10` means 10 millis ago)
 ///
-/// If `kafka_time_offset` has not been configured, an empty Option is
-/// returned.
+/// `start_timestamp` additionally accepts a string holding either an absolute
+/// timestamp (e.g. `'2021-06-02 18:37:14+00'`) or a negative interval
+/// relative to now (e.g. `'-7 days'`).
+///
+/// If neither option has been configured, an empty Option is returned.
 pub async fn lookup_start_offsets(
     consumer: Arc<BaseConsumer<KafkaErrCheckContext>>,
     topic: &str,
     with_options: &BTreeMap<String, Value>,
     now: u64,
 ) -> Result<Option<Vec<i64>>, anyhow::Error> {
-    let time_offset = with_options.get("kafka_time_offset");
-    if time_offset.is_none() {
-        return Ok(None);
-    } else if with_options.contains_key("start_offset") {
-        bail!("`start_offset` and `kafka_time_offset` cannot be set at the same time.")
+    let mut options = START_TIMESTAMP_OPTIONS
+        .iter()
+        .filter_map(|name| with_options.get(*name).map(|value| (*name, value)));
+    let (name, value) = match (options.next(), options.next()) {
+        (None, _) => return Ok(None),
+        (Some(_), Some(_)) => {
+            bail!("`start_timestamp` and `kafka_time_offset` cannot be set at the same time.")
+        }
+        (Some(option), None) => option,
+    };
+    if with_options.contains_key("start_offset") {
+        bail!(
+            "`start_offset` and `{}` cannot be set at the same time.",
+            name
+        )
     }
 
-    // Validate and resolve `kafka_time_offset`.
-    let time_offset = match time_offset.unwrap() {
-        Value::Number(s) => match s.parse::<i64>() {
-            // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
-            Ok(ts) if ts < 0 => {
-                let now: i64 = now.try_into()?;
-                let ts = now - ts.abs();
-                if ts <= 0 {
-                    bail!("Relative `kafka_time_offset` must be smaller than current system timestamp")
-                }
-                ts
-            }
-            // Timestamp in millis (e.g. 1622659034343)
-            Ok(ts) => ts,
-            _ => bail!("`kafka_time_offset` must be a number"),
-        },
-        _ => bail!("`kafka_time_offset` must be a number"),
-    };
+    // Validate and resolve the timestamp.
+    let time_offset = resolve_start_timestamp(name, value, now)?;
 
     // Lookup offsets
     // TODO(guswynn): see if we can add broker to this name
@@ -410,6 +419,43 @@ pub async fn lookup_start_offsets(
     .await?
 }
 
+/// Resolves the value of the `start_timestamp` or `kafka_time_offset` option
+/// `name` to a timestamp in milliseconds since the Unix epoch.
+fn resolve_start_timestamp(name: &str, value: &Value, now: u64) -> Result<i64, anyhow::Error> {
+    let now: i64 = now.try_into()?;
+    // Timestamp in millis *before* now (e.g. -10 means 10 millis ago)
+    let relative = |millis: i64| match now.checked_add(millis) {
+        Some(ts) if ts > 0 => Ok(ts),
+        _ => bail!(
+            "Relative `{}` must be smaller than current system timestamp",
+            name
+        ),
+    };
+    match value {
+        Value::Number(s) => match s.parse::<i64>() {
+            Ok(ts) if ts < 0 => relative(ts),
+            // Timestamp in millis (e.g. 1622659034343)
+            Ok(ts) => Ok(ts),
+            _ => bail!("`{}` must be a number", name),
+        },
+        Value::String(s) if name == "start_timestamp" => {
+            if let Ok(ts) = strconv::parse_timestamptz(s) {
+                return Ok(ts.timestamp_millis());
+            }
+            match strconv::parse_interval(s) {
+                Ok(interval) if interval.is_negative() => {
+                    relative(i64::try_from(interval.as_microseconds() / 1_000)?)
+                }
+                Ok(_) => bail!("`start_timestamp` interval must be negative"),
+                Err(_) => {
+                    bail!("`start_timestamp` must be a timestamp, a negative interval or a number")
+                }
+            }
+        }
+        _ => bail!("`{}` must be a number", name),
+    }
+}
+
 // Kafka supports bulk lookup of watermarks, but it is not exposed in rdkafka.
 // If that ever changes, we will want to first collect all pids that have no
 // offset for a given timestamp and then do a single request (instead of doing
@@ -529,3 +575,41 @@ pub fn generate_ccsr_client_config(
 
     Ok(client_config)
 }
+
+#[cfg(test)]
+mod tests {
+    use mz_sql_parser::ast::Value;
+
+    use super::resolve_start_timestamp;
+
+    #[test]
+    fn test_resolve_start_timestamp() {
+        let now = 1_622_659_034_343;
+        let resolve = |name, value| resolve_start_timestamp(name, &value, now);
+        let number = |n: &str| Value::Number(n.into());
+        let string = |s: &str| Value::String(s.into());
+
+        for name in ["start_timestamp", "kafka_time_offset"] {
+            assert_eq!(resolve(name, number("42")).unwrap(), 42);
+            assert_eq!(resolve(name, number("-10")).unwrap(), 1_622_659_034_333);
+            assert!(resolve(name, number("-1622659034343")).is_err());
+            assert!(resolve(name, number("1.5")).is_err());
+        }
+
+        assert_eq!(
+            resolve("start_timestamp", string("2021-06-02 18:37:14.343+00")).unwrap(),
+            1_622_659_034_343
+        );
+        assert_eq!(
+            resolve("start_timestamp", string("1970-01-01 00:00:00.002")).unwrap(),
+            2
+        );
+        assert_eq!(
+            resolve("start_timestamp", string("-7 days")).unwrap(),
+            1_622_659_034_343 - 7 * 24 * 60 * 60 * 1000
+        );
+        assert!(resolve("start_timestamp", string("7 days")).is_err());
+        assert!(resolve("start_timestamp", string("yesterday-ish")).is_err());
+        assert!(resolve("kafka_time_offset", string("-7 days")).is_err());
+    }
+}
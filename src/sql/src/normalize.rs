@@ -15,8 +15,10 @@
 //! [`ast`]: crate::ast
 
 use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context};
+use globset::{Glob, GlobBuilder};
 use itertools::Itertools;
 
 use mz_dataflow_types::sources::{AwsAssumeRole, AwsConfig, AwsCredentials, SerdeUri};
@@ -25,9 +27,10 @@ use mz_sql_parser::ast::display::AstDisplay;
 use mz_sql_parser::ast::visit_mut::{self, VisitMut};
 use mz_sql_parser::ast::{
     AstInfo, CreateIndexStatement, CreateSinkStatement, CreateSourceStatement,
-    CreateTableStatement, CreateTypeAs, CreateTypeStatement, CreateViewStatement, Function,
-    FunctionArgs, Ident, IfExistsBehavior, Op, Query, Raw, SqlOption, Statement, TableFactor,
-    TableFunction, UnresolvedObjectName, Value, ViewDefinition,
+    CreateSubscriptionStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
+    CreateViewStatement, DecodeErrorPolicy, Function, FunctionArgs, Ident, IfExistsBehavior, Op,
+    Query, Raw, SqlOption, Statement, TableFactor, TableFunction, UnresolvedObjectName, Value,
+    ViewDefinition,
 };
 
 use crate::names::{resolve_names_stmt, Aug, DatabaseSpecifier, FullName, PartialName};
@@ -279,10 +282,14 @@ pub fn create_statement(
             if_not_exists,
             materialized,
             key_constraint: _,
+            on_decode_error,
         }) => {
             *name = allocate_name(name)?;
             *if_not_exists = false;
             *materialized = false;
+            if let DecodeErrorPolicy::Redirect(table) = on_decode_error {
+                *table = resolve_item(table)?;
+            }
         }
 
         Statement::CreateTable(CreateTableStatement {
@@ -324,6 +331,17 @@ pub fn create_statement(
             *if_not_exists = false;
         }
 
+        Statement::CreateSubscription(CreateSubscriptionStatement {
+            name,
+            from,
+            with_options: _,
+            if_not_exists,
+        }) => {
+            *name = allocate_name(name)?;
+            *from = resolve_item(from)?;
+            *if_not_exists = false;
+        }
+
         Statement::CreateView(CreateViewStatement {
             temporary,
             materialized,
@@ -578,6 +596,36 @@ pub fn aws_config(
     })
 }
 
+/// Normalizes the path of a file source that names several files.
+///
+/// A path that ends in `/` names every file in that directory, and a path that
+/// contains glob metacharacters names every file that matches it. For such
+/// paths, returns the deepest directory that contains all matching files and
+/// the glob that matches them. Returns `None` for paths that name a single
+/// file.
+pub fn file_glob(path: &str) -> Result<Option<(PathBuf, Glob)>, anyhow::Error> {
+    let is_glob_component = |s: &str| s.contains(|c: char| matches!(c, '*' | '?' | '[' | '{'));
+    let pattern = if path.ends_with('/') {
+        format!("{}*", path)
+    } else if is_glob_component(path) {
+        path.to_owned()
+    } else {
+        return Ok(None);
+    };
+    if !path.starts_with('/') {
+        bail!("file source globs and directories must be absolute paths");
+    }
+    let root: PathBuf = Path::new(&pattern)
+        .components()
+        .take_while(|c| !is_glob_component(&c.as_os_str().to_string_lossy()))
+        .collect();
+    let glob = GlobBuilder::new(&pattern)
+        .literal_separator(true)
+        .backslash_escape(true)
+        .build()?;
+    Ok(Some((root, glob)))
+}
+
 #[cfg(test)]
 mod tests {
     use std::error::Error;
@@ -587,6 +635,25 @@ mod tests {
     use super::*;
     use crate::catalog::DummyCatalog;
 
+    #[test]
+    fn file_glob() -> Result<(), Box<dyn Error>> {
+        assert_eq!(super::file_glob("/var/log/app.log")?, None);
+
+        let (root, glob) = super::file_glob("/var/log/app/*.log")?.unwrap();
+        assert_eq!(root, Path::new("/var/log/app"));
+        assert_eq!(glob.glob(), "/var/log/app/*.log");
+
+        let (root, glob) = super::file_glob("/var/log/app/")?.unwrap();
+        assert_eq!(root, Path::new("/var/log/app"));
+        assert_eq!(glob.glob(), "/var/log/app/*");
+
+        let (root, _) = super::file_glob("/var/log/{a,b}/**/app.log")?.unwrap();
+        assert_eq!(root, Path::new("/var/log"));
+
+        assert!(super::file_glob("logs/*.log").is_err());
+        Ok(())
+    }
+
     #[test]
     fn normalized_create() -> Result<(), Box<dyn Error>> {
         let scx = &StatementContext::new(None, &DummyCatalog);
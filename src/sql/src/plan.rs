@@ -34,11 +34,11 @@ use enum_kinds::EnumKind;
 use serde::{Deserialize, Serialize};
 
 use mz_dataflow_types::{
-    sinks::SinkConnectorBuilder, sinks::SinkEnvelope, sources::SourceConnector,
+    sinks::SinkConnectorBuilder, sinks::SinkEnvelope, sinks::TailEnvelope, sources::SourceConnector,
 };
 use mz_expr::{GlobalId, MirRelationExpr, MirScalarExpr, RowSetFinishing};
 use mz_ore::now::{self, NOW_ZERO};
-use mz_repr::{ColumnName, Diff, RelationDesc, Row, ScalarType};
+use mz_repr::{ColumnName, Diff, RelationDesc, Row, ScalarType, Timestamp};
 
 use crate::ast::{
     ExplainOptions, ExplainStage, Expr, FetchDirection, NoticeSeverity, ObjectType, Raw, Statement,
@@ -75,6 +75,7 @@ pub enum Plan {
     CreateRole(CreateRolePlan),
     CreateSource(CreateSourcePlan),
     CreateSink(CreateSinkPlan),
+    CreateSubscription(CreateSubscriptionPlan),
     CreateTable(CreateTablePlan),
     CreateView(CreateViewPlan),
     CreateViews(CreateViewsPlan),
@@ -105,6 +106,9 @@ pub enum Plan {
     AlterIndexResetOptions(AlterIndexResetOptionsPlan),
     AlterIndexEnable(AlterIndexEnablePlan),
     AlterItemRename(AlterItemRenamePlan),
+    AlterSource(AlterSourcePlan),
+    AlterSink(AlterSinkPlan),
+    AlterSubscription(AlterSubscriptionPlan),
     Declare(DeclarePlan),
     Fetch(FetchPlan),
     Close(ClosePlan),
@@ -154,6 +158,13 @@ pub struct CreateSinkPlan {
     pub if_not_exists: bool,
 }
 
+#[derive(Debug)]
+pub struct CreateSubscriptionPlan {
+    pub name: FullName,
+    pub subscription: Subscription,
+    pub if_not_exists: bool,
+}
+
 #[derive(Debug)]
 pub struct CreateTablePlan {
     pub name: FullName,
@@ -241,11 +252,14 @@ pub struct TailPlan {
     pub ts: Option<MirScalarExpr>,
     pub copy_to: Option<CopyFormat>,
     pub emit_progress: bool,
+    pub envelope: Option<TailEnvelope>,
 }
 
 #[derive(Debug)]
 pub enum TailFrom {
     Id(GlobalId),
+    /// Resume the named subscription from its last acknowledged timestamp.
+    Subscription(GlobalId),
     Query {
         expr: MirRelationExpr,
         desc: RelationDesc,
@@ -318,6 +332,28 @@ pub struct AlterIndexEnablePlan {
     pub id: GlobalId,
 }
 
+#[derive(Debug)]
+pub struct AlterSourcePlan {
+    pub id: GlobalId,
+    /// The source as replanned against its refreshed schema.
+    pub source: Source,
+}
+
+#[derive(Debug)]
+pub struct AlterSinkPlan {
+    pub id: GlobalId,
+    /// The sink resumes with the updates after this timestamp.
+    pub resume_from: Timestamp,
+}
+
+#[derive(Debug)]
+pub struct AlterSubscriptionPlan {
+    pub id: GlobalId,
+    /// The consumer has durably processed all updates up to and including
+    /// this timestamp.
+    pub acknowledged: Timestamp,
+}
+
 #[derive(Debug)]
 pub struct AlterItemRenamePlan {
     pub id: GlobalId,
@@ -379,8 +415,11 @@ pub struct Table {
 pub struct Source {
     pub create_sql: String,
     pub connector: SourceConnector,
+    /// The secret of a webhook source, which is not part of `create_sql`.
+    pub webhook_secret: Option<String>,
     pub desc: RelationDesc,
     pub expr: mz_expr::MirRelationExpr,
+    pub depends_on: Vec<GlobalId>,
 }
 
 #[derive(Clone, Debug)]
@@ -392,6 +431,16 @@ pub struct Sink {
     pub depends_on: Vec<GlobalId>,
 }
 
+#[derive(Clone, Debug)]
+pub struct Subscription {
+    pub create_sql: String,
+    pub from: GlobalId,
+    /// How far, in milliseconds, the acknowledged timestamp may trail the
+    /// current time before the subscription stops holding back compaction.
+    pub max_lag_ms: u64,
+    pub depends_on: Vec<GlobalId>,
+}
+
 #[derive(Clone, Debug)]
 pub struct View {
     pub create_sql: String,
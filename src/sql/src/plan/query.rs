@@ -859,6 +859,40 @@ pub fn plan_index_exprs<'a>(
     Ok((out, qcx.ids.into_iter().collect()))
 }
 
+/// Plans an expression in a Kafka sink's `PARTITION BY` or `HEADERS` clause,
+/// which is evaluated against each row of the sink's input.
+///
+/// Partition keys and header values are byte strings, so expressions of any
+/// type other than `bytea` are converted to their text representation.
+pub fn plan_sink_expr(
+    scx: &StatementContext,
+    on_desc: &RelationDesc,
+    name: &str,
+    mut expr: Expr<Raw>,
+) -> Result<(mz_expr::MirScalarExpr, Vec<GlobalId>), PlanError> {
+    let scope = Scope::from_source(None, on_desc.iter_names());
+    let mut qcx = QueryContext::root(scx, QueryLifetime::Static);
+
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = resolve_names_expr(&mut qcx, expr)?;
+
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name,
+        scope: &scope,
+        relation_type: on_desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+        allow_windows: false,
+    };
+    let expr = plan_expr(ecx, &expr)?.type_as_any(ecx)?;
+    let expr = match ecx.scalar_type(&expr) {
+        ScalarType::Bytes | ScalarType::String => expr,
+        _ => expr.cast_to(ecx, CastContext::Explicit, &ScalarType::String)?,
+    };
+    Ok((expr.lower_uncorrelated()?, qcx.ids.into_iter().collect()))
+}
+
 fn plan_expr_or_col_index(ecx: &ExprContext, e: &Expr<Aug>) -> Result<HirScalarExpr, PlanError> {
     match check_col_index(&ecx.name, e, ecx.relation_type.column_types.len())? {
         Some(column) => Ok(HirScalarExpr::column(column)),
@@ -17,7 +17,7 @@ use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::time::Duration;
 
-use anyhow::{anyhow, bail};
+use anyhow::{anyhow, bail, Context};
 use aws_arn::ARN;
 use bytes::Bytes;
 use chrono::{NaiveDate, NaiveDateTime};
@@ -27,28 +27,35 @@ use prost::Message;
 use regex::Regex;
 use reqwest::Url;
 use tracing::{debug, warn};
+use uuid::Uuid;
 
 use mz_dataflow_types::{
     postgres_source::PostgresSourceDetails,
     sinks::{
-        AvroOcfSinkConnectorBuilder, KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention,
-        KafkaSinkFormat, SinkConnectorBuilder, SinkEnvelope,
+        AvroOcfSinkConnectorBuilder, FileSinkConnectorBuilder, FileSinkFormat, FileSinkTarget,
+        KafkaSinkConnectorBuilder, KafkaSinkConnectorRetention, KafkaSinkFormat,
+        PostgresSinkConnectorBuilder, SinkConnectorBuilder, SinkEnvelope,
     },
     sources::{
+        decode_error_redirect_desc,
         encoding::{
             included_column_desc, AvroEncoding, AvroOcfEncoding, ColumnSpec, CsvEncoding,
-            DataEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
+            DataEncoding, ParquetEncoding, ProtobufEncoding, RegexEncoding, SourceDataEncoding,
         },
         provide_default_metadata, DebeziumDedupProjection, DebeziumEnvelope, DebeziumMode,
-        DebeziumSourceProjection, ExternalSourceConnector, FileSourceConnector, IncludedColumnPos,
-        KafkaSourceConnector, KeyEnvelope, KinesisSourceConnector, PostgresSourceConnector,
-        PubNubSourceConnector, S3SourceConnector, SourceConnector, SourceEnvelope, Timeline,
-        UnplannedSourceEnvelope, UpsertStyle,
+        DebeziumSourceProjection, DecodeErrorPolicy as SourceDecodeErrorPolicy, EventTimeConfig,
+        ExternalSourceConnector, FileSourceConnector, IncludedColumnPos, IncludedHeader,
+        KafkaSourceConnector, KeyEnvelope, KinesisSourceConnector, LoadGenerator,
+        LoadGeneratorSourceConnector, PostgresSourceConnector, PubNubSourceConnector,
+        S3SourceConnector, SourceConnector, SourceEnvelope, Timeline, UnplannedSourceEnvelope,
+        UpsertStyle, WebhookSourceConnector,
     },
 };
-use mz_expr::GlobalId;
+use mz_expr::{GlobalId, MirScalarExpr};
 use mz_interchange::avro::{self, AvroSchemaGenerator};
 use mz_interchange::envelopes;
+use mz_interchange::parquet;
+use mz_interchange::protobuf::ProtobufSchemaGenerator;
 use mz_ore::collections::CollectionExt;
 use mz_ore::str::StrExt;
 use mz_repr::{strconv, ColumnName, RelationDesc, RelationType, ScalarType};
@@ -56,16 +63,18 @@ use mz_sql_parser::ast::{CsrSeedCompiledOrLegacy, SourceIncludeMetadata};
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AvroSchema, ColumnOption,
-    Compression, CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption,
-    CreateRoleStatement, CreateSchemaStatement, CreateSinkConnector, CreateSinkStatement,
-    CreateSourceConnector, CreateSourceFormat, CreateSourceStatement, CreateTableStatement,
-    CreateTypeAs, CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions,
-    CreateViewsStatement, CsrConnectorAvro, CsrConnectorProto, CsrSeedCompiled, CsvColumns,
-    DbzMode, DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident,
-    IfExistsBehavior, KafkaConsistency, KeyConstraint, ObjectType, ProtobufSchema, Raw,
-    SourceIncludeMetadataType, SqlOption, Statement, TableConstraint, UnresolvedObjectName, Value,
-    ViewDefinition, WithOption,
+    AlterIndexAction, AlterIndexStatement, AlterObjectRenameStatement, AlterSinkAction,
+    AlterSinkStatement, AlterSourceAction, AlterSourceStatement, AlterSubscriptionAction,
+    AlterSubscriptionStatement, AvroSchema, ColumnOption, Compression, CreateDatabaseStatement,
+    CreateIndexStatement, CreateRoleOption, CreateRoleStatement, CreateSchemaStatement,
+    CreateSinkConnector, CreateSinkStatement, CreateSourceConnector, CreateSourceFormat,
+    CreateSourceStatement, CreateSubscriptionStatement, CreateTableStatement, CreateTypeAs,
+    CreateTypeStatement, CreateViewStatement, CreateViewsDefinitions, CreateViewsStatement,
+    CsrConnectorAvro, CsrConnectorProto, CsrSeedCompiled, CsvColumns, DbzMode, DecodeErrorPolicy,
+    DropDatabaseStatement, DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior,
+    KafkaConsistency, KafkaSinkHeader, KafkaSinkKey, KeyConstraint, ObjectType, ProtobufSchema,
+    Raw, SourceIncludeMetadataType, SqlOption, Statement, TableConstraint, UnresolvedObjectName,
+    Value, ViewDefinition, WithOption,
 };
 use crate::catalog::{CatalogItem, CatalogItemType, CatalogType, CatalogTypeDetails};
 use crate::kafka_util;
@@ -79,13 +88,14 @@ use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{
     plan_utils, query, AlterIndexEnablePlan, AlterIndexResetOptionsPlan, AlterIndexSetOptionsPlan,
-    AlterItemRenamePlan, AlterNoopPlan, CreateDatabasePlan, CreateIndexPlan, CreateRolePlan,
-    CreateSchemaPlan, CreateSinkPlan, CreateSourcePlan, CreateTablePlan, CreateTypePlan,
-    CreateViewPlan, CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan,
-    DropSchemaPlan, HirRelationExpr, Index, IndexOption, IndexOptionName, Params, Plan, Sink,
-    Source, Table, Type, View,
+    AlterItemRenamePlan, AlterNoopPlan, AlterSinkPlan, AlterSourcePlan, AlterSubscriptionPlan,
+    CreateDatabasePlan, CreateIndexPlan, CreateRolePlan, CreateSchemaPlan, CreateSinkPlan,
+    CreateSourcePlan, CreateSubscriptionPlan, CreateTablePlan, CreateTypePlan, CreateViewPlan,
+    CreateViewsPlan, DropDatabasePlan, DropItemsPlan, DropRolesPlan, DropSchemaPlan,
+    HirRelationExpr, Index, IndexOption, IndexOptionName, Params, Plan, Sink, Source, Subscription,
+    Table, Type, View,
 };
-use crate::pure::Schema;
+use crate::pure::{value_csr_connector_avro, Schema};
 
 pub fn describe_create_database(
     _: &StatementContext,
@@ -299,6 +309,7 @@ pub fn plan_create_source(
         format,
         key_constraint,
         include_metadata,
+        on_decode_error,
     } = &stmt;
 
     let with_options_original = with_options;
@@ -314,12 +325,34 @@ pub fn plan_create_source(
         },
         None => scx.catalog.config().timestamp_frequency,
     };
-    if !matches!(connector, CreateSourceConnector::Kafka { .. }) && !include_metadata.is_empty() {
+    if !matches!(
+        connector,
+        CreateSourceConnector::Kafka { .. } | CreateSourceConnector::Webhook
+    ) && !include_metadata.is_empty()
+    {
         bail_unsupported!("INCLUDE metadata with non-Kafka sources");
     }
 
+    // The secret of a webhook source is stored separately from the source's
+    // definition, so that it is not revealed by `SHOW CREATE SOURCE`.
+    let mut webhook_secret = None;
     let (external_connector, encoding) = match connector {
-        CreateSourceConnector::Kafka { broker, topic, .. } => {
+        CreateSourceConnector::Kafka {
+            broker,
+            topic,
+            pattern,
+            ..
+        } => {
+            // Topic patterns must match the entire topic name.
+            let topic_pattern = if *pattern {
+                match Regex::new(&format!("^(?:{})$", topic)) {
+                    Ok(regex) => Some(mz_repr::adt::regex::Regex(regex)),
+                    Err(e) => bail!("invalid topic pattern: {}", e),
+                }
+            } else {
+                None
+            };
+
             let config_options = kafka_util::extract_config(&mut with_options)?;
 
             let group_id_prefix = match with_options.remove("group_id_prefix") {
@@ -333,6 +366,10 @@ pub fn plan_create_source(
                 _ => bail!("start_offset must be a nonnegative integer"),
             };
 
+            if *pattern && with_options.contains_key("start_offset") {
+                bail!("start_offset is not supported with TOPIC PATTERN");
+            }
+
             let mut start_offsets = HashMap::new();
             match with_options.remove("start_offset") {
                 None => {
@@ -359,6 +396,7 @@ pub fn plan_create_source(
             let mut connector = KafkaSourceConnector {
                 addrs: broker.parse()?,
                 topic: topic.clone(),
+                topic_pattern,
                 config_options,
                 start_offsets,
                 group_id_prefix,
@@ -367,6 +405,8 @@ pub fn plan_create_source(
                 include_partition: None,
                 include_topic: None,
                 include_offset: None,
+                include_headers: None,
+                include_header: Vec::new(),
             };
 
             let unwrap_name = |alias: Option<Ident>, default, pos| {
@@ -400,16 +440,30 @@ pub fn plan_create_source(
                         connector.include_partition = unwrap_name(item.alias, "partition", pos);
                     }
                     SourceIncludeMetadataType::Topic => {
-                        // TODO(bwm): This requires deeper thought, the current structure of the
-                        // code requires us to clone the topic name around all over the place
-                        // whether or not anyone ever uses it. Considering we expect the
-                        // overwhelming majority of people will *not* want topics in dataflows that
-                        // is an unnacceptable cost.
-                        bail_unsupported!("INCLUDE TOPIC");
+                        // Only sources with a topic pattern carry the topic name of each
+                        // message around, as it is otherwise the same for every message.
+                        if !*pattern {
+                            bail_unsupported!("INCLUDE TOPIC without TOPIC PATTERN");
+                        }
+                        connector.include_topic = unwrap_name(item.alias, "topic", pos);
                     }
                     SourceIncludeMetadataType::Offset => {
                         connector.include_offset = unwrap_name(item.alias, "offset", pos);
                     }
+                    SourceIncludeMetadataType::Headers => {
+                        connector.include_headers = unwrap_name(item.alias, "headers", pos);
+                    }
+                    SourceIncludeMetadataType::Header { key, use_bytes } => {
+                        let name = match item.alias {
+                            Some(alias) => alias.to_string(),
+                            None => key.clone(),
+                        };
+                        connector.include_header.push(IncludedHeader {
+                            key,
+                            column: IncludedColumnPos { name, pos },
+                            use_bytes,
+                        });
+                    }
                     SourceIncludeMetadataType::Key => {} // handled below
                 }
             }
@@ -447,18 +501,51 @@ pub fn plan_create_source(
                 Some(_) => bail!("tail must be a boolean"),
             };
 
+            let (path, pattern) = match normalize::file_glob(path)? {
+                Some((root, glob)) => (root, Some(glob)),
+                None => (path.into(), None),
+            };
             let connector = ExternalSourceConnector::File(FileSourceConnector {
-                path: path.clone().into(),
-                compression: match compression {
-                    Compression::Gzip => mz_dataflow_types::sources::Compression::Gzip,
-                    Compression::None => mz_dataflow_types::sources::Compression::None,
-                },
+                path,
+                pattern: pattern.clone(),
+                compression: plan_compression(compression),
                 tail,
             });
             let encoding = get_encoding(format, envelope, with_options_original)?;
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 bail!("File sources do not support key decoding");
             }
+            // Records from several files are interleaved, so each file must be
+            // made up of self-contained lines.
+            if pattern.is_some() {
+                match encoding.value_ref() {
+                    DataEncoding::Bytes
+                    | DataEncoding::Text
+                    | DataEncoding::Regex(_)
+                    | DataEncoding::Protobuf(_)
+                    | DataEncoding::Csv(CsvEncoding {
+                        columns: ColumnSpec::Count(_),
+                        ..
+                    }) => (),
+                    DataEncoding::Csv(_) => {
+                        bail_unsupported!("CSV WITH HEADER with a glob or directory path")
+                    }
+                    encoding => bail_unsupported!(format!(
+                        "FORMAT {} with a glob or directory path",
+                        encoding.op_name().to_uppercase()
+                    )),
+                }
+            }
+            if let DataEncoding::Parquet(_) = encoding.value_ref() {
+                // Already consumed by `get_encoding`.
+                with_options.remove("parquet_schema");
+                if tail {
+                    bail_unsupported!("tailing Parquet files");
+                }
+                if !matches!(compression, Compression::None) {
+                    bail!("Parquet sources do not support COMPRESSION; Parquet files are compressed internally");
+                }
+            }
             (connector, encoding)
         }
         CreateSourceConnector::S3 {
@@ -495,15 +582,19 @@ pub fn plan_create_source(
                     })
                     .transpose()?,
                 aws,
-                compression: match compression {
-                    Compression::Gzip => mz_dataflow_types::sources::Compression::Gzip,
-                    Compression::None => mz_dataflow_types::sources::Compression::None,
-                },
+                compression: plan_compression(compression),
             });
             let encoding = get_encoding(format, envelope, with_options_original)?;
             if matches!(encoding, SourceDataEncoding::KeyValue { .. }) {
                 bail!("S3 sources do not support key decoding");
             }
+            if let DataEncoding::Parquet(_) = encoding.value_ref() {
+                // Already consumed by `get_encoding`.
+                with_options.remove("parquet_schema");
+                if !matches!(compression, Compression::None) {
+                    bail!("Parquet sources do not support COMPRESSION; Parquet files are compressed internally");
+                }
+            }
             (connector, encoding)
         }
         CreateSourceConnector::Postgres {
@@ -543,6 +634,120 @@ pub fn plan_create_source(
             });
             (connector, SourceDataEncoding::Single(DataEncoding::Text))
         }
+        CreateSourceConnector::LoadGenerator { generator } => {
+            if !matches!(format, CreateSourceFormat::None) {
+                bail!("load generator sources cannot specify a format");
+            }
+            if !matches!(envelope, Envelope::None) {
+                bail_unsupported!(format!("ENVELOPE {} with load generator sources", envelope));
+            }
+            let tick_interval = match with_options.remove("tick_interval") {
+                None => Duration::from_secs(1),
+                Some(Value::String(s)) => mz_repr::util::parse_duration(&s)?,
+                Some(_) => bail!("tick_interval must be a string"),
+            };
+            if tick_interval == Duration::ZERO {
+                bail!("tick_interval must be positive");
+            }
+            let max_cardinality = match with_options.remove("max_cardinality") {
+                None => None,
+                Some(_) if !matches!(generator, mz_sql_parser::ast::LoadGenerator::Counter) => {
+                    bail!("max_cardinality is only supported for COUNTER load generators")
+                }
+                Some(Value::Number(n)) => match n.parse::<u64>() {
+                    Ok(n) if n > 0 && n <= i64::MAX as u64 => Some(n),
+                    _ => bail!("max_cardinality must be a positive integer"),
+                },
+                Some(_) => bail!("max_cardinality must be a positive integer"),
+            };
+            let generator = match generator {
+                mz_sql_parser::ast::LoadGenerator::Counter => {
+                    LoadGenerator::Counter { max_cardinality }
+                }
+                mz_sql_parser::ast::LoadGenerator::Auction => LoadGenerator::Auction,
+                mz_sql_parser::ast::LoadGenerator::Tpch { scale_factor } => {
+                    let scale_factor = match scale_factor {
+                        Value::Number(n) => match n.parse::<f64>() {
+                            Ok(sf) if sf > 0.0 && sf.is_finite() => sf,
+                            _ => bail!("TPCH scale factor must be a positive number"),
+                        },
+                        _ => bail!("TPCH scale factor must be a positive number"),
+                    };
+                    // TPC-H defines 1,500,000 orders per unit of scale. At
+                    // least two orders are required to keep both fulfilled
+                    // and open orders around.
+                    let count_orders = ((1_500_000.0 * scale_factor) as u64).max(2);
+                    LoadGenerator::Tpch { count_orders }
+                }
+            };
+            let encoding = SourceDataEncoding::Single(DataEncoding::RowCodec(generator.desc()));
+            let connector = ExternalSourceConnector::LoadGenerator(LoadGeneratorSourceConnector {
+                generator,
+                tick_interval,
+            });
+            (connector, encoding)
+        }
+        CreateSourceConnector::Webhook => {
+            // Planning a source loaded from the catalog sees no secret, as it
+            // is stripped from the stored definition below. The coordinator
+            // rejects new webhook sources without one.
+            webhook_secret = match with_options.remove("hmac_secret") {
+                None => None,
+                Some(Value::String(secret)) => Some(secret),
+                Some(_) => bail!("hmac_secret must be a string"),
+            };
+            let signature_header = match with_options.remove("hmac_header") {
+                None => bail!("webhook sources require hmac_header"),
+                Some(Value::String(header)) => header.to_lowercase(),
+                Some(_) => bail!("hmac_header must be a string"),
+            };
+
+            if !matches!(envelope, Envelope::None) {
+                bail_unsupported!(format!("ENVELOPE {} with webhook sources", envelope));
+            }
+            let encoding = get_encoding(format, envelope, with_options_original)?;
+            match &encoding {
+                SourceDataEncoding::KeyValue { .. } => {
+                    bail!("webhook sources cannot specify a KEY FORMAT")
+                }
+                SourceDataEncoding::Single(
+                    DataEncoding::Bytes
+                    | DataEncoding::Text
+                    | DataEncoding::Csv(_)
+                    | DataEncoding::Regex(_)
+                    | DataEncoding::Protobuf(_)
+                    | DataEncoding::Avro(_),
+                ) => (),
+                SourceDataEncoding::Single(encoding) => bail_unsupported!(format!(
+                    "FORMAT {} with webhook sources",
+                    encoding.op_name().to_uppercase()
+                )),
+            }
+
+            let mut include_headers = None;
+            for (pos, item) in include_metadata.iter().enumerate() {
+                match &item.ty {
+                    SourceIncludeMetadataType::Headers => {
+                        include_headers = Some(IncludedColumnPos {
+                            name: item
+                                .alias
+                                .as_ref()
+                                .map(|a| a.to_string())
+                                .unwrap_or_else(|| String::from("headers")),
+                            pos,
+                        });
+                    }
+                    ty => bail_unsupported!(format!("INCLUDE {} with webhook sources", ty)),
+                }
+            }
+
+            let connector = ExternalSourceConnector::Webhook(WebhookSourceConnector {
+                signature_header,
+                include_headers,
+                persisted_name: None,
+            });
+            (connector, encoding)
+        }
         CreateSourceConnector::AvroOcf { path, .. } => {
             let tail = match with_options.remove("tail") {
                 None => false,
@@ -552,6 +757,7 @@ pub fn plan_create_source(
 
             let connector = ExternalSourceConnector::AvroOcf(FileSourceConnector {
                 path: path.clone().into(),
+                pattern: None,
                 compression: mz_dataflow_types::sources::Compression::None,
                 tail,
             });
@@ -571,12 +777,31 @@ pub fn plan_create_source(
             (connector, encoding)
         }
     };
+    let is_parquet = |encoding: &DataEncoding| matches!(encoding, DataEncoding::Parquet(_));
+    if (is_parquet(encoding.value_ref()) || encoding.key_ref().map_or(false, is_parquet))
+        && !matches!(
+            connector,
+            CreateSourceConnector::File { .. } | CreateSourceConnector::S3 { .. }
+        )
+    {
+        bail_unsupported!("FORMAT PARQUET with non-file, non-S3 sources");
+    }
     let (key_desc, value_desc) = encoding.desc()?;
 
     let key_envelope = get_key_envelope(include_metadata, envelope, &encoding)?;
 
     // TODO: remove bails as more support for upsert is added.
     let envelope = match &envelope {
+        // Postgres sources and load generators produce their own deletes, so
+        // their output is not append-only despite their lack of an envelope.
+        mz_sql_parser::ast::Envelope::None
+            if matches!(
+                external_connector,
+                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::LoadGenerator(_)
+            ) =>
+        {
+            UnplannedSourceEnvelope::DifferentialRow
+        }
         // TODO: fixup key envelope
         mz_sql_parser::ast::Envelope::None => {
             UnplannedSourceEnvelope::None(key_envelope.unwrap_or(KeyEnvelope::None))
@@ -712,9 +937,39 @@ pub fn plan_create_source(
                 // but OCF goes through a separate pipeline that it hasn't been implemented for.
                 bail_unsupported!("ENVELOPE MATERIALIZE over OCF (Avro files)")
             }
-            match format {
-                CreateSourceFormat::Bare(Format::Avro(_)) => {}
-                _ => bail_unsupported!("non-Avro-encoded ENVELOPE MATERIALIZE"),
+            match connector {
+                CreateSourceConnector::File { .. } => {
+                    bail_unsupported!("ENVELOPE MATERIALIZE with file sources")
+                }
+                CreateSourceConnector::S3 { .. } => {
+                    bail_unsupported!("ENVELOPE MATERIALIZE with S3 sources")
+                }
+                _ => (),
+            }
+            if let CreateSourceFormat::KeyValue { .. } = format {
+                bail_unsupported!("ENVELOPE MATERIALIZE with KEY FORMAT")
+            }
+            match encoding.value_ref() {
+                DataEncoding::Avro(_) | DataEncoding::Json => {}
+                DataEncoding::Protobuf(ProtobufEncoding {
+                    descriptors,
+                    message_name,
+                    confluent_wire_format,
+                }) => {
+                    // Validate that the message has the shape of a CDCv2 envelope.
+                    let descriptors = mz_interchange::protobuf::DecodedDescriptors::from_bytes(
+                        descriptors,
+                        message_name.to_owned(),
+                    )?;
+                    mz_interchange::protobuf::cdc_v2::Decoder::new(
+                        descriptors,
+                        *confluent_wire_format,
+                    )?;
+                }
+                encoding => bail_unsupported!(format!(
+                    "{}-encoded ENVELOPE MATERIALIZE",
+                    encoding.op_name()
+                )),
             }
             UnplannedSourceEnvelope::CdcV2
         }
@@ -800,9 +1055,138 @@ pub fn plan_create_source(
         }
     }
 
+    let event_time = match with_options.remove("timestamp_column") {
+        None => {
+            for option in [
+                "max_out_of_orderness",
+                "idle_timeout",
+                "max_event_time_skew",
+            ] {
+                if with_options.contains_key(option) {
+                    bail!("{} requires timestamp_column", option);
+                }
+            }
+            None
+        }
+        Some(Value::String(column)) => {
+            // These sources retract rows, and a retraction can't be bound
+            // to the event time of the row it retracts.
+            if matches!(
+                external_connector,
+                ExternalSourceConnector::Postgres(_) | ExternalSourceConnector::LoadGenerator(_)
+            ) {
+                bail_unsupported!(format!(
+                    "timestamp_column with {} sources",
+                    external_connector.name()
+                ));
+            }
+            if !matches!(envelope, SourceEnvelope::None(_)) {
+                bail_unsupported!("timestamp_column with ENVELOPE other than NONE");
+            }
+            let column_name = ColumnName::from(column.as_str());
+            let (column, typ) = desc
+                .get_by_name(&column_name)
+                .ok_or_else(|| anyhow!("timestamp_column {} does not exist", column_name))?;
+            if desc.get_unambiguous_name(column).is_none() {
+                bail!("timestamp_column {} is ambiguous", column_name);
+            }
+            if !matches!(
+                typ.scalar_type,
+                ScalarType::Timestamp | ScalarType::TimestampTz
+            ) {
+                bail!(
+                    "timestamp_column {} must have type timestamp or timestamp with time zone",
+                    column_name
+                );
+            }
+            let max_out_of_orderness = match with_options.remove("max_out_of_orderness") {
+                None => Duration::ZERO,
+                Some(Value::String(s)) => mz_repr::util::parse_duration(&s)?,
+                Some(_) => bail!("max_out_of_orderness must be a string"),
+            };
+            let idle_timeout = match with_options.remove("idle_timeout") {
+                None => None,
+                Some(Value::String(s)) => match mz_repr::util::parse_duration(&s)? {
+                    Duration::ZERO => bail!("idle_timeout must be positive"),
+                    d => Some(d),
+                },
+                Some(_) => bail!("idle_timeout must be a string"),
+            };
+            let max_skew = match with_options.remove("max_event_time_skew") {
+                None => None,
+                Some(Value::String(s)) => Some(mz_repr::util::parse_duration(&s)?),
+                Some(_) => bail!("max_event_time_skew must be a string"),
+            };
+            Some(EventTimeConfig {
+                column,
+                max_out_of_orderness,
+                idle_timeout,
+                max_skew,
+            })
+        }
+        Some(_) => bail!("timestamp_column must be a string"),
+    };
+
+    let mut depends_on = vec![];
+    let decode_errors = match on_decode_error {
+        DecodeErrorPolicy::Fail => SourceDecodeErrorPolicy::Fail,
+        _ if matches!(envelope, SourceEnvelope::CdcV2) => {
+            bail_unsupported!("ON DECODE ERROR with ENVELOPE MATERIALIZE")
+        }
+        // These sources produce rows directly rather than decoding records.
+        _ if matches!(
+            external_connector,
+            ExternalSourceConnector::Postgres(_)
+                | ExternalSourceConnector::PubNub(_)
+                | ExternalSourceConnector::LoadGenerator(_)
+        ) =>
+        {
+            bail_unsupported!(format!(
+                "ON DECODE ERROR with {} sources",
+                external_connector.name()
+            ))
+        }
+        DecodeErrorPolicy::Skip => SourceDecodeErrorPolicy::Skip,
+        DecodeErrorPolicy::Redirect(table) => {
+            let table = scx.resolve_item(table.clone())?;
+            if table.item_type() != CatalogItemType::Table {
+                bail!(
+                    "ON DECODE ERROR REDIRECT TO target {} must be a table",
+                    table.name()
+                );
+            }
+            let expected = decode_error_redirect_desc();
+            let actual = table.desc()?;
+            let compatible = actual.arity() == expected.arity()
+                && actual
+                    .typ()
+                    .column_types
+                    .iter()
+                    .zip(expected.typ().column_types.iter())
+                    .all(|(actual, expected)| {
+                        actual.scalar_type == expected.scalar_type
+                            && (actual.nullable || !expected.nullable)
+                    });
+            if !compatible {
+                bail!(
+                    "ON DECODE ERROR REDIRECT TO target {} must have columns \
+                     (key bytea, value bytea, partition text, offset bigint, error text)",
+                    table.name()
+                );
+            }
+            depends_on.push(table.id());
+            SourceDecodeErrorPolicy::Redirect(table.id())
+        }
+    };
+
     let if_not_exists = *if_not_exists;
     let materialized = *materialized;
     let name = scx.allocate_name(normalize::unresolved_object_name(name.clone())?);
+    let mut stmt = stmt;
+    if webhook_secret.is_some() {
+        stmt.with_options
+            .retain(|option| option.name().as_str() != "hmac_secret");
+    }
     let create_sql = normalize::create_statement(&scx, Statement::CreateSource(stmt))?;
 
     // Allow users to specify a timeline. If they do not, determine a default timeline for the source.
@@ -811,15 +1195,16 @@ pub fn plan_create_source(
             Value::String(timeline) => Timeline::User(timeline),
             v => bail!("unsupported timeline value {}", v.to_ast_string()),
         }
-    } else {
-        match envelope {
-            SourceEnvelope::CdcV2 => match with_options.remove("epoch_ms_timeline") {
-                None => Timeline::External(name.to_string()),
-                Some(Value::Boolean(true)) => Timeline::EpochMilliseconds,
-                Some(v) => bail!("unsupported epoch_ms_timeline value {}", v),
-            },
-            _ => Timeline::EpochMilliseconds,
+    } else if matches!(envelope, SourceEnvelope::CdcV2) || event_time.is_some() {
+        // These sources determine their own timestamps, which needn't keep
+        // pace with the wall clock.
+        match with_options.remove("epoch_ms_timeline") {
+            None => Timeline::External(name.to_string()),
+            Some(Value::Boolean(true)) => Timeline::EpochMilliseconds,
+            Some(v) => bail!("unsupported epoch_ms_timeline value {}", v),
         }
+    } else {
+        Timeline::EpochMilliseconds
     };
 
     let expr = HirRelationExpr::Get {
@@ -828,6 +1213,11 @@ pub fn plan_create_source(
     }
     .lower();
 
+    // Already consumed by purification, which names the columns after it.
+    if let DataEncoding::Csv(_) = encoding.value_ref() {
+        with_options.remove("csv_header");
+    }
+
     let source = Source {
         create_sql,
         connector: SourceConnector::External {
@@ -837,9 +1227,13 @@ pub fn plan_create_source(
             metadata_columns: metadata_column_types,
             ts_frequency,
             timeline,
+            decode_errors,
+            event_time,
         },
+        webhook_secret,
         expr,
         desc,
+        depends_on,
     };
 
     normalize::ensure_empty_options(&with_options, "CREATE SOURCE")?;
@@ -995,6 +1389,18 @@ fn typecheck_debezium_dedup(
     })
 }
 
+fn plan_compression(compression: &Compression) -> mz_dataflow_types::sources::Compression {
+    match compression {
+        Compression::Gzip => mz_dataflow_types::sources::Compression::Gzip,
+        Compression::Zstd => mz_dataflow_types::sources::Compression::Zstd,
+        Compression::Bzip2 => mz_dataflow_types::sources::Compression::Bzip2,
+        Compression::Xz => mz_dataflow_types::sources::Compression::Xz,
+        Compression::Snappy => mz_dataflow_types::sources::Compression::Snappy,
+        Compression::Auto => mz_dataflow_types::sources::Compression::Auto,
+        Compression::None => mz_dataflow_types::sources::Compression::None,
+    }
+}
+
 fn get_encoding<T: mz_sql_parser::ast::AstInfo>(
     format: &CreateSourceFormat<Raw>,
     envelope: &Envelope,
@@ -1016,6 +1422,13 @@ fn get_encoding<T: mz_sql_parser::ast::AstInfo>(
         }
     };
 
+    // JSON is only understood as the encoding of ENVELOPE MATERIALIZE messages.
+    let is_json = matches!(encoding.value_ref(), DataEncoding::Json)
+        || matches!(encoding.key_ref(), Some(DataEncoding::Json));
+    if is_json && !matches!(envelope, Envelope::CdcV2) {
+        bail_unsupported!("JSON sources");
+    }
+
     let requires_keyvalue = matches!(
         envelope,
         Envelope::Debezium(DbzMode::Upsert) | Envelope::Upsert
@@ -1128,6 +1541,7 @@ fn get_encoding_inner<T: mz_sql_parser::ast::AstInfo>(
                         url,
                         seed,
                         with_options: ccsr_options,
+                        ..
                     },
             } => {
                 if let Some(CsrSeedCompiledOrLegacy::Compiled(CsrSeedCompiled { key, value })) =
@@ -1183,6 +1597,10 @@ fn get_encoding_inner<T: mz_sql_parser::ast::AstInfo>(
                     confluent_wire_format: false,
                 })
             }
+            ProtobufSchema::Derived => bail!(
+                "FORMAT PROTOBUF sources require a schema: specify MESSAGE '...' USING SCHEMA or \
+                USING CONFLUENT SCHEMA REGISTRY"
+            ),
         },
         Format::Regex(regex) => {
             let regex = Regex::new(&regex)?;
@@ -1210,8 +1628,19 @@ fn get_encoding_inner<T: mz_sql_parser::ast::AstInfo>(
                 },
             })
         }
-        Format::Json => bail_unsupported!("JSON sources"),
+        Format::Json => DataEncoding::Json,
         Format::Text => DataEncoding::Text,
+        Format::Parquet => {
+            let schema = match normalize::options(with_options).remove("parquet_schema") {
+                Some(Value::String(s)) => s,
+                Some(_) => bail!("parquet_schema option must be a string"),
+                None => bail!("[internal error] parquet_schema should be set in purify"),
+            };
+            DataEncoding::Parquet(ParquetEncoding {
+                columns: parquet::parse_schema(&schema)?,
+                projection: None,
+            })
+        }
     }))
 }
 
@@ -1238,10 +1667,13 @@ fn get_key_envelope(
                 //
                 // Otherwise it gets the names of the columns in the type
                 let is_composite = match key {
-                    DataEncoding::AvroOcf { .. } | DataEncoding::Postgres => {
+                    DataEncoding::AvroOcf { .. }
+                    | DataEncoding::Parquet(_)
+                    | DataEncoding::Postgres
+                    | DataEncoding::RowCodec(_) => {
                         bail!("{} sources cannot use INCLUDE KEY", key.op_name())
                     }
-                    DataEncoding::Bytes | DataEncoding::Text => false,
+                    DataEncoding::Bytes | DataEncoding::Text | DataEncoding::Json => false,
                     DataEncoding::Avro(_)
                     | DataEncoding::Csv(_)
                     | DataEncoding::Protobuf(_)
@@ -1418,7 +1850,11 @@ fn kafka_sink_builder(
     relation_key_indices: Option<Vec<usize>>,
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     value_desc: RelationDesc,
+    partition_by: Option<MirScalarExpr>,
+    headers: Vec<(String, MirScalarExpr)>,
+    envelope: SinkEnvelope,
     topic_suffix_nonce: String,
+    cluster_id: Uuid,
     root_dependencies: &[&dyn CatalogItem],
 ) -> Result<SinkConnectorBuilder, anyhow::Error> {
     let consistency_topic = match with_options.remove("consistency_topic") {
@@ -1436,6 +1872,24 @@ fn kafka_sink_builder(
         None => false,
         Some(_) => bail!("reuse_topic must be a boolean"),
     };
+    if envelope == SinkEnvelope::CdcV2
+        && (reuse_topic || consistency_topic.is_some() || consistency.is_some())
+    {
+        // CDCv2 progress statements are written inline, and a CDCv2 source
+        // already deduplicates updates that are written more than once.
+        bail!("ENVELOPE MATERIALIZE sinks do not support consistency topics or reuse_topic");
+    }
+    let progress_topic = match with_options.remove("progress_topic") {
+        None => None,
+        Some(Value::String(topic)) => Some(topic),
+        Some(_) => bail!("progress_topic must be a string"),
+    };
+    // Only exactly-once sinks record their progress, and all of them do.
+    let progress_topic = match (reuse_topic, progress_topic) {
+        (true, topic) => Some(topic.unwrap_or_else(|| "mz_sink_progress".into())),
+        (false, Some(_)) => bail!("progress_topic requires reuse_topic"),
+        (false, None) => None,
+    };
     let config_options = kafka_util::extract_config(with_options)?;
 
     let avro_key_fullname = match with_options.remove("avro_key_fullname") {
@@ -1481,21 +1935,30 @@ fn kafka_sink_builder(
                 &mut ccsr_with_options,
             )?;
 
-            let include_transaction =
-                reuse_topic || consistency_topic.is_some() || consistency.is_some();
-            let schema_generator = AvroSchemaGenerator::new(
-                avro_key_fullname.as_deref(),
-                avro_value_fullname.as_deref(),
-                key_desc_and_indices
-                    .as_ref()
-                    .map(|(desc, _indices)| desc.clone()),
-                value_desc.clone(),
-                include_transaction,
-            );
-            let value_schema = schema_generator.value_writer_schema().to_string();
-            let key_schema = schema_generator
-                .key_writer_schema()
-                .map(|key_schema| key_schema.to_string());
+            let (key_schema, value_schema) = if envelope == SinkEnvelope::CdcV2 {
+                if avro_value_fullname.is_some() {
+                    bail!("avro_value_fullname is not supported with ENVELOPE MATERIALIZE");
+                }
+                let value_schema = avro::cdc_v2::build_schema_for_desc(value_desc.clone());
+                (None, value_schema.to_string())
+            } else {
+                let include_transaction =
+                    reuse_topic || consistency_topic.is_some() || consistency.is_some();
+                let schema_generator = AvroSchemaGenerator::new(
+                    avro_key_fullname.as_deref(),
+                    avro_value_fullname.as_deref(),
+                    key_desc_and_indices
+                        .as_ref()
+                        .map(|(desc, _indices)| desc.clone()),
+                    value_desc.clone(),
+                    include_transaction,
+                );
+                let value_schema = schema_generator.value_writer_schema().to_string();
+                let key_schema = schema_generator
+                    .key_writer_schema()
+                    .map(|key_schema| key_schema.to_string());
+                (key_schema, value_schema)
+            };
 
             normalize::ensure_empty_options(&ccsr_with_options, "CONFLUENT SCHEMA REGISTRY")?;
 
@@ -1507,6 +1970,57 @@ fn kafka_sink_builder(
             }
         }
         Some(Format::Json) => KafkaSinkFormat::Json,
+        Some(Format::Protobuf(schema)) => {
+            if envelope == SinkEnvelope::CdcV2 {
+                bail_unsupported!("ENVELOPE MATERIALIZE with FORMAT PROTOBUF");
+            }
+            let ccsr_config = match schema {
+                ProtobufSchema::Derived => None,
+                ProtobufSchema::Csr {
+                    csr_connector:
+                        CsrConnectorProto {
+                            url,
+                            message_name,
+                            seed,
+                            with_options,
+                        },
+                } => {
+                    if seed.is_some() {
+                        bail!("SEED option does not make sense with sinks");
+                    }
+                    if message_name.is_some() {
+                        bail!("MESSAGE option does not make sense with sinks");
+                    }
+                    let mut ccsr_with_options = normalize::options(&with_options);
+                    let ccsr_config = kafka_util::generate_ccsr_client_config(
+                        url.parse()?,
+                        &config_options,
+                        &mut ccsr_with_options,
+                    )?;
+                    normalize::ensure_empty_options(&ccsr_with_options, "CONFLUENT SCHEMA REGISTRY")?;
+                    Some(ccsr_config)
+                }
+                ProtobufSchema::InlineSchema { .. } => bail!(
+                    "FORMAT PROTOBUF sinks derive their schema from the sinked relation and do not accept a schema"
+                ),
+            };
+
+            let include_transaction =
+                reuse_topic || consistency_topic.is_some() || consistency.is_some();
+            let schema_generator = ProtobufSchemaGenerator::new(
+                key_desc_and_indices
+                    .as_ref()
+                    .map(|(desc, _indices)| desc.clone()),
+                value_desc.clone(),
+                include_transaction,
+            )?;
+
+            KafkaSinkFormat::Protobuf {
+                key_schema: schema_generator.key_schema(),
+                value_schema: schema_generator.value_schema(),
+                ccsr_config,
+            }
+        }
         Some(format) => bail_unsupported!(format!("sink format {:?}", format)),
         None => bail_unsupported!("sink without format"),
     };
@@ -1525,11 +2039,18 @@ fn kafka_sink_builder(
     let transitive_source_dependencies: Vec<_> = if reuse_topic {
         for item in root_dependencies.iter() {
             if item.item_type() == CatalogItemType::Source {
+                // After a restart, exactly-once sinks skip the updates at or
+                // before their recorded progress. That is only correct if the
+                // source yields the same updates at the same timestamps
+                // again; other sources would cause data to be republished or
+                // lost.
                 if !item.source_connector()?.yields_stable_input() {
                     bail!(
-                    "reuse_topic requires that sink input dependencies are replayable, {} is not",
-                    item.name()
-                );
+                        "reuse_topic requires that sink input dependencies are replayable, {} is not: \
+                        only sources that assign the same timestamps to the same data after a \
+                        restart can back exactly-once sinks",
+                        item.name()
+                    );
                 }
             } else if item.item_type() != CatalogItemType::Source {
                 bail!(
@@ -1611,7 +2132,11 @@ fn kafka_sink_builder(
         relation_key_indices,
         key_desc_and_indices,
         value_desc,
+        partition_by,
+        headers,
         reuse_topic,
+        progress_topic,
+        cluster_id,
         transitive_source_dependencies,
         retention,
     }))
@@ -1666,49 +2191,54 @@ fn get_kafka_sink_consistency_config(
                     },
                 ))
             }
+            Some(Format::Json) => Some((topic, KafkaSinkFormat::Json)),
             None => {
                 // If a CONSISTENCY FORMAT is not provided, default to the FORMAT of the sink.
                 match sink_format {
                     format @ KafkaSinkFormat::Avro { .. } => Some((topic, format.clone())),
-                    KafkaSinkFormat::Json => bail_unsupported!("CONSISTENCY FORMAT JSON"),
+                    KafkaSinkFormat::Json => Some((topic, KafkaSinkFormat::Json)),
+                    KafkaSinkFormat::Protobuf { .. } => bail!(
+                        "FORMAT PROTOBUF sinks require an explicit consistency format, \
+                        like CONSISTENCY (TOPIC '...' FORMAT JSON)"
+                    ),
                 }
             }
             Some(other) => bail_unsupported!(format!("CONSISTENCY FORMAT {}", &other)),
         },
         None => {
-            // Support use of `consistency_topic` with option if the sink is Avro-formatted
-            // for backwards compatibility.
+            // Support use of `consistency_topic` with option for backwards
+            // compatibility. The consistency records use the format of the sink.
             if reuse_topic | consistency_topic.is_some() {
+                let consistency_topic = match consistency_topic {
+                    Some(topic) => topic,
+                    None => {
+                        let default_consistency_topic = format!("{}-consistency", topic_prefix);
+                        debug!(
+                            "Using default consistency topic '{}' for topic '{}'",
+                            default_consistency_topic, topic_prefix
+                        );
+                        default_consistency_topic
+                    }
+                };
                 match sink_format {
                     KafkaSinkFormat::Avro {
                         schema_registry_url,
                         ccsr_config,
                         ..
-                    } => {
-                        let consistency_topic = match consistency_topic {
-                            Some(topic) => topic,
-                            None => {
-                                let default_consistency_topic =
-                                    format!("{}-consistency", topic_prefix);
-                                debug!(
-                                    "Using default consistency topic '{}' for topic '{}'",
-                                    default_consistency_topic, topic_prefix
-                                );
-                                default_consistency_topic
-                            }
-                        };
-                        Some((
-                            consistency_topic,
-                            KafkaSinkFormat::Avro {
-                                schema_registry_url: schema_registry_url.clone(),
-                                key_schema: None,
-                                value_schema: avro::get_debezium_transaction_schema()
-                                    .canonical_form(),
-                                ccsr_config: ccsr_config.clone(),
-                            },
-                        ))
-                    }
-                    KafkaSinkFormat::Json => bail!("For FORMAT JSON, you need to manually specify an Avro consistency topic using 'CONSISTENCY TOPIC consistency_topic CONSISTENCY FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY url'. The default of using a JSON consistency topic is not supported."),
+                    } => Some((
+                        consistency_topic,
+                        KafkaSinkFormat::Avro {
+                            schema_registry_url: schema_registry_url.clone(),
+                            key_schema: None,
+                            value_schema: avro::get_debezium_transaction_schema().canonical_form(),
+                            ccsr_config: ccsr_config.clone(),
+                        },
+                    )),
+                    KafkaSinkFormat::Json => Some((consistency_topic, KafkaSinkFormat::Json)),
+                    KafkaSinkFormat::Protobuf { .. } => bail!(
+                        "FORMAT PROTOBUF sinks require an explicit consistency format, \
+                        like CONSISTENCY (TOPIC '...' FORMAT JSON)"
+                    ),
                 }
             } else {
                 None
@@ -1742,6 +2272,124 @@ fn avro_ocf_sink_builder(
     }))
 }
 
+fn file_sink_builder(
+    format: Option<Format<Raw>>,
+    target: FileSinkTarget,
+    with_options: &mut BTreeMap<String, Value>,
+    directory_suffix: String,
+    value_desc: RelationDesc,
+) -> Result<SinkConnectorBuilder, anyhow::Error> {
+    let format = match format {
+        Some(Format::Parquet) => FileSinkFormat::Parquet,
+        Some(Format::Json) => FileSinkFormat::Json,
+        Some(format) => bail_unsupported!(format!("file sink format {}", format)),
+        None => bail!("file sinks require FORMAT PARQUET or FORMAT JSON"),
+    };
+
+    // Each record in the sink's files carries the time and diff of the update
+    // alongside the columns of the sinked relation.
+    for name in ["time", "diff"] {
+        if value_desc.get_by_name(&name.into()).is_some() {
+            bail!(
+                "file sinks write a column named {}, which conflicts with a column of the sinked relation",
+                name
+            );
+        }
+    }
+
+    if let FileSinkTarget::Local { path } = &target {
+        if path.exists() && !path.is_dir() {
+            bail!("file sink path {} is not a directory", path.display());
+        }
+    }
+
+    let max_file_bytes = match with_options.remove("max_file_size") {
+        None => 64 << 20,
+        Some(Value::Number(n)) => n.parse::<u64>()?,
+        Some(_) => bail!("max_file_size must be an integer"),
+    };
+    if max_file_bytes == 0 {
+        bail!("max_file_size must be positive");
+    }
+
+    let max_file_interval = match with_options.remove("max_file_interval") {
+        None => Duration::from_secs(60),
+        Some(Value::String(s)) => mz_repr::util::parse_duration(&s)?,
+        Some(_) => bail!("max_file_interval must be a string"),
+    };
+    if max_file_interval == Duration::ZERO {
+        bail!("max_file_interval must be positive");
+    }
+
+    Ok(SinkConnectorBuilder::File(FileSinkConnectorBuilder {
+        value_desc,
+        format,
+        target,
+        directory_suffix,
+        max_file_bytes,
+        max_file_interval,
+    }))
+}
+
+fn postgres_sink_builder(
+    format: Option<Format<Raw>>,
+    conn: String,
+    table: String,
+    with_options: &mut BTreeMap<String, Value>,
+    key_desc_and_indices: (RelationDesc, Vec<usize>),
+    value_desc: RelationDesc,
+    root_dependencies: &[&dyn CatalogItem],
+) -> Result<SinkConnectorBuilder, anyhow::Error> {
+    if format.is_some() {
+        bail!("Postgres sinks do not support FORMAT");
+    }
+
+    // Validate the connection string up front, rather than when the sink is
+    // started.
+    conn.parse::<tokio_postgres::Config>()
+        .context("invalid Postgres connection string")?;
+
+    let (schema, table) = match table.split_once('.') {
+        Some((schema, table)) => (schema.to_string(), table.to_string()),
+        None => ("public".to_string(), table),
+    };
+    if schema.is_empty() || table.is_empty() || table.contains('.') {
+        bail!("Postgres sink table must be of the form 'table' or 'schema.table'");
+    }
+
+    let progress_table = match with_options.remove("progress_table") {
+        None => "mz_sink_progress".to_string(),
+        Some(Value::String(progress_table)) => progress_table,
+        Some(_) => bail!("progress_table must be a string"),
+    };
+    if progress_table == table {
+        bail!("progress_table must differ from the sink's table");
+    }
+
+    // Postgres never considers NULLs equal, so rows with NULL keys could
+    // neither be updated nor deleted.
+    if let Some((name, _)) = key_desc_and_indices.0.iter().find(|(_, typ)| typ.nullable) {
+        bail!(
+            "Postgres sink key column {} must not be nullable",
+            name.as_str().quoted()
+        );
+    }
+
+    Ok(SinkConnectorBuilder::Postgres(
+        PostgresSinkConnectorBuilder {
+            conn,
+            schema,
+            table,
+            key_desc_and_indices,
+            value_desc,
+            progress_table,
+            // Hold back compaction of the sink's sources until their changes have
+            // been committed upstream, so that a restarted sink can replay them.
+            transitive_source_dependencies: root_dependencies.iter().map(|i| i.id()).collect(),
+        },
+    ))
+}
+
 pub fn describe_create_sink(
     _: &StatementContext,
     _: CreateSinkStatement<Raw>,
@@ -1766,16 +2414,25 @@ pub fn plan_create_sink(
         if_not_exists,
     } = stmt;
 
+    let is_file_sink = matches!(
+        connector,
+        CreateSinkConnector::File { .. } | CreateSinkConnector::S3 { .. }
+    );
     let envelope = match envelope {
+        // File sinks write every update along with its time and diff, which is
+        // the CDCv2 data model.
+        None if is_file_sink => SinkEnvelope::CdcV2,
+        // Postgres sinks mirror their input into a keyed table.
+        None if matches!(connector, CreateSinkConnector::Postgres { .. }) => SinkEnvelope::Upsert,
         None | Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Plain)) => {
             SinkEnvelope::Debezium
         }
         Some(Envelope::Upsert) => SinkEnvelope::Upsert,
-        Some(Envelope::CdcV2) => bail_unsupported!("CDCv2 sinks"),
+        Some(Envelope::CdcV2) => SinkEnvelope::CdcV2,
         Some(Envelope::Debezium(mz_sql_parser::ast::DbzMode::Upsert)) => {
             bail_unsupported!("UPSERT doesn't make sense for sinks")
         }
-        Some(Envelope::None) => bail_unsupported!("\"ENVELOPE NONE\" sinks"),
+        Some(Envelope::None) => SinkEnvelope::Append,
     };
     let name = scx.allocate_name(normalize::unresolved_object_name(name)?);
     let from = scx.resolve_item(from)?;
@@ -1790,51 +2447,32 @@ pub fn plan_create_sink(
     let desc = from.desc()?;
     let key_indices = match &connector {
         CreateSinkConnector::Kafka { key, .. } => {
-            if let Some(key) = key.clone() {
-                let key_columns = key
-                    .key_columns
-                    .into_iter()
-                    .map(normalize::column_name)
-                    .collect::<Vec<_>>();
-                let mut uniq = HashSet::new();
-                for col in key_columns.iter() {
-                    if !uniq.insert(col) {
-                        bail!("Repeated column name in sink key: {}", col);
-                    }
-                }
-                let indices = key_columns
-                    .iter()
-                    .map(|col| -> anyhow::Result<usize> {
-                        let name_idx = desc
-                            .get_by_name(col)
-                            .map(|(idx, _type)| idx)
-                            .ok_or_else(|| anyhow!("No such column: {}", col))?;
-                        if desc.get_unambiguous_name(name_idx).is_none() {
-                            bail!("Ambiguous column: {}", col);
-                        }
-                        Ok(name_idx)
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                let is_valid_key =
-                    desc.typ().keys.iter().any(|key_columns| {
-                        key_columns.iter().all(|column| indices.contains(column))
-                    });
-                if key.not_enforced && envelope == SinkEnvelope::Upsert {
-                    // TODO: We should report a warning notice back to the user via the pgwire
-                    // protocol. See https://github.com/MaterializeInc/materialize/issues/9333.
-                    warn!(
-                        "Verification of upsert key disabled for sink '{}' via 'NOT ENFORCED'. This is potentially dangerous and can lead to crashing materialize when the specified key is not in fact a unique key of the sinked view.",
-                        name
-                    );
-                } else if !is_valid_key && envelope == SinkEnvelope::Upsert {
-                    return Err(invalid_upsert_key_err(&desc, &key_columns));
-                }
-                Some(indices)
-            } else {
-                None
+            if key.is_some() && envelope == SinkEnvelope::CdcV2 {
+                bail!("ENVELOPE MATERIALIZE sinks do not support KEY");
+            }
+            match key {
+                Some(key) => Some(sink_key_indices(&desc, key, envelope, &name)?),
+                None => None,
             }
         }
-        CreateSinkConnector::AvroOcf { .. } => None,
+        CreateSinkConnector::AvroOcf { .. } => {
+            if envelope == SinkEnvelope::CdcV2 {
+                bail_unsupported!("ENVELOPE MATERIALIZE with Avro OCF sinks");
+            }
+            None
+        }
+        CreateSinkConnector::File { .. } | CreateSinkConnector::S3 { .. } => {
+            if envelope != SinkEnvelope::CdcV2 {
+                bail!("file sinks only support ENVELOPE MATERIALIZE");
+            }
+            None
+        }
+        CreateSinkConnector::Postgres { key, .. } => {
+            if envelope != SinkEnvelope::Upsert {
+                bail!("Postgres sinks only support ENVELOPE UPSERT");
+            }
+            Some(sink_key_indices(&desc, key, envelope, &name)?)
+        }
     };
 
     // pick the first valid natural relation key, if any
@@ -1854,7 +2492,7 @@ pub fn plan_create_sink(
 
     let value_desc = match envelope {
         SinkEnvelope::Debezium => envelopes::dbz_desc(desc.clone()),
-        SinkEnvelope::Upsert => desc.clone(),
+        SinkEnvelope::Upsert | SinkEnvelope::Append | SinkEnvelope::CdcV2 => desc.clone(),
     };
 
     if as_of.is_some() {
@@ -1864,6 +2502,49 @@ pub fn plan_create_sink(
     let mut depends_on = vec![from.id()];
     depends_on.extend(from.uses());
 
+    let (partition_by, headers) = match &connector {
+        CreateSinkConnector::Kafka {
+            partition_by,
+            headers,
+            ..
+        } => {
+            if envelope == SinkEnvelope::CdcV2 && (partition_by.is_some() || !headers.is_empty()) {
+                bail!("ENVELOPE MATERIALIZE sinks do not support PARTITION BY or HEADERS");
+            }
+            let partition_by = match partition_by {
+                Some(expr) => {
+                    let (expr, expr_depends_on) =
+                        query::plan_sink_expr(scx, &desc, "PARTITION BY", expr.clone())?;
+                    // Upsert sinks write deletions as messages without a value, so
+                    // the partition of a deletion can only be chosen consistently
+                    // with the partition of the row it deletes if it depends
+                    // solely on the key.
+                    if let (SinkEnvelope::Upsert, Some((_, key_indices))) =
+                        (envelope, &key_desc_and_indices)
+                    {
+                        if !expr.support().iter().all(|c| key_indices.contains(c)) {
+                            bail!("PARTITION BY for ENVELOPE UPSERT sinks may only refer to KEY columns");
+                        }
+                    }
+                    depends_on.extend(expr_depends_on);
+                    Some(expr)
+                }
+                None => None,
+            };
+            let headers = headers
+                .iter()
+                .map(|KafkaSinkHeader { name, value }| {
+                    let (expr, expr_depends_on) =
+                        query::plan_sink_expr(scx, &desc, "HEADERS", value.clone())?;
+                    depends_on.extend(expr_depends_on);
+                    Ok((name.clone(), expr))
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            (partition_by, headers)
+        }
+        _ => (None, vec![]),
+    };
+
     let root_user_dependencies = get_root_dependencies(scx, &depends_on);
 
     let connector_builder = match connector {
@@ -1881,12 +2562,48 @@ pub fn plan_create_sink(
             relation_key_indices,
             key_desc_and_indices,
             value_desc,
+            partition_by,
+            headers,
+            envelope,
             suffix_nonce,
+            scx.catalog.config().cluster_id,
             &root_user_dependencies,
         )?,
         CreateSinkConnector::AvroOcf { path } => {
             avro_ocf_sink_builder(format, path, suffix_nonce, value_desc)?
         }
+        CreateSinkConnector::File { path } => file_sink_builder(
+            format,
+            FileSinkTarget::Local {
+                path: PathBuf::from(path),
+            },
+            &mut with_options,
+            suffix_nonce,
+            value_desc,
+        )?,
+        CreateSinkConnector::S3 { bucket, prefix } => {
+            let aws = normalize::aws_config(&mut with_options, None)?;
+            file_sink_builder(
+                format,
+                FileSinkTarget::S3 {
+                    bucket,
+                    prefix: prefix.unwrap_or_default(),
+                    aws,
+                },
+                &mut with_options,
+                suffix_nonce,
+                value_desc,
+            )?
+        }
+        CreateSinkConnector::Postgres { conn, table, .. } => postgres_sink_builder(
+            format,
+            conn,
+            table,
+            &mut with_options,
+            key_desc_and_indices.expect("Postgres sinks always have a key"),
+            value_desc,
+            &root_user_dependencies,
+        )?,
     };
 
     normalize::ensure_empty_options(&with_options, "CREATE SINK")?;
@@ -1905,6 +2622,57 @@ pub fn plan_create_sink(
     }))
 }
 
+/// Resolves the columns of a user-specified sink key to their indices in
+/// `desc`, verifying that they form a unique key if the sink upserts on them.
+fn sink_key_indices(
+    desc: &RelationDesc,
+    key: &KafkaSinkKey,
+    envelope: SinkEnvelope,
+    name: &FullName,
+) -> Result<Vec<usize>, anyhow::Error> {
+    let key_columns = key
+        .key_columns
+        .iter()
+        .cloned()
+        .map(normalize::column_name)
+        .collect::<Vec<_>>();
+    let mut uniq = HashSet::new();
+    for col in key_columns.iter() {
+        if !uniq.insert(col) {
+            bail!("Repeated column name in sink key: {}", col);
+        }
+    }
+    let indices = key_columns
+        .iter()
+        .map(|col| -> anyhow::Result<usize> {
+            let name_idx = desc
+                .get_by_name(col)
+                .map(|(idx, _type)| idx)
+                .ok_or_else(|| anyhow!("No such column: {}", col))?;
+            if desc.get_unambiguous_name(name_idx).is_none() {
+                bail!("Ambiguous column: {}", col);
+            }
+            Ok(name_idx)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let is_valid_key = desc
+        .typ()
+        .keys
+        .iter()
+        .any(|key_columns| key_columns.iter().all(|column| indices.contains(column)));
+    if key.not_enforced && envelope == SinkEnvelope::Upsert {
+        // TODO: We should report a warning notice back to the user via the pgwire
+        // protocol. See https://github.com/MaterializeInc/materialize/issues/9333.
+        warn!(
+            "Verification of upsert key disabled for sink '{}' via 'NOT ENFORCED'. This is potentially dangerous and can lead to crashing materialize when the specified key is not in fact a unique key of the sinked view.",
+            name
+        );
+    } else if !is_valid_key && envelope == SinkEnvelope::Upsert {
+        return Err(invalid_upsert_key_err(desc, &key_columns));
+    }
+    Ok(indices)
+}
+
 fn invalid_upsert_key_err(desc: &RelationDesc, requested_user_key: &[ColumnName]) -> anyhow::Error {
     let requested_user_key = requested_user_key
         .iter()
@@ -1982,6 +2750,66 @@ fn get_root_dependencies<'a>(
     result
 }
 
+with_options! {
+    struct SubscriptionWithOptions {
+        max_lag: Interval,
+    }
+}
+
+pub fn describe_create_subscription(
+    _: &StatementContext,
+    _: CreateSubscriptionStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_create_subscription(
+    scx: &StatementContext,
+    stmt: CreateSubscriptionStatement,
+) -> Result<Plan, anyhow::Error> {
+    let create_sql = normalize::create_statement(scx, Statement::CreateSubscription(stmt.clone()))?;
+    let CreateSubscriptionStatement {
+        name,
+        from,
+        with_options,
+        if_not_exists,
+    } = stmt;
+    let name = scx.allocate_name(normalize::unresolved_object_name(name)?);
+    let from = scx.resolve_item(from)?;
+    match from.item_type() {
+        CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::View => (),
+        CatalogItemType::Func
+        | CatalogItemType::Index
+        | CatalogItemType::Sink
+        | CatalogItemType::Subscription
+        | CatalogItemType::Type => bail!(
+            "cannot create a subscription for {} because it is a {}",
+            from.name(),
+            from.item_type(),
+        ),
+    }
+
+    let with_options = SubscriptionWithOptions::try_from(with_options)?;
+    let max_lag = match with_options.max_lag {
+        Some(max_lag) => max_lag.duration()?,
+        // Hold back compaction for at most an hour by default.
+        None => Duration::from_secs(60 * 60),
+    };
+    let max_lag_ms =
+        u64::try_from(max_lag.as_millis()).map_err(|_| anyhow!("max_lag is too large"))?;
+
+    Ok(Plan::CreateSubscription(CreateSubscriptionPlan {
+        name,
+        subscription: Subscription {
+            create_sql,
+            from: from.id(),
+            max_lag_ms,
+            depends_on: vec![from.id()],
+        },
+        if_not_exists,
+    }))
+}
+
 pub fn describe_create_index(
     _: &StatementContext,
     _: CreateIndexStatement<Raw>,
@@ -2352,6 +3180,7 @@ pub fn plan_drop_objects(
         | ObjectType::View
         | ObjectType::Index
         | ObjectType::Sink
+        | ObjectType::Subscription
         | ObjectType::Type => plan_drop_items(scx, object_type, if_exists, names, cascade),
         ObjectType::Role => plan_drop_role(scx, if_exists, names),
         ObjectType::Object => unreachable!("cannot drop generic OBJECT, must provide object type"),
@@ -2486,6 +3315,7 @@ pub fn plan_drop_item(
                     | CatalogItemType::Source
                     | CatalogItemType::View
                     | CatalogItemType::Sink
+                    | CatalogItemType::Subscription
                     | CatalogItemType::Type => {
                         bail!(
                             "cannot drop {}: still depended upon by catalog item '{}'",
@@ -2627,3 +3457,196 @@ pub fn plan_alter_object_rename(
         object_type,
     }))
 }
+
+pub fn describe_alter_source(
+    _: &StatementContext,
+    _: AlterSourceStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_source(
+    scx: &StatementContext,
+    AlterSourceStatement {
+        source_name,
+        if_exists,
+        action,
+    }: AlterSourceStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(source_name) {
+        Ok(source) => source,
+        Err(_) if if_exists => {
+            // TODO(benesch): generate a notice indicating this source does not
+            // exist.
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Source,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Source {
+        bail!("{} is a {} not a source", entry.name(), entry.item_type())
+    }
+
+    match action {
+        AlterSourceAction::RefreshSchema { seed } => {
+            let seed = seed.ok_or_else(|| {
+                anyhow!("internal error: ALTER SOURCE ... REFRESH SCHEMA was not purified")
+            })?;
+            let mut stmt =
+                match mz_sql_parser::parser::parse_statements(entry.create_sql())?.into_element() {
+                    Statement::CreateSource(stmt) => stmt,
+                    _ => unreachable!("source defined by non-CREATE SOURCE statement"),
+                };
+            // Metadata columns follow the value columns, so new value columns
+            // could not be appended to the source.
+            if stmt
+                .include_metadata
+                .iter()
+                .any(|item| item.ty != SourceIncludeMetadataType::Key)
+            {
+                bail_unsupported!("ALTER SOURCE ... REFRESH SCHEMA with INCLUDE metadata");
+            }
+            let old_value_schema = match value_csr_connector_avro(&mut stmt.format) {
+                Some(csr_connector) => csr_connector
+                    .seed
+                    .replace(seed.clone())
+                    .map(|seed| seed.value_schema),
+                None => bail_unsupported!(
+                    "ALTER SOURCE ... REFRESH SCHEMA with sources not using FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY"
+                ),
+            };
+
+            let source = match plan_create_source(scx, stmt)? {
+                Plan::CreateSource(CreateSourcePlan { source, .. }) => source,
+                _ => unreachable!("CREATE SOURCE planned to non-CREATE SOURCE plan"),
+            };
+
+            // Existing columns must be unchanged, so that the views that depend
+            // on the source remain valid. New columns must be nullable, as
+            // records written with earlier schemas have no values for them.
+            let old_desc = entry.desc()?;
+            let new_desc = &source.desc;
+            for (i, (name, typ)) in old_desc.iter().enumerate() {
+                match new_desc.iter().nth(i) {
+                    Some((new_name, new_typ)) if new_name == name && new_typ == typ => (),
+                    _ => bail!(
+                        "cannot refresh schema of source {}: column {} was changed or removed",
+                        entry.name(),
+                        name.as_str().quoted()
+                    ),
+                }
+            }
+            for (name, typ) in new_desc.iter().skip(old_desc.arity()) {
+                if !typ.nullable {
+                    bail!(
+                        "cannot refresh schema of source {}: new column {} must be nullable",
+                        entry.name(),
+                        name.as_str().quoted()
+                    );
+                }
+            }
+            // Records written with the previous schema are read again when the
+            // source's indexes are rebuilt, so the new schema must be able to
+            // read them, e.g. new fields must have defaults.
+            if let Some(old_value_schema) = old_value_schema {
+                let writer_schema = avro::parse_schema(&old_value_schema)?;
+                let reader_schema = avro::parse_schema(&seed.value_schema)?;
+                if let Err(e) = mz_avro::schema::resolve_schemas(&writer_schema, &reader_schema) {
+                    bail!(
+                        "cannot refresh schema of source {}: records written with the previous schema cannot be read with the new schema: {}",
+                        entry.name(),
+                        e
+                    );
+                }
+            }
+
+            Ok(Plan::AlterSource(AlterSourcePlan {
+                id: entry.id(),
+                source,
+            }))
+        }
+    }
+}
+
+pub fn describe_alter_sink(
+    _: &StatementContext,
+    _: AlterSinkStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_sink(
+    scx: &StatementContext,
+    AlterSinkStatement {
+        sink_name,
+        if_exists,
+        action,
+    }: AlterSinkStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(sink_name) {
+        Ok(sink) => sink,
+        Err(_) if if_exists => {
+            // TODO(benesch): generate a notice indicating this sink does not
+            // exist.
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Sink,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Sink {
+        bail!("{} is a {} not a sink", entry.name(), entry.item_type())
+    }
+
+    match action {
+        AlterSinkAction::ResumeFrom { timestamp } => Ok(Plan::AlterSink(AlterSinkPlan {
+            id: entry.id(),
+            resume_from: timestamp,
+        })),
+    }
+}
+
+pub fn describe_alter_subscription(
+    _: &StatementContext,
+    _: AlterSubscriptionStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_subscription(
+    scx: &StatementContext,
+    AlterSubscriptionStatement {
+        subscription_name,
+        if_exists,
+        action,
+    }: AlterSubscriptionStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(subscription_name) {
+        Ok(subscription) => subscription,
+        Err(_) if if_exists => {
+            // TODO(benesch): generate a notice indicating this subscription
+            // does not exist.
+            return Ok(Plan::AlterNoop(AlterNoopPlan {
+                object_type: ObjectType::Subscription,
+            }));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Subscription {
+        bail!(
+            "{} is a {} not a subscription",
+            entry.name(),
+            entry.item_type()
+        )
+    }
+
+    match action {
+        AlterSubscriptionAction::Acknowledge { timestamp } => {
+            Ok(Plan::AlterSubscription(AlterSubscriptionPlan {
+                id: entry.id(),
+                acknowledged: timestamp,
+            }))
+        }
+    }
+}
@@ -12,10 +12,11 @@
 //! This module houses the handlers for statements that manipulate data, like
 //! `INSERT`, `SELECT`, `TAIL`, and `COPY`.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 
+use mz_dataflow_types::sinks::TailEnvelope;
 use mz_expr::MirRelationExpr;
 use mz_ore::collections::CollectionExt;
 use mz_repr::adt::numeric::NumericMaxScale;
@@ -24,9 +25,11 @@ use mz_repr::{RelationDesc, ScalarType};
 use crate::ast::{
     CopyDirection, CopyRelation, CopyStatement, CopyTarget, CreateViewStatement, DeleteStatement,
     ExplainStage, ExplainStatement, Explainee, Ident, InsertStatement, Query, Raw, SelectStatement,
-    Statement, TailRelation, TailStatement, UnresolvedObjectName, UpdateStatement, ViewDefinition,
+    Statement, TailEnvelope as AstTailEnvelope, TailRelation, TailStatement, UnresolvedObjectName,
+    UpdateStatement, ViewDefinition,
 };
 use crate::catalog::CatalogItemType;
+use crate::normalize;
 use crate::plan::query;
 use crate::plan::query::QueryLifetime;
 use crate::plan::statement::{StatementContext, StatementDesc};
@@ -285,17 +288,32 @@ with_options! {
 pub fn describe_tail(
     scx: &StatementContext,
     TailStatement {
-        relation, options, ..
+        relation,
+        envelope,
+        options,
+        ..
     }: TailStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
     let relation_desc = match relation {
-        TailRelation::Name(name) => scx.resolve_item(name)?.desc()?.clone(),
+        TailRelation::Name(name) => {
+            let entry = scx.resolve_item(name)?;
+            match entry.item_type() {
+                // A subscription produces the rows of the relation it is for.
+                CatalogItemType::Subscription => {
+                    scx.get_item_by_id(&entry.uses()[0]).desc()?.clone()
+                }
+                _ => entry.desc()?.clone(),
+            }
+        }
         TailRelation::Query(query) => {
             let query::PlannedQuery { desc, .. } =
                 query::plan_root_query(scx, query, QueryLifetime::OneShot(scx.pcx()?))?;
             desc
         }
     };
+    let envelope = envelope
+        .map(|envelope| plan_tail_envelope(&relation_desc, envelope))
+        .transpose()?;
     let options = TailOptions::try_from(options)?;
     let progress = options.progress.unwrap_or(false);
     let mut desc = RelationDesc::empty().with_column(
@@ -308,31 +326,112 @@ pub fn describe_tail(
     if progress {
         desc = desc.with_column("mz_progressed", ScalarType::Bool.nullable(false));
     }
-    desc = desc.with_column("mz_diff", ScalarType::Int64.nullable(true));
-    for (name, mut ty) in relation_desc.into_iter() {
-        if progress {
-            ty.nullable = true;
+    match envelope {
+        None => {
+            desc = desc.with_column("mz_diff", ScalarType::Int64.nullable(true));
+            for (name, mut ty) in relation_desc.into_iter() {
+                if progress {
+                    ty.nullable = true;
+                }
+                desc = desc.with_column(name, ty);
+            }
+        }
+        Some(envelope) => {
+            desc = desc.with_column("mz_state", ScalarType::String.nullable(progress));
+            let key_indices = envelope.key_indices();
+            for &idx in key_indices {
+                let mut ty = relation_desc.typ().column_types[idx].clone();
+                if progress {
+                    ty.nullable = true;
+                }
+                desc = desc.with_column(relation_desc.get_name(idx), ty);
+            }
+            // The value columns are NULL for deletes and key violations.
+            let value_columns = relation_desc
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| !key_indices.contains(idx))
+                .map(|(_, (name, ty))| (name.clone(), ty.clone().nullable(true)))
+                .collect::<Vec<_>>();
+            match envelope {
+                TailEnvelope::Upsert { .. } => {
+                    for (name, ty) in value_columns {
+                        desc = desc.with_column(name, ty);
+                    }
+                }
+                TailEnvelope::Debezium { .. } => {
+                    for prefix in &["before", "after"] {
+                        for (name, ty) in &value_columns {
+                            desc = desc.with_column(format!("{}_{}", prefix, name), ty.clone());
+                        }
+                    }
+                }
+            }
         }
-        desc = desc.with_column(name, ty);
     }
     Ok(StatementDesc::new(Some(desc)))
 }
 
+/// Resolves the key columns of a `TAIL` envelope against the tailed relation.
+fn plan_tail_envelope(
+    desc: &RelationDesc,
+    envelope: AstTailEnvelope,
+) -> Result<TailEnvelope, anyhow::Error> {
+    let key_columns = match &envelope {
+        AstTailEnvelope::Upsert { key_columns } | AstTailEnvelope::Debezium { key_columns } => {
+            key_columns
+        }
+    };
+    if key_columns.is_empty() {
+        bail!("TAIL envelope requires at least one key column");
+    }
+    let mut uniq = HashSet::new();
+    let mut key_indices = vec![];
+    for col in key_columns.iter().cloned().map(normalize::column_name) {
+        let idx = desc
+            .get_by_name(&col)
+            .map(|(idx, _type)| idx)
+            .ok_or_else(|| anyhow!("No such column: {}", col))?;
+        if desc.get_unambiguous_name(idx).is_none() {
+            bail!("Ambiguous column: {}", col);
+        }
+        if !uniq.insert(idx) {
+            bail!("Repeated column name in TAIL key: {}", col);
+        }
+        key_indices.push(idx);
+    }
+    Ok(match envelope {
+        AstTailEnvelope::Upsert { .. } => TailEnvelope::Upsert { key_indices },
+        AstTailEnvelope::Debezium { .. } => TailEnvelope::Debezium { key_indices },
+    })
+}
+
 pub fn plan_tail(
     scx: &StatementContext,
     TailStatement {
         relation,
+        envelope,
         options,
         as_of,
     }: TailStatement<Raw>,
     copy_to: Option<CopyFormat>,
 ) -> Result<Plan, anyhow::Error> {
-    let from = match relation {
+    let options = TailOptions::try_from(options)?;
+    let (from, desc) = match relation {
         TailRelation::Name(name) => {
             let entry = scx.resolve_item(name)?;
             match entry.item_type() {
                 CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::View => {
-                    TailFrom::Id(entry.id())
+                    (TailFrom::Id(entry.id()), entry.desc()?.clone())
+                }
+                CatalogItemType::Subscription => {
+                    // Where a subscription starts is determined by its last
+                    // acknowledged timestamp.
+                    if as_of.is_some() || options.snapshot.is_some() {
+                        bail!("TAIL of a subscription cannot specify AS OF or SNAPSHOT");
+                    }
+                    let from = scx.get_item_by_id(&entry.uses()[0]);
+                    (TailFrom::Subscription(entry.id()), from.desc()?.clone())
                 }
                 CatalogItemType::Func
                 | CatalogItemType::Index
@@ -357,22 +456,27 @@ pub fn plan_tail(
                 QueryLifetime::OneShot(scx.pcx()?),
             )?;
             assert!(query.finishing.is_trivial(query.desc.arity()));
-            TailFrom::Query {
+            let desc = query.desc.clone();
+            let from = TailFrom::Query {
                 expr: query.expr,
                 desc: query.desc,
                 depends_on: query.depends_on,
-            }
+            };
+            (from, desc)
         }
     };
 
+    let envelope = envelope
+        .map(|envelope| plan_tail_envelope(&desc, envelope))
+        .transpose()?;
     let ts = as_of.map(|e| query::plan_as_of(scx, e)).transpose()?;
-    let options = TailOptions::try_from(options)?;
     Ok(Plan::Tail(TailPlan {
         from,
         ts,
         with_snapshot: options.snapshot.unwrap_or(true),
         copy_to,
         emit_progress: options.progress.unwrap_or(false),
+        envelope,
     }))
 }
 
@@ -19,7 +19,8 @@ use std::sync::Arc;
 
 use anyhow::{anyhow, bail, ensure, Context};
 use aws_arn::ARN;
-use csv::ReaderBuilder;
+use csv::{ReaderBuilder, StringRecord};
+use globset::GlobBuilder;
 use itertools::Itertools;
 use protobuf_native::compiler::{SourceTreeDescriptorDatabase, VirtualSourceTree};
 use protobuf_native::MessageLite;
@@ -35,6 +36,9 @@ use mz_dataflow_types::sources::{AwsConfig, AwsExternalId};
 use mz_dataflow_types::sources::{
     ExternalSourceConnector, PostgresSourceConnector, SourceConnector,
 };
+use mz_interchange::parquet::{self, ParquetColumn};
+use mz_ore::collections::CollectionExt;
+use mz_ore::str::StrExt;
 use mz_pgrepr::Type;
 use mz_repr::strconv;
 use mz_sql_parser::parser::parse_data_type;
@@ -42,14 +46,15 @@ use mz_sql_parser::parser::parse_data_type;
 use prost::Message;
 
 use crate::ast::{
-    AvroSchema, CreateSourceConnector, CreateSourceFormat, CreateSourceStatement,
-    CreateViewsDefinitions, CreateViewsSourceTarget, CreateViewsStatement, CsrConnectorAvro,
-    CsrConnectorProto, CsrSeed, CsrSeedCompiled, CsrSeedCompiledEncoding, CsrSeedCompiledOrLegacy,
-    CsvColumns, DbzMode, Envelope, Expr, Format, Ident, Op, ProtobufSchema, Query, Raw, RawName,
-    Select, SelectItem, SetExpr, SqlOption, Statement, SubscriptPosition, TableFactor,
-    TableWithJoins, UnresolvedObjectName, Value, ViewDefinition, WithOption, WithOptionValue,
+    AlterSourceAction, AlterSourceStatement, AvroSchema, CreateSourceConnector, CreateSourceFormat,
+    CreateSourceStatement, CreateViewsDefinitions, CreateViewsSourceTarget, CreateViewsStatement,
+    CsrConnectorAvro, CsrConnectorProto, CsrSeed, CsrSeedCompiled, CsrSeedCompiledEncoding,
+    CsrSeedCompiledOrLegacy, CsvColumns, DbzMode, Envelope, Expr, Format, Ident, Op,
+    ProtobufSchema, Query, Raw, RawName, S3KeySource, Select, SelectItem, SetExpr, SqlOption,
+    Statement, SubscriptPosition, TableFactor, TableWithJoins, UnresolvedObjectName, Value,
+    ViewDefinition, WithOption, WithOptionValue,
 };
-use crate::catalog::SessionCatalog;
+use crate::catalog::{CatalogItemType, SessionCatalog};
 use crate::kafka_util;
 use crate::normalize;
 
@@ -86,10 +91,38 @@ pub fn purify(
         Err(anyhow!("SQL statement does not refer to a source"))
     };
 
+    // Similarly, refreshing the schema of a source requires the source's
+    // definition, which we look up before entering the async section. If the
+    // item does not exist or is not a source, planning will report the error.
+    let refresh_create_sql = if let Statement::AlterSource(AlterSourceStatement {
+        source_name,
+        action: AlterSourceAction::RefreshSchema { seed: None },
+        ..
+    }) = &stmt
+    {
+        normalize::unresolved_object_name(source_name.clone())
+            .ok()
+            .and_then(|name| catalog.resolve_item(&name).ok())
+            .filter(|item| item.item_type() == CatalogItemType::Source)
+            .map(|item| item.create_sql().to_owned())
+    } else {
+        None
+    };
+
     let now = catalog.now();
     let aws_external_id = catalog.config().aws_external_id.clone();
 
     async move {
+        if let Statement::AlterSource(AlterSourceStatement {
+            action: AlterSourceAction::RefreshSchema { seed },
+            ..
+        }) = &mut stmt
+        {
+            if let Some(create_sql) = refresh_create_sql {
+                *seed = Some(refresh_csr_seed(&create_sql).await?);
+            }
+        }
+
         if let Statement::CreateSource(CreateSourceStatement {
             connector,
             format,
@@ -103,21 +136,39 @@ pub fn purify(
             let mut config_options = BTreeMap::new();
 
             let mut file = None;
+            let mut s3_aws_config = None;
             match connector {
-                CreateSourceConnector::Kafka { broker, topic, .. } => {
+                CreateSourceConnector::Kafka {
+                    broker,
+                    topic,
+                    pattern,
+                    ..
+                } => {
                     if !broker.contains(':') {
                         *broker += ":9092";
                     }
 
                     // Verify that the provided security options are valid and then test them.
                     config_options = kafka_util::extract_config(&mut with_options_map)?;
-                    let consumer = kafka_util::create_consumer(&broker, &topic, &config_options)
-                        .await
-                        .map_err(|e| {
-                            anyhow!("Failed to create and connect Kafka consumer: {}", e)
-                        })?;
+                    let consumer = kafka_util::create_consumer(
+                        &broker,
+                        (!*pattern).then(|| topic.as_str()),
+                        &config_options,
+                    )
+                    .await
+                    .map_err(|e| anyhow!("Failed to create and connect Kafka consumer: {}", e))?;
 
-                    // Translate `kafka_time_offset` to `start_offset`.
+                    // The topics matching a pattern, and so their partitions, are not
+                    // known ahead of time, so neither are the offsets to start at.
+                    if *pattern {
+                        for name in kafka_util::START_TIMESTAMP_OPTIONS {
+                            if with_options_map.contains_key(name) {
+                                bail!("`{}` is not supported with TOPIC PATTERN", name);
+                            }
+                        }
+                    }
+
+                    // Translate `start_timestamp` or `kafka_time_offset` to `start_offset`.
                     match kafka_util::lookup_start_offsets(
                         Arc::clone(&consumer),
                         &topic,
@@ -127,10 +178,10 @@ pub fn purify(
                     .await?
                     {
                         Some(start_offsets) => {
-                            // Drop `kafka_time_offset`
+                            // Drop `start_timestamp` or `kafka_time_offset`
                             with_options.retain(|val| match val {
                                 mz_sql_parser::ast::SqlOption::Value { name, .. } => {
-                                    name.as_str() != "kafka_time_offset"
+                                    !kafka_util::START_TIMESTAMP_OPTIONS.contains(&name.as_str())
                                 }
                                 _ => true,
                             });
@@ -167,16 +218,30 @@ pub fn purify(
                     })?;
                 }
                 // Report an error if a file cannot be opened, or if it is a directory.
-                CreateSourceConnector::File { path, .. } => {
-                    let f = File::open(&path).await?;
-                    if f.metadata().await?.is_dir() {
-                        bail!("Expected a regular file, but {} is a directory.", path);
+                // Globs instead require the directory that contains the matching files
+                // to exist.
+                CreateSourceConnector::File { path, .. } => match normalize::file_glob(path)? {
+                    Some((root, _)) => {
+                        if !tokio::fs::metadata(&root).await?.is_dir() {
+                            bail!("Expected a directory, but {} is not.", root.display());
+                        }
                     }
-                    file = Some(f);
-                }
+                    None => {
+                        let f = File::open(&path).await?;
+                        if f.metadata().await?.is_dir() {
+                            bail!(
+                                "Expected a regular file, but {} is a directory. \
+                                 Add a trailing slash to read every file in it.",
+                                path
+                            );
+                        }
+                        file = Some(f);
+                    }
+                },
                 CreateSourceConnector::S3 { .. } => {
                     let aws_config = normalize::aws_config(&mut with_options_map, None)?;
-                    validate_aws_credentials(&aws_config, aws_external_id).await?;
+                    validate_aws_credentials(&aws_config, aws_external_id.clone()).await?;
+                    s3_aws_config = Some(aws_config);
                 }
                 CreateSourceConnector::Kinesis { arn } => {
                     let region = arn
@@ -187,7 +252,7 @@ pub fn purify(
 
                     let aws_config =
                         normalize::aws_config(&mut with_options_map, Some(region.into()))?;
-                    validate_aws_credentials(&aws_config, aws_external_id).await?;
+                    validate_aws_credentials(&aws_config, aws_external_id.clone()).await?;
                 }
                 CreateSourceConnector::Postgres {
                     conn,
@@ -211,7 +276,49 @@ pub fn purify(
                     };
                     *details = Some(hex::encode(details_proto.encode_to_vec()));
                 }
-                CreateSourceConnector::PubNub { .. } => (),
+                CreateSourceConnector::PubNub { .. }
+                | CreateSourceConnector::Webhook
+                | CreateSourceConnector::LoadGenerator { .. } => (),
+            }
+
+            // Parquet files carry their schema in their footer. Record the schema
+            // of a sample file, so that the source's columns don't depend on
+            // which files exist when the source is planned.
+            if let CreateSourceFormat::Bare(Format::Parquet) = format {
+                if !with_options_map.contains_key("parquet_schema") {
+                    let columns = match &*connector {
+                        CreateSourceConnector::File { path, .. } => {
+                            if normalize::file_glob(path)?.is_some() {
+                                bail_unsupported!("FORMAT PARQUET with a glob or directory path");
+                            }
+                            let path = path.clone();
+                            task::block_in_place(|| {
+                                let mut f = std::fs::File::open(&path)?;
+                                parquet::read_schema(&mut f)
+                                    .with_context(|| format!("reading Parquet schema of {}", path))
+                            })?
+                        }
+                        CreateSourceConnector::S3 {
+                            key_sources,
+                            pattern,
+                            ..
+                        } => {
+                            infer_s3_parquet_schema(
+                                key_sources,
+                                pattern.as_deref(),
+                                s3_aws_config.as_ref().expect("set for S3 sources"),
+                                aws_external_id,
+                            )
+                            .await?
+                        }
+                        // Rejected during planning.
+                        _ => vec![],
+                    };
+                    with_options.push(SqlOption::Value {
+                        name: Ident::new("parquet_schema"),
+                        value: Value::String(parquet::encode_schema(&columns)),
+                    });
+                }
             }
 
             purify_source_format(
@@ -537,14 +644,22 @@ async fn purify_source_format_single(
                     *schema = mz_sql_parser::ast::Schema::Inline(buf);
                 }
             }
+            ProtobufSchema::Derived => (),
         },
         Format::Csv {
             delimiter,
             ref mut columns,
         } => {
-            purify_csv(file, connector, *delimiter, columns).await?;
+            // Sources that delimit their own records have no file to read the
+            // header row from, but may be told it explicitly.
+            let header = match normalize::options(with_options).remove("csv_header") {
+                Some(Value::String(header)) => Some(header),
+                Some(_) => bail!("csv_header option must be a string"),
+                None => None,
+            };
+            purify_csv(file, header.as_deref(), connector, *delimiter, columns).await?;
         }
-        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text => (),
+        Format::Bytes | Format::Regex(_) | Format::Json | Format::Text | Format::Parquet => (),
     }
     Ok(())
 }
@@ -555,7 +670,10 @@ async fn purify_csr_connector_proto(
     envelope: &Envelope,
     with_options: &Vec<SqlOption<Raw>>,
 ) -> Result<(), anyhow::Error> {
-    let topic = if let CreateSourceConnector::Kafka { topic, .. } = connector {
+    let topic = if let CreateSourceConnector::Kafka { topic, pattern, .. } = connector {
+        if *pattern {
+            bail!("Confluent Schema Registry is not supported with TOPIC PATTERN")
+        }
         topic
     } else {
         bail!("Confluent Schema Registry is only supported with Kafka sources")
@@ -563,6 +681,7 @@ async fn purify_csr_connector_proto(
 
     let CsrConnectorProto {
         url,
+        message_name,
         seed,
         with_options: ccsr_options,
     } = csr_connector;
@@ -576,9 +695,13 @@ async fn purify_csr_connector_proto(
                 &mut normalize::options(&ccsr_options),
             )?;
 
-            let value =
-                compile_proto(&format!("{}-value", topic), ccsr_config.clone().build()?).await?;
-            let key = compile_proto(&format!("{}-key", topic), ccsr_config.build()?)
+            let value = compile_proto(
+                &format!("{}-value", topic),
+                message_name.as_deref(),
+                ccsr_config.clone().build()?,
+            )
+            .await?;
+            let key = compile_proto(&format!("{}-key", topic), None, ccsr_config.build()?)
                 .await
                 .ok();
 
@@ -606,7 +729,10 @@ async fn purify_csr_connector_avro(
     envelope: &Envelope,
     connector_options: &BTreeMap<String, String>,
 ) -> Result<(), anyhow::Error> {
-    let topic = if let CreateSourceConnector::Kafka { topic, .. } = connector {
+    let topic = if let CreateSourceConnector::Kafka { topic, pattern, .. } = connector {
+        if *pattern {
+            bail!("Confluent Schema Registry is not supported with TOPIC PATTERN")
+        }
         topic
     } else {
         bail!("Confluent Schema Registry is only supported with Kafka sources")
@@ -646,43 +772,87 @@ async fn purify_csr_connector_avro(
     Ok(())
 }
 
+/// Returns the Confluent Schema Registry connector that describes the value
+/// of an Avro-formatted source, if there is one.
+pub(crate) fn value_csr_connector_avro(
+    format: &mut CreateSourceFormat<Raw>,
+) -> Option<&mut CsrConnectorAvro<Raw>> {
+    match format {
+        CreateSourceFormat::Bare(Format::Avro(AvroSchema::Csr { csr_connector }))
+        | CreateSourceFormat::KeyValue {
+            value: Format::Avro(AvroSchema::Csr { csr_connector }),
+            ..
+        } => Some(csr_connector),
+        _ => None,
+    }
+}
+
+/// Fetches the latest value schema for the source defined by `create_sql`
+/// from its schema registry. The key schema is left as it was, as changing
+/// it would change the key columns of the source.
+async fn refresh_csr_seed(create_sql: &str) -> Result<CsrSeed, anyhow::Error> {
+    let mut stmt = match mz_sql_parser::parser::parse_statements(create_sql)?.into_element() {
+        Statement::CreateSource(stmt) => stmt,
+        _ => bail!("ALTER SOURCE ... REFRESH SCHEMA can only be used with sources"),
+    };
+    let topic = match &stmt.connector {
+        CreateSourceConnector::Kafka { topic, .. } => topic.clone(),
+        _ => bail_unsupported!("ALTER SOURCE ... REFRESH SCHEMA with non-Kafka sources"),
+    };
+    let connector_options =
+        kafka_util::extract_config(&mut normalize::options(&stmt.with_options))?;
+    let csr_connector = match value_csr_connector_avro(&mut stmt.format) {
+        Some(csr_connector) => csr_connector,
+        None => bail_unsupported!(
+            "ALTER SOURCE ... REFRESH SCHEMA with sources not using FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY"
+        ),
+    };
+
+    let url = csr_connector.url.parse()?;
+    let ccsr_config = task::block_in_place(|| {
+        kafka_util::generate_ccsr_client_config(
+            url,
+            &connector_options,
+            &mut normalize::options(&csr_connector.with_options),
+        )
+    })?;
+    let Schema { value_schema, .. } = get_remote_csr_schema(ccsr_config, topic).await?;
+
+    Ok(CsrSeed {
+        key_schema: csr_connector
+            .seed
+            .as_ref()
+            .and_then(|seed| seed.key_schema.clone()),
+        value_schema,
+    })
+}
+
 pub async fn purify_csv(
     file: Option<File>,
+    header: Option<&str>,
     connector: &CreateSourceConnector,
     delimiter: char,
     columns: &mut CsvColumns,
 ) -> anyhow::Result<()> {
-    if matches!(columns, CsvColumns::Header { .. })
-        && !matches!(
-            connector,
-            CreateSourceConnector::File { .. } | CreateSourceConnector::S3 { .. }
-        )
-    {
-        bail_unsupported!("CSV WITH HEADER with non-file or S3 sources");
-    }
-
-    let first_row = if let Some(file) = file {
+    let first_row = if let Some(header) = header {
+        if let CsvColumns::Count(_) = columns {
+            bail!("csv_header option requires CSV WITH HEADER");
+        }
+        if !delimiter.is_ascii() {
+            bail!("CSV delimiter must be ascii");
+        }
+        match parse_csv_header(header, delimiter)? {
+            Some(headers) => Some(headers),
+            None => bail!("csv_header option must not be empty"),
+        }
+    } else if let Some(file) = file {
         let file = tokio::io::BufReader::new(file);
         let csv_header = file.lines().next_line().await;
         if !delimiter.is_ascii() {
             bail!("CSV delimiter must be ascii");
         }
         match csv_header {
-            Ok(Some(csv_header)) => {
-                let mut reader = ReaderBuilder::new()
-                    .delimiter(delimiter as u8)
-                    .has_headers(false)
-                    .from_reader(csv_header.as_bytes());
-
-                if let Some(result) = reader.records().next() {
-                    match result {
-                        Ok(headers) => Some(headers),
-                        Err(e) => bail!("Unable to parse header row: {}", e),
-                    }
-                } else {
-                    None
-                }
-            }
+            Ok(Some(csv_header)) => parse_csv_header(&csv_header, delimiter)?,
             Ok(None) => {
                 if let CsvColumns::Header { names } = columns {
                     if names.is_empty() {
@@ -748,7 +918,11 @@ pub async fn purify_csv(
             CreateSourceConnector::S3 { .. } => {
                 bail!("CSV WITH HEADER for S3 sources requiers specifying the header columns")
             }
-            _ => bail!("CSV WITH HEADER is only supported for S3 and file sources"),
+            // Streaming sources have no first row to read the header from.
+            _ => bail!(
+                "CSV WITH HEADER for streaming sources requires specifying the header columns \
+                 or the csv_header option"
+            ),
         },
         (CsvColumns::Header { names }, None) => {
             // we don't need to do any verification if we are told the names of the headers
@@ -774,6 +948,20 @@ pub async fn purify_csv(
     Ok(())
 }
 
+/// Parses the header row of a CSV source into the names of its columns.
+fn parse_csv_header(header: &str, delimiter: char) -> anyhow::Result<Option<StringRecord>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(delimiter as u8)
+        .has_headers(false)
+        .from_reader(header.as_bytes());
+
+    match reader.records().next() {
+        Some(Ok(headers)) => Ok(Some(headers)),
+        Some(Err(e)) => bail!("Unable to parse header row: {}", e),
+        None => Ok(None),
+    }
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -813,8 +1001,12 @@ async fn get_remote_csr_schema(
 }
 
 /// Collect protobuf message descriptor from CSR and compile the descriptor.
+///
+/// If `message_name` is not specified, the schema must contain exactly one
+/// top-level message, which is the message that is decoded.
 async fn compile_proto(
     subject_name: &String,
+    message_name: Option<&str>,
     ccsr_client: Client,
 ) -> Result<CsrSeedCompiledEncoding, anyhow::Error> {
     let (primary_subject, dependency_subjects) =
@@ -833,12 +1025,30 @@ async fn compile_proto(
         .as_mut()
         .build_file_descriptor_set(&[Path::new(&primary_subject.name)])?;
 
-    // Ensure there is exactly one message in the file.
-    let primary_fd = fds.file(0);
-    let message_name = match primary_fd.message_type_size() {
-        1 => String::from_utf8_lossy(primary_fd.message_type(0).name()).into_owned(),
-        0 => bail_unsupported!(9598, "Protobuf schemas with no messages"),
-        _ => bail_unsupported!(9598, "Protobuf schemas with multiple messages"),
+    // Unless the message is named explicitly, ensure there is exactly one
+    // message in the file. The message named explicitly is validated during
+    // planning.
+    let message_name = match message_name {
+        Some(message_name) => message_name.to_owned(),
+        None => {
+            let primary_fd = fds.file(0);
+            let message = match primary_fd.message_type_size() {
+                1 => String::from_utf8_lossy(primary_fd.message_type(0).name()).into_owned(),
+                0 => bail_unsupported!(9598, "Protobuf schemas with no messages"),
+                _ => bail!(
+                    "Protobuf schema for subject {} contains multiple messages; \
+                    specify the message to decode with \
+                    FORMAT PROTOBUF MESSAGE '...' USING CONFLUENT SCHEMA REGISTRY",
+                    subject_name.quoted()
+                ),
+            };
+            let package = String::from_utf8_lossy(primary_fd.package());
+            if package.is_empty() {
+                message
+            } else {
+                format!("{}.{}", package, message)
+            }
+        }
     };
 
     // Encode the file descriptor set into a SQL byte string.
@@ -866,3 +1076,74 @@ async fn validate_aws_credentials(
         .context("Unable to validate AWS credentials")?;
     Ok(())
 }
+
+/// Determines the columns of an S3 source with `FORMAT PARQUET` from the first
+/// object that the source will ingest from its scanned buckets.
+async fn infer_s3_parquet_schema(
+    key_sources: &[S3KeySource],
+    pattern: Option<&str>,
+    aws_config: &AwsConfig,
+    aws_external_id: AwsExternalId,
+) -> Result<Vec<ParquetColumn>, anyhow::Error> {
+    let pattern = pattern
+        .map(|p| {
+            GlobBuilder::new(p)
+                .literal_separator(true)
+                .backslash_escape(true)
+                .build()
+                .map(|g| g.compile_matcher())
+        })
+        .transpose()?;
+    let config = aws_config.load(aws_external_id).await;
+    let client = mz_aws_util::s3::client(&config);
+
+    for key_source in key_sources {
+        let bucket = match key_source {
+            S3KeySource::Scan { bucket } => bucket,
+            // Notifications only announce objects created in the future.
+            S3KeySource::SqsNotifications { .. } => continue,
+        };
+        let mut continuation_token = None;
+        loop {
+            let response = client
+                .list_objects_v2()
+                .bucket(bucket)
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .with_context(|| format!("Unable to list S3 bucket {}", bucket))?;
+            let key = response
+                .contents
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|object| object.key)
+                .find(|key| pattern.as_ref().map_or(true, |p| p.is_match(key)));
+            if let Some(key) = key {
+                let object = client
+                    .get_object()
+                    .bucket(bucket)
+                    .key(&key)
+                    .send()
+                    .await
+                    .with_context(|| format!("Unable to get S3 object {}/{}", bucket, key))?;
+                let bytes = object
+                    .body
+                    .collect()
+                    .await
+                    .with_context(|| format!("Unable to read S3 object {}/{}", bucket, key))?
+                    .into_bytes();
+                return parquet::read_schema(&mut std::io::Cursor::new(bytes))
+                    .with_context(|| format!("reading Parquet schema of {}/{}", bucket, key));
+            }
+            match response.next_continuation_token {
+                Some(token) => continuation_token = Some(token),
+                None => break,
+            }
+        }
+    }
+
+    bail!(
+        "Unable to find an S3 object to determine the Parquet schema from; \
+        specify it with the parquet_schema option"
+    )
+}
@@ -41,10 +41,12 @@ use crate::util;
 
 mod avro_ocf;
 mod file;
+mod file_sink;
 mod http;
 mod kafka;
 mod kinesis;
 mod mysql;
+mod parquet;
 mod postgres;
 mod protobuf;
 mod psql;
@@ -473,6 +475,9 @@ pub(crate) async fn build(
                     }
                     "file-append" => Box::new(file::build_append(builtin).map_err(wrap_err)?),
                     "file-delete" => Box::new(file::build_delete(builtin).map_err(wrap_err)?),
+                    "file-sink-verify" => {
+                        Box::new(file_sink::build_verify(builtin).map_err(wrap_err)?)
+                    }
                     "http-request" => Box::new(http::build_request(builtin).map_err(wrap_err)?),
                     "kafka-add-partitions" => {
                         Box::new(kafka::build_add_partitions(builtin).map_err(wrap_err)?)
@@ -495,6 +500,7 @@ pub(crate) async fn build(
                     "kinesis-verify" => Box::new(kinesis::build_verify(builtin).map_err(wrap_err)?),
                     "mysql-connect" => Box::new(mysql::build_connect(builtin).map_err(wrap_err)?),
                     "mysql-execute" => Box::new(mysql::build_execute(builtin).map_err(wrap_err)?),
+                    "parquet-write" => Box::new(parquet::build_write(builtin).map_err(wrap_err)?),
                     "postgres-connect" => {
                         Box::new(postgres::build_connect(builtin).map_err(wrap_err)?)
                     }
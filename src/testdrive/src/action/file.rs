@@ -7,11 +7,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::io::Write;
 use std::path;
 use std::str::FromStr;
 
-use anyhow::bail;
-use async_compression::tokio::write::GzipEncoder;
+use anyhow::{anyhow, bail, Context};
+use async_compression::tokio::write::{BzEncoder, GzipEncoder, XzEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use tokio::fs::{self, OpenOptions};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
@@ -28,6 +29,10 @@ pub struct AppendAction {
 
 pub enum Compression {
     Gzip,
+    Zstd,
+    Bzip2,
+    Xz,
+    Snappy,
     None,
 }
 
@@ -37,6 +42,10 @@ impl FromStr for Compression {
     fn from_str(s: &str) -> Result<Self, anyhow::Error> {
         match s {
             "gzip" => Ok(Compression::Gzip),
+            "zstd" => Ok(Compression::Zstd),
+            "bzip2" => Ok(Compression::Bzip2),
+            "xz" => Ok(Compression::Xz),
+            "snappy" => Ok(Compression::Snappy),
             "none" => Ok(Compression::None),
             f => bail!("unknown compression format: {}", f),
         }
@@ -99,10 +108,16 @@ impl Action for AppendAction {
 
         let mut file: Box<dyn AsyncWrite + Unpin + Send> = match self.compression {
             Compression::Gzip => Box::new(GzipEncoder::new(file)),
-            Compression::None => Box::new(file),
+            Compression::Zstd => Box::new(ZstdEncoder::new(file)),
+            Compression::Bzip2 => Box::new(BzEncoder::new(file)),
+            Compression::Xz => Box::new(XzEncoder::new(file)),
+            Compression::Snappy | Compression::None => Box::new(file),
         };
 
-        file.write_all(&self.contents).await?;
+        match self.compression {
+            Compression::Snappy => file.write_all(&encode_snappy(&self.contents)?).await?,
+            _ => file.write_all(&self.contents).await?,
+        }
         file.shutdown().await?;
 
         Ok(ControlFlow::Continue)
@@ -132,3 +147,14 @@ impl Action for DeleteAction {
         Ok(ControlFlow::Continue)
     }
 }
+
+/// Encodes `contents` in the Snappy framing format.
+pub fn encode_snappy(contents: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+    let mut encoder = snap::write::FrameEncoder::new(vec![]);
+    encoder
+        .write_all(contents)
+        .context("writing to snappy encoder")?;
+    encoder
+        .into_inner()
+        .map_err(|e| anyhow!("writing to snappy encoder: {}", e.error()))
+}
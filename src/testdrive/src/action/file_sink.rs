@@ -0,0 +1,160 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use serde_json::Value;
+
+use mz_ore::retry::Retry;
+
+use crate::action::{Action, ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+pub struct VerifyAction {
+    sink: String,
+    expected: Vec<String>,
+}
+
+pub fn build_verify(mut cmd: BuiltinCommand) -> Result<VerifyAction, anyhow::Error> {
+    let sink = cmd.args.string("sink")?;
+    let expected = cmd.input;
+    cmd.args.done()?;
+    Ok(VerifyAction { sink, expected })
+}
+
+#[async_trait]
+impl Action for VerifyAction {
+    async fn undo(&self, _state: &mut State) -> Result<(), anyhow::Error> {
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<ControlFlow, anyhow::Error> {
+        let (bucket, path) = Retry::default()
+            .max_duration(state.default_timeout)
+            .retry_async(|_| async {
+                let row = state
+                    .pgclient
+                    .query_one(
+                        "SELECT bucket, path FROM mz_catalog_names
+                     JOIN mz_file_sinks ON global_id = sink_id
+                     WHERE name = $1",
+                        &[&self.sink],
+                    )
+                    .await
+                    .context("querying materialize")?;
+                let bucket: Option<String> = row.get("bucket");
+                let path: String = row.get("path");
+                Ok::<_, anyhow::Error>((bucket, path))
+            })
+            .await
+            .context("retrieving sink location")?;
+
+        match &bucket {
+            None => println!("Verifying results in directory {}", path),
+            Some(bucket) => println!("Verifying results in s3://{}/{}", bucket, path),
+        }
+
+        let mut expected = self
+            .expected
+            .iter()
+            .map(|line| serde_json::from_str(line).context("parsing expected record"))
+            .collect::<Result<Vec<Value>, _>>()?;
+        sort_values(&mut expected);
+
+        // The sink writes files as timestamps close, so keep re-reading the
+        // manifest until the listed files contain the expected records.
+        Retry::default()
+            .max_duration(state.default_timeout)
+            .retry_async(|_| async {
+                let manifest: Value = serde_json::from_slice(
+                    &read_file(state, &bucket, &path, "manifest.json").await?,
+                )
+                .context("parsing manifest")?;
+                let mut actual = vec![];
+                for file in manifest["files"].as_array().into_iter().flatten() {
+                    let name = match file["name"].as_str() {
+                        Some(name) => name,
+                        None => bail!("manifest entry without a name: {}", file),
+                    };
+                    if !name.ends_with(".ndjson") {
+                        bail!("only JSON file sinks can be verified, found {}", name);
+                    }
+                    let contents = read_file(state, &bucket, &path, name).await?;
+                    for line in contents.split(|b| *b == b'\n').filter(|l| !l.is_empty()) {
+                        let mut record: Value =
+                            serde_json::from_slice(line).context("parsing sinked record")?;
+                        // Timestamps are not deterministic, so ignore them.
+                        if let Some(record) = record.as_object_mut() {
+                            record.remove("time");
+                        }
+                        actual.push(record);
+                    }
+                }
+                sort_values(&mut actual);
+                if actual != expected {
+                    bail!(
+                        "sinked records did not match\nexpected:\n{}\nactual:\n{}",
+                        format_values(&expected),
+                        format_values(&actual),
+                    );
+                }
+                Ok(())
+            })
+            .await?;
+
+        Ok(ControlFlow::Continue)
+    }
+}
+
+async fn read_file(
+    state: &State,
+    bucket: &Option<String>,
+    path: &str,
+    name: &str,
+) -> Result<Vec<u8>, anyhow::Error> {
+    match bucket {
+        None => {
+            let path = PathBuf::from(path).join(name);
+            tokio::fs::read(&path)
+                .await
+                .with_context(|| format!("reading {}", path.display()))
+        }
+        Some(bucket) => {
+            let key = format!("{}{}", path, name);
+            let object = state
+                .s3_client
+                .get_object()
+                .bucket(bucket)
+                .key(&key)
+                .send()
+                .await
+                .with_context(|| format!("getting s3://{}/{}", bucket, key))?;
+            let body = object
+                .body
+                .collect()
+                .await
+                .with_context(|| format!("reading s3://{}/{}", bucket, key))?;
+            Ok(body.into_bytes().to_vec())
+        }
+    }
+}
+
+fn sort_values(values: &mut Vec<Value>) {
+    values.sort_by_cached_key(|value| value.to_string());
+}
+
+fn format_values(values: &[Value]) -> String {
+    values
+        .iter()
+        .map(|value| value.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -7,9 +7,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use async_trait::async_trait;
-use reqwest::Method;
+use reqwest::{Method, StatusCode};
 
 use crate::action::{Action, ControlFlow, State};
 use crate::parser::BuiltinCommand;
@@ -20,14 +20,27 @@ pub struct RequestAction {
     url: String,
     method: Method,
     content_type: Option<String>,
+    header: Option<(String, String)>,
+    expected_status: Option<StatusCode>,
     body: String,
 }
 
 pub fn build_request(mut cmd: BuiltinCommand) -> Result<RequestAction, anyhow::Error> {
+    let header = match cmd.args.opt_string("header") {
+        None => None,
+        Some(header) => {
+            let (name, value) = header
+                .split_once(':')
+                .ok_or_else(|| anyhow!("header must be of the form name:value"))?;
+            Some((name.to_string(), value.to_string()))
+        }
+    };
     Ok(RequestAction {
         url: cmd.args.string("url")?,
         method: cmd.args.parse("method")?,
         content_type: cmd.args.opt_string("content-type"),
+        header,
+        expected_status: cmd.args.opt_parse("expected-status")?,
         body: cmd.input.join("\n"),
     })
 }
@@ -51,15 +64,24 @@ impl Action for RequestAction {
             request = request.header(CONTENT_TYPE, value);
         }
 
+        if let Some((name, value)) = &self.header {
+            request = request.header(name, value);
+        }
+
         let response = request.send().await?;
         let status = response.status();
 
         println!("{}\n{}", status, response.text().await?);
 
-        if status.is_success() {
-            Ok(ControlFlow::Continue)
-        } else {
-            bail!("http request returned failing status: {}", status)
+        match self.expected_status {
+            Some(expected) if status != expected => bail!(
+                "http request returned status {}, but expected {}",
+                status,
+                expected
+            ),
+            Some(_) => Ok(ControlFlow::Continue),
+            None if status.is_success() => Ok(ControlFlow::Continue),
+            None => bail!("http request returned failing status: {}", status),
         }
     }
 }
@@ -18,10 +18,13 @@ use futures::stream::{FuturesUnordered, StreamExt};
 use maplit::hashmap;
 use prost::Message;
 use prost_reflect::{DynamicMessage, FileDescriptor, MessageDescriptor};
+use rdkafka::message::OwnedHeaders;
 use rdkafka::producer::FutureRecord;
 use serde::de::DeserializeOwned;
 use tokio::fs;
 
+use mz_interchange::{confluent, protobuf};
+
 use crate::action::{self, Action, ControlFlow, State};
 use crate::format::avro::{self, Schema};
 use crate::format::bytes;
@@ -35,6 +38,7 @@ pub struct IngestAction {
     format: Format,
     key_format: Option<Format>,
     timestamp: Option<i64>,
+    headers: Vec<(String, String)>,
     publish: bool,
     rows: Vec<String>,
     start_iteration: isize,
@@ -52,7 +56,7 @@ enum Format {
         message: String,
         confluent_wire_format: bool,
         schema_id_subject: Option<String>,
-        schema_message_id: u8,
+        schema_message_indexes: Option<Vec<i32>>,
     },
     Bytes {
         terminator: Option<u8>,
@@ -69,7 +73,7 @@ enum Transcoder {
         message: MessageDescriptor,
         confluent_wire_format: bool,
         schema_id: i32,
-        schema_message_id: u8,
+        schema_message_indexes: Vec<i32>,
     },
     Bytes {
         terminator: Option<u8>,
@@ -122,22 +126,23 @@ impl Transcoder {
                 message,
                 confluent_wire_format,
                 schema_id,
-                schema_message_id,
+                schema_message_indexes,
             } => {
                 if let Some(val) = Self::decode_json::<_, serde_json::Value>(row)? {
                     let message = DynamicMessage::deserialize(message.clone(), val)
                         .context("parsing protobuf JSON")?;
                     let mut out = vec![];
                     if *confluent_wire_format {
-                        // See: https://github.com/MaterializeInc/materialize/issues/9250
                         // The first byte is a magic byte (0) that indicates the Confluent
                         // serialization format version, and the next four bytes are a
-                        // 32-bit schema ID, which we default to something fun.
-                        // And, as we only support single-message proto files for now,
-                        // we also set the following message id to 0.
+                        // 32-bit schema ID. They are followed by the path of indexes
+                        // that identifies the message within the schema.
                         out.write_u8(0).unwrap();
                         out.write_i32::<NetworkEndian>(*schema_id).unwrap();
-                        out.write_u8(*schema_message_id).unwrap();
+                        confluent::encode_protobuf_message_indexes(
+                            &mut out,
+                            schema_message_indexes,
+                        );
                     }
                     message.encode(&mut out)?;
                     Ok(Some(out))
@@ -166,6 +171,19 @@ impl Transcoder {
     }
 }
 
+/// Parses a comma-separated list of Protobuf message indexes, which
+/// overrides the path of the message in the Confluent wire format.
+fn parse_message_indexes(indexes: Option<String>) -> Result<Option<Vec<i32>>, anyhow::Error> {
+    indexes
+        .map(|indexes| {
+            indexes
+                .split(',')
+                .map(|index| index.trim().parse().context("parsing message index"))
+                .collect()
+        })
+        .transpose()
+}
+
 pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Error> {
     let topic_prefix = format!("testdrive-{}", cmd.args.string("topic")?);
     let partition = cmd.args.opt_parse::<i32>("partition")?;
@@ -187,7 +205,9 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
                 // false
                 confluent_wire_format: cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(false),
                 schema_id_subject: cmd.args.opt_string("schema-id-subject"),
-                schema_message_id: cmd.args.opt_parse::<u8>("schema-message-id")?.unwrap_or(0),
+                schema_message_indexes: parse_message_indexes(
+                    cmd.args.opt_string("schema-message-indexes"),
+                )?,
             }
         }
         "bytes" => Format::Bytes { terminator: None },
@@ -206,10 +226,9 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
                 message,
                 confluent_wire_format: cmd.args.opt_bool("confluent-wire-format")?.unwrap_or(false),
                 schema_id_subject: cmd.args.opt_string("key-schema-id-subject"),
-                schema_message_id: cmd
-                    .args
-                    .opt_parse::<u8>("key-schema-message-id")?
-                    .unwrap_or(0),
+                schema_message_indexes: parse_message_indexes(
+                    cmd.args.opt_string("key-schema-message-indexes"),
+                )?,
             })
         }
         Some("bytes") => Some(Format::Bytes {
@@ -223,6 +242,13 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
         None => None,
     };
     let timestamp = cmd.args.opt_parse("timestamp")?;
+    // Headers are a JSON array of `[key, value]` pairs, so that a key may
+    // be repeated.
+    let headers = match cmd.args.opt_string("headers") {
+        None => vec![],
+        Some(headers) => serde_json::from_str(&headers)
+            .with_context(|| format!("parsing headers: {}", headers))?,
+    };
     cmd.args.done()?;
 
     if publish
@@ -238,6 +264,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, anyhow::Err
         format,
         key_format,
         timestamp,
+        headers,
         publish,
         rows: cmd.input,
         start_iteration,
@@ -310,7 +337,7 @@ impl Action for IngestAction {
                     message,
                     confluent_wire_format,
                     schema_id_subject,
-                    schema_message_id,
+                    schema_message_indexes,
                 } => {
                     let schema_id = if confluent_wire_format {
                         ccsr_client
@@ -329,6 +356,13 @@ impl Action for IngestAction {
                         .context("reading protobuf descriptor file")?;
                     let fd = FileDescriptor::decode(&*bytes)
                         .context("parsing protobuf descriptor file")?;
+                    let schema_message_indexes = match schema_message_indexes {
+                        Some(schema_message_indexes) => schema_message_indexes,
+                        None if confluent_wire_format => {
+                            protobuf::message_index_path(&bytes, &message)?
+                        }
+                        None => vec![],
+                    };
                     let message = fd
                         .get_message_by_name(&message)
                         .ok_or_else(|| anyhow!("unknown message name {}", message))?;
@@ -336,7 +370,7 @@ impl Action for IngestAction {
                         message,
                         confluent_wire_format,
                         schema_id,
-                        schema_message_id,
+                        schema_message_indexes,
                     })
                 }
                 Format::Bytes { terminator } => Ok(Transcoder::Bytes { terminator }),
@@ -384,6 +418,13 @@ impl Action for IngestAction {
                     if let Some(timestamp) = self.timestamp {
                         record = record.timestamp(timestamp);
                     }
+                    if !self.headers.is_empty() {
+                        let mut headers = OwnedHeaders::new();
+                        for (key, value) in &self.headers {
+                            headers = headers.add(key, value.as_str());
+                        }
+                        record = record.headers(headers);
+                    }
                     producer.send(record, timeout).await
                 });
             }
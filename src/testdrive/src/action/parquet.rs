@@ -0,0 +1,150 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path;
+
+use anyhow::{anyhow, bail, Context};
+use async_trait::async_trait;
+use aws_sdk_s3::types::ByteStream;
+use serde_json::Value;
+
+use mz_interchange::parquet::{self, ParquetColumn, ParquetColumnType, ParquetEncoder};
+use mz_repr::{Datum, Row};
+
+use crate::action::{Action, ControlFlow, State};
+use crate::parser::BuiltinCommand;
+
+/// Where a [`WriteAction`] writes its file.
+enum Target {
+    /// A file in the temporary directory.
+    File { path: String },
+    /// An object in an S3 bucket created by `s3-create-bucket`.
+    S3 { bucket_prefix: String, key: String },
+}
+
+pub struct WriteAction {
+    target: Target,
+    columns: Vec<ParquetColumn>,
+    records: Vec<String>,
+}
+
+pub fn build_write(mut cmd: BuiltinCommand) -> Result<WriteAction, anyhow::Error> {
+    let target = match cmd.args.opt_string("bucket") {
+        Some(bucket) => Target::S3 {
+            bucket_prefix: format!("testdrive-{}", bucket),
+            key: cmd.args.string("key")?,
+        },
+        None => {
+            let path = cmd.args.string("path")?;
+            if path.contains(path::MAIN_SEPARATOR) {
+                // The goal isn't security, but preventing mistakes.
+                bail!("separators in paths are forbidden");
+            }
+            Target::File { path }
+        }
+    };
+    let columns = parquet::parse_schema(&cmd.args.string("schema")?)?;
+    let records = cmd.input;
+    cmd.args.done()?;
+    Ok(WriteAction {
+        target,
+        columns,
+        records,
+    })
+}
+
+#[async_trait]
+impl Action for WriteAction {
+    async fn undo(&self, _state: &mut State) -> Result<(), anyhow::Error> {
+        // Files are written to a fresh temporary directory, and buckets are
+        // cleaned up by `s3-create-bucket`, so there is nothing to do here.
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<ControlFlow, anyhow::Error> {
+        let mut updates = vec![];
+        for record in &self.records {
+            let values: Vec<Value> =
+                serde_json::from_str(record).context("parsing Parquet record")?;
+            if values.len() != self.columns.len() {
+                bail!(
+                    "Parquet record {} has {} values, but the schema has {} columns",
+                    record,
+                    values.len(),
+                    self.columns.len()
+                );
+            }
+            let datums = self
+                .columns
+                .iter()
+                .zip(&values)
+                .map(|(column, value)| json_to_datum(column, value))
+                .collect::<Result<Vec<_>, _>>()?;
+            updates.push((Row::pack_slice(&datums), 0, 1));
+        }
+
+        let encoder = ParquetEncoder::new(parquet::columns_to_relationdesc(&self.columns));
+        let mut contents = vec![];
+        encoder.encode(&mut contents, &updates)?;
+
+        match &self.target {
+            Target::File { path } => {
+                let path = state.temp_path.join(path);
+                println!("Writing Parquet file {}", path.display());
+                tokio::fs::write(&path, contents)
+                    .await
+                    .with_context(|| format!("writing {}", path.display()))?;
+            }
+            Target::S3 { bucket_prefix, key } => {
+                let bucket = format!("{}-{}", bucket_prefix, state.seed);
+                println!("Put Parquet S3 object {}/{}", bucket, key);
+                state
+                    .s3_client
+                    .put_object()
+                    .bucket(&bucket)
+                    .key(key)
+                    .body(ByteStream::from(contents))
+                    .send()
+                    .await
+                    .context("putting Parquet S3 object")?;
+            }
+        }
+        Ok(ControlFlow::Continue)
+    }
+}
+
+/// Converts the JSON representation of a value of `column` into a datum.
+fn json_to_datum<'a>(column: &ParquetColumn, value: &'a Value) -> Result<Datum<'a>, anyhow::Error> {
+    let datum = match (column.ty, value) {
+        (_, Value::Null) => Some(Datum::Null),
+        (ParquetColumnType::Boolean, Value::Bool(b)) => Some(Datum::from(*b)),
+        (ParquetColumnType::Int16, Value::Number(n)) => n
+            .as_i64()
+            .and_then(|n| i16::try_from(n).ok())
+            .map(Datum::from),
+        (ParquetColumnType::Int32, Value::Number(n)) => n
+            .as_i64()
+            .and_then(|n| i32::try_from(n).ok())
+            .map(Datum::from),
+        (ParquetColumnType::Int64, Value::Number(n)) => n.as_i64().map(Datum::from),
+        (ParquetColumnType::Float32, Value::Number(n)) => n.as_f64().map(|n| Datum::from(n as f32)),
+        (ParquetColumnType::Float64, Value::Number(n)) => n.as_f64().map(Datum::from),
+        (ParquetColumnType::Text, Value::String(s)) => Some(Datum::String(s)),
+        (ParquetColumnType::Bytes, Value::String(s)) => Some(Datum::Bytes(s.as_bytes())),
+        _ => None,
+    };
+    datum.ok_or_else(|| {
+        anyhow!(
+            "cannot write {} as a value of Parquet column {} of type {}",
+            value,
+            column.name,
+            column.ty
+        )
+    })
+}
@@ -12,6 +12,7 @@ use std::io::Write;
 use std::time::{Duration, Instant};
 
 use anyhow::{anyhow, bail, Context};
+use async_compression::tokio::write::{BzEncoder, XzEncoder, ZstdEncoder};
 use async_trait::async_trait;
 use aws_sdk_s3::error::{CreateBucketError, CreateBucketErrorKind};
 use aws_sdk_s3::model::{
@@ -22,8 +23,9 @@ use aws_sdk_s3::types::{ByteStream, SdkError};
 use aws_sdk_sqs::model::{DeleteMessageBatchRequestEntry, QueueAttributeName};
 use flate2::write::GzEncoder;
 use flate2::Compression as Flate2Compression;
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 
-use crate::action::file::{build_compression, Compression};
+use crate::action::file::{build_compression, encode_snappy, Compression};
 use crate::action::{Action, ControlFlow, State};
 use crate::parser::BuiltinCommand;
 
@@ -120,6 +122,22 @@ impl Action for PutObjectAction {
                     .context("writing to gzip encoder")?;
                 encoder.finish().context("writing to gzip encoder")
             }
+            Compression::Zstd => {
+                let mut encoder = ZstdEncoder::new(Vec::new());
+                encode(&mut encoder, &buffer).await?;
+                Ok(encoder.into_inner())
+            }
+            Compression::Bzip2 => {
+                let mut encoder = BzEncoder::new(Vec::new());
+                encode(&mut encoder, &buffer).await?;
+                Ok(encoder.into_inner())
+            }
+            Compression::Xz => {
+                let mut encoder = XzEncoder::new(Vec::new());
+                encode(&mut encoder, &buffer).await?;
+                Ok(encoder.into_inner())
+            }
+            Compression::Snappy => encode_snappy(&buffer),
         }?;
 
         state
@@ -131,6 +149,10 @@ impl Action for PutObjectAction {
             .set_content_encoding(match self.compression {
                 Compression::None => None,
                 Compression::Gzip => Some("gzip".to_string()),
+                Compression::Zstd => Some("zstd".to_string()),
+                Compression::Bzip2 => Some("bzip2".to_string()),
+                Compression::Xz => Some("xz".to_string()),
+                Compression::Snappy => Some("x-snappy-framed".to_string()),
             })
             .key(&self.key)
             .send()
@@ -142,6 +164,17 @@ impl Action for PutObjectAction {
     }
 }
 
+async fn encode<W>(encoder: &mut W, buffer: &[u8]) -> Result<(), anyhow::Error>
+where
+    W: AsyncWrite + Unpin,
+{
+    encoder
+        .write_all(buffer)
+        .await
+        .context("writing to encoder")?;
+    encoder.shutdown().await.context("writing to encoder")
+}
+
 pub struct DeleteObjectAction {
     bucket_prefix: String,
     keys: Vec<String>,
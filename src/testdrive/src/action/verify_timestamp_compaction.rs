@@ -74,10 +74,10 @@ impl Action for VerifyTimestampCompactionAction {
 
                         // We consider progress to be eventually compacting at least up to the original highest
                         // timestamp binding.
-                        let lo_binding = bindings.iter().map(|(_, ts, _)| *ts).min();
+                        let lo_binding = bindings.iter().map(|(_, ts, _, _)| *ts).min();
                         let progress = if retry_state.i == 0 {
                             initial_highest.store(
-                                bindings.iter().map(|(_, ts, _)| *ts).max().unwrap_or(u64::MIN),
+                                bindings.iter().map(|(_, ts, _, _)| *ts).max().unwrap_or(u64::MIN),
                                 Ordering::SeqCst,
                             );
                             false
@@ -8,7 +8,7 @@
 // by the Apache License, Version 2.0.
 
 //! Analysis to identify monotonic collections, especially TopK inputs.
-use mz_expr::{GlobalId, Id, LocalId};
+use mz_expr::{BinaryFunc, GlobalId, Id, LocalId, MirScalarExpr, NullaryFunc};
 use mz_expr::{MirRelationExpr, RECURSION_LIMIT};
 use mz_ore::stack::{CheckedRecursion, RecursionGuard};
 use std::collections::HashSet;
@@ -52,11 +52,13 @@ impl MonotonicFlag {
                 MirRelationExpr::Project { input, .. } => self.apply(input, sources, locals)?,
                 MirRelationExpr::Filter { input, predicates } => {
                     let is_monotonic = self.apply(input, sources, locals)?;
-                    // Non-temporal predicates can introduce non-monotonicity, as they
-                    // can result in the future removal of records.
-                    // TODO: this could be improved to only restrict if upper bounds
-                    // are present, as temporal lower bounds only delay introduction.
-                    is_monotonic && !predicates.iter().any(|p| p.contains_temporal())
+                    // Temporal predicates can introduce non-monotonicity, as they
+                    // can result in the future removal of records. Temporal lower
+                    // bounds, however, only delay the introduction of records.
+                    is_monotonic
+                        && predicates
+                            .iter()
+                            .all(|p| !p.contains_temporal() || is_temporal_lower_bound(p))
                 }
                 MirRelationExpr::Map { input, .. } => self.apply(input, sources, locals)?,
                 MirRelationExpr::TopK {
@@ -129,3 +131,22 @@ impl MonotonicFlag {
         })
     }
 }
+
+/// Reports whether `predicate` only bounds `mz_logical_timestamp()` from below.
+fn is_temporal_lower_bound(predicate: &MirScalarExpr) -> bool {
+    let is_mlt =
+        |e: &MirScalarExpr| *e == MirScalarExpr::CallNullary(NullaryFunc::MzLogicalTimestamp);
+    match predicate {
+        MirScalarExpr::CallBinary {
+            func: BinaryFunc::Gt | BinaryFunc::Gte,
+            expr1,
+            expr2,
+        } => is_mlt(expr1) && !expr2.contains_temporal(),
+        MirScalarExpr::CallBinary {
+            func: BinaryFunc::Lt | BinaryFunc::Lte,
+            expr1,
+            expr2,
+        } => is_mlt(expr2) && !expr1.contains_temporal(),
+        _ => false,
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright Materialize, Inc. and contributors. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Tests for the treatment of temporal filters by [`MonotonicFlag`].
+
+use std::collections::HashSet;
+
+use mz_expr::{BinaryFunc, ColumnOrder, GlobalId, MirRelationExpr, MirScalarExpr, NullaryFunc};
+use mz_repr::{RelationType, ScalarType};
+use mz_transform::monotonic::MonotonicFlag;
+
+const SOURCE: GlobalId = GlobalId::User(1);
+
+fn source() -> MirRelationExpr {
+    MirRelationExpr::global_get(
+        SOURCE,
+        RelationType::new(vec![ScalarType::Int64.nullable(false)]),
+    )
+}
+
+fn mz_logical_timestamp() -> MirScalarExpr {
+    MirScalarExpr::CallNullary(NullaryFunc::MzLogicalTimestamp)
+}
+
+fn is_monotonic(mut expr: MirRelationExpr, sources: &[GlobalId]) -> bool {
+    MonotonicFlag::default()
+        .apply(
+            &mut expr,
+            &sources.iter().copied().collect(),
+            &mut HashSet::new(),
+        )
+        .unwrap()
+}
+
+#[test]
+fn test_temporal_lower_bounds_preserve_monotonicity() {
+    for predicate in [
+        mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte),
+        mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Gt),
+        MirScalarExpr::column(0).call_binary(mz_logical_timestamp(), BinaryFunc::Lte),
+        MirScalarExpr::column(0).call_binary(mz_logical_timestamp(), BinaryFunc::Lt),
+    ] {
+        assert!(is_monotonic(source().filter(vec![predicate]), &[SOURCE]));
+    }
+}
+
+#[test]
+fn test_temporal_upper_bounds_break_monotonicity() {
+    for predicate in [
+        mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Lte),
+        mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Lt),
+        MirScalarExpr::column(0).call_binary(mz_logical_timestamp(), BinaryFunc::Gte),
+        MirScalarExpr::column(0).call_binary(mz_logical_timestamp(), BinaryFunc::Gt),
+        mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Eq),
+    ] {
+        assert!(!is_monotonic(source().filter(vec![predicate]), &[SOURCE]));
+    }
+}
+
+#[test]
+fn test_temporal_bounds_on_both_sides_break_monotonicity() {
+    let lower = mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte);
+    let upper = mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Lt);
+    assert!(!is_monotonic(
+        source().filter(vec![lower, upper]),
+        &[SOURCE]
+    ));
+}
+
+#[test]
+fn test_temporal_lower_bounds_require_monotonic_inputs() {
+    let lower = mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte);
+    assert!(!is_monotonic(source().filter(vec![lower]), &[]));
+}
+
+#[test]
+fn test_temporal_lower_bounds_mark_top_k_monotonic() {
+    let lower = mz_logical_timestamp().call_binary(MirScalarExpr::column(0), BinaryFunc::Gte);
+    let mut expr = source().filter(vec![lower]).top_k(
+        vec![],
+        vec![ColumnOrder {
+            column: 0,
+            desc: false,
+        }],
+        Some(1),
+        0,
+    );
+    MonotonicFlag::default()
+        .apply(
+            &mut expr,
+            &[SOURCE].into_iter().collect(),
+            &mut HashSet::new(),
+        )
+        .unwrap();
+    match expr {
+        MirRelationExpr::TopK { monotonic, .. } => assert!(monotonic),
+        _ => unreachable!(),
+    }
+}
@@ -10,6 +10,7 @@
 from materialize.mzcompose import Composition
 from materialize.mzcompose.services import (
     Kafka,
+    Localstack,
     Materialized,
     SchemaRegistry,
     Testdrive,
@@ -22,14 +23,24 @@ mz_disable_user_indexes = Materialized(
     options="--disable-user-indexes",
 )
 
+# Webhook sources only retain their requests across restarts if user tables are
+# persisted.
+mz_persistent_user_tables = Materialized(
+    name="mz_persistent_user_tables",
+    hostname="materialized",
+    options="--persistent-user-tables",
+)
+
 testdrive_no_reset = Testdrive(name="testdrive_no_reset", no_reset=True)
 
 SERVICES = [
     Zookeeper(),
     Kafka(auto_create_topics=True),
     SchemaRegistry(),
+    Localstack(),
     Materialized(),
     mz_disable_user_indexes,
+    mz_persistent_user_tables,
     Testdrive(),
     testdrive_no_reset,
 ]
@@ -63,6 +74,75 @@ def workflow_github_8021(c: Composition) -> None:
     c.kill("materialized")
 
 
+def workflow_kinesis(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["localstack"])
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run(
+        "testdrive-svc",
+        "--seed=1",
+        "--aws-endpoint=http://localstack:4566",
+        "kinesis-before-restart.td",
+    )
+
+    # Ensure the source resumes from its recorded shard sequence numbers
+    c.kill("materialized")
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run(
+        "testdrive_no_reset",
+        "--seed=1",
+        "--aws-endpoint=http://localstack:4566",
+        "kinesis-after-restart.td",
+    )
+    c.kill("materialized")
+
+
+def workflow_webhook(c: Composition) -> None:
+    c.up("mz_persistent_user_tables")
+    c.wait_for_materialized("mz_persistent_user_tables")
+    c.run("testdrive-svc", "webhook-before-restart.td")
+
+    # Ensure the source retains the requests it accepted before the restart
+    c.kill("mz_persistent_user_tables")
+    c.up("mz_persistent_user_tables")
+    c.wait_for_materialized("mz_persistent_user_tables")
+    c.run("testdrive_no_reset", "webhook-after-restart.td")
+    c.kill("mz_persistent_user_tables")
+
+
+def workflow_alter_source(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["zookeeper", "kafka", "schema-registry"])
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run("testdrive-svc", "--seed=1", "alter-source-before-restart.td")
+
+    # Ensure the views on the altered source still plan to their columns
+    c.kill("materialized")
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run("testdrive_no_reset", "--seed=1", "alter-source-after-restart.td")
+    c.kill("materialized")
+
+
+def workflow_subscription(c: Composition) -> None:
+    c.start_and_wait_for_tcp(services=["zookeeper", "kafka", "schema-registry"])
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run("testdrive-svc", "--seed=1", "subscription-before-restart.td")
+
+    # Ensure the subscription resumes after the timestamp it acknowledged
+    c.kill("materialized")
+    c.up("materialized")
+    c.wait_for_materialized("materialized")
+    c.run("testdrive_no_reset", "--seed=1", "subscription-after-restart.td")
+    c.kill("materialized")
+
+
 def workflow_default(c: Composition) -> None:
     workflow_disable_user_indexes(c)
     workflow_github_8021(c)
+    workflow_kinesis(c)
+    workflow_webhook(c)
+    workflow_alter_source(c)
+    workflow_subscription(c)